@@ -11,7 +11,7 @@ use crate::core::config::VolkiConfig;
 use crate::core::plugins::registry::PluginRegistry;
 use crate::core::volkiwithstds::collections::Vec;
 use crate::libs::lang::js::formatter;
-use crate::libs::lang::js::formatter::FileStatus;
+use crate::libs::lang::js::formatter::{FileResult, FileStatus};
 use crate::libs::lang::js::formatter::config::FormatConfig;
 
 pub struct FormatCommand;
@@ -107,15 +107,14 @@ impl Command for FormatCommand {
                 veprintln!();
                 output::print_hint("run volki format to fix");
                 veprintln!();
-                return Err(CliError::InvalidUsage(crate::vformat!(
-                    "{} file(s) not formatted",
-                    changed
-                )));
+                return outcome(&results, check);
+            }
+            if errors == 0 {
+                output::print_item(
+                    &style::green(style::CHECK),
+                    &crate::vformat!("all {total} file(s) already formatted"),
+                );
             }
-            output::print_item(
-                &style::green(style::CHECK),
-                &crate::vformat!("all {total} file(s) already formatted"),
-            );
         } else {
             output::print_summary_box(&[
                 &crate::vformat!(
@@ -136,6 +135,56 @@ impl Command for FormatCommand {
         output::print_hint("use --check to verify without writing");
         veprintln!();
 
+        outcome(&results, check)
+    }
+}
+
+/// Files that could not be read or parsed are errors; with `--check`, so are
+/// files that would be reformatted.
+fn outcome(results: &[FileResult], check: bool) -> Result<(), CliError> {
+    let errors = results
+        .iter()
+        .filter(|r| match r.status {
+            FileStatus::Error(_) => true,
+            FileStatus::Changed => check,
+            FileStatus::Unchanged => false,
+        })
+        .count();
+    if errors == 0 {
         Ok(())
+    } else {
+        Err(CliError::Partial { errors, warnings: 0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::String;
+    use crate::core::volkiwithstds::path::PathBuf;
+
+    fn result(path: &str, status: FileStatus) -> FileResult {
+        FileResult { path: PathBuf::from(path), status }
+    }
+
+    #[test]
+    fn outcome_counts_mixed_results() {
+        let results = vvec![
+            result("a.js", FileStatus::Changed),
+            result("b.js", FileStatus::Unchanged),
+            result("c.js", FileStatus::Error(String::from("unexpected token"))),
+            result("d.js", FileStatus::Changed),
+        ];
+
+        match outcome(&results, false) {
+            Err(CliError::Partial { errors: 1, warnings: 0 }) => {}
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+        match outcome(&results, true) {
+            Err(CliError::Partial { errors: 3, warnings: 0 }) => {}
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+        assert!(outcome(&results[1..2], true).is_ok());
+        assert!(outcome(&results[..2], false).is_ok());
     }
 }
@@ -7,19 +7,39 @@ use super::style;
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum CliError {
-    UnknownCommand(String),
+    /// The unknown name and the closest registered command, if any.
+    UnknownCommand(String, Option<String>),
     MissingArgument(String),
     UnknownFlag(String),
     MissingValue(String),
     InvalidUsage(String),
     ConfigRequired,
     ConfigSectionRequired(String),
+    /// The command ran to completion, but some inputs failed (files that did
+    /// not format, checks that did not pass). Per-item details have already
+    /// been printed; only the totals line is left to show.
+    Partial { errors: usize, warnings: usize },
 }
 
+/// Process exit code for [`CliError::Partial`], so CI can tell failed checks
+/// from a command that could not run at all (exit code 1).
+pub const PARTIAL_EXIT_CODE: i32 = 2;
+
 impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Partial { .. } => PARTIAL_EXIT_CODE,
+            _ => 1,
+        }
+    }
+
     pub fn hint(&self) -> Option<String> {
         match self {
-            CliError::UnknownCommand(_) => Some(crate::vformat!(
+            CliError::UnknownCommand(_, Some(suggestion)) => Some(crate::vformat!(
+                "did you mean {}?",
+                style::bold(suggestion)
+            )),
+            CliError::UnknownCommand(_, None) => Some(crate::vformat!(
                 "run {} to see available commands",
                 style::bold("volki --help")
             )),
@@ -44,6 +64,7 @@ impl CliError {
                 "add a [{}] section to your volki.toml",
                 section
             )),
+            CliError::Partial { .. } => None,
         }
     }
 }
@@ -51,7 +72,7 @@ impl CliError {
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CliError::UnknownCommand(cmd) => {
+            CliError::UnknownCommand(cmd, _) => {
                 write!(f, "unknown command '{cmd}'")
             }
             CliError::MissingArgument(arg) => {
@@ -72,6 +93,9 @@ impl fmt::Display for CliError {
             CliError::ConfigSectionRequired(section) => {
                 write!(f, "[{section}] section not found in volki.toml")
             }
+            CliError::Partial { errors, warnings } => {
+                write!(f, "{errors} error(s), {warnings} warning(s)")
+            }
         }
     }
 }
@@ -82,7 +106,7 @@ mod tests {
 
     #[test]
     fn display_unknown_command() {
-        let err = CliError::UnknownCommand(String::from("foo"));
+        let err = CliError::UnknownCommand(String::from("foo"), None);
         let msg = crate::vformat!("{err}");
         assert!(msg.contains("unknown command"));
         assert!(msg.contains("foo"));
@@ -121,10 +145,27 @@ mod tests {
 
     #[test]
     fn hint_unknown_command() {
-        let err = CliError::UnknownCommand(String::from("foo"));
+        let err = CliError::UnknownCommand(String::from("foo"), None);
         assert!(err.hint().is_some());
     }
 
+    #[test]
+    fn hint_unknown_command_with_suggestion() {
+        let err = CliError::UnknownCommand(String::from("stauts"), Some(String::from("status")));
+        let hint = err.hint().unwrap();
+        assert!(hint.contains("did you mean"));
+        assert!(hint.contains("status"));
+    }
+
+    #[test]
+    fn partial_has_distinct_exit_code() {
+        let err = CliError::Partial { errors: 2, warnings: 1 };
+        assert_eq!(err.exit_code(), PARTIAL_EXIT_CODE);
+        assert_ne!(err.exit_code(), CliError::ConfigRequired.exit_code());
+        assert_eq!(crate::vformat!("{err}"), "2 error(s), 1 warning(s)");
+        assert!(err.hint().is_none());
+    }
+
     #[test]
     fn hint_invalid_usage_is_none() {
         let err = CliError::InvalidUsage(String::from("whatever"));
@@ -1,6 +1,7 @@
 pub mod ansi;
 pub mod confirm;
 pub mod key;
+pub mod multi_select;
 pub mod raw_mode;
 pub mod render;
 pub mod select;
@@ -13,6 +14,7 @@ use super::error::CliError;
 use super::terminal;
 
 pub use confirm::Confirm;
+pub use multi_select::MultiSelect;
 pub use select::Select;
 pub use text_field::TextField;
 
@@ -23,12 +25,14 @@ pub struct Form {
 pub enum FormField {
     Text { name: String, field: TextField },
     Select { name: String, field: Select },
+    MultiSelect { name: String, field: MultiSelect },
     Confirm { name: String, field: Confirm },
 }
 
 #[derive(Debug)]
 pub struct FormResult {
     values: HashMap<String, String>,
+    lists: HashMap<String, Vec<String>>,
 }
 
 impl FormResult {
@@ -39,6 +43,12 @@ impl FormResult {
     pub fn get_bool(&self, key: &str) -> Option<bool> {
         self.values.get(&String::from(key)).map(|v| v == "true")
     }
+
+    /// Values picked in a `MultiSelect`, in option order. `get` returns the
+    /// same selection joined for display.
+    pub fn get_list(&self, key: &str) -> Option<&[String]> {
+        self.lists.get(&String::from(key)).map(|v| v.as_slice())
+    }
 }
 
 impl Form {
@@ -62,6 +72,14 @@ impl Form {
         self
     }
 
+    pub fn multi_select(mut self, name: &str, field: MultiSelect) -> Self {
+        self.fields.push(FormField::MultiSelect {
+            name: String::from(name),
+            field,
+        });
+        self
+    }
+
     pub fn confirm(mut self, name: &str, field: Confirm) -> Self {
         self.fields.push(FormField::Confirm {
             name: String::from(name),
@@ -78,6 +96,7 @@ impl Form {
         }
 
         let mut values = HashMap::new();
+        let mut lists = HashMap::new();
 
         for field in self.fields {
             match field {
@@ -89,6 +108,11 @@ impl Form {
                     let (_, val) = field.run()?;
                     values.insert(name, val);
                 }
+                FormField::MultiSelect { name, field } => {
+                    let vals = field.run()?;
+                    values.insert(name.clone(), multi_select::join_values(&vals));
+                    lists.insert(name, vals);
+                }
                 FormField::Confirm { name, field } => {
                     let val = field.run()?;
                     values.insert(name, vformat!("{val}"));
@@ -96,7 +120,7 @@ impl Form {
             }
         }
 
-        Ok(FormResult { values })
+        Ok(FormResult { values, lists })
     }
 }
 
@@ -110,7 +134,7 @@ mod tests {
         let mut values = HashMap::new();
         values.insert(String::from("name"), String::from("mydb"));
         values.insert(String::from("confirm"), String::from("true"));
-        let result = FormResult { values };
+        let result = FormResult { values, lists: HashMap::new() };
 
         assert_eq!(result.get("name"), Some("mydb"));
         assert_eq!(result.get("missing"), None);
@@ -121,21 +145,39 @@ mod tests {
         let mut values = HashMap::new();
         values.insert(String::from("proceed"), String::from("true"));
         values.insert(String::from("skip"), String::from("false"));
-        let result = FormResult { values };
+        let result = FormResult { values, lists: HashMap::new() };
 
         assert_eq!(result.get_bool("proceed"), Some(true));
         assert_eq!(result.get_bool("skip"), Some(false));
         assert_eq!(result.get_bool("missing"), None);
     }
 
+    #[test]
+    fn form_result_get_list() {
+        let tables = vvec![String::from("users"), String::from("a,b")];
+        let mut values = HashMap::new();
+        values.insert(String::from("tables"), multi_select::join_values(&tables));
+        let mut lists = HashMap::new();
+        lists.insert(String::from("tables"), tables);
+        lists.insert(String::from("none"), Vec::new());
+        let result = FormResult { values, lists };
+
+        let expected = vvec![String::from("users"), String::from("a,b")];
+        assert_eq!(result.get_list("tables"), Some(expected.as_slice()));
+        assert_eq!(result.get("tables"), Some("users, a,b"));
+        assert_eq!(result.get_list("none"), Some(&[][..]));
+        assert_eq!(result.get_list("missing"), None);
+    }
+
     #[test]
     fn form_builder_chain() {
         let form = Form::new()
             .text("name", TextField::new("Database name"))
             .select("dialect", Select::new("Dialect", vvec!["postgres", "mysql"]))
+            .multi_select("tables", MultiSelect::new("Tables", vvec!["users", "orders"]))
             .confirm("proceed", Confirm::new("Create?").default_yes());
 
-        assert_eq!(form.fields.len(), 3);
+        assert_eq!(form.fields.len(), 4);
     }
 
     #[test]
@@ -0,0 +1,162 @@
+use crate::core::cli::error::CliError;
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::veprintln;
+
+use super::ansi;
+use super::key::{self, Key};
+use super::raw_mode::RawModeGuard;
+use super::render;
+
+pub struct MultiSelect {
+    label: String,
+    options: Vec<String>,
+    checked: Vec<bool>,
+}
+
+impl MultiSelect {
+    pub fn new(label: &str, options: Vec<&str>) -> Self {
+        let checked = options.iter().map(|_| false).collect();
+        MultiSelect {
+            label: String::from(label),
+            options: options.into_iter().map(String::from).collect(),
+            checked,
+        }
+    }
+
+    /// Pre-check the options at the given indices. Out-of-range indices are ignored.
+    pub fn checked(mut self, indices: &[usize]) -> Self {
+        for &i in indices {
+            if let Some(c) = self.checked.get_mut(i) {
+                *c = true;
+            }
+        }
+        self
+    }
+
+    pub fn run(&self) -> Result<Vec<String>, CliError> {
+        if self.options.is_empty() {
+            return Err(CliError::InvalidUsage(String::from("no options provided")));
+        }
+
+        let _guard = RawModeGuard::enter()?;
+        let mut checked = self.checked.clone();
+        let mut focused = 0usize;
+
+        ansi::hide_cursor();
+
+        // Initial render: prompt + options
+        render::render_prompt(&self.label);
+        veprintln!();
+        self.render_options(focused, &checked);
+        ansi::flush();
+
+        loop {
+            let k = key::read_key();
+            match k {
+                Key::Up => {
+                    if focused == 0 {
+                        focused = self.options.len() - 1;
+                    } else {
+                        focused -= 1;
+                    }
+                    self.redraw_options(focused, &checked);
+                }
+                Key::Down => {
+                    focused = (focused + 1) % self.options.len();
+                    self.redraw_options(focused, &checked);
+                }
+                Key::Space => {
+                    checked[focused] = !checked[focused];
+                    self.redraw_options(focused, &checked);
+                }
+                Key::Enter => {
+                    let total_lines = 1 + self.options.len();
+                    ansi::erase_lines(total_lines);
+                    ansi::show_cursor();
+                    let values = self.selected_values(&checked);
+                    render::render_answered(&self.label, &join_values(&values));
+                    return Ok(values);
+                }
+                Key::CtrlC => {
+                    let total_lines = 1 + self.options.len();
+                    ansi::erase_lines(total_lines);
+                    ansi::show_cursor();
+                    return Err(CliError::InvalidUsage(String::from("cancelled")));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn selected_values(&self, checked: &[bool]) -> Vec<String> {
+        self.options
+            .iter()
+            .zip(checked.iter())
+            .filter(|(_, c)| **c)
+            .map(|(opt, _)| opt.clone())
+            .collect()
+    }
+
+    fn render_options(&self, focused: usize, checked: &[bool]) {
+        for (i, opt) in self.options.iter().enumerate() {
+            render::render_checkbox(opt, i == focused, checked[i]);
+        }
+    }
+
+    fn redraw_options(&self, focused: usize, checked: &[bool]) {
+        // Move up to first option line, erase and rewrite
+        ansi::move_up(self.options.len());
+        for (i, opt) in self.options.iter().enumerate() {
+            ansi::erase_line();
+            render::render_checkbox(opt, i == focused, checked[i]);
+        }
+        ansi::flush();
+    }
+}
+
+/// Join selected values for display (comma-separated). `FormResult` keeps
+/// the selection itself as a list, so values may contain commas.
+pub fn join_values(values: &[String]) -> String {
+    let mut out = String::new();
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(v);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vvec;
+
+    #[test]
+    fn multi_select_builds() {
+        let m = MultiSelect::new("Tables", vvec!["users", "orders", "items"]);
+        assert_eq!(m.label.as_str(), "Tables");
+        assert_eq!(m.options.len(), 3);
+        assert!(m.checked.iter().all(|c| !c));
+    }
+
+    #[test]
+    fn multi_select_checked_ignores_out_of_range() {
+        let m = MultiSelect::new("Tables", vvec!["users", "orders"]).checked(&[1, 5]);
+        assert_eq!(m.checked, vvec![false, true]);
+    }
+
+    #[test]
+    fn multi_select_selected_values_in_option_order() {
+        let m = MultiSelect::new("Tables", vvec!["users", "orders", "items"]);
+        let values = m.selected_values(&[true, false, true]);
+        assert_eq!(values, vvec![String::from("users"), String::from("items")]);
+    }
+
+    #[test]
+    fn join_values_comma_separated() {
+        let values = vvec![String::from("a"), String::from("b")];
+        assert_eq!(join_values(&values).as_str(), "a, b");
+        assert_eq!(join_values(&[]).as_str(), "");
+    }
+}
@@ -27,6 +27,10 @@ pub fn render_option(label: &str, selected: bool) {
     }
 }
 
+pub fn render_checkbox(label: &str, focused: bool, checked: bool) {
+    veprintln!("{}", format_checkbox(label, focused, checked));
+}
+
 pub fn render_error(msg: &str) {
     veprintln!("    {} {}", style::red(style::CROSS), style::red(msg));
 }
@@ -53,6 +57,19 @@ pub fn format_option(label: &str, selected: bool) -> String {
     }
 }
 
+pub fn format_checkbox(label: &str, focused: bool, checked: bool) -> String {
+    let mark = if checked {
+        style::green(style::BOX_CHECKED)
+    } else {
+        style::dim(style::BOX_EMPTY)
+    };
+    if focused {
+        crate::vformat!("  {} {} {}", style::cyan(style::ARROW), mark, label)
+    } else {
+        crate::vformat!("    {} {}", mark, style::dim(label))
+    }
+}
+
 pub fn format_error(msg: &str) -> String {
     crate::vformat!("    {} {}", style::red(style::CROSS), style::red(msg))
 }
@@ -90,6 +107,22 @@ mod tests {
         assert!(s.contains(style::PENDING));
     }
 
+    #[test]
+    fn checkbox_checked_and_focused() {
+        let s = format_checkbox("users", true, true);
+        assert!(s.contains("users"));
+        assert!(s.contains(style::BOX_CHECKED));
+        assert!(s.contains(style::ARROW));
+    }
+
+    #[test]
+    fn checkbox_unchecked_unfocused() {
+        let s = format_checkbox("orders", false, false);
+        assert!(s.contains("orders"));
+        assert!(s.contains(style::BOX_EMPTY));
+        assert!(!s.contains(style::ARROW));
+    }
+
     #[test]
     fn error_contains_message() {
         let s = format_error("must not be empty");
@@ -11,6 +11,7 @@ pub struct TextField {
     label: String,
     default: Option<String>,
     validator: Option<Box<dyn Fn(&str) -> Result<(), String>>>,
+    masked: bool,
 }
 
 impl TextField {
@@ -19,6 +20,7 @@ impl TextField {
             label: String::from(label),
             default: None,
             validator: None,
+            masked: false,
         }
     }
 
@@ -35,6 +37,12 @@ impl TextField {
         self
     }
 
+    /// Echo `*` for each typed character instead of the input itself.
+    pub fn mask(mut self) -> Self {
+        self.masked = true;
+        self
+    }
+
     pub fn run(&self) -> Result<String, CliError> {
         let _guard = RawModeGuard::enter()?;
 
@@ -45,7 +53,7 @@ impl TextField {
         // Initial render: prompt line + input line
         render::render_prompt(&self.label);
         veprintln!();
-        render::render_input(&value.iter().copied().collect::<String>());
+        render::render_input(&self.display(&value));
         self.position_cursor(cursor);
         ansi::flush();
 
@@ -115,7 +123,7 @@ impl TextField {
                     // Clear interactive lines and show answered state
                     let lines_to_clear = if error_showing { 3 } else { 2 };
                     self.clear_all(lines_to_clear);
-                    render::render_answered(&self.label, &val_str);
+                    render::render_answered(&self.label, &self.display(&value));
                     return Ok(val_str);
                 }
                 Key::CtrlC => {
@@ -128,10 +136,18 @@ impl TextField {
         }
     }
 
+    /// What the terminal shows for `value`: the text itself, or one `*` per char when masked.
+    fn display(&self, value: &[char]) -> String {
+        if self.masked {
+            value.iter().map(|_| '*').collect()
+        } else {
+            value.iter().copied().collect()
+        }
+    }
+
     fn redraw_input(&self, value: &[char], cursor: usize) {
         ansi::erase_line();
-        let val_str: String = value.iter().copied().collect();
-        render::render_input(&val_str);
+        render::render_input(&self.display(value));
         self.position_cursor(cursor);
         ansi::flush();
     }
@@ -184,6 +200,33 @@ mod tests {
         assert!(tf.validator.is_none());
     }
 
+    #[test]
+    fn text_field_mask() {
+        let tf = TextField::new("Password");
+        assert!(!tf.masked);
+        let tf = tf.mask();
+        assert!(tf.masked);
+    }
+
+    #[test]
+    fn masked_display_hides_input() {
+        let tf = TextField::new("Password").mask();
+        let mut value: Vec<char> = "s3cr3t".chars().collect();
+        assert_eq!(tf.display(&value).as_str(), "******");
+
+        // Backspace drops the real char and its mask together
+        value.pop();
+        assert_eq!(tf.display(&value).as_str(), "*****");
+        assert_eq!(value.iter().copied().collect::<String>().as_str(), "s3cr3");
+    }
+
+    #[test]
+    fn unmasked_display_echoes_input() {
+        let tf = TextField::new("Name");
+        let value: Vec<char> = "mydb".chars().collect();
+        assert_eq!(tf.display(&value).as_str(), "mydb");
+    }
+
     #[test]
     fn validator_accepts_valid() {
         let tf = TextField::new("Name").validate(|v| {
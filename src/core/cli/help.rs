@@ -46,6 +46,11 @@ pub fn print_top_level(commands: &[&dyn Command]) {
         style::cyan(&crate::vformat!("{:<12}", "--no-color")),
         style::dim("disable colored output"),
     );
+    veprintln!(
+        "    {}    {}",
+        style::cyan(&crate::vformat!("{:<12}", "--compact")),
+        style::dim("print tables without indent or divider"),
+    );
 
     veprintln!();
     veprintln!(
@@ -22,7 +22,7 @@ use commands::license::LicenseCommand;
 use commands::outdated::OutdatedCommand;
 use commands::run::RunCommand;
 use commands::status::StatusCommand;
-use crate::libs::db::cli::{DbCommand, DbHubCommand, UserCommand, TableCommand, WebEditorCommand};
+use crate::libs::db::cli::{DbCommand, DbHubCommand, MigrateCommand, QueryCommand, UserCommand, TableCommand, WebEditorCommand};
 use crate::libs::web::cli::{WebHubCommand, WebBuildCommand, WebStartCommand, WebDevCommand};
 use crate::core::volkiwithstds::collections::String;
 use registry::CommandRegistry;
@@ -40,7 +40,9 @@ pub fn build_cli() -> CommandRegistry {
     registry.register(vbox!(FormatCommand => dyn command::Command));
     registry.register(vbox!(InitCommand => dyn command::Command));
     registry.register(vbox!(LicenseCommand => dyn command::Command));
+    registry.register(vbox!(MigrateCommand => dyn command::Command));
     registry.register(vbox!(OutdatedCommand => dyn command::Command));
+    registry.register(vbox!(QueryCommand => dyn command::Command));
     registry.register(vbox!(RunCommand => dyn command::Command));
     registry.register(vbox!(StatusCommand => dyn command::Command));
     registry.register(vbox!(TableCommand => dyn command::Command));
@@ -91,6 +93,11 @@ pub fn print_hint_line(message: &str) {
 
 /// Render a CLI error with Volki-styled sections, traces, and hints.
 pub fn print_cli_error(err: &CliError) {
+    if let CliError::Partial { errors, warnings } = err {
+        print_totals(*errors, *warnings);
+        return;
+    }
+
     let rendered = crate::vformat!("{err}");
     let mut printed_primary = false;
     let mut error_count: usize = 0;
@@ -171,6 +178,10 @@ pub fn print_cli_error(err: &CliError) {
         print_hint_line(hint.as_str());
     }
 
+    print_totals(error_count, warning_count);
+}
+
+fn print_totals(error_count: usize, warning_count: usize) {
     let error_label = if error_count == 1 { "error" } else { "errors" };
     let warning_label = if warning_count == 1 { "warning" } else { "warnings" };
     veprintln!(
@@ -1,5 +1,6 @@
 use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::veprintln;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 use super::style;
 
@@ -64,50 +65,268 @@ pub fn print_summary_box(lines: &[&str]) {
     veprintln!("{bottom}");
 }
 
+/// How `print_table` handles cells wider than their column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellOverflow {
+    /// Cut the cell and end it with `…`.
+    Truncate,
+    /// Continue the cell on additional lines within the column.
+    Wrap,
+    /// Never shrink columns; print every value in full.
+    Full,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TableOptions {
+    pub overflow: CellOverflow,
+    /// No indent, divider or styling: aligned columns separated by two
+    /// spaces, for narrow terminals and piping into other tools.
+    pub compact: bool,
+    /// Upper bound for any single column, applied before fitting the table.
+    pub max_col_width: Option<usize>,
+    /// Total width to fit into. `None` uses the terminal width when stderr is a TTY.
+    pub max_width: Option<usize>,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            overflow: CellOverflow::Truncate,
+            compact: false,
+            max_col_width: None,
+            max_width: None,
+        }
+    }
+}
+
+impl TableOptions {
+    /// Print every value in full, e.g. for `--no-truncate` or when piping to a file.
+    pub fn full() -> Self {
+        TableOptions {
+            overflow: CellOverflow::Full,
+            ..Self::default()
+        }
+    }
+
+    pub fn overflow(mut self, overflow: CellOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    pub fn max_col_width(mut self, width: usize) -> Self {
+        self.max_col_width = Some(width);
+        self
+    }
+
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+}
+
+/// Narrowest a column is shrunk to when fitting the table to the terminal.
+const MIN_COL_WIDTH: usize = 4;
+const TABLE_INDENT: usize = 2;
+const COL_GAP: usize = 2;
+
+/// Set by the global `--compact` flag; every table prints compact.
+static COMPACT_TABLES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_compact_tables(compact: bool) {
+    COMPACT_TABLES.store(compact, Ordering::Relaxed);
+}
+
 /// `aligns`: 'l' (left) or 'r' (right) per column, defaults to 'l'.
 pub fn print_table(headers: &[&str], rows: &[Vec<String>], aligns: &[char]) {
+    print_table_with(headers, rows, aligns, &TableOptions::default());
+}
+
+/// Like `print_table`, but with explicit overflow handling.
+pub fn print_table_with(
+    headers: &[&str],
+    rows: &[Vec<String>],
+    aligns: &[char],
+    opts: &TableOptions,
+) {
+    let mut opts = *opts;
+    opts.compact |= COMPACT_TABLES.load(Ordering::Relaxed);
+    if opts.max_width.is_none() && super::terminal::is_tty() {
+        opts.max_width = Some(super::terminal::terminal_width());
+    }
+    for line in format_table(headers, rows, aligns, &opts) {
+        veprintln!("{line}");
+    }
+}
+
+fn format_table(
+    headers: &[&str],
+    rows: &[Vec<String>],
+    aligns: &[char],
+    opts: &TableOptions,
+) -> Vec<String> {
+    let mut lines = Vec::new();
     if headers.is_empty() {
-        return;
+        return lines;
     }
 
     let cols = headers.len();
-    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| visible_width(h)).collect();
     for row in rows {
         for (i, cell) in row.iter().enumerate() {
             if i < cols {
-                widths[i] = widths[i].max(strip_ansi(cell).len());
+                widths[i] = widths[i].max(visible_width(cell));
             }
         }
     }
+    if opts.overflow != CellOverflow::Full {
+        fit_widths(&mut widths, opts);
+    }
 
-    let mut header_parts = Vec::new();
-    for (i, h) in headers.iter().enumerate() {
-        header_parts.push(crate::vformat!("{:<width$}", h, width = widths[i]));
+    let header_cells: Vec<String> = headers.iter().map(|h| String::from(h)).collect();
+    if opts.compact {
+        for line in layout_row(&header_cells, &widths, aligns, opts.overflow) {
+            lines.push(String::from(line.trim_end()));
+        }
+        for row in rows {
+            for line in layout_row(row, &widths, aligns, opts.overflow) {
+                lines.push(String::from(line.trim_end()));
+            }
+        }
+        return lines;
+    }
+
+    for (n, line) in layout_row(&header_cells, &widths, &[], opts.overflow)
+        .iter()
+        .enumerate()
+    {
+        // Only the first header line is bold; wrapped continuations stay plain.
+        if n == 0 {
+            lines.push(crate::vformat!("  {}", style::bold(line)));
+        } else {
+            lines.push(crate::vformat!("  {line}"));
+        }
     }
-    veprintln!("  {}", style::bold(&header_parts.join("  ")));
 
     let divider_parts: Vec<String> = widths
         .iter()
         .map(|w| String::from("-").repeat(*w))
         .collect();
-    veprintln!("  {}", style::dim(&divider_parts.join("  ")));
+    lines.push(crate::vformat!(
+        "  {}",
+        style::dim(&divider_parts.join("  "))
+    ));
 
     for row in rows {
+        for line in layout_row(row, &widths, aligns, opts.overflow) {
+            lines.push(crate::vformat!("  {line}"));
+        }
+    }
+    lines
+}
+
+/// Shrink columns (widest first) until the table fits in `opts.max_width`.
+fn fit_widths(widths: &mut [usize], opts: &TableOptions) {
+    if let Some(cap) = opts.max_col_width {
+        for w in widths.iter_mut() {
+            *w = (*w).min(cap.max(1));
+        }
+    }
+    let Some(total) = opts.max_width else {
+        return;
+    };
+    let indent = if opts.compact { 0 } else { TABLE_INDENT };
+    let overhead = indent + COL_GAP * widths.len().saturating_sub(1);
+    let budget = total.saturating_sub(overhead);
+    while widths.iter().sum::<usize>() > budget {
+        let (idx, widest) = widths
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|(_, w)| *w)
+            .unwrap_or((0, 0));
+        if widest <= MIN_COL_WIDTH {
+            break;
+        }
+        widths[idx] -= 1;
+    }
+}
+
+/// Render one logical row as one or more physical lines.
+fn layout_row(
+    row: &[String],
+    widths: &[usize],
+    aligns: &[char],
+    overflow: CellOverflow,
+) -> Vec<String> {
+    let cols = widths.len();
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    for (i, cell) in row.iter().enumerate() {
+        if i >= cols {
+            break;
+        }
+        let w = widths[i];
+        if visible_width(cell) <= w || overflow == CellOverflow::Full {
+            cells.push(crate::vvec![cell.clone()]);
+        } else if overflow == CellOverflow::Wrap {
+            cells.push(wrap_cell(&strip_ansi(cell), w));
+        } else {
+            cells.push(crate::vvec![truncate_cell(&strip_ansi(cell), w)]);
+        }
+    }
+
+    let height = cells.iter().map(|c| c.len()).max().unwrap_or(1);
+    let mut lines = Vec::with_capacity(height);
+    for n in 0..height {
         let mut parts = Vec::new();
-        for (i, cell) in row.iter().enumerate() {
-            if i < cols {
-                let align = aligns.get(i).copied().unwrap_or('l');
-                let visible_len = strip_ansi(cell).len();
-                let pad = widths[i].saturating_sub(visible_len);
-                if align == 'r' {
-                    parts.push(crate::vformat!("{}{}", String::from(" ").repeat(pad), cell));
-                } else {
-                    parts.push(crate::vformat!("{}{}", cell, String::from(" ").repeat(pad)));
-                }
+        for (i, cell_lines) in cells.iter().enumerate() {
+            let empty = String::new();
+            let piece = cell_lines.get(n).unwrap_or(&empty);
+            let align = aligns.get(i).copied().unwrap_or('l');
+            let pad = widths[i].saturating_sub(visible_width(piece));
+            if align == 'r' {
+                parts.push(crate::vformat!(
+                    "{}{}",
+                    String::from(" ").repeat(pad),
+                    piece
+                ));
+            } else {
+                parts.push(crate::vformat!(
+                    "{}{}",
+                    piece,
+                    String::from(" ").repeat(pad)
+                ));
             }
         }
-        veprintln!("  {}", parts.join("  "));
+        lines.push(parts.join("  "));
+    }
+    lines
+}
+
+fn truncate_cell(text: &str, width: usize) -> String {
+    if width == 0 {
+        return String::new();
     }
+    let mut out: String = text.chars().take(width - 1).collect();
+    out.push('\u{2026}');
+    out
+}
+
+fn wrap_cell(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let width = width.max(1);
+    chars
+        .chunks(width)
+        .map(|chunk| chunk.iter().copied().collect())
+        .collect()
+}
+
+fn visible_width(s: &str) -> usize {
+    strip_ansi(s).chars().count()
 }
 
 /// Each item is `(depth, text)` where depth 0 = root, 1 = child, etc.
@@ -186,7 +405,7 @@ pub fn print_code_frame(
     }
 }
 
-fn strip_ansi(s: &str) -> String {
+pub(crate) fn strip_ansi(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let mut chars = s.chars();
     while let Some(c) = chars.next() {
@@ -227,4 +446,100 @@ mod tests {
     fn strip_ansi_empty() {
         assert_eq!(strip_ansi(""), "");
     }
+
+    fn table_rows() -> Vec<Vec<String>> {
+        crate::vvec![
+            crate::vvec![String::from("1"), String::from("short")],
+            crate::vvec![
+                String::from("2"),
+                String::from("a very long description value"),
+            ],
+        ]
+    }
+
+    fn plain(lines: &[String]) -> Vec<String> {
+        lines.iter().map(|l| strip_ansi(l)).collect()
+    }
+
+    #[test]
+    fn table_truncates_to_max_width() {
+        let opts = TableOptions::default().max_width(20);
+        let lines = plain(&format_table(
+            &["Id", "Desc"],
+            &table_rows(),
+            &['r', 'l'],
+            &opts,
+        ));
+        assert_eq!(lines.len(), 4);
+        for line in &lines {
+            assert!(line.chars().count() <= 20, "line too wide: {line:?}");
+        }
+        assert!(lines[3].ends_with("\u{2026}"));
+        assert!(lines[2].contains("short"));
+    }
+
+    #[test]
+    fn table_full_keeps_values() {
+        let opts = TableOptions::full().max_width(20);
+        let lines = plain(&format_table(
+            &["Id", "Desc"],
+            &table_rows(),
+            &['r', 'l'],
+            &opts,
+        ));
+        assert!(lines[3].contains("a very long description value"));
+    }
+
+    #[test]
+    fn table_wraps_within_column() {
+        let opts = TableOptions::default()
+            .overflow(CellOverflow::Wrap)
+            .max_col_width(10);
+        let lines = plain(&format_table(
+            &["Id", "Desc"],
+            &table_rows(),
+            &['r', 'l'],
+            &opts,
+        ));
+        // header, divider, "short" row, then the long value over 3 lines
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[3].as_str(), "   2  a very lon");
+        assert_eq!(lines[4].as_str(), "      g descript");
+        assert_eq!(lines[5].as_str(), "      ion value ");
+    }
+
+    #[test]
+    fn table_honors_right_alignment() {
+        let rows = crate::vvec![crate::vvec![String::from("7"), String::from("x")]];
+        let opts = TableOptions::default();
+        let lines = plain(&format_table(&["Count", "Name"], &rows, &['r', 'l'], &opts));
+        assert_eq!(lines[2].as_str(), "      7  x   ");
+    }
+
+    #[test]
+    fn table_compact_has_no_indent_or_divider() {
+        let rows = crate::vvec![
+            crate::vvec![String::from("7"), String::from("x")],
+            crate::vvec![String::from("120"), String::from("yz")],
+        ];
+        let opts = TableOptions::default().compact();
+        let lines = format_table(&["Count", "Name"], &rows, &['r', 'l'], &opts);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].as_str(), "Count  Name");
+        assert_eq!(lines[1].as_str(), "    7  x");
+        assert_eq!(lines[2].as_str(), "  120  yz");
+    }
+
+    #[test]
+    fn truncate_cell_adds_ellipsis() {
+        assert_eq!(truncate_cell("abcdef", 4).as_str(), "abc\u{2026}");
+        assert_eq!(truncate_cell("abcdef", 0).as_str(), "");
+    }
+
+    #[test]
+    fn wrap_cell_splits_on_width() {
+        let parts = wrap_cell("abcdefg", 3);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[2].as_str(), "g");
+    }
 }
@@ -59,6 +59,10 @@ impl ParsedArgs {
             .filter(|s| !s.takes_value)
             .map(|s| (s.name, s))
             .collect();
+        let short_opts: HashMap<char, &OptionSpec> = specs
+            .iter()
+            .filter_map(|s| s.short.map(|c| (c, s)))
+            .collect();
 
         let tokens = &raw.tokens;
         let mut i = 0;
@@ -76,17 +80,60 @@ impl ParsedArgs {
                     break;
                 }
 
+                // `--name=value` is equivalent to `--name value`
+                let (name, inline) = match name.split_once('=') {
+                    Some((n, v)) => (n, Some(v)),
+                    None => (name, None),
+                };
+
                 if value_opts.contains_key(&name) {
-                    i += 1;
-                    if i >= tokens.len() {
-                        return Err(CliError::MissingValue(String::from(name)));
-                    }
-                    options.insert(String::from(name), tokens[i].clone());
+                    let value = match inline {
+                        Some(v) => String::from(v),
+                        None => {
+                            i += 1;
+                            if i >= tokens.len() {
+                                return Err(CliError::MissingValue(String::from(name)));
+                            }
+                            tokens[i].clone()
+                        }
+                    };
+                    options.insert(String::from(name), value);
                 } else if flag_opts.contains_key(&name) {
+                    if inline.is_some() {
+                        return Err(CliError::InvalidUsage(crate::vformat!(
+                            "flag '--{name}' does not take a value"
+                        )));
+                    }
                     flags.insert(String::from(name), true);
                 } else {
                     return Err(CliError::UnknownFlag(String::from(name)));
                 }
+            } else if let Some(shorts) = token
+                .strip_prefix('-')
+                .filter(|s| !s.is_empty() && !s.starts_with(|c: char| c.is_ascii_digit()))
+            {
+                // `-qv` bundles boolean short flags; a value-taking short
+                // option may only appear last and consumes the next token.
+                let count = shorts.chars().count();
+                for (pos, c) in shorts.chars().enumerate() {
+                    let spec = short_opts
+                        .get(&c)
+                        .ok_or_else(|| CliError::UnknownFlag(crate::vformat!("{c}")))?;
+                    if spec.takes_value {
+                        if pos + 1 != count {
+                            return Err(CliError::InvalidUsage(crate::vformat!(
+                                "option '-{c}' takes a value and must come last in '{token}'"
+                            )));
+                        }
+                        i += 1;
+                        if i >= tokens.len() {
+                            return Err(CliError::MissingValue(String::from(spec.name)));
+                        }
+                        options.insert(String::from(spec.name), tokens[i].clone());
+                    } else {
+                        flags.insert(String::from(spec.name), true);
+                    }
+                }
             } else {
                 positional.push(token.clone());
             }
@@ -176,6 +223,17 @@ mod tests {
         }
     }
 
+    fn spec_short(name: &'static str, short: char, takes_value: bool) -> OptionSpec {
+        OptionSpec {
+            name,
+            description: "",
+            takes_value,
+            required: false,
+            default_value: None,
+            short: Some(short),
+        }
+    }
+
     // --- RawArgs::from_vec ---
 
     #[test]
@@ -320,6 +378,128 @@ mod tests {
         assert_eq!(parsed.positional(), sv(&["pos1"]).as_slice());
     }
 
+    #[test]
+    fn resolve_key_equals_value() {
+        let specs = [spec_value("db")];
+        let joined = RawArgs {
+            subcommand: None,
+            tokens: sv(&["--db=primary"]),
+        };
+        let split = RawArgs {
+            subcommand: None,
+            tokens: sv(&["--db", "primary"]),
+        };
+        let a = ParsedArgs::resolve(&joined, &specs).unwrap();
+        let b = ParsedArgs::resolve(&split, &specs).unwrap();
+        assert_eq!(a.get_option("db"), Some("primary"));
+        assert_eq!(a.get_option("db"), b.get_option("db"));
+        assert!(a.positional().is_empty());
+    }
+
+    #[test]
+    fn resolve_key_equals_empty_and_nested_equals() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["--filter=a=b", "--path="]),
+        };
+        let specs = [spec_value("filter"), spec_value("path")];
+        let parsed = ParsedArgs::resolve(&raw, &specs).unwrap();
+        assert_eq!(parsed.get_option("filter"), Some("a=b"));
+        assert_eq!(parsed.get_option("path"), Some(""));
+    }
+
+    #[test]
+    fn resolve_flag_with_inline_value_error() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["--group=yes"]),
+        };
+        let specs = [spec_flag("group")];
+        let result = ParsedArgs::resolve(&raw, &specs);
+        assert!(matches!(result, Err(CliError::InvalidUsage(_))));
+    }
+
+    #[test]
+    fn resolve_short_flags_separate_and_bundled() {
+        let specs = [
+            spec_short("quiet", 'q', false),
+            spec_short("verbose", 'v', false),
+        ];
+        for tokens in [sv(&["-q", "-v"]), sv(&["-qv"]), sv(&["-vq"])] {
+            let raw = RawArgs {
+                subcommand: None,
+                tokens,
+            };
+            let parsed = ParsedArgs::resolve(&raw, &specs).unwrap();
+            assert!(parsed.get_flag("quiet"));
+            assert!(parsed.get_flag("verbose"));
+            assert!(parsed.positional().is_empty());
+        }
+    }
+
+    #[test]
+    fn resolve_short_value_consumes_next() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["-qp", "/tmp", "rest"]),
+        };
+        let specs = [
+            spec_short("quiet", 'q', false),
+            spec_short("path", 'p', true),
+        ];
+        let parsed = ParsedArgs::resolve(&raw, &specs).unwrap();
+        assert!(parsed.get_flag("quiet"));
+        assert_eq!(parsed.get_option("path"), Some("/tmp"));
+        assert_eq!(parsed.positional(), sv(&["rest"]).as_slice());
+    }
+
+    #[test]
+    fn resolve_short_value_not_last_error() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["-pq", "/tmp"]),
+        };
+        let specs = [
+            spec_short("quiet", 'q', false),
+            spec_short("path", 'p', true),
+        ];
+        let result = ParsedArgs::resolve(&raw, &specs);
+        assert!(matches!(result, Err(CliError::InvalidUsage(_))));
+    }
+
+    #[test]
+    fn resolve_short_missing_value_error() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["-p"]),
+        };
+        let specs = [spec_short("path", 'p', true)];
+        let result = ParsedArgs::resolve(&raw, &specs);
+        assert!(matches!(result, Err(CliError::MissingValue(_))));
+    }
+
+    #[test]
+    fn resolve_unknown_short_flag_error() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["-x"]),
+        };
+        let specs = [spec_short("quiet", 'q', false)];
+        let result = ParsedArgs::resolve(&raw, &specs);
+        assert!(matches!(result, Err(CliError::UnknownFlag(_))));
+    }
+
+    #[test]
+    fn resolve_negative_number_and_dash_are_positional() {
+        let raw = RawArgs {
+            subcommand: None,
+            tokens: sv(&["-5", "-"]),
+        };
+        let specs: [OptionSpec; 0] = [];
+        let parsed = ParsedArgs::resolve(&raw, &specs).unwrap();
+        assert_eq!(parsed.positional(), sv(&["-5", "-"]).as_slice());
+    }
+
     // --- has_help_flag ---
 
     #[test]
@@ -1,9 +1,9 @@
-use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::io::traits::Write;
 use crate::core::volkiwithstds::time::Instant;
 use crate::{veprint, veprintln};
 
-use super::style;
+use super::{style, terminal};
 
 pub struct ProgressBar {
     total: u64,
@@ -144,6 +144,201 @@ impl ProgressBar {
     }
 }
 
+/// Several bars drawn together, one line each, e.g. one per file going
+/// through compile and wasm build. On a terminal every update redraws the
+/// whole block in place so bars never interleave; otherwise each bar prints
+/// a plain line per quarter of progress and when it finishes.
+pub struct MultiProgress {
+    bars: Vec<Bar>,
+    tty: bool,
+    bar_width: usize,
+    drawn_lines: usize,
+    frames: usize,
+}
+
+struct Bar {
+    label: String,
+    phase: String,
+    total: u64,
+    current: u64,
+    state: BarState,
+    /// Last drawn percentage (tty) or quarter (plain output).
+    last_drawn: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BarState {
+    Running,
+    Done,
+    Failed,
+}
+
+impl MultiProgress {
+    pub fn new() -> Self {
+        Self::with_tty(terminal::is_tty())
+    }
+
+    pub fn with_tty(tty: bool) -> Self {
+        MultiProgress {
+            bars: Vec::new(),
+            tty,
+            bar_width: 20,
+            drawn_lines: 0,
+            frames: 0,
+        }
+    }
+
+    /// Adds a bar and returns its index for later updates.
+    pub fn add(&mut self, total: u64, label: &str) -> usize {
+        self.bars.push(Bar {
+            label: String::from(label),
+            phase: String::new(),
+            total,
+            current: 0,
+            state: BarState::Running,
+            last_drawn: u64::MAX,
+        });
+        self.bars.len() - 1
+    }
+
+    /// Names the step a bar is in (`compile`, `wasm`, ...).
+    pub fn set_phase(&mut self, bar: usize, phase: &str) {
+        if self.bars[bar].phase.as_str() == phase {
+            return;
+        }
+        self.bars[bar].phase = String::from(phase);
+        self.bars[bar].last_drawn = u64::MAX;
+        self.update(bar);
+    }
+
+    pub fn set(&mut self, bar: usize, current: u64) {
+        let b = &mut self.bars[bar];
+        b.current = current.min(b.total);
+        self.update(bar);
+    }
+
+    pub fn inc(&mut self, bar: usize, amount: u64) {
+        let b = &mut self.bars[bar];
+        b.current = (b.current + amount).min(b.total);
+        self.update(bar);
+    }
+
+    pub fn finish(&mut self, bar: usize) {
+        self.end(bar, BarState::Done);
+    }
+
+    pub fn finish_with_error(&mut self, bar: usize) {
+        self.end(bar, BarState::Failed);
+    }
+
+    /// Number of frames (tty) or lines (plain output) drawn so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    fn end(&mut self, bar: usize, state: BarState) {
+        if self.bars[bar].state != BarState::Running {
+            return;
+        }
+        self.bars[bar].state = state;
+        if self.tty {
+            self.draw_all();
+        } else {
+            self.draw_line(bar);
+        }
+    }
+
+    fn update(&mut self, bar: usize) {
+        let b = &self.bars[bar];
+        if b.state != BarState::Running {
+            return;
+        }
+        let pct = percent(b.current, b.total);
+        if self.tty {
+            if pct != b.last_drawn {
+                self.bars[bar].last_drawn = pct;
+                self.draw_all();
+            }
+        } else {
+            // Full progress is reported by finish()
+            let quarter = pct / 25;
+            if quarter != b.last_drawn && quarter > 0 && pct < 100 {
+                self.bars[bar].last_drawn = quarter;
+                self.draw_line(bar);
+            }
+        }
+    }
+
+    fn draw_all(&mut self) {
+        let mut frame = String::new();
+        if self.drawn_lines > 0 {
+            frame.push_str(&crate::vformat!("\x1b[{}A", self.drawn_lines));
+        }
+        for bar in &self.bars {
+            frame.push_str("\r\x1b[2K");
+            frame.push_str(&self.render(bar));
+            frame.push('\n');
+        }
+        self.drawn_lines = self.bars.len();
+        self.frames += 1;
+        veprint!("{frame}");
+        let _ = crate::core::volkiwithstds::io::stderr().flush();
+    }
+
+    fn draw_line(&mut self, bar: usize) {
+        self.frames += 1;
+        veprintln!("{}", self.render(&self.bars[bar]));
+    }
+
+    fn render(&self, bar: &Bar) -> String {
+        let pct = percent(bar.current, bar.total);
+        let filled = (self.bar_width as u64 * bar.current)
+            .checked_div(bar.total)
+            .map_or(self.bar_width, |f| f as usize);
+        let bar_filled = String::from("\u{2588}").repeat(filled);
+        let bar_empty = String::from("\u{2591}").repeat(self.bar_width - filled);
+        let bar_str = if style::use_color() {
+            let color = if bar.state == BarState::Failed {
+                style::RED
+            } else {
+                style::PURPLE
+            };
+            crate::vformat!("{}{}{}{bar_empty}", color, bar_filled, style::RESET)
+        } else {
+            crate::vformat!("{bar_filled}{bar_empty}")
+        };
+        let phase = if bar.phase.is_empty() {
+            String::new()
+        } else {
+            crate::vformat!(" {}", style::dim(&bar.phase))
+        };
+        let mark = match bar.state {
+            BarState::Running => String::new(),
+            BarState::Done => crate::vformat!(" {}", style::green(style::CHECK)),
+            BarState::Failed => crate::vformat!(" {}", style::red(style::CROSS)),
+        };
+        crate::vformat!(
+            "  {}{}  {}  {}/{}  {pct}%{}",
+            bar.label,
+            phase,
+            bar_str,
+            bar.current,
+            bar.total,
+            mark,
+        )
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn percent(current: u64, total: u64) -> u64 {
+    (100 * current).checked_div(total).unwrap_or(100)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,4 +397,36 @@ mod tests {
         let pb = ProgressBar::new(10, "test");
         pb.finish_with_error();
     }
+
+    fn drive_two_bars(mp: &mut MultiProgress) {
+        let a = mp.add(4, "app/page.volki");
+        let b = mp.add(4, "app/about/page.volki");
+        mp.set_phase(a, "compile");
+        mp.set_phase(b, "compile");
+        for step in 1..=4 {
+            mp.set(a, step);
+            mp.set(a, step); // unchanged, no redraw
+            mp.inc(b, 1);
+        }
+        mp.finish(a);
+        mp.finish_with_error(b);
+        mp.finish(b); // already finished
+    }
+
+    #[test]
+    fn multi_tty_redraws_once_per_change() {
+        let mut mp = MultiProgress::with_tty(true);
+        drive_two_bars(&mut mp);
+        // 2 phases + 4 steps per bar + 2 finishes
+        assert_eq!(mp.frames(), 12);
+        assert_eq!(mp.drawn_lines, 2);
+    }
+
+    #[test]
+    fn multi_plain_prints_quarters_and_finish() {
+        let mut mp = MultiProgress::with_tty(false);
+        drive_two_bars(&mut mp);
+        // 25/50/75% + finish per bar, phase changes print nothing at 0%
+        assert_eq!(mp.frames(), 8);
+    }
 }
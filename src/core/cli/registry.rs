@@ -1,4 +1,4 @@
-use crate::core::utils::log::{self as logger, LogLevel};
+use crate::core::utils::log as logger;
 use crate::core::volkiwithstds::collections::{Box, String, Vec};
 use crate::log_debug;
 use crate::veprintln;
@@ -10,6 +10,9 @@ use super::output;
 use super::parser::{ParsedArgs, RawArgs};
 use super::style;
 
+/// Flags handled here for every command and removed before parsing.
+const GLOBAL_FLAGS: &[&str] = &["--no-color", "--compact"];
+
 pub struct CommandRegistry {
     commands: Vec<Box<dyn Command>>,
 }
@@ -26,26 +29,19 @@ impl CommandRegistry {
     }
 
     pub fn run(&self) -> Result<(), CliError> {
-        let raw = RawArgs::from_env();
+        let mut raw = RawArgs::from_env();
 
         // Detect --no-color early (before any output)
         if raw.tokens.iter().any(|t| t == "--no-color") {
             style::disable_color();
         }
-
-        // Detect --verbose or VOLKI_LOG env for log level
-        if raw.tokens.iter().any(|t| t == "--verbose") {
-            logger::set_level(LogLevel::Debug);
-        } else if let Some(val) = crate::core::volkiwithstds::env::var("VOLKI_LOG") {
-            match val.as_str() {
-                "debug" => logger::set_level(LogLevel::Debug),
-                "info" => logger::set_level(LogLevel::Info),
-                "warn" => logger::set_level(LogLevel::Warn),
-                "error" => logger::set_level(LogLevel::Error),
-                "off" => logger::set_level(LogLevel::Off),
-                _ => {}
-            }
+        if raw.tokens.iter().any(|t| t == "--compact") {
+            output::set_compact_tables(true);
         }
+        // Global flags apply to every command, so no command declares them
+        raw.tokens.retain(|t| !GLOBAL_FLAGS.contains(&t.as_str()));
+
+        logger::init(raw.tokens.iter().any(|t| t == "--verbose"));
 
         // Top-level --help or no subcommand
         if raw.subcommand.is_none()
@@ -79,7 +75,9 @@ impl CommandRegistry {
             .commands
             .iter()
             .find(|c| c.name() == sub)
-            .ok_or_else(|| CliError::UnknownCommand(String::from(sub)))?;
+            .ok_or_else(|| {
+                CliError::UnknownCommand(String::from(sub), self.suggest(sub).map(String::from))
+            })?;
 
         // Per-command --help
         if ParsedArgs::has_help_flag(&raw.tokens) {
@@ -109,6 +107,21 @@ impl CommandRegistry {
         cmd.execute(&parsed)
     }
 
+    /// Closest registered command name to `name`, if it is a plausible typo.
+    /// Allows one edit per three characters of the candidate, at least one.
+    pub fn suggest(&self, name: &str) -> Option<&str> {
+        let mut best: Option<(usize, &str)> = None;
+        for cmd in self.commands.iter() {
+            let candidate = cmd.name();
+            let distance = levenshtein(name, candidate);
+            let threshold = (candidate.chars().count() / 3).max(1);
+            if distance <= threshold && best.is_none_or(|(d, _)| distance < d) {
+                best = Some((distance, candidate));
+            }
+        }
+        best.map(|(_, candidate)| candidate)
+    }
+
     fn validate_required(
         specs: &[super::command::OptionSpec],
         parsed: &ParsedArgs,
@@ -121,3 +134,43 @@ impl CommandRegistry {
         Ok(())
     }
 }
+
+/// Edit distance between `a` and `b` (insertions, deletions, substitutions).
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr: Vec<usize> = (0..=b_chars.len()).map(|_| 0).collect();
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b_chars.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("status", "status"), 0);
+        assert_eq!(levenshtein("stauts", "status"), 2);
+        assert_eq!(levenshtein("", "run"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn suggest_over_registered_commands() {
+        let registry = super::super::build_cli();
+        assert_eq!(registry.suggest("stauts"), Some("status"));
+        assert_eq!(registry.suggest("fromat"), Some("format"));
+        assert_eq!(registry.suggest("db:migarte"), Some("db:migrate"));
+        assert_eq!(registry.suggest("rn"), Some("run"));
+        assert_eq!(registry.suggest("deploy"), None);
+        assert_eq!(registry.suggest("x"), None);
+    }
+}
@@ -1,13 +1,13 @@
 use core::sync::atomic::{AtomicBool, Ordering};
 
-use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::io::traits::Write;
-use crate::core::volkiwithstds::sync::Arc;
+use crate::core::volkiwithstds::sync::{Arc, Mutex};
 use crate::core::volkiwithstds::thread;
 use crate::core::volkiwithstds::time::Duration;
 use crate::{veprint, veprintln};
 
-use super::style;
+use super::{style, terminal};
 
 const BRAILLE_FRAMES: &[&str] = &[
     "\u{280B}", // ⠋
@@ -24,39 +24,83 @@ const BRAILLE_FRAMES: &[&str] = &[
 
 pub struct Spinner {
     label: String,
+    ticker: Arc<Mutex<Ticker>>,
     running: Arc<AtomicBool>,
     handle: Option<thread::JoinHandle<()>>,
 }
 
+/// Frame and message shared between a spinner and its drawing thread.
+struct Ticker {
+    frames: Vec<String>,
+    frame_idx: usize,
+    message: String,
+}
+
+impl Ticker {
+    /// The line for the current frame; advances to the next frame.
+    fn tick(&mut self) -> String {
+        let line = if self.frames.is_empty() {
+            crate::vformat!("  {}", self.message)
+        } else {
+            let frame = &self.frames[self.frame_idx % self.frames.len()];
+            crate::vformat!("  {} {}", style::purple(frame), self.message)
+        };
+        self.frame_idx = self.frame_idx.wrapping_add(1);
+        line
+    }
+}
+
 impl Spinner {
     pub fn new(label: &str) -> Self {
-        let running = Arc::new(AtomicBool::new(true));
-        let label_owned = String::from(label);
-
-        let r = running.clone();
-        let l = label_owned.clone();
+        Self::with_frames(label, BRAILLE_FRAMES)
+    }
 
-        let handle = thread::spawn(move || {
-            let mut frame_idx = 0;
-            while r.load(Ordering::Relaxed) {
-                let frame = BRAILLE_FRAMES[frame_idx % BRAILLE_FRAMES.len()];
-                let spinner_char = style::purple(frame);
-                veprint!("\r  {} {}", spinner_char, l);
+    /// A spinner cycling through `frames` instead of the braille set.
+    /// When stderr is not a terminal, `label` is printed once and nothing
+    /// animates.
+    pub fn with_frames(label: &str, frames: &[&str]) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let ticker = Arc::new(Mutex::new(Ticker {
+            frames: frames.iter().map(|f| String::from(f)).collect(),
+            frame_idx: 0,
+            message: String::from(label),
+        }));
+
+        let handle = if terminal::is_tty() {
+            let r = running.clone();
+            let t = ticker.clone();
+            Some(thread::spawn(move || {
+                while r.load(Ordering::Relaxed) {
+                    let line = t.lock().tick();
+                    veprint!("\r\x1b[2K{}", line);
+                    let _ = crate::core::volkiwithstds::io::stderr().flush();
+                    thread::sleep(Duration::from_millis(80));
+                }
+                veprint!("\r\x1b[2K");
                 let _ = crate::core::volkiwithstds::io::stderr().flush();
-                frame_idx += 1;
-                thread::sleep(Duration::from_millis(80));
-            }
-            veprint!("\r{}\r", String::from(" ").repeat(l.len() + 6));
-            let _ = crate::core::volkiwithstds::io::stderr().flush();
-        });
+            }))
+        } else {
+            veprintln!("  {}", label);
+            None
+        };
 
         Spinner {
-            label: label_owned,
+            label: String::from(label),
+            ticker,
             running,
-            handle: Some(handle),
+            handle,
         }
     }
 
+    /// Replaces the text after the frame; shown from the next tick.
+    pub fn set_message(&self, message: &str) {
+        self.ticker.lock().message = String::from(message);
+    }
+
+    pub fn message(&self) -> String {
+        self.ticker.lock().message.clone()
+    }
+
     pub fn stop_with(mut self, symbol: &str, message: &str) {
         self.running.store(false, Ordering::Relaxed);
         if let Some(handle) = self.handle.take() {
@@ -97,6 +141,32 @@ mod tests {
         assert_eq!(BRAILLE_FRAMES.len(), 10);
     }
 
+    #[test]
+    fn ticker_cycles_frames_and_shows_message() {
+        let mut ticker = Ticker {
+            frames: crate::vvec![String::from("a"), String::from("b")],
+            frame_idx: 0,
+            message: String::from("connecting"),
+        };
+        let first = ticker.tick();
+        assert!(first.contains("a") && first.ends_with(" connecting"));
+        assert!(ticker.tick().contains("b"));
+        assert!(ticker.tick().contains("a"));
+
+        ticker.message = String::from("retrying");
+        assert!(ticker.tick().ends_with(" retrying"));
+    }
+
+    #[test]
+    fn spinner_set_message() {
+        let spinner = Spinner::with_frames("connecting", &["-", "+"]);
+        assert_eq!(spinner.message(), "connecting");
+        spinner.set_message("handshake");
+        assert_eq!(spinner.message(), "handshake");
+        assert_eq!(spinner.label(), "connecting");
+        spinner.stop_with(style::CHECK, "done");
+    }
+
     #[test]
     fn spinner_creates_and_stops() {
         let spinner = Spinner::new("test");
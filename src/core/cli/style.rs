@@ -36,6 +36,8 @@ pub const WARN: &str = "\u{26A0}"; // ⚠
 pub const ARROW: &str = "\u{2192}"; // →
 pub const BULLET: &str = "\u{25CF}"; // ●
 pub const PENDING: &str = "\u{25CC}"; // ◌
+pub const BOX_CHECKED: &str = "\u{25A3}"; // ▣
+pub const BOX_EMPTY: &str = "\u{25A1}"; // □
 pub const WOLF: &str = "\u{1F43A}"; // 🐺
 pub const PAW: &str = "\u{1F43E}"; // 🐾
 pub const SEARCH: &str = "\u{1F50D}"; // 🔍
@@ -0,0 +1,127 @@
+//! Compression — gzip encoding and decoding over the system zlib.
+
+use crate::core::volkiwithstds::collections::Vec;
+use crate::core::volkiwithstds::sys::zlib;
+use core::fmt;
+
+/// A zlib failure, carrying the zlib return code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressError {
+    pub code: i32,
+}
+
+impl fmt::Display for CompressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "zlib error {}", self.code)
+    }
+}
+
+const CHUNK: usize = 16 * 1024;
+
+/// Compresses `data` into a gzip member (RFC 1952).
+pub fn gzip(data: &[u8]) -> Result<Vec<u8>, CompressError> {
+    let mut strm = zlib::z_stream::zeroed();
+    let rc = unsafe {
+        zlib::deflateInit2_(
+            &mut strm,
+            zlib::Z_DEFAULT_COMPRESSION,
+            zlib::Z_DEFLATED,
+            zlib::GZIP_WINDOW_BITS,
+            8,
+            zlib::Z_DEFAULT_STRATEGY,
+            zlib::ZLIB_VERSION.as_ptr() as *const _,
+            core::mem::size_of::<zlib::z_stream>() as i32,
+        )
+    };
+    if rc != zlib::Z_OK {
+        return Err(CompressError { code: rc });
+    }
+
+    let bound = unsafe { zlib::deflateBound(&mut strm, data.len() as u64) } as usize;
+    let mut out: Vec<u8> = Vec::with_capacity(bound);
+    strm.next_in = data.as_ptr();
+    strm.avail_in = data.len() as u32;
+    strm.next_out = out.as_mut_ptr();
+    strm.avail_out = bound as u32;
+
+    // deflateBound guarantees a single Z_FINISH call fits.
+    let rc = unsafe { zlib::deflate(&mut strm, zlib::Z_FINISH) };
+    let written = strm.total_out as usize;
+    unsafe { zlib::deflateEnd(&mut strm) };
+    if rc != zlib::Z_STREAM_END {
+        return Err(CompressError { code: rc });
+    }
+    unsafe { out.set_len(written) };
+    Ok(out)
+}
+
+/// Decompresses a gzip member.
+pub fn gunzip(data: &[u8]) -> Result<Vec<u8>, CompressError> {
+    let mut strm = zlib::z_stream::zeroed();
+    let rc = unsafe {
+        zlib::inflateInit2_(
+            &mut strm,
+            zlib::GZIP_WINDOW_BITS,
+            zlib::ZLIB_VERSION.as_ptr() as *const _,
+            core::mem::size_of::<zlib::z_stream>() as i32,
+        )
+    };
+    if rc != zlib::Z_OK {
+        return Err(CompressError { code: rc });
+    }
+
+    let mut out: Vec<u8> = Vec::new();
+    strm.next_in = data.as_ptr();
+    strm.avail_in = data.len() as u32;
+    let result = loop {
+        out.reserve(CHUNK);
+        let len = out.len();
+        strm.next_out = unsafe { out.as_mut_ptr().add(len) };
+        strm.avail_out = CHUNK as u32;
+        let rc = unsafe { zlib::inflate(&mut strm, zlib::Z_NO_FLUSH) };
+        let produced = CHUNK - strm.avail_out as usize;
+        unsafe { out.set_len(len + produced) };
+        match rc {
+            zlib::Z_STREAM_END => break Ok(()),
+            zlib::Z_OK => {}
+            // Out of input before the stream ended: truncated data
+            zlib::Z_BUF_ERROR if strm.avail_in == 0 => break Err(CompressError { code: rc }),
+            zlib::Z_BUF_ERROR => {}
+            _ => break Err(CompressError { code: rc }),
+        }
+    };
+    unsafe { zlib::inflateEnd(&mut strm) };
+    result.map(|_| out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut payload = Vec::new();
+        for i in 0..2000u32 {
+            payload
+                .extend_from_slice(crate::vformat!("<li class=\"item\">row {i}</li>\n").as_bytes());
+        }
+        let packed = gzip(&payload).unwrap();
+        assert!(packed.len() < payload.len() / 4);
+        // gzip magic bytes
+        assert_eq!(&packed[..2], &[0x1f, 0x8b]);
+        assert_eq!(gunzip(&packed).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_empty() {
+        let packed = gzip(b"").unwrap();
+        assert!(gunzip(&packed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_garbage_and_truncation() {
+        assert!(gunzip(b"not gzip at all").is_err());
+        let packed = gzip(b"hello hello hello hello").unwrap();
+        assert!(gunzip(&packed[..packed.len() - 6]).is_err());
+    }
+}
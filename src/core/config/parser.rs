@@ -1,4 +1,5 @@
 use crate::core::volkiwithstds::collections::{HashMap, String, Vec};
+use crate::core::volkiwithstds::env;
 use core::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,19 +49,30 @@ impl Value {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Table {
     entries: HashMap<String, Value>,
+    arrays: HashMap<String, Vec<Table>>,
+    /// Keys whose `${VAR}` references could not be resolved. They only
+    /// fail when read, so an unset variable doesn't break unrelated commands.
+    unresolved: HashMap<String, ParseError>,
 }
 
 impl Table {
+    /// Look up a key. A key whose variables could not be resolved reads as
+    /// missing; use [`Table::try_get`] where that should be an error.
     pub fn get(&self, section: &str, key: &str) -> Option<&Value> {
-        let full = if section.is_empty() {
-            String::from(key)
-        } else {
-            crate::vformat!("{section}.{key}")
-        };
-        self.entries.get(&full)
+        self.entries.get(&full_key(section, key))
+    }
+
+    /// Like [`Table::get`], but returns the interpolation error for a key
+    /// that references an unset variable.
+    pub fn try_get(&self, section: &str, key: &str) -> Result<Option<&Value>, ParseError> {
+        let full = full_key(section, key);
+        match self.unresolved.get(&full) {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.entries.get(&full)),
+        }
     }
 
     pub fn has_section(&self, section: &str) -> bool {
@@ -73,6 +85,16 @@ impl Table {
         false
     }
 
+    /// The `[[name]]` blocks in file order. Each element is its own table,
+    /// so keys are looked up relative to the block, e.g.
+    /// `get_array_of_tables("servers")[0].get("", "host")`.
+    pub fn get_array_of_tables(&self, name: &str) -> &[Table] {
+        match self.arrays.get(name) {
+            Some(tables) => tables,
+            None => &[],
+        }
+    }
+
     #[allow(dead_code)]
     pub fn entries(&self) -> &HashMap<String, Value> {
         &self.entries
@@ -92,7 +114,15 @@ impl Table {
     }
 }
 
-#[derive(Debug)]
+fn full_key(section: &str, key: &str) -> String {
+    if section.is_empty() {
+        String::from(key)
+    } else {
+        crate::vformat!("{section}.{key}")
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ParseError {
     pub line: usize,
     pub message: String,
@@ -105,8 +135,10 @@ impl fmt::Display for ParseError {
 }
 
 pub fn parse(content: &str) -> Result<Table, ParseError> {
-    let mut entries = HashMap::new();
+    let mut table = Table::default();
     let mut current_section = String::new();
+    // Set while inside a `[[name]]` block; keys go to its latest element.
+    let mut current_array: Option<String> = None;
 
     for (i, line) in content.lines().enumerate() {
         let trimmed = line.trim();
@@ -115,12 +147,47 @@ pub fn parse(content: &str) -> Result<Table, ParseError> {
             continue;
         }
 
-        if trimmed.starts_with("[") && !trimmed.starts_with("[[") {
+        if trimmed.starts_with("[[") {
+            let end = trimmed.find("]]").ok_or_else(|| ParseError {
+                line: i + 1,
+                message: String::from("unclosed array-of-tables header"),
+            })?;
+            let name = trimmed[2..end].trim();
+            if name.is_empty() {
+                return Err(ParseError {
+                    line: i + 1,
+                    message: String::from("empty array-of-tables name"),
+                });
+            }
+            table
+                .arrays
+                .entry(String::from(name))
+                .or_default()
+                .push(Table::default());
+            current_array = Some(String::from(name));
+            current_section = String::new();
+            continue;
+        }
+
+        if trimmed.starts_with("[") {
             let end = trimmed.find(']').ok_or_else(|| ParseError {
                 line: i + 1,
                 message: String::from("unclosed section header"),
             })?;
-            current_section = String::from(trimmed[1..end].trim());
+            let section = trimmed[1..end].trim();
+            // `[name.sub]` right after `[[name]]` is a sub-table of that element
+            let sub = current_array.as_ref().and_then(|name| {
+                section
+                    .strip_prefix(name.as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+            });
+            match sub {
+                Some(rest) => current_section = String::from(rest),
+                None => {
+                    current_array = None;
+                    current_section = String::from(section);
+                }
+            }
             continue;
         }
 
@@ -139,10 +206,16 @@ pub fn parse(content: &str) -> Result<Table, ParseError> {
             });
         }
 
-        let value = parse_value(raw_val).ok_or_else(|| ParseError {
+        let invalid = || ParseError {
             line: i + 1,
             message: crate::vformat!("invalid value: {raw_val}"),
-        })?;
+        };
+        // An inline table flattens into the same dotted keys a section would
+        let values = if raw_val.starts_with('{') {
+            parse_inline_table(raw_val).ok_or_else(invalid)?
+        } else {
+            crate::vvec![(String::new(), parse_value(raw_val).ok_or_else(invalid)?)]
+        };
 
         let full_key = if current_section.is_empty() {
             String::from(key)
@@ -150,10 +223,103 @@ pub fn parse(content: &str) -> Result<Table, ParseError> {
             crate::vformat!("{}.{key}", current_section)
         };
 
-        entries.insert(full_key, value);
+        let target = match &current_array {
+            Some(name) => table
+                .arrays
+                .get_mut(name)
+                .and_then(|tables| tables.last_mut()),
+            None => None,
+        };
+        let target = match target {
+            Some(element) => element,
+            None => &mut table,
+        };
+        for (sub, mut value) in values {
+            let key = if sub.is_empty() {
+                full_key.clone()
+            } else {
+                crate::vformat!("{full_key}.{sub}")
+            };
+            match interpolate_value(&mut value, &env::var) {
+                Ok(()) => {
+                    target.unresolved.remove(&key);
+                    target.entries.insert(key, value);
+                }
+                Err(message) => {
+                    target.entries.remove(&key);
+                    target.unresolved.insert(
+                        key,
+                        ParseError {
+                            line: i + 1,
+                            message,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(table)
+}
+
+/// Substitute `${VAR}` and `${VAR:-default}` in every string inside `value`.
+/// As in the shell, the default also applies when `VAR` is set but empty.
+/// `$${` writes a literal `${`.
+fn interpolate_value(
+    value: &mut Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), String> {
+    match value {
+        Value::Str(s) if s.contains("${") => {
+            *s = interpolate(s, lookup)?;
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                interpolate_value(item, lookup)?;
+            }
+        }
+        _ => {}
     }
+    Ok(())
+}
 
-    Ok(Table { entries })
+fn interpolate(s: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        if let Some(text) = rest[..start].strip_suffix('$') {
+            out.push_str(text);
+            out.push_str("${");
+            rest = after;
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let end = after
+            .find('}')
+            .ok_or_else(|| crate::vformat!("unclosed variable reference in \"{s}\""))?;
+        let expr = &after[..end];
+        let (name, default) = match expr.find(":-") {
+            Some(pos) => (&expr[..pos], Some(&expr[pos + 2..])),
+            None => (expr, None),
+        };
+        if name.is_empty() {
+            return Err(crate::vformat!("empty variable name in \"{s}\""));
+        }
+        match (lookup(name), default) {
+            (Some(v), Some(d)) if v.is_empty() => out.push_str(d),
+            (Some(v), _) => out.push_str(&v),
+            (None, Some(d)) => out.push_str(d),
+            (None, None) => {
+                return Err(crate::vformat!(
+                    "environment variable {name} is not set (use ${{{name}:-default}} for a fallback)"
+                ));
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
 }
 
 fn parse_value(raw: &str) -> Option<Value> {
@@ -184,6 +350,59 @@ fn parse_value(raw: &str) -> Option<Value> {
     None
 }
 
+/// Parse `{ a = 1, b = { c = "x" } }` into `[("a", 1), ("b.c", "x")]`.
+/// A trailing comma is accepted.
+fn parse_inline_table(raw: &str) -> Option<Vec<(String, Value)>> {
+    if !raw.ends_with('}') {
+        return None;
+    }
+    let mut result = Vec::new();
+    for pair in split_top_level(&raw[1..raw.len() - 1]) {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        let eq = pair.find('=')?;
+        let key = pair[..eq].trim();
+        let val = pair[eq + 1..].trim();
+        if key.is_empty() {
+            return None;
+        }
+        if val.starts_with('{') {
+            for (sub, value) in parse_inline_table(val)? {
+                result.push((crate::vformat!("{key}.{sub}"), value));
+            }
+        } else {
+            result.push((String::from(key), parse_value(val)?));
+        }
+    }
+    Some(result)
+}
+
+/// Split on commas outside quotes, braces and brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '{' | '[') => depth += 1,
+            (None, '}' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
 fn parse_array_value(raw: &str) -> Option<Value> {
     if !raw.ends_with(']') {
         return None;
@@ -369,4 +588,180 @@ mod tests {
         assert_eq!(entries[1].0.as_str(), "key2");
         assert_eq!(entries[1].1.as_str(), "val2");
     }
+
+    #[test]
+    fn array_of_tables_two_blocks() {
+        let input = "name = \"app\"\n\n[[servers]]\nhost = \"a.local\"\nport = 80\n\n[[servers]]\nhost = \"b.local\"\nweight = 2\n";
+        let t = parse(input).unwrap();
+        let servers = t.get_array_of_tables("servers");
+        assert_eq!(servers.len(), 2);
+        assert_eq!(
+            servers[0].get("", "host").unwrap().as_str(),
+            Some("a.local")
+        );
+        assert_eq!(servers[0].get("", "port").unwrap().as_int(), Some(80));
+        assert!(servers[0].get("", "weight").is_none());
+        assert_eq!(
+            servers[1].get("", "host").unwrap().as_str(),
+            Some("b.local")
+        );
+        assert_eq!(servers[1].get("", "weight").unwrap().as_int(), Some(2));
+        assert!(servers[1].get("", "port").is_none());
+        assert_eq!(t.get("", "name").unwrap().as_str(), Some("app"));
+        assert!(t.get("", "host").is_none());
+    }
+
+    #[test]
+    fn array_of_tables_then_section() {
+        let input =
+            "[[mounts]]\npath = \"/a\"\n[mounts.headers]\nx = \"1\"\n[web]\ndist = \"out\"\n";
+        let t = parse(input).unwrap();
+        let mounts = t.get_array_of_tables("mounts");
+        assert_eq!(mounts.len(), 1);
+        assert_eq!(mounts[0].get("headers", "x").unwrap().as_str(), Some("1"));
+        assert_eq!(t.get("web", "dist").unwrap().as_str(), Some("out"));
+        assert!(!t.has_section("mounts"));
+    }
+
+    #[test]
+    fn array_of_tables_missing() {
+        let t = parse("[web]\ndist = \"out\"").unwrap();
+        assert!(t.get_array_of_tables("servers").is_empty());
+    }
+
+    #[test]
+    fn array_of_tables_unclosed() {
+        let result = parse("[[servers]\nhost = \"a\"");
+        assert!(
+            result
+                .unwrap_err()
+                .message
+                .contains("unclosed array-of-tables")
+        );
+    }
+
+    #[test]
+    fn inline_table_matches_section() {
+        let inline = parse("db = { dialect = \"postgres\" }").unwrap();
+        let section = parse("[db]\ndialect = \"postgres\"").unwrap();
+        assert_eq!(inline.entries().len(), section.entries().len());
+        assert_eq!(inline.get("db", "dialect"), section.get("db", "dialect"));
+        assert_eq!(
+            inline.get("db", "dialect").unwrap().as_str(),
+            Some("postgres")
+        );
+        assert!(inline.has_section("db"));
+    }
+
+    #[test]
+    fn inline_table_nested_and_trailing_comma() {
+        let t = parse(
+            "[db]\nmain = { dialect = \"postgres\", host = \"localhost\", pool = { max = 10, }, }",
+        )
+        .unwrap();
+        assert_eq!(
+            t.get("db.main", "dialect").unwrap().as_str(),
+            Some("postgres")
+        );
+        assert_eq!(
+            t.get("db.main", "host").unwrap().as_str(),
+            Some("localhost")
+        );
+        assert_eq!(t.get("db.main.pool", "max").unwrap().as_int(), Some(10));
+    }
+
+    #[test]
+    fn inline_table_commas_in_values() {
+        let t = parse("x = { s = \"a, b\", list = [1, 2] }").unwrap();
+        assert_eq!(t.get("x", "s").unwrap().as_str(), Some("a, b"));
+        assert_eq!(t.get("x", "list").unwrap().as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn inline_table_invalid() {
+        assert!(parse("x = { a = 1").is_err());
+        assert!(parse("x = { = 1 }").is_err());
+        assert!(parse("x = { a }").is_err());
+    }
+
+    fn fake_env(name: &str) -> Option<String> {
+        match name {
+            "DB_PASSWORD" => Some(String::from("s3cret")),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolate_set_variable() {
+        assert_eq!(
+            interpolate("pw=${DB_PASSWORD}!", &fake_env)
+                .unwrap()
+                .as_str(),
+            "pw=s3cret!"
+        );
+        assert_eq!(interpolate("${EMPTY}", &fake_env).unwrap().as_str(), "");
+        assert_eq!(
+            interpolate("no vars", &fake_env).unwrap().as_str(),
+            "no vars"
+        );
+    }
+
+    #[test]
+    fn interpolate_default_used_when_unset() {
+        assert_eq!(
+            interpolate("${DB_HOST:-localhost}:${DB_PORT:-5432}", &fake_env)
+                .unwrap()
+                .as_str(),
+            "localhost:5432"
+        );
+    }
+
+    #[test]
+    fn interpolate_default_used_when_empty() {
+        assert_eq!(interpolate("${EMPTY:-x}", &fake_env).unwrap().as_str(), "x");
+    }
+
+    #[test]
+    fn interpolate_escaped_reference() {
+        assert_eq!(
+            interpolate("$${DB_PASSWORD} is ${DB_PASSWORD}", &fake_env)
+                .unwrap()
+                .as_str(),
+            "${DB_PASSWORD} is s3cret"
+        );
+        assert_eq!(
+            interpolate("$${UNSET", &fake_env).unwrap().as_str(),
+            "${UNSET"
+        );
+    }
+
+    #[test]
+    fn interpolate_unset_variable_errors() {
+        let err = interpolate("${VOLKI_SURELY_UNSET}", &fake_env).unwrap_err();
+        assert!(err.contains("VOLKI_SURELY_UNSET is not set"));
+        assert!(interpolate("${OPEN", &fake_env).is_err());
+        assert!(interpolate("${}", &fake_env).is_err());
+    }
+
+    #[test]
+    fn parse_interpolates_from_environment() {
+        let path = env::var("PATH").unwrap();
+        let t = parse("[db]\npath = \"${PATH}\"\nhost = \"${VOLKI_TEST_UNSET_VAR:-db.local}\"")
+            .unwrap();
+        assert_eq!(t.get("db", "path").unwrap().as_str(), Some(path.as_str()));
+        assert_eq!(t.get("db", "host").unwrap().as_str(), Some("db.local"));
+    }
+
+    #[test]
+    fn unresolved_key_fails_only_when_read() {
+        let t = parse("[db]\nhost = \"db.local\"\npassword = \"${VOLKI_TEST_UNSET_VAR}\"").unwrap();
+        assert_eq!(t.get("db", "host").unwrap().as_str(), Some("db.local"));
+        assert!(t.get("db", "password").is_none());
+
+        let err = t.try_get("db", "password").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("VOLKI_TEST_UNSET_VAR"));
+        assert!(t.try_get("db", "host").unwrap().is_some());
+    }
 }
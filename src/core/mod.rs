@@ -2,6 +2,7 @@
 pub mod volkiwithstds;
 pub mod utils;
 pub mod cli;
+pub mod compress;
 pub mod config;
 pub mod package;
 pub mod plugins;
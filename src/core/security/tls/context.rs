@@ -15,6 +15,16 @@ unsafe impl Sync for SslContext {}
 impl SslContext {
     /// Create a new server-side SSL context with TLSv1.2+ only.
     pub fn new_server() -> Result<Self, TlsError> {
+        Self::with_method(unsafe { openssl::TLS_server_method() })
+    }
+
+    /// Create a new client-side SSL context with TLSv1.2+ only.
+    /// Peer certificates are not verified (encryption only).
+    pub fn new_client() -> Result<Self, TlsError> {
+        Self::with_method(unsafe { openssl::TLS_client_method() })
+    }
+
+    fn with_method(method: *const openssl::SSL_METHOD) -> Result<Self, TlsError> {
         unsafe {
             // Initialize OpenSSL
             openssl::OPENSSL_init_ssl(
@@ -23,7 +33,6 @@ impl SslContext {
                 core::ptr::null(),
             );
 
-            let method = openssl::TLS_server_method();
             if method.is_null() {
                 return Err(TlsError::InitFailed);
             }
@@ -1,6 +1,7 @@
 //! TLS stream helpers — free functions operating on raw `*mut SSL`.
 
 use crate::core::volkiwithstds::io::error::IoError;
+use crate::core::volkiwithstds::path::CString;
 use crate::core::volkiwithstds::sys::openssl;
 use super::error::{TlsError, get_openssl_error};
 
@@ -38,6 +39,50 @@ pub fn ssl_accept(ssl: *mut openssl::SSL) -> Result<bool, TlsError> {
     }
 }
 
+/// Perform a blocking TLS client handshake.
+///
+/// # Safety
+/// `ssl` must be a live SSL object already bound to a socket.
+pub unsafe fn ssl_connect(ssl: *mut openssl::SSL) -> Result<(), TlsError> {
+    unsafe {
+        openssl::ERR_clear_error();
+        let ret = openssl::SSL_connect(ssl);
+        if ret == 1 {
+            return Ok(());
+        }
+        let err = openssl::SSL_get_error(ssl, ret);
+        match err {
+            openssl::SSL_ERROR_WANT_READ => Err(TlsError::WantRead),
+            openssl::SSL_ERROR_WANT_WRITE => Err(TlsError::WantWrite),
+            openssl::SSL_ERROR_ZERO_RETURN => Err(TlsError::ConnectionClosed),
+            openssl::SSL_ERROR_SYSCALL => {
+                Err(TlsError::SyscallError(IoError::last_os_error()))
+            }
+            _ => Err(TlsError::HandshakeFailed(get_openssl_error())),
+        }
+    }
+}
+
+/// Set the SNI host name sent in the client hello.
+///
+/// # Safety
+/// `ssl` must be a live SSL object.
+pub unsafe fn ssl_set_hostname(ssl: *mut openssl::SSL, host: &str) -> Result<(), TlsError> {
+    let c_host = CString::new(host);
+    unsafe {
+        let ret = openssl::SSL_ctrl(
+            ssl,
+            openssl::SSL_CTRL_SET_TLSEXT_HOSTNAME,
+            openssl::TLSEXT_NAMETYPE_host_name,
+            c_host.as_ptr() as *mut openssl::c_void,
+        );
+        if ret != 1 {
+            return Err(TlsError::InitFailed);
+        }
+    }
+    Ok(())
+}
+
 /// Read decrypted data from a TLS connection.
 /// Returns number of bytes read, or a TLS error.
 pub fn ssl_read(ssl: *mut openssl::SSL, buf: &mut [u8]) -> Result<usize, TlsError> {
@@ -94,6 +139,15 @@ pub fn ssl_write(ssl: *mut openssl::SSL, buf: &[u8]) -> Result<usize, TlsError>
     }
 }
 
+/// Number of decrypted bytes buffered inside OpenSSL, readable without
+/// touching the socket.
+///
+/// # Safety
+/// `ssl` must be a live SSL object.
+pub unsafe fn ssl_pending(ssl: *mut openssl::SSL) -> usize {
+    unsafe { openssl::SSL_pending(ssl).max(0) as usize }
+}
+
 /// Shut down a TLS connection (sends close_notify).
 pub fn ssl_shutdown(ssl: *mut openssl::SSL) {
     unsafe {
@@ -23,6 +23,18 @@ impl LogLevel {
         }
     }
 
+    /// Parses a `VOLKI_LOG` value. Unknown names yield `None`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            "off" => Some(LogLevel::Off),
+            _ => None,
+        }
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             LogLevel::Debug => "debug",
@@ -40,6 +52,18 @@ pub fn set_level(level: LogLevel) {
     LEVEL.store(level as u8, Ordering::Relaxed);
 }
 
+/// Sets the level once at startup: `--verbose` wins, then `VOLKI_LOG`,
+/// otherwise the `Error` default stays.
+pub fn init(verbose: bool) {
+    if verbose {
+        set_level(LogLevel::Debug);
+    } else if let Some(level) =
+        crate::core::volkiwithstds::env::var("VOLKI_LOG").and_then(|v| LogLevel::parse(&v))
+    {
+        set_level(level);
+    }
+}
+
 pub fn level() -> LogLevel {
     LogLevel::from_u8(LEVEL.load(Ordering::Relaxed))
 }
@@ -57,44 +81,52 @@ pub fn log(msg_level: LogLevel, module: &str, msg: &str) {
 #[macro_export]
 macro_rules! log_debug {
     ($($arg:tt)*) => {
-        $crate::core::utils::log::log(
-            $crate::core::utils::log::LogLevel::Debug,
-            module_path!(),
-            &$crate::vformat!($($arg)*),
-        )
+        if $crate::core::utils::log::enabled($crate::core::utils::log::LogLevel::Debug) {
+            $crate::core::utils::log::log(
+                $crate::core::utils::log::LogLevel::Debug,
+                module_path!(),
+                &$crate::vformat!($($arg)*),
+            )
+        }
     };
 }
 
 #[macro_export]
 macro_rules! log_info {
     ($($arg:tt)*) => {
-        $crate::core::utils::log::log(
-            $crate::core::utils::log::LogLevel::Info,
-            module_path!(),
-            &$crate::vformat!($($arg)*),
-        )
+        if $crate::core::utils::log::enabled($crate::core::utils::log::LogLevel::Info) {
+            $crate::core::utils::log::log(
+                $crate::core::utils::log::LogLevel::Info,
+                module_path!(),
+                &$crate::vformat!($($arg)*),
+            )
+        }
     };
 }
 
 #[macro_export]
 macro_rules! log_warn {
     ($($arg:tt)*) => {
-        $crate::core::utils::log::log(
-            $crate::core::utils::log::LogLevel::Warn,
-            module_path!(),
-            &$crate::vformat!($($arg)*),
-        )
+        if $crate::core::utils::log::enabled($crate::core::utils::log::LogLevel::Warn) {
+            $crate::core::utils::log::log(
+                $crate::core::utils::log::LogLevel::Warn,
+                module_path!(),
+                &$crate::vformat!($($arg)*),
+            )
+        }
     };
 }
 
 #[macro_export]
 macro_rules! log_error {
     ($($arg:tt)*) => {
-        $crate::core::utils::log::log(
-            $crate::core::utils::log::LogLevel::Error,
-            module_path!(),
-            &$crate::vformat!($($arg)*),
-        )
+        if $crate::core::utils::log::enabled($crate::core::utils::log::LogLevel::Error) {
+            $crate::core::utils::log::log(
+                $crate::core::utils::log::LogLevel::Error,
+                module_path!(),
+                &$crate::vformat!($($arg)*),
+            )
+        }
     };
 }
 
@@ -169,6 +201,34 @@ mod tests {
         assert!(LogLevel::Error < LogLevel::Off);
     }
 
+    #[test]
+    fn parse_level_names() {
+        assert_eq!(LogLevel::parse("debug"), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::parse(" WARN "), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("off"), Some(LogLevel::Off));
+        assert_eq!(LogLevel::parse("loud"), None);
+    }
+
+    #[test]
+    fn macros_skip_formatting_below_level() {
+        struct Counted<'a>(&'a core::cell::Cell<u32>);
+        impl core::fmt::Display for Counted<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.set(self.0.get() + 1);
+                f.write_str("x")
+            }
+        }
+
+        let formatted = core::cell::Cell::new(0);
+        with_level(LogLevel::Warn, || {
+            log_debug!("{}", Counted(&formatted));
+            log_info!("{}", Counted(&formatted));
+            assert_eq!(formatted.get(), 0);
+            log_warn!("{}", Counted(&formatted));
+            assert_eq!(formatted.get(), 1);
+        });
+    }
+
     #[test]
     fn macros_compile() {
         with_level(LogLevel::Debug, || {
@@ -3,9 +3,12 @@ use crate::core::volkiwithstds::collections::{HashMap, String, Vec};
 #[derive(Debug, Clone)]
 pub enum JsonValue {
     Str(String),
+    Number(f64),
+    Bool(bool),
     Array(Vec<JsonValue>),
     Object(HashMap<String, JsonValue>),
     Null,
+    /// Anything the lenient parser skipped (e.g. nesting past the depth limit).
     Other,
 }
 
@@ -13,6 +16,8 @@ impl PartialEq for JsonValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (JsonValue::Str(a), JsonValue::Str(b)) => a == b,
+            (JsonValue::Number(a), JsonValue::Number(b)) => a == b,
+            (JsonValue::Bool(a), JsonValue::Bool(b)) => a == b,
             (JsonValue::Array(a), JsonValue::Array(b)) => a == b,
             (JsonValue::Object(a), JsonValue::Object(b)) => {
                 if a.len() != b.len() {
@@ -43,6 +48,30 @@ impl JsonValue {
         }
     }
 
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it is integral and fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Number(n) if *n > -9.0e18 && *n < 9.0e18 && *n == (*n as i64) as f64 => {
+                Some(*n as i64)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
     pub fn as_array(&self) -> Option<&[JsonValue]> {
         match self {
             JsonValue::Array(a) => Some(a),
@@ -67,8 +96,8 @@ enum Token {
     Colon,
     Comma,
     Str(String),
-    Number,
-    Bool,
+    Number(f64),
+    Bool(bool),
     Null,
 }
 
@@ -134,16 +163,19 @@ impl<'a> Tokenizer<'a> {
             }
             b'"' => self.read_string().map(Token::Str),
             b't' | b'f' => {
+                let start = self.pos;
                 self.skip_literal();
-                Some(Token::Bool)
+                Some(Token::Bool(&self.data[start..self.pos] == b"true"))
             }
             b'n' => {
                 self.skip_literal();
                 Some(Token::Null)
             }
             b'0'..=b'9' | b'-' => {
+                let start = self.pos;
                 self.skip_number();
-                Some(Token::Number)
+                let text = core::str::from_utf8(&self.data[start..self.pos]).unwrap_or("");
+                Some(Token::Number(text.parse().unwrap_or(0.0)))
             }
             _ => {
                 self.pos += 1;
@@ -213,14 +245,36 @@ impl<'a> Tokenizer<'a> {
         Some(result)
     }
 
+    /// Reads the four hex digits after `\u`. A high surrogate followed by
+    /// an escaped low surrogate combines into one character; an unpaired
+    /// surrogate becomes U+FFFD.
     fn read_unicode_escape(&mut self) -> Option<char> {
+        let code = self.read_hex4()?;
+        if !(0xD800..0xE000).contains(&code) {
+            return char::from_u32(code);
+        }
+        if code < 0xDC00 && self.data[self.pos..].starts_with(b"\\u") {
+            let save = self.pos;
+            self.pos += 2;
+            match self.read_hex4() {
+                Some(low) if (0xDC00..0xE000).contains(&low) => {
+                    let combined = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                    return char::from_u32(combined);
+                }
+                _ => self.pos = save,
+            }
+        }
+        Some('\u{FFFD}')
+    }
+
+    fn read_hex4(&mut self) -> Option<u32> {
         if self.pos + 4 > self.data.len() {
             return None;
         }
         let hex = core::str::from_utf8(&self.data[self.pos..self.pos + 4]).ok()?;
         let code = u32::from_str_radix(hex, 16).ok()?;
         self.pos += 4;
-        char::from_u32(code)
+        Some(code)
     }
 
     fn skip_literal(&mut self) {
@@ -255,7 +309,8 @@ fn parse_value(tok: &mut Tokenizer, depth: u32) -> JsonValue {
         Token::ObjectStart => parse_object(tok, depth),
         Token::ArrayStart => parse_array(tok, depth),
         Token::Null => JsonValue::Null,
-        Token::Number | Token::Bool => JsonValue::Other,
+        Token::Number(n) => JsonValue::Number(n),
+        Token::Bool(b) => JsonValue::Bool(b),
         _ => JsonValue::Other,
     }
 }
@@ -363,6 +418,251 @@ fn skip_value(tok: &mut Tokenizer) {
     }
 }
 
+/// Append `s` to `out` as a quoted JSON string literal.
+pub fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&crate::vformat!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Why [`parse`] rejected a document. `pos` is a byte offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    pub pos: usize,
+    pub message: &'static str,
+}
+
+impl core::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.pos)
+    }
+}
+
+/// Deepest nesting [`parse`] accepts.
+const STRICT_MAX_DEPTH: u32 = 128;
+
+/// Parses a JSON document per RFC 8259, rejecting trailing commas,
+/// unescaped control characters in strings, invalid escapes, malformed
+/// numbers and trailing content.
+pub fn parse(json: &str) -> Result<JsonValue, JsonError> {
+    let mut p = Strict {
+        data: json.as_bytes(),
+        pos: 0,
+    };
+    if p.data.starts_with(b"\xEF\xBB\xBF") {
+        p.pos = 3;
+    }
+    let value = p.value(0)?;
+    p.skip_whitespace();
+    if p.pos < p.data.len() {
+        return Err(p.error("unexpected content after the document"));
+    }
+    Ok(value)
+}
+
+struct Strict<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Strict<'_> {
+    fn error(&self, message: &'static str) -> JsonError {
+        JsonError {
+            pos: self.pos,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(message));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self, depth: u32) -> Result<JsonValue, JsonError> {
+        if depth > STRICT_MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string().map(JsonValue::Str),
+            Some(b't') => self.literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.literal("null", JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self, depth: u32) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut map = HashMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(map));
+        }
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'"') => {}
+                Some(b'}') => return Err(self.error("trailing comma in object")),
+                _ => return Err(self.error("expected a string key")),
+            }
+            let key = self.string()?;
+            self.expect(b':', "expected ':' after object key")?;
+            let value = self.value(depth + 1)?;
+            map.insert(key, value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(map));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: u32) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b']') {
+                return Err(self.error("trailing comma in array"));
+            }
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Validates the string at `pos`, then decodes it with the lenient
+    /// tokenizer, which handles escapes and surrogate pairs.
+    fn string(&mut self) -> Result<String, JsonError> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => match self.data.get(self.pos + 1) {
+                    Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => self.pos += 2,
+                    Some(b'u') => {
+                        let hex = self.data.get(self.pos + 2..self.pos + 6);
+                        if !hex.is_some_and(|h| h.iter().all(u8::is_ascii_hexdigit)) {
+                            return Err(self.error("invalid \\u escape"));
+                        }
+                        self.pos += 6;
+                    }
+                    _ => return Err(self.error("invalid escape in string")),
+                },
+                Some(b) if b < 0x20 => return Err(self.error("unescaped control character in string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        let mut tok = Tokenizer {
+            data: self.data,
+            pos: start,
+        };
+        Ok(tok.read_string().unwrap_or_default())
+    }
+
+    fn literal(&mut self, word: &'static str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if !self.data[self.pos..].starts_with(word.as_bytes()) {
+            return Err(self.error("invalid literal"));
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    /// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => {
+                self.pos += 1;
+                if self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                    return Err(self.error("leading zero in number"));
+                }
+            }
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("expected a digit")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("expected a digit after '.'"));
+            }
+            self.digits();
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("expected a digit in exponent"));
+            }
+            self.digits();
+        }
+        let text = core::str::from_utf8(&self.data[start..self.pos]).unwrap_or("0");
+        Ok(JsonValue::Number(text.parse().unwrap_or(0.0)))
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+}
+
 pub fn extract_top_level(json: &str) -> HashMap<String, JsonValue> {
     let mut tok = Tokenizer::new(json.as_bytes());
     match parse_value(&mut tok, 0) {
@@ -370,3 +670,212 @@ pub fn extract_top_level(json: &str) -> HashMap<String, JsonValue> {
         _ => HashMap::new(),
     }
 }
+
+/// Types that can be serialized as JSON.
+pub trait ToJson {
+    fn write_json(&self, out: &mut String);
+
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+}
+
+impl ToJson for str {
+    fn write_json(&self, out: &mut String) {
+        write_string(out, self);
+    }
+}
+
+impl ToJson for String {
+    fn write_json(&self, out: &mut String) {
+        write_string(out, self);
+    }
+}
+
+impl ToJson for bool {
+    fn write_json(&self, out: &mut String) {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+macro_rules! to_json_int {
+    ($($t:ty),*) => {
+        $(impl ToJson for $t {
+            fn write_json(&self, out: &mut String) {
+                out.push_str(&crate::vformat!("{self}"));
+            }
+        })*
+    };
+}
+
+to_json_int!(i32, i64, u32, u64, usize);
+
+impl ToJson for f64 {
+    /// JSON has no NaN or Infinity; those serialize as `null`.
+    fn write_json(&self, out: &mut String) {
+        if self.is_finite() {
+            out.push_str(&crate::vformat!("{self}"));
+        } else {
+            out.push_str("null");
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Some(v) => v.write_json(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn write_json(&self, out: &mut String) {
+        out.push('[');
+        for (i, v) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            v.write_json(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn write_json(&self, out: &mut String) {
+        self.as_slice().write_json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    /// Keys are written in sorted order so output is deterministic.
+    fn write_json(&self, out: &mut String) {
+        let mut keys: Vec<&String> = self.keys().collect();
+        keys.sort();
+        out.push('{');
+        for (i, k) in keys.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write_string(out, k);
+            out.push(':');
+            if let Some(v) = self.get(k.as_str()) {
+                v.write_json(out);
+            }
+        }
+        out.push('}');
+    }
+}
+
+impl ToJson for JsonValue {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            JsonValue::Str(s) => write_string(out, s),
+            JsonValue::Number(n) => n.write_json(out),
+            JsonValue::Bool(b) => b.write_json(out),
+            JsonValue::Array(a) => a.write_json(out),
+            JsonValue::Object(o) => o.write_json(out),
+            JsonValue::Null | JsonValue::Other => out.push_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn write_json(&self, out: &mut String) {
+        (**self).write_json(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vvec;
+
+    #[test]
+    fn test_parse_scalars() {
+        let v = parse(r#"{"n": -1.5e2, "i": 42, "t": true, "f": false, "z": null}"#).unwrap();
+        let o = v.as_object().unwrap();
+        assert_eq!(o.get("n").unwrap().as_f64(), Some(-150.0));
+        assert_eq!(o.get("i").unwrap().as_i64(), Some(42));
+        assert_eq!(o.get("t").unwrap().as_bool(), Some(true));
+        assert_eq!(o.get("f").unwrap().as_bool(), Some(false));
+        assert_eq!(o.get("z"), Some(&JsonValue::Null));
+        assert_eq!(o.get("n").unwrap().as_i64(), Some(-150));
+        assert_eq!(JsonValue::Number(1.5).as_i64(), None);
+    }
+
+    #[test]
+    fn test_to_json_scalars() {
+        assert_eq!("a\"b".to_json().as_str(), "\"a\\\"b\"");
+        assert_eq!(true.to_json().as_str(), "true");
+        assert_eq!(42i64.to_json().as_str(), "42");
+        assert_eq!(1.5f64.to_json().as_str(), "1.5");
+        assert_eq!(f64::NAN.to_json().as_str(), "null");
+        assert_eq!(None::<i32>.to_json().as_str(), "null");
+    }
+
+    #[test]
+    fn test_to_json_collections() {
+        let list = vvec![1u32, 2, 3];
+        assert_eq!(list.to_json().as_str(), "[1,2,3]");
+
+        let mut map = HashMap::new();
+        map.insert(String::from("b"), vvec![String::from("x")]);
+        map.insert(String::from("a"), Vec::new());
+        assert_eq!(map.to_json().as_str(), r#"{"a":[],"b":["x"]}"#);
+    }
+
+    #[test]
+    fn test_json_value_round_trip() {
+        let src = r#"{"items":[1,"two",false,null],"nested":{"k":2.5}}"#;
+        let v = parse(src).unwrap();
+        assert_eq!(v.to_json().as_str(), src);
+        assert_eq!(parse(v.to_json().as_str()).unwrap(), v);
+    }
+
+    #[test]
+    fn test_control_characters_escaped() {
+        let s = "a\u{0}\u{8}\u{c}\n\r\t\u{1f}é";
+        assert_eq!(s.to_json().as_str(), "\"a\\u0000\\b\\f\\n\\r\\t\\u001fé\"");
+        assert_eq!(parse(s.to_json().as_str()).unwrap().as_str(), Some(s));
+    }
+
+    #[test]
+    fn test_surrogate_pairs_and_newlines_round_trip() {
+        let v = parse(r#""\ud83d\ude00 ok""#).unwrap();
+        assert_eq!(v.as_str(), Some("😀 ok"));
+        // Unpaired surrogates decode to the replacement character
+        assert_eq!(parse(r#""\ud83d!""#).unwrap().as_str(), Some("\u{FFFD}!"));
+
+        let text = "line one\nline two 🎉";
+        let json = text.to_json();
+        assert_eq!(json.as_str(), "\"line one\\nline two 🎉\"");
+        assert_eq!(parse(json.as_str()).unwrap().as_str(), Some(text));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert!(parse(r#"{"a":[1,2.5e3,-0,true,null],"b":{}}"#).is_ok());
+        for bad in [
+            "[1,]",
+            r#"{"a":1,}"#,
+            "\"a\nb\"",
+            r#""\q""#,
+            "01",
+            "1.",
+            "[1] x",
+            "{a:1}",
+            "tru",
+            "",
+        ] {
+            assert!(parse(bad).is_err(), "accepted {bad:?}");
+        }
+        let err = parse("[1,]").unwrap_err();
+        assert_eq!(err.message, "trailing comma in array");
+        assert_eq!(err.pos, 3);
+    }
+}
@@ -73,6 +73,11 @@ impl String {
         self.as_str().trim()
     }
 
+    /// Returns an iterator over the non-empty, whitespace-separated parts.
+    pub fn split_whitespace(&self) -> core::str::SplitWhitespace<'_> {
+        self.as_str().split_whitespace()
+    }
+
     /// Whether the string starts with a pattern.
     pub fn starts_with(&self, pat: &str) -> bool {
         self.as_str().starts_with(pat)
@@ -415,4 +420,27 @@ mod tests {
         write!(s, "hello {}", 42).unwrap();
         assert_eq!(s.as_str(), "hello 42");
     }
+
+    #[test]
+    fn test_split_whitespace_mixed_runs() {
+        let s = String::from("  flex\t\titems-center \n\r\n p-4  \t");
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        assert_eq!(parts.as_slice(), &["flex", "items-center", "p-4"]);
+        assert_eq!(String::from(" \t\n ").split_whitespace().count(), 0);
+    }
+
+    #[test]
+    fn test_trim_matches_repeated_chars() {
+        // The pattern-taking trims come from `str` through `Deref`
+        let s = String::from("//api/users///");
+        assert_eq!(s.trim_matches('/'), "api/users");
+        assert_eq!(s.trim_start_matches('/'), "api/users///");
+        assert_eq!(s.trim_end_matches('/'), "//api/users");
+        assert_eq!(String::from("xxyxx").trim_matches('x'), "y");
+        assert_eq!(
+            String::from("__init__").trim_matches(|c: char| c == '_'),
+            "init"
+        );
+        assert_eq!(String::from("0012300").trim_start_matches("00"), "12300");
+    }
 }
@@ -210,36 +210,13 @@ impl<T> Vec<T> {
         self.sort_by(|a, b| a.cmp(b));
     }
 
-    /// Sort with a custom comparison function.
+    /// Sort (stable) with a custom comparison function. Elements that
+    /// compare equal keep their original order.
     pub fn sort_by<F: FnMut(&T, &T) -> core::cmp::Ordering>(&mut self, mut compare: F) {
-        let len = self.len;
-        if len <= 1 {
-            return;
-        }
-        if len <= 32 {
-            // Insertion sort for small arrays
-            for i in 1..len {
-                let mut j = i;
-                while j > 0
-                    && compare(unsafe { &*self.buf.ptr().add(j - 1) }, unsafe {
-                        &*self.buf.ptr().add(j)
-                    }) == core::cmp::Ordering::Greater
-                {
-                    unsafe {
-                        let a = self.buf.ptr().add(j - 1);
-                        let b = self.buf.ptr().add(j);
-                        ptr::swap(a, b);
-                    }
-                    j -= 1;
-                }
-            }
-        } else {
-            // Simple in-place merge sort
-            merge_sort(self.as_mut_slice(), &mut compare);
-        }
+        merge_sort(self.as_mut_slice(), &mut compare);
     }
 
-    /// Sort by a key extraction function.
+    /// Sort (stable) by a key extraction function.
     pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
         self.sort_by(|a, b| f(a).cmp(&f(b)));
     }
@@ -443,7 +420,8 @@ impl<T> Vec<T> {
     }
 }
 
-/// Merge sort for slices.
+/// Stable merge sort for slices: insertion sort below 32 elements, and an
+/// in-place merge that never moves an element past an equal one.
 fn merge_sort<T, F: FnMut(&T, &T) -> core::cmp::Ordering>(slice: &mut [T], compare: &mut F) {
     let len = slice.len();
     if len <= 1 {
@@ -464,23 +442,17 @@ fn merge_sort<T, F: FnMut(&T, &T) -> core::cmp::Ordering>(slice: &mut [T], compa
     merge_sort(&mut slice[..mid], compare);
     merge_sort(&mut slice[mid..], compare);
 
-    // Merge in-place using rotation
+    // Merge in-place: a right-half element that sorts strictly before
+    // slice[left] is rotated into place; ties leave the left element first
     let mut left = 0;
     let mut right = mid;
     while left < right && right < len {
         if compare(&slice[left], &slice[right]) != core::cmp::Ordering::Greater {
             left += 1;
         } else {
-            // Rotate slice[left..=right] so that slice[right] moves to slice[left]
-            let val_right = right;
-            let mut j = right;
-            while j > left {
-                slice.swap(j, j - 1);
-                j -= 1;
-            }
+            slice[left..=right].rotate_right(1);
             left += 1;
             right += 1;
-            let _ = val_right;
         }
     }
 }
@@ -775,6 +747,27 @@ mod tests {
         assert_eq!(v[2], 3);
     }
 
+    #[test]
+    fn test_sort_by_key_is_stable() {
+        struct Row {
+            score: u32,
+            id: usize,
+        }
+        // Enough rows to go through the merge path, with many ties
+        let mut rows: Vec<Row> = (0..100).map(|id| Row { score: (id * 7 % 5) as u32, id }).collect();
+        rows.sort_by_key(|r| r.score);
+        for pair in rows.windows(2) {
+            assert!(pair[0].score <= pair[1].score);
+            if pair[0].score == pair[1].score {
+                assert!(pair[0].id < pair[1].id);
+            }
+        }
+
+        let mut small = crate::vvec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+        small.sort_by(|a, b| b.0.cmp(&a.0));
+        assert_eq!(small.as_slice(), &[(2, 'a'), (2, 'c'), (1, 'b'), (1, 'd')]);
+    }
+
     #[test]
     fn test_from_iterator() {
         let v: Vec<i32> = [1, 2, 3].iter().copied().collect();
@@ -69,17 +69,21 @@ impl Iterator for ReadDir {
                 continue;
             }
 
+            let path = self.parent.join(name.as_str());
+
             let file_type = unsafe {
                 match (*entry).d_type {
                     4 => FileType::Directory, // DT_DIR
                     8 => FileType::File,      // DT_REG
                     10 => FileType::Symlink,  // DT_LNK
+                    // DT_UNKNOWN: the filesystem doesn't fill d_type, so ask lstat
+                    0 => super::symlink_metadata(path.as_path())
+                        .map(|m| m.file_type())
+                        .unwrap_or(FileType::Other),
                     _ => FileType::Other,
                 }
             };
 
-            let path = self.parent.join(name.as_str());
-
             return Some(Ok(DirEntry {
                 path: PathBuf::from(path.as_str()),
                 name,
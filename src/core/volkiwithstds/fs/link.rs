@@ -0,0 +1,88 @@
+//! Symbolic links — symlink, read_link.
+
+use crate::core::volkiwithstds::collections::Vec;
+use crate::core::volkiwithstds::io::error::{IoError, IoErrorKind, Result};
+use crate::core::volkiwithstds::path::{Path, PathBuf};
+use crate::core::volkiwithstds::sys::syscalls;
+
+/// Create a symbolic link at `link` pointing to `target`. The target is
+/// stored as given, so a relative target resolves against the link's directory.
+pub fn symlink(target: &Path, link: &Path) -> Result<()> {
+    let c_target = target.to_c_string();
+    let c_link = link.to_c_string();
+    let ret = unsafe { syscalls::symlink(c_target.as_ptr(), c_link.as_ptr()) };
+    if ret != 0 {
+        return Err(IoError::last_os_error());
+    }
+    Ok(())
+}
+
+/// Read the target stored in a symbolic link.
+pub fn read_link(path: &Path) -> Result<PathBuf> {
+    let c_path = path.to_c_string();
+    let mut cap = 256;
+    loop {
+        let mut buf: Vec<u8> = Vec::with_capacity(cap);
+        let n = unsafe {
+            syscalls::readlink(
+                c_path.as_ptr(),
+                buf.as_mut_ptr() as *mut syscalls::c_char,
+                cap,
+            )
+        };
+        if n < 0 {
+            return Err(IoError::last_os_error());
+        }
+        let n = n as usize;
+        // readlink truncates silently; a full buffer may mean a longer target
+        if n == cap {
+            cap *= 2;
+            continue;
+        }
+        unsafe { buf.set_len(n) };
+        return match core::str::from_utf8(buf.as_slice()) {
+            Ok(s) => Ok(PathBuf::from(s)),
+            Err(_) => Err(IoError::new(
+                IoErrorKind::InvalidData,
+                "symlink target is not valid UTF-8",
+            )),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::{env, fs};
+
+    #[test]
+    fn test_symlink_read_back_and_file_type() {
+        let dir = env::temp_dir().join("volki_symlink_test");
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.as_path()).unwrap();
+        fs::write_str(dir.join("target.txt").as_path(), "hello").unwrap();
+
+        let link = dir.join("link.txt");
+        symlink(Path::new("target.txt"), link.as_path()).unwrap();
+
+        assert_eq!(read_link(link.as_path()).unwrap().as_str(), "target.txt");
+        assert_eq!(fs::read_to_string(link.as_path()).unwrap().as_str(), "hello");
+
+        let entry = fs::read_dir(dir.as_path())
+            .unwrap()
+            .flatten()
+            .find(|e| e.file_name() == "link.txt")
+            .unwrap();
+        assert_eq!(entry.file_type(), fs::FileType::Symlink);
+
+        let meta = fs::symlink_metadata(link.as_path()).unwrap();
+        assert!(meta.is_symlink());
+        assert_eq!(meta.file_type(), fs::FileType::Symlink);
+        assert_eq!(fs::metadata(link.as_path()).unwrap().file_type(), fs::FileType::File);
+
+        assert!(read_link(dir.join("target.txt").as_path()).is_err());
+        assert!(symlink(Path::new("x"), link.as_path()).is_err());
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+}
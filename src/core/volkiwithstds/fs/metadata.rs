@@ -1,12 +1,17 @@
-//! Filesystem metadata — exists, is_dir, is_file via stat().
+//! Filesystem metadata — exists, is_dir, is_file via stat()/lstat().
 
+use super::dir::FileType;
 use crate::core::volkiwithstds::path::Path;
 use crate::core::volkiwithstds::sys::syscalls;
+use crate::core::volkiwithstds::time::Duration;
 
 /// Metadata about a filesystem entry.
 pub struct Metadata {
     mode: u32,
     size: u64,
+    modified: Duration,
+    dev: u64,
+    ino: u64,
 }
 
 impl Metadata {
@@ -15,6 +20,21 @@ impl Metadata {
         self.size
     }
 
+    /// Returns the last modification time as a duration since the Unix epoch.
+    pub fn modified(&self) -> Duration {
+        self.modified
+    }
+
+    /// ID of the device containing the entry.
+    pub fn dev(&self) -> u64 {
+        self.dev
+    }
+
+    /// Inode number. Together with `dev` it identifies the entry on disk.
+    pub fn ino(&self) -> u64 {
+        self.ino
+    }
+
     /// Returns true if this is a directory.
     pub fn is_dir(&self) -> bool {
         (self.mode & syscalls::S_IFMT) == syscalls::S_IFDIR
@@ -24,13 +44,41 @@ impl Metadata {
     pub fn is_file(&self) -> bool {
         (self.mode & syscalls::S_IFMT) == syscalls::S_IFREG
     }
+
+    /// The kind of entry, as `read_dir` would report it.
+    pub fn file_type(&self) -> FileType {
+        match self.mode & syscalls::S_IFMT {
+            syscalls::S_IFDIR => FileType::Directory,
+            syscalls::S_IFREG => FileType::File,
+            syscalls::S_IFLNK => FileType::Symlink,
+            _ => FileType::Other,
+        }
+    }
+
+    /// Returns true if this is a symbolic link. Only possible for metadata
+    /// from `symlink_metadata`, since `metadata` follows links.
+    pub fn is_symlink(&self) -> bool {
+        (self.mode & syscalls::S_IFMT) == syscalls::S_IFLNK
+    }
 }
 
-/// Get metadata for a path.
+/// Get metadata for a path, following symlinks.
 pub fn metadata(path: &Path) -> crate::core::volkiwithstds::io::Result<Metadata> {
+    stat_with(path, syscalls::stat)
+}
+
+/// Get metadata for a path without following a final symlink.
+pub fn symlink_metadata(path: &Path) -> crate::core::volkiwithstds::io::Result<Metadata> {
+    stat_with(path, syscalls::lstat)
+}
+
+fn stat_with(
+    path: &Path,
+    stat: unsafe extern "C" fn(*const syscalls::c_char, *mut syscalls::stat_buf) -> syscalls::c_int,
+) -> crate::core::volkiwithstds::io::Result<Metadata> {
     let c_path = path.to_c_string();
     let mut stat_buf: syscalls::stat_buf = unsafe { core::mem::zeroed() };
-    let ret = unsafe { syscalls::stat(c_path.as_ptr(), &mut stat_buf) };
+    let ret = unsafe { stat(c_path.as_ptr(), &mut stat_buf) };
     if ret != 0 {
         return Err(crate::core::volkiwithstds::io::IoError::last_os_error());
     }
@@ -43,6 +91,9 @@ pub fn metadata(path: &Path) -> crate::core::volkiwithstds::io::Result<Metadata>
     Ok(Metadata {
         mode,
         size: stat_buf.st_size as u64,
+        modified: Duration::new(stat_buf.st_mtime as u64, stat_buf.st_mtime_nsec as u32),
+        dev: stat_buf.st_dev as u64,
+        ino: stat_buf.st_ino,
     })
 }
 
@@ -60,3 +111,33 @@ pub fn is_dir(path: &Path) -> bool {
 pub fn is_file(path: &Path) -> bool {
     metadata(path).map(|m| m.is_file()).unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::{env, fs};
+
+    #[test]
+    fn test_metadata_len_modified_and_kind() {
+        let dir = env::temp_dir().join("volki_metadata_test");
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.as_path()).unwrap();
+        let file = dir.join("data.bin");
+        fs::write(file.as_path(), &[7u8; 1234]).unwrap();
+
+        let meta = metadata(file.as_path()).unwrap();
+        assert_eq!(meta.len(), 1234);
+        assert!(meta.is_file());
+        assert!(!meta.is_dir());
+        assert!(!meta.is_symlink());
+        // Written just now, so well after 2020-01-01
+        assert!(meta.modified().as_secs() > 1_577_836_800);
+
+        let dir_meta = symlink_metadata(dir.as_path()).unwrap();
+        assert!(dir_meta.is_dir());
+        assert!(!dir_meta.is_symlink());
+        assert!(metadata(dir.join("missing").as_path()).is_err());
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+}
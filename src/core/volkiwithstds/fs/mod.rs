@@ -2,8 +2,10 @@
 
 pub mod dir;
 pub mod file;
+pub mod link;
 pub mod metadata;
 
 pub use dir::{create_dir, create_dir_all, read_dir, remove_dir, remove_dir_all, remove_file, DirEntry, FileType, ReadDir};
 pub use file::{read, read_to_string, write, write_str, File};
-pub use metadata::{exists, is_dir, is_file, metadata, Metadata};
+pub use link::{read_link, symlink};
+pub use metadata::{exists, is_dir, is_file, metadata, symlink_metadata, Metadata};
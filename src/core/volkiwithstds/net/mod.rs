@@ -1,9 +1,11 @@
-//! Networking — TcpStream.
+//! Networking — TcpStream, TcpListener, UdpSocket.
 
+use crate::core::volkiwithstds::collections::String;
 use crate::core::volkiwithstds::io::error::{IoError, IoErrorKind, Result};
 use crate::core::volkiwithstds::io::traits::{Read, Write};
 use crate::core::volkiwithstds::path::CString;
 use crate::core::volkiwithstds::sys::{errno, syscalls};
+use core::fmt;
 
 /// A TCP stream connected to a remote host.
 pub struct TcpStream {
@@ -13,6 +15,16 @@ pub struct TcpStream {
 impl TcpStream {
     /// Connect to a remote host.
     pub fn connect(addr: (&str, u16)) -> Result<Self> {
+        Self::connect_inner(addr, None)
+    }
+
+    /// Connect to a remote host, failing with `TimedOut` if the TCP
+    /// handshake takes longer than `timeout_ms`.
+    pub fn connect_timeout(addr: (&str, u16), timeout_ms: i32) -> Result<Self> {
+        Self::connect_inner(addr, Some(timeout_ms))
+    }
+
+    fn connect_inner(addr: (&str, u16), timeout_ms: Option<i32>) -> Result<Self> {
         let (host, port) = addr;
 
         let c_host = CString::new(host);
@@ -48,19 +60,79 @@ impl TcpStream {
             unsafe { syscalls::freeaddrinfo(result); }
             return Err(IoError::last_os_error());
         }
+        // Closes the socket on any early return below
+        let stream = Self { fd };
+        if timeout_ms.is_some()
+            && let Err(e) = stream.set_nonblocking(true)
+        {
+            unsafe { syscalls::freeaddrinfo(result); }
+            return Err(e);
+        }
 
         let connect_ret = unsafe {
             syscalls::connect(fd, ai.ai_addr as *const syscalls::sockaddr, ai.ai_addrlen)
         };
+        let connect_err = if connect_ret < 0 { errno::get_errno() } else { 0 };
 
         unsafe { syscalls::freeaddrinfo(result); }
 
-        if connect_ret < 0 {
-            unsafe { syscalls::close(fd); }
-            return Err(IoError::last_os_error());
+        match timeout_ms {
+            None if connect_ret < 0 => return Err(IoError::from_errno(connect_err)),
+            None => {}
+            Some(ms) => {
+                if connect_ret < 0 {
+                    if connect_err != errno::EINPROGRESS {
+                        return Err(IoError::from_errno(connect_err));
+                    }
+                    stream.finish_connect(ms)?;
+                }
+                stream.set_nonblocking(false)?;
+            }
         }
 
-        Ok(Self { fd })
+        Ok(stream)
+    }
+
+    /// Wait for a non-blocking connect to complete and report its outcome.
+    fn finish_connect(&self, timeout_ms: i32) -> Result<()> {
+        let mut fds = syscalls::pollfd {
+            fd: self.fd,
+            events: syscalls::POLLOUT,
+            revents: 0,
+        };
+        loop {
+            let ret = unsafe { syscalls::poll(&mut fds, 1, timeout_ms) };
+            if ret < 0 {
+                let err = errno::get_errno();
+                if err == errno::EINTR {
+                    continue;
+                }
+                return Err(IoError::from_errno(err));
+            }
+            if ret == 0 {
+                return Err(IoError::new(IoErrorKind::TimedOut, "connection timed out"));
+            }
+            break;
+        }
+
+        let mut err: syscalls::c_int = 0;
+        let mut len = core::mem::size_of::<syscalls::c_int>() as u32;
+        let ret = unsafe {
+            syscalls::getsockopt(
+                self.fd,
+                syscalls::SOL_SOCKET,
+                syscalls::SO_ERROR,
+                &mut err as *mut syscalls::c_int as *mut syscalls::c_void,
+                &mut len,
+            )
+        };
+        if ret < 0 {
+            return Err(IoError::last_os_error());
+        }
+        if err != 0 {
+            return Err(IoError::from_errno(err));
+        }
+        Ok(())
     }
 
     /// Set non-blocking mode.
@@ -81,10 +153,41 @@ impl TcpStream {
         Ok(())
     }
 
+    /// The remote address, formatted as `ip:port`.
+    pub fn peer_addr(&self) -> Result<String> {
+        socket_addr(self.fd, syscalls::getpeername).map(|a| crate::vformat!("{}", a))
+    }
+
+    /// The local address, formatted as `ip:port`.
+    pub fn local_addr(&self) -> Result<String> {
+        socket_addr(self.fd, syscalls::getsockname).map(|a| crate::vformat!("{}", a))
+    }
+
     /// Returns the raw file descriptor.
     pub fn as_raw_fd(&self) -> i32 {
         self.fd
     }
+
+    /// Block until data can be read or `timeout_ms` passes. Returns whether
+    /// the stream became readable; end-of-stream counts as readable.
+    pub fn wait_readable(&self, timeout_ms: i32) -> Result<bool> {
+        let mut fds = syscalls::pollfd {
+            fd: self.fd,
+            events: syscalls::POLLIN,
+            revents: 0,
+        };
+        loop {
+            let ret = unsafe { syscalls::poll(&mut fds, 1, timeout_ms) };
+            if ret < 0 {
+                let err = errno::get_errno();
+                if err == errno::EINTR {
+                    continue;
+                }
+                return Err(IoError::from_errno(err));
+            }
+            return Ok(ret > 0);
+        }
+    }
 }
 
 impl Read for TcpStream {
@@ -227,6 +330,11 @@ impl TcpListener {
         Ok(TcpStream { fd: client_fd })
     }
 
+    /// The bound address, formatted as `ip:port`.
+    pub fn local_addr(&self) -> Result<String> {
+        socket_addr(self.fd, syscalls::getsockname).map(|a| crate::vformat!("{}", a))
+    }
+
     /// Set non-blocking mode.
     pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
         let flags = unsafe { syscalls::fcntl(self.fd, syscalls::F_GETFL) };
@@ -259,6 +367,223 @@ impl Drop for TcpListener {
     }
 }
 
+/// An IPv4 address and port, as reported for a datagram's sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketAddrV4 {
+    pub ip: [u8; 4],
+    pub port: u16,
+}
+
+impl SocketAddrV4 {
+    fn from_sockaddr(addr: &syscalls::sockaddr_in) -> Self {
+        Self {
+            ip: addr.sin_addr.to_ne_bytes(),
+            port: u16::from_be(addr.sin_port),
+        }
+    }
+}
+
+impl fmt::Display for SocketAddrV4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.ip;
+        write!(f, "{}.{}.{}.{}:{}", a, b, c, d, self.port)
+    }
+}
+
+/// A UDP socket.
+pub struct UdpSocket {
+    fd: i32,
+}
+
+impl UdpSocket {
+    /// Bind to the given address. Port 0 picks an ephemeral port.
+    pub fn bind(addr: (&str, u16)) -> Result<Self> {
+        let ai = AddrInfo::resolve(addr, syscalls::SOCK_DGRAM, syscalls::AI_PASSIVE)?;
+        let info = ai.first();
+        let fd = unsafe { syscalls::socket(info.ai_family, info.ai_socktype, info.ai_protocol) };
+        if fd < 0 {
+            return Err(IoError::last_os_error());
+        }
+        let ret = unsafe { syscalls::bind(fd, info.ai_addr as *const syscalls::sockaddr, info.ai_addrlen) };
+        if ret < 0 {
+            let err = IoError::last_os_error();
+            unsafe { syscalls::close(fd); }
+            return Err(err);
+        }
+        Ok(Self { fd })
+    }
+
+    /// Set the default peer for `send`/`recv` and filter out datagrams
+    /// from anyone else.
+    pub fn connect(&self, addr: (&str, u16)) -> Result<()> {
+        let ai = AddrInfo::resolve(addr, syscalls::SOCK_DGRAM, 0)?;
+        let info = ai.first();
+        let ret = unsafe { syscalls::connect(self.fd, info.ai_addr as *const syscalls::sockaddr, info.ai_addrlen) };
+        if ret < 0 {
+            return Err(IoError::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Send a datagram to the connected peer.
+    pub fn send(&self, buf: &[u8]) -> Result<usize> {
+        retry_eintr(|| unsafe {
+            syscalls::send(self.fd, buf.as_ptr() as *const syscalls::c_void, buf.len(), 0)
+        })
+    }
+
+    /// Send a datagram to `addr`.
+    pub fn send_to(&self, buf: &[u8], addr: (&str, u16)) -> Result<usize> {
+        let ai = AddrInfo::resolve(addr, syscalls::SOCK_DGRAM, 0)?;
+        let info = ai.first();
+        retry_eintr(|| unsafe {
+            syscalls::sendto(
+                self.fd,
+                buf.as_ptr() as *const syscalls::c_void,
+                buf.len(),
+                0,
+                info.ai_addr as *const syscalls::sockaddr,
+                info.ai_addrlen,
+            )
+        })
+    }
+
+    /// Receive one datagram from the connected peer. Bytes beyond
+    /// `buf.len()` are discarded.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        retry_eintr(|| unsafe {
+            syscalls::recv(self.fd, buf.as_mut_ptr() as *mut syscalls::c_void, buf.len(), 0)
+        })
+    }
+
+    /// Receive one datagram and report who sent it.
+    pub fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, SocketAddrV4)> {
+        let mut addr: syscalls::sockaddr_in = unsafe { core::mem::zeroed() };
+        let mut addrlen = core::mem::size_of::<syscalls::sockaddr_in>() as u32;
+        let n = retry_eintr(|| unsafe {
+            syscalls::recvfrom(
+                self.fd,
+                buf.as_mut_ptr() as *mut syscalls::c_void,
+                buf.len(),
+                0,
+                &mut addr as *mut syscalls::sockaddr_in as *mut syscalls::sockaddr,
+                &mut addrlen,
+            )
+        })?;
+        Ok((n, SocketAddrV4::from_sockaddr(&addr)))
+    }
+
+    /// The address this socket is bound to.
+    pub fn local_addr(&self) -> Result<SocketAddrV4> {
+        socket_addr(self.fd, syscalls::getsockname)
+    }
+
+    /// Set non-blocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        let flags = unsafe { syscalls::fcntl(self.fd, syscalls::F_GETFL) };
+        if flags < 0 {
+            return Err(IoError::last_os_error());
+        }
+        let new_flags = if nonblocking {
+            flags | syscalls::O_NONBLOCK
+        } else {
+            flags & !syscalls::O_NONBLOCK
+        };
+        let ret = unsafe { syscalls::fcntl(self.fd, syscalls::F_SETFL, new_flags) };
+        if ret < 0 {
+            return Err(IoError::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Returns the raw file descriptor.
+    pub fn as_raw_fd(&self) -> i32 {
+        self.fd
+    }
+}
+
+impl Drop for UdpSocket {
+    fn drop(&mut self) {
+        if self.fd >= 0 {
+            unsafe { syscalls::close(self.fd); }
+        }
+    }
+}
+
+/// Read an IPv4 socket address with `getpeername` or `getsockname`.
+fn socket_addr(
+    fd: i32,
+    getter: unsafe extern "C" fn(i32, *mut syscalls::sockaddr, *mut u32) -> i32,
+) -> Result<SocketAddrV4> {
+    let mut addr: syscalls::sockaddr_in = unsafe { core::mem::zeroed() };
+    let mut addrlen = core::mem::size_of::<syscalls::sockaddr_in>() as u32;
+    let ret = unsafe {
+        getter(
+            fd,
+            &mut addr as *mut syscalls::sockaddr_in as *mut syscalls::sockaddr,
+            &mut addrlen,
+        )
+    };
+    if ret < 0 {
+        return Err(IoError::last_os_error());
+    }
+    if addr.sin_family != syscalls::AF_INET as u16 {
+        return Err(IoError::new(IoErrorKind::InvalidData, "not an IPv4 address"));
+    }
+    Ok(SocketAddrV4::from_sockaddr(&addr))
+}
+
+/// getaddrinfo result list, freed on drop.
+struct AddrInfo {
+    list: *mut syscalls::addrinfo,
+}
+
+impl AddrInfo {
+    fn resolve(addr: (&str, u16), socktype: i32, flags: i32) -> Result<Self> {
+        let (host, port) = addr;
+        let c_host = CString::new(host);
+        let mut port_buf = [0u8; 8];
+        let c_port = CString::new(port_to_str(port, &mut port_buf));
+
+        let mut hints: syscalls::addrinfo = unsafe { core::mem::zeroed() };
+        hints.ai_family = syscalls::AF_INET;
+        hints.ai_socktype = socktype;
+        hints.ai_flags = flags;
+
+        let mut list: *mut syscalls::addrinfo = core::ptr::null_mut();
+        let ret = unsafe { syscalls::getaddrinfo(c_host.as_ptr(), c_port.as_ptr(), &hints, &mut list) };
+        if ret != 0 || list.is_null() {
+            return Err(IoError::new(IoErrorKind::Other, "failed to resolve address"));
+        }
+        Ok(Self { list })
+    }
+
+    fn first(&self) -> &syscalls::addrinfo {
+        unsafe { &*self.list }
+    }
+}
+
+impl Drop for AddrInfo {
+    fn drop(&mut self) {
+        unsafe { syscalls::freeaddrinfo(self.list); }
+    }
+}
+
+/// Run a send/recv-style syscall, retrying on EINTR.
+fn retry_eintr<F: FnMut() -> isize>(mut op: F) -> Result<usize> {
+    loop {
+        let ret = op();
+        if ret < 0 {
+            let err = errno::get_errno();
+            if err == errno::EINTR {
+                continue;
+            }
+            return Err(IoError::from_errno(err));
+        }
+        return Ok(ret as usize);
+    }
+}
+
 /// Extract the peer's IPv4 address from a connected socket fd.
 /// Returns the IPv4 address as a network-order u32, or None on failure.
 pub fn peer_ip_from_fd(fd: i32) -> Option<u32> {
@@ -293,3 +618,65 @@ fn port_to_str(port: u16, buf: &mut [u8; 8]) -> &str {
     }
     unsafe { core::str::from_utf8_unchecked(&buf[pos..]) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_udp_send_to_recv_from_loopback() {
+        let server = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        let client = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        let server_port = server.local_addr().unwrap().port;
+        assert_ne!(server_port, 0);
+
+        let sent = client.send_to(b"metric:1|c", ("127.0.0.1", server_port)).unwrap();
+        assert_eq!(sent, 10);
+
+        let mut buf = [0u8; 64];
+        let (n, from) = server.recv_from(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"metric:1|c");
+        assert_eq!(from, client.local_addr().unwrap());
+        assert_eq!(from.ip, [127, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_udp_connected_send_recv() {
+        let a = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        let b = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        a.connect(("127.0.0.1", b.local_addr().unwrap().port)).unwrap();
+        b.connect(("127.0.0.1", a.local_addr().unwrap().port)).unwrap();
+
+        a.send(b"ping").unwrap();
+        let mut buf = [0u8; 16];
+        let n = b.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"ping");
+
+        b.send(b"pong").unwrap();
+        let n = a.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"pong");
+    }
+
+    #[test]
+    fn test_socket_addr_display() {
+        let addr = SocketAddrV4 { ip: [10, 0, 0, 1], port: 8125 };
+        assert_eq!(crate::vformat!("{}", addr).as_str(), "10.0.0.1:8125");
+    }
+
+    #[test]
+    fn test_tcp_peer_addr_is_loopback() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let bound = listener.local_addr().unwrap();
+        let port: u16 = bound.as_str().rsplit(':').next().unwrap().parse().unwrap();
+        assert_eq!(bound, crate::vformat!("127.0.0.1:{}", port));
+
+        let client = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let accepted = listener.accept().unwrap();
+
+        let peer = accepted.peer_addr().unwrap();
+        assert!(peer.starts_with("127.0.0.1:"));
+        assert_eq!(peer, client.local_addr().unwrap());
+        assert_eq!(accepted.local_addr().unwrap(), bound);
+        assert_eq!(client.peer_addr().unwrap(), bound);
+    }
+}
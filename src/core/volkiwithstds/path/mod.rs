@@ -43,8 +43,12 @@ impl Path {
         &self.inner
     }
 
-    /// Join this path with another component.
+    /// Join this path with another component. An absolute `other`
+    /// replaces the path entirely, as in std.
     pub fn join(&self, other: &str) -> PathBuf {
+        if other.starts_with('/') {
+            return PathBuf::from(other);
+        }
         let mut buf = PathBuf::from(self.as_str());
         if !self.inner.is_empty() && !self.inner.ends_with("/") {
             buf.inner.push('/');
@@ -87,6 +91,15 @@ impl Path {
         }
     }
 
+    /// Returns a new PathBuf with the final component replaced by `name`,
+    /// or with `name` appended when there is no final component (`/`).
+    pub fn with_file_name(&self, name: &str) -> PathBuf {
+        match self.parent() {
+            Some(parent) if self.file_name().is_some() => parent.join(name),
+            _ => self.join(name),
+        }
+    }
+
     /// Returns a new PathBuf with the extension replaced.
     pub fn with_extension(&self, ext: &str) -> PathBuf {
         let mut new = self.to_path_buf();
@@ -370,6 +383,24 @@ impl Default for PathBuf {
     }
 }
 
+/// Joins the parts with [`PathBuf::push`], so `["a", "b/", "c"]` is `a/b/c`.
+impl<'a> FromIterator<&'a str> for PathBuf {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut path = PathBuf::new();
+        path.extend(iter);
+        path
+    }
+}
+
+/// Pushes each part in turn.
+impl<'a> Extend<&'a str> for PathBuf {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for part in iter {
+            self.push(part);
+        }
+    }
+}
+
 impl From<&str> for PathBuf {
     fn from(s: &str) -> Self {
         PathBuf::from(s)
@@ -395,6 +426,20 @@ mod tests {
         assert_eq!(joined.as_str(), "/usr/bin");
     }
 
+    #[test]
+    fn test_path_join_absolute_replaces() {
+        assert_eq!(Path::new("/home/user").join("/etc/hosts").as_str(), "/etc/hosts");
+        assert_eq!(Path::new("/home/user/").join("docs").as_str(), "/home/user/docs");
+    }
+
+    #[test]
+    fn test_path_with_file_name() {
+        assert_eq!(Path::new("/srv/app/page.volki").with_file_name("layout.volki").as_str(), "/srv/app/layout.volki");
+        assert_eq!(Path::new("/etc").with_file_name("hosts").as_str(), "/hosts");
+        assert_eq!(Path::new("notes.txt").with_file_name("todo.txt").as_str(), "todo.txt");
+        assert_eq!(Path::new("/").with_file_name("root").as_str(), "/root");
+    }
+
     #[test]
     fn test_path_parent() {
         let p = Path::new("/usr/bin/ls");
@@ -414,6 +459,16 @@ mod tests {
         assert_eq!(p.extension(), Some("txt"));
     }
 
+    #[test]
+    fn test_pathbuf_collect_and_extend() {
+        let p: PathBuf = ["a", "b", "c"].into_iter().collect();
+        assert_eq!(p.as_str(), "a/b/c");
+
+        let mut p: PathBuf = ["/srv/", "app"].into_iter().collect();
+        p.extend(["public/", "index.html"]);
+        assert_eq!(p.as_str(), "/srv/app/public/index.html");
+    }
+
     #[test]
     fn test_pathbuf_push() {
         let mut p = PathBuf::from("/usr");
@@ -57,6 +57,11 @@ pub const ECONNREFUSED: c_int = 61;
 #[cfg(target_os = "linux")]
 pub const ECONNREFUSED: c_int = 111;
 
+#[cfg(target_os = "macos")]
+pub const EINPROGRESS: c_int = 36;
+#[cfg(target_os = "linux")]
+pub const EINPROGRESS: c_int = 115;
+
 #[cfg(target_os = "macos")]
 pub const ETIMEDOUT: c_int = 60;
 #[cfg(target_os = "linux")]
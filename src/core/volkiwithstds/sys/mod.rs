@@ -2,4 +2,6 @@
 
 pub mod errno;
 pub mod openssl;
+pub mod sqlite3;
 pub mod syscalls;
+pub mod zlib;
@@ -43,6 +43,10 @@ pub const SSL_OP_NO_SSLv3: c_long = 0x02000000;
 pub const SSL_OP_NO_TLSv1: c_long = 0x04000000;
 pub const SSL_OP_NO_TLSv1_1: c_long = 0x10000000;
 
+// SSL_ctrl commands (SSL_set_tlsext_host_name is a macro over SSL_ctrl)
+pub const SSL_CTRL_SET_TLSEXT_HOSTNAME: c_int = 55;
+pub const TLSEXT_NAMETYPE_host_name: c_long = 0;
+
 // OPENSSL_init_ssl flags
 pub const OPENSSL_INIT_LOAD_SSL_STRINGS: u64 = 0x00200000;
 pub const OPENSSL_INIT_LOAD_CRYPTO_STRINGS: u64 = 0x00000002;
@@ -57,6 +61,7 @@ unsafe extern "C" {
 
     // Method
     pub fn TLS_server_method() -> *const SSL_METHOD;
+    pub fn TLS_client_method() -> *const SSL_METHOD;
 
     // SSL_CTX
     pub fn SSL_CTX_new(method: *const SSL_METHOD) -> *mut SSL_CTX;
@@ -79,7 +84,10 @@ unsafe extern "C" {
     pub fn SSL_free(ssl: *mut SSL);
     pub fn SSL_set_fd(ssl: *mut SSL, fd: c_int) -> c_int;
     pub fn SSL_accept(ssl: *mut SSL) -> c_int;
+    pub fn SSL_connect(ssl: *mut SSL) -> c_int;
+    pub fn SSL_ctrl(ssl: *mut SSL, cmd: c_int, larg: c_long, parg: *mut c_void) -> c_long;
     pub fn SSL_read(ssl: *mut SSL, buf: *mut c_void, num: c_int) -> c_int;
+    pub fn SSL_pending(ssl: *const SSL) -> c_int;
     pub fn SSL_write(ssl: *mut SSL, buf: *const c_void, num: c_int) -> c_int;
     pub fn SSL_shutdown(ssl: *mut SSL) -> c_int;
     pub fn SSL_get_error(ssl: *const SSL, ret: c_int) -> c_int;
@@ -0,0 +1,99 @@
+//! SQLite FFI bindings — raw `extern "C"` declarations for `libsqlite3`.
+
+#![allow(non_camel_case_types, dead_code)]
+
+use super::syscalls::c_int;
+pub use super::syscalls::{c_char, c_void};
+
+// ── Opaque types ────────────────────────────────────────────────────────────
+
+#[repr(C)]
+pub struct sqlite3 {
+    _opaque: [u8; 0],
+}
+
+#[repr(C)]
+pub struct sqlite3_stmt {
+    _opaque: [u8; 0],
+}
+
+// ── Constants ───────────────────────────────────────────────────────────────
+
+// Result codes
+pub const SQLITE_OK: c_int = 0;
+pub const SQLITE_ROW: c_int = 100;
+pub const SQLITE_DONE: c_int = 101;
+
+// sqlite3_open_v2 flags
+pub const SQLITE_OPEN_READONLY: c_int = 0x00000001;
+pub const SQLITE_OPEN_READWRITE: c_int = 0x00000002;
+pub const SQLITE_OPEN_CREATE: c_int = 0x00000004;
+pub const SQLITE_OPEN_URI: c_int = 0x00000040;
+
+// Fundamental column types
+pub const SQLITE_INTEGER: c_int = 1;
+pub const SQLITE_FLOAT: c_int = 2;
+pub const SQLITE_TEXT: c_int = 3;
+pub const SQLITE_BLOB: c_int = 4;
+pub const SQLITE_NULL: c_int = 5;
+
+/// Destructor sentinel telling SQLite to copy bound text/blob data
+/// (`SQLITE_TRANSIENT` is `(sqlite3_destructor_type)-1` in C).
+pub const SQLITE_TRANSIENT: isize = -1;
+
+// ── extern "C" declarations ─────────────────────────────────────────────────
+
+#[link(name = "sqlite3")]
+unsafe extern "C" {
+    // Connections
+    pub fn sqlite3_open_v2(
+        filename: *const c_char,
+        pp_db: *mut *mut sqlite3,
+        flags: c_int,
+        z_vfs: *const c_char,
+    ) -> c_int;
+    pub fn sqlite3_close_v2(db: *mut sqlite3) -> c_int;
+    pub fn sqlite3_errmsg(db: *mut sqlite3) -> *const c_char;
+    pub fn sqlite3_changes(db: *mut sqlite3) -> c_int;
+
+    // Statements
+    pub fn sqlite3_prepare_v2(
+        db: *mut sqlite3,
+        z_sql: *const c_char,
+        n_byte: c_int,
+        pp_stmt: *mut *mut sqlite3_stmt,
+        pz_tail: *mut *const c_char,
+    ) -> c_int;
+    pub fn sqlite3_step(stmt: *mut sqlite3_stmt) -> c_int;
+    pub fn sqlite3_finalize(stmt: *mut sqlite3_stmt) -> c_int;
+    pub fn sqlite3_bind_parameter_count(stmt: *mut sqlite3_stmt) -> c_int;
+
+    // Binding (1-based indices)
+    pub fn sqlite3_bind_null(stmt: *mut sqlite3_stmt, idx: c_int) -> c_int;
+    pub fn sqlite3_bind_int64(stmt: *mut sqlite3_stmt, idx: c_int, value: i64) -> c_int;
+    pub fn sqlite3_bind_double(stmt: *mut sqlite3_stmt, idx: c_int, value: f64) -> c_int;
+    pub fn sqlite3_bind_text(
+        stmt: *mut sqlite3_stmt,
+        idx: c_int,
+        value: *const c_char,
+        n: c_int,
+        destructor: isize,
+    ) -> c_int;
+    pub fn sqlite3_bind_blob(
+        stmt: *mut sqlite3_stmt,
+        idx: c_int,
+        value: *const c_void,
+        n: c_int,
+        destructor: isize,
+    ) -> c_int;
+
+    // Result columns (0-based indices)
+    pub fn sqlite3_column_count(stmt: *mut sqlite3_stmt) -> c_int;
+    pub fn sqlite3_column_name(stmt: *mut sqlite3_stmt, idx: c_int) -> *const c_char;
+    pub fn sqlite3_column_type(stmt: *mut sqlite3_stmt, idx: c_int) -> c_int;
+    pub fn sqlite3_column_int64(stmt: *mut sqlite3_stmt, idx: c_int) -> i64;
+    pub fn sqlite3_column_double(stmt: *mut sqlite3_stmt, idx: c_int) -> f64;
+    pub fn sqlite3_column_text(stmt: *mut sqlite3_stmt, idx: c_int) -> *const u8;
+    pub fn sqlite3_column_blob(stmt: *mut sqlite3_stmt, idx: c_int) -> *const c_void;
+    pub fn sqlite3_column_bytes(stmt: *mut sqlite3_stmt, idx: c_int) -> c_int;
+}
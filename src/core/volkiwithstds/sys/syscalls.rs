@@ -21,6 +21,11 @@ pub type pthread_t = *mut u8;
 #[cfg(target_os = "linux")]
 pub type pthread_t = u64;
 
+#[cfg(target_os = "macos")]
+pub type pthread_key_t = c_ulong;
+#[cfg(target_os = "linux")]
+pub type pthread_key_t = c_uint;
+
 // ── Opaque directory types ──────────────────────────────────────────────────
 
 #[repr(C)]
@@ -188,11 +193,13 @@ pub const CLOCK_MONOTONIC: c_int = 1;
 pub const S_IFMT: u32 = 0o170000;
 pub const S_IFDIR: u32 = 0o040000;
 pub const S_IFREG: u32 = 0o100000;
+pub const S_IFLNK: u32 = 0o120000;
 
 // socket
 pub const AF_INET: c_int = 2;
 pub const AI_PASSIVE: c_int = 1;
 pub const SOCK_STREAM: c_int = 1;
+pub const SOCK_DGRAM: c_int = 2;
 pub const SOL_SOCKET: c_int = {
     #[cfg(target_os = "macos")]
     { 0xffff }
@@ -205,6 +212,12 @@ pub const SO_REUSEADDR: c_int = {
     #[cfg(target_os = "linux")]
     { 2 }
 };
+pub const SO_ERROR: c_int = {
+    #[cfg(target_os = "macos")]
+    { 0x1007 }
+    #[cfg(target_os = "linux")]
+    { 4 }
+};
 pub const SOMAXCONN: c_int = 128;
 
 // fcntl
@@ -268,6 +281,23 @@ pub struct kevent {
     pub udata: *mut c_void,
 }
 
+// ── poll ───────────────────────────────────────────────────────────────────
+
+pub const POLLIN: i16 = 0x001;
+pub const POLLOUT: i16 = 0x004;
+
+#[cfg(target_os = "macos")]
+pub type nfds_t = c_uint;
+#[cfg(target_os = "linux")]
+pub type nfds_t = c_ulong;
+
+#[repr(C)]
+pub struct pollfd {
+    pub fd: c_int,
+    pub events: i16,
+    pub revents: i16,
+}
+
 // ── epoll (Linux) ──────────────────────────────────────────────────────────
 
 #[cfg(target_os = "linux")]
@@ -319,6 +349,9 @@ unsafe extern "C" {
 
     // Metadata
     pub fn stat(path: *const c_char, buf: *mut stat_buf) -> c_int;
+    pub fn lstat(path: *const c_char, buf: *mut stat_buf) -> c_int;
+    pub fn symlink(target: *const c_char, linkpath: *const c_char) -> c_int;
+    pub fn readlink(path: *const c_char, buf: *mut c_char, bufsiz: size_t) -> ssize_t;
     pub fn fstat(fd: c_int, buf: *mut stat_buf) -> c_int;
 
     // Directories
@@ -345,8 +378,34 @@ unsafe extern "C" {
         optval: *const c_void,
         optlen: u32,
     ) -> c_int;
+    pub fn getsockopt(
+        fd: c_int,
+        level: c_int,
+        optname: c_int,
+        optval: *mut c_void,
+        optlen: *mut u32,
+    ) -> c_int;
     pub fn shutdown(fd: c_int, how: c_int) -> c_int;
     pub fn getpeername(fd: c_int, addr: *mut sockaddr, addrlen: *mut u32) -> c_int;
+    pub fn getsockname(fd: c_int, addr: *mut sockaddr, addrlen: *mut u32) -> c_int;
+    pub fn send(fd: c_int, buf: *const c_void, len: size_t, flags: c_int) -> ssize_t;
+    pub fn recv(fd: c_int, buf: *mut c_void, len: size_t, flags: c_int) -> ssize_t;
+    pub fn sendto(
+        fd: c_int,
+        buf: *const c_void,
+        len: size_t,
+        flags: c_int,
+        addr: *const sockaddr,
+        addrlen: u32,
+    ) -> ssize_t;
+    pub fn recvfrom(
+        fd: c_int,
+        buf: *mut c_void,
+        len: size_t,
+        flags: c_int,
+        addr: *mut sockaddr,
+        addrlen: *mut u32,
+    ) -> ssize_t;
     pub fn getaddrinfo(
         node: *const c_char,
         service: *const c_char,
@@ -369,6 +428,13 @@ unsafe extern "C" {
     ) -> c_int;
     pub fn pthread_join(thread: pthread_t, retval: *mut *mut c_void) -> c_int;
     pub fn pthread_detach(thread: pthread_t) -> c_int;
+    pub fn pthread_key_create(
+        key: *mut pthread_key_t,
+        destructor: Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> c_int;
+    pub fn pthread_key_delete(key: pthread_key_t) -> c_int;
+    pub fn pthread_getspecific(key: pthread_key_t) -> *mut c_void;
+    pub fn pthread_setspecific(key: pthread_key_t, value: *const c_void) -> c_int;
 
     // Time
     pub fn clock_gettime(clk_id: c_int, tp: *mut timespec) -> c_int;
@@ -410,6 +476,8 @@ unsafe extern "C" {
         timeout: *const timespec,
     ) -> c_int;
 
+    pub fn poll(fds: *mut pollfd, nfds: nfds_t, timeout: c_int) -> c_int;
+
     // epoll (Linux)
     #[cfg(target_os = "linux")]
     pub fn epoll_create1(flags: c_int) -> c_int;
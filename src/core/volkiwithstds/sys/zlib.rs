@@ -0,0 +1,93 @@
+//! zlib FFI bindings — raw `extern "C"` declarations for `libz`.
+
+#![allow(non_camel_case_types, dead_code)]
+
+use super::syscalls::{c_char, c_int, c_uint, c_ulong, c_void};
+
+/// `z_stream` from `zlib.h`. Allocator fields left null select zlib's
+/// default `malloc`/`free`.
+#[repr(C)]
+pub struct z_stream {
+    pub next_in: *const u8,
+    pub avail_in: c_uint,
+    pub total_in: c_ulong,
+    pub next_out: *mut u8,
+    pub avail_out: c_uint,
+    pub total_out: c_ulong,
+    pub msg: *const c_char,
+    pub state: *mut c_void,
+    pub zalloc: *const c_void,
+    pub zfree: *const c_void,
+    pub opaque: *mut c_void,
+    pub data_type: c_int,
+    pub adler: c_ulong,
+    pub reserved: c_ulong,
+}
+
+impl z_stream {
+    pub fn zeroed() -> Self {
+        Self {
+            next_in: core::ptr::null(),
+            avail_in: 0,
+            total_in: 0,
+            next_out: core::ptr::null_mut(),
+            avail_out: 0,
+            total_out: 0,
+            msg: core::ptr::null(),
+            state: core::ptr::null_mut(),
+            zalloc: core::ptr::null(),
+            zfree: core::ptr::null(),
+            opaque: core::ptr::null_mut(),
+            data_type: 0,
+            adler: 0,
+            reserved: 0,
+        }
+    }
+}
+
+// ── Constants ───────────────────────────────────────────────────────────────
+
+/// Only the major version is checked by `*Init2_`.
+pub const ZLIB_VERSION: &[u8] = b"1.2.11\0";
+
+pub const Z_NO_FLUSH: c_int = 0;
+pub const Z_FINISH: c_int = 4;
+
+pub const Z_OK: c_int = 0;
+pub const Z_STREAM_END: c_int = 1;
+pub const Z_BUF_ERROR: c_int = -5;
+
+pub const Z_DEFAULT_COMPRESSION: c_int = -1;
+pub const Z_DEFLATED: c_int = 8;
+pub const Z_DEFAULT_STRATEGY: c_int = 0;
+
+/// `windowBits` of 15 plus 16 selects the gzip wrapper instead of zlib's.
+pub const GZIP_WINDOW_BITS: c_int = 15 + 16;
+
+// ── extern "C" declarations ─────────────────────────────────────────────────
+
+#[link(name = "z")]
+unsafe extern "C" {
+    pub fn deflateInit2_(
+        strm: *mut z_stream,
+        level: c_int,
+        method: c_int,
+        window_bits: c_int,
+        mem_level: c_int,
+        strategy: c_int,
+        version: *const c_char,
+        stream_size: c_int,
+    ) -> c_int;
+    pub fn deflate(strm: *mut z_stream, flush: c_int) -> c_int;
+    pub fn deflateEnd(strm: *mut z_stream) -> c_int;
+    pub fn deflateBound(strm: *mut z_stream, source_len: c_ulong) -> c_ulong;
+
+    pub fn inflateInit2_(
+        strm: *mut z_stream,
+        window_bits: c_int,
+        version: *const c_char,
+        stream_size: c_int,
+    ) -> c_int;
+    pub fn inflate(strm: *mut z_stream, flush: c_int) -> c_int;
+    pub fn inflateEnd(strm: *mut z_stream) -> c_int;
+}
@@ -0,0 +1,148 @@
+//! Thread-local storage via pthread keys.
+
+use crate::core::volkiwithstds::collections::Box;
+use crate::core::volkiwithstds::sys::syscalls;
+use core::ptr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A value with one lazily-initialized instance per thread.
+///
+/// Meant to live in a `static`. Each thread's value is created by `init` on
+/// first access and dropped when that thread exits.
+pub struct ThreadLocal<T> {
+    /// pthread key plus one; zero means the key has not been created yet.
+    key: AtomicUsize,
+    init: fn() -> T,
+}
+
+/// Destructor registered with the key — runs on the exiting thread.
+unsafe extern "C" fn drop_value<T>(value: *mut syscalls::c_void) {
+    drop(unsafe { Box::from_raw(value as *mut T) });
+}
+
+impl<T> ThreadLocal<T> {
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            key: AtomicUsize::new(0),
+            init,
+        }
+    }
+
+    /// Run `f` with this thread's value, creating it on first use.
+    pub fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        let key = self.key();
+        let mut value = unsafe { syscalls::pthread_getspecific(key) } as *const T;
+        if value.is_null() {
+            let raw = Box::into_raw(Box::new((self.init)()));
+            let ret = unsafe { syscalls::pthread_setspecific(key, raw as *const syscalls::c_void) };
+            assert!(ret == 0, "pthread_setspecific failed");
+            value = raw;
+        }
+        f(unsafe { &*value })
+    }
+
+    /// The pthread key, created on first call. Threads racing to create it
+    /// agree on one key and the losers delete theirs.
+    fn key(&self) -> syscalls::pthread_key_t {
+        let current = self.key.load(Ordering::Acquire);
+        if current != 0 {
+            return (current - 1) as syscalls::pthread_key_t;
+        }
+        let mut key: syscalls::pthread_key_t = 0;
+        let ret = unsafe { syscalls::pthread_key_create(&mut key, Some(drop_value::<T>)) };
+        assert!(ret == 0, "pthread_key_create failed");
+        match self
+            .key
+            .compare_exchange(0, key as usize + 1, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => key,
+            Err(winner) => {
+                unsafe { syscalls::pthread_key_delete(key) };
+                (winner - 1) as syscalls::pthread_key_t
+            }
+        }
+    }
+}
+
+impl<T> Drop for ThreadLocal<T> {
+    /// Frees the calling thread's value and deletes the key. Values still
+    /// held by other live threads are leaked, as with `pthread_key_delete`.
+    fn drop(&mut self) {
+        let current = *self.key.get_mut();
+        if current == 0 {
+            return;
+        }
+        let key = (current - 1) as syscalls::pthread_key_t;
+        unsafe {
+            let value = syscalls::pthread_getspecific(key);
+            if !value.is_null() {
+                syscalls::pthread_setspecific(key, ptr::null());
+                drop_value::<T>(value);
+            }
+            syscalls::pthread_key_delete(key);
+        }
+    }
+}
+
+// Safety: every thread only ever touches its own value.
+unsafe impl<T> Sync for ThreadLocal<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::thread;
+    use core::cell::{Cell, RefCell};
+
+    #[test]
+    fn test_threads_see_independent_values() {
+        static COUNTER: ThreadLocal<Cell<u32>> = ThreadLocal::new(|| Cell::new(0));
+        COUNTER.with(|c| c.set(100));
+
+        let handles: crate::core::volkiwithstds::collections::Vec<_> = (1..=2u32)
+            .map(|n| {
+                thread::spawn(move || {
+                    COUNTER.with(|c| assert_eq!(c.get(), 0));
+                    for _ in 0..n {
+                        COUNTER.with(|c| c.set(c.get() + 1));
+                    }
+                    COUNTER.with(|c| c.get())
+                })
+            })
+            .collect();
+        let seen: crate::core::volkiwithstds::collections::Vec<u32> =
+            handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(seen.as_slice(), &[1, 2]);
+        assert_eq!(COUNTER.with(|c| c.get()), 100);
+    }
+
+    #[test]
+    fn test_value_dropped_on_thread_exit() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct Tracked;
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        static TRACKED: ThreadLocal<Tracked> = ThreadLocal::new(|| Tracked);
+
+        thread::spawn(|| TRACKED.with(|_| ())).join().unwrap();
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_scratch_buffer_reused_within_thread() {
+        static SCRATCH: ThreadLocal<RefCell<crate::core::volkiwithstds::collections::String>> =
+            ThreadLocal::new(|| {
+                RefCell::new(crate::core::volkiwithstds::collections::String::new())
+            });
+        SCRATCH.with(|s| s.borrow_mut().push_str("abc"));
+        SCRATCH.with(|s| {
+            let mut s = s.borrow_mut();
+            assert_eq!(s.as_str(), "abc");
+            s.clear();
+        });
+        SCRATCH.with(|s| assert!(s.borrow().is_empty()));
+    }
+}
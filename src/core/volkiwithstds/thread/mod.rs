@@ -1,7 +1,9 @@
-//! Threading — spawn, sleep.
+//! Threading — spawn, sleep, thread-local storage.
 
+pub mod local;
 pub mod sleep;
 pub mod spawn;
 
+pub use local::ThreadLocal;
 pub use sleep::sleep;
 pub use spawn::{spawn, JoinHandle};
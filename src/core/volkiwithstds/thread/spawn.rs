@@ -1,6 +1,7 @@
 //! Thread spawning via pthread_create.
 
 use crate::core::volkiwithstds::alloc;
+use crate::core::volkiwithstds::io::{self, IoError, IoErrorKind};
 use crate::core::volkiwithstds::sys::syscalls;
 use core::mem;
 use core::ptr;
@@ -9,6 +10,9 @@ use core::ptr;
 pub struct JoinHandle<T> {
     thread: syscalls::pthread_t,
     result_ptr: *mut Option<T>,
+    /// Heap block holding the typed payload, freed by `join`.
+    payload: *mut u8,
+    payload_size: usize,
 }
 
 /// Type-erased payload for pthread_create.
@@ -30,6 +34,7 @@ unsafe extern "C" fn raw_trampoline(arg: *mut syscalls::c_void) -> *mut syscalls
     let raw = arg as *mut RawPayload;
     let invoke = unsafe { (*raw).invoke };
     let data = unsafe { (*raw).data };
+    unsafe { alloc::dealloc(arg as *mut u8, mem::size_of::<RawPayload>()) };
     unsafe { invoke(data) };
     ptr::null_mut()
 }
@@ -109,23 +114,63 @@ where
         panic!("failed to spawn thread");
     }
 
-    JoinHandle { thread, result_ptr }
+    JoinHandle {
+        thread,
+        result_ptr,
+        payload: typed_ptr,
+        payload_size: typed_size,
+    }
 }
 
 impl<T> JoinHandle<T> {
-    /// Wait for the thread to finish and return its result.
-    pub fn join(self) -> T {
+    /// Wait for the thread to finish and return the closure's value.
+    ///
+    /// Returns an error if the thread cannot be joined or exited without
+    /// producing a value. Builds use `panic = "abort"`, so a panic in the
+    /// closure ends the process rather than surfacing here.
+    pub fn join(self) -> io::Result<T> {
         let mut retval: *mut syscalls::c_void = ptr::null_mut();
-        unsafe {
-            syscalls::pthread_join(self.thread, &mut retval);
+        let ret = unsafe { syscalls::pthread_join(self.thread, &mut retval) };
+        if ret != 0 {
+            // The thread may still be running and writing to the payload
+            return Err(IoError::from_errno(ret));
         }
-        // The result was written by the trampoline into the typed payload
+        // The result was written by the trampoline into the typed payload;
+        // the closure itself was already taken, so only the block is freed.
         let result = unsafe { (*self.result_ptr).take() };
-        // Note: we intentionally leak the payload allocations here for simplicity.
-        // In a production implementation, we'd track and free them.
-        result.expect("thread did not produce a result")
+        if self.payload_size != 0 {
+            unsafe { alloc::dealloc(self.payload, self.payload_size) };
+        }
+        result.ok_or_else(|| IoError::new(IoErrorKind::Other, "thread did not produce a result"))
     }
 }
 
 // Safety: JoinHandle is Send (the thread result is Send)
 unsafe impl<T: Send> Send for JoinHandle<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::Vec;
+
+    #[test]
+    fn test_join_returns_closure_value() {
+        let handle = spawn(|| (1..=100u64).sum::<u64>());
+        assert_eq!(handle.join().unwrap(), 5050);
+    }
+
+    #[test]
+    fn test_join_collects_results_from_many_threads() {
+        let handles: Vec<JoinHandle<u64>> = (0..4u64)
+            .map(|shard| spawn(move || (shard * 10..shard * 10 + 10).sum()))
+            .collect();
+        let total: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(total, (0..40u64).sum());
+    }
+
+    #[test]
+    fn test_join_returns_owned_heap_value() {
+        let handle = spawn(|| crate::vformat!("shard-{}", 7));
+        assert_eq!(handle.join().unwrap().as_str(), "shard-7");
+    }
+}
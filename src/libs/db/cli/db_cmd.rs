@@ -1,4 +1,7 @@
-use super::{connect_db, db_option, load_db_config, query_and_print, require_name};
+use super::{
+    connect_db, db_option, load_db_config, no_truncate_option, query_and_print, require_name,
+    table_options,
+};
 use crate::core::cli::command::{Command, OptionSpec};
 use crate::core::cli::confirm::{self, ConfirmResult};
 use crate::core::cli::error::CliError;
@@ -24,6 +27,7 @@ impl Command for DbCommand {
     fn options(&self) -> Vec<OptionSpec> {
         vvec![
             db_option(),
+            no_truncate_option(),
             OptionSpec {
                 name: "name",
                 description: "Database name (for create/drop)",
@@ -58,9 +62,11 @@ impl Command for DbCommand {
                         pg_encoding_to_char(encoding) as encoding \
                  FROM pg_database \
                  ORDER BY datname",
+                &[],
                 &["Name", "Owner", "Encoding"],
                 &['l', 'l', 'l'],
                 db_name,
+                &table_options(args),
             ),
             "create" => self.create_db(args, db_name),
             "drop" => self.drop_db(args, db_name),
@@ -1,10 +1,24 @@
+use super::{DbConfig, DbConnection, Dialect, discover_db_names};
 use crate::core::cli::command::{Command, OptionSpec};
 use crate::core::cli::error::CliError;
 use crate::core::cli::output;
 use crate::core::cli::parser::ParsedArgs;
 use crate::core::cli::style;
-use crate::core::volkiwithstds::collections::Vec;
-use crate::veprintln;
+use crate::core::config::parser::Table;
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::sync::{Arc, Mutex};
+use crate::core::volkiwithstds::thread;
+use crate::core::volkiwithstds::time::{Duration, Instant};
+use crate::libs::db::langs::postgres::lib::connection::Connection;
+use crate::libs::db::langs::sqlite::lib::Connection as SqliteConnection;
+use crate::{veprintln, vformat};
+
+/// How long each probe may spend on the TCP connect.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// How long to wait for all probes, handshake, auth and the count query
+/// included. A server that accepts and then stalls is reported as timed out.
+const PROBE_DEADLINE: Duration = Duration::from_millis(5000);
 
 pub struct DbHubCommand;
 
@@ -30,13 +44,13 @@ impl Command for DbHubCommand {
         let cwd = crate::core::volkiwithstds::env::current_dir().ok();
         if let Some(ref dir) = cwd {
             if let Ok(config) = crate::core::config::VolkiConfig::load(dir) {
-                let names = super::discover_db_names(config.table());
-                if names.is_empty() {
-                    if config.table().get("db", "dialect").is_some() {
-                        veprintln!("  {}  {}", style::dim("config:"), "(single)");
-                    }
-                } else {
-                    veprintln!("  {}  {}", style::dim("config:"), names.join(", "),);
+                let statuses = probe_all(config.table(), PROBE_TIMEOUT, PROBE_DEADLINE);
+                if !statuses.is_empty() {
+                    output::print_table(
+                        &["Name", "Dialect", "Status", "Tables"],
+                        &status_rows(&statuses),
+                        &['l', 'l', 'l', 'r'],
+                    );
                 }
                 veprintln!();
             }
@@ -57,7 +71,17 @@ impl Command for DbHubCommand {
         veprintln!(
             "    {}    {}",
             style::cyan(&crate::vformat!("{:<12}", "db:table")),
-            style::dim("list, describe, drop, truncate tables"),
+            style::dim("list, describe, drop, truncate, export tables"),
+        );
+        veprintln!(
+            "    {}    {}",
+            style::cyan(&crate::vformat!("{:<12}", "db:migrate")),
+            style::dim("apply and list schema migrations"),
+        );
+        veprintln!(
+            "    {}    {}",
+            style::cyan(&crate::vformat!("{:<12}", "db:query")),
+            style::dim("run SQL, with --explain and --timing"),
         );
         veprintln!(
             "    {}    {}",
@@ -71,9 +95,137 @@ impl Command for DbHubCommand {
     }
 }
 
+/// Outcome of probing one configured database.
+struct DbStatus {
+    name: String,
+    dialect: String,
+    /// Table count, or why the database could not be reached.
+    result: Result<String, String>,
+}
+
+type ProbeSlot = Arc<Mutex<Option<Result<String, String>>>>;
+
+/// Probe every database in the config at once, one thread each. A failing
+/// database only marks its own row. Probes still running at `deadline` are
+/// abandoned; their threads are never joined.
+fn probe_all(table: &Table, timeout: Duration, deadline: Duration) -> Vec<DbStatus> {
+    let names = discover_db_names(table);
+    let sections: Vec<(String, String)> = if !names.is_empty() {
+        names
+            .iter()
+            .map(|n| (n.clone(), vformat!("db.{n}")))
+            .collect()
+    } else if table.get("db", "dialect").is_some() {
+        crate::vvec![(String::from("(single)"), String::from("db"))]
+    } else {
+        Vec::new()
+    };
+
+    let mut pending = Vec::new();
+    for (name, section) in sections {
+        let dialect = table
+            .get(&section, "dialect")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_default();
+        let probe = DbConfig::from_config(table, &section)
+            .map(|config| {
+                let slot: ProbeSlot = Arc::new(Mutex::new(None));
+                let writer = slot.clone();
+                let handle = thread::spawn(move || {
+                    let result = probe(&config, timeout);
+                    *writer.lock() = Some(result);
+                });
+                (slot, handle)
+            })
+            .map_err(|e| first_line(&vformat!("{e}")));
+        pending.push((name, dialect, probe));
+    }
+
+    let give_up = Instant::now() + deadline;
+    let done = |p: &Result<(ProbeSlot, _), String>| {
+        p.as_ref().map_or(true, |(slot, _)| slot.lock().is_some())
+    };
+    while Instant::now() < give_up && !pending.iter().all(|(_, _, p)| done(p)) {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    pending
+        .into_iter()
+        .map(|(name, dialect, probe)| {
+            let result = probe.and_then(|(slot, handle)| {
+                let result = slot.lock().take();
+                match result {
+                    Some(result) => {
+                        let _ = handle.join();
+                        result
+                    }
+                    None => Err(String::from("timed out")),
+                }
+            });
+            DbStatus {
+                name,
+                dialect,
+                result,
+            }
+        })
+        .collect()
+}
+
+/// Connect and count the user tables.
+fn probe(config: &DbConfig, timeout: Duration) -> Result<String, String> {
+    let mut conn = match &config.dialect {
+        Dialect::Postgres => Connection::connect_timeout(
+            &config.host,
+            config.port,
+            &config.user,
+            &config.database,
+            &config.password,
+            config.sslmode,
+            timeout,
+        )
+        .map(DbConnection::Postgres)
+        .map_err(|e| vformat!("{e}"))?,
+        Dialect::Sqlite => SqliteConnection::open_readonly(&config.database)
+            .map(DbConnection::Sqlite)
+            .map_err(|e| vformat!("{e}"))?,
+        other => return Err(vformat!("{other} driver is not yet implemented")),
+    };
+    let sql = if config.dialect == Dialect::Sqlite {
+        "SELECT count(*) FROM sqlite_master WHERE type = 'table'"
+    } else {
+        "SELECT count(*) FROM information_schema.tables WHERE table_schema = 'public'"
+    };
+    let rows = conn.query(sql).map_err(|e| vformat!("{e}"))?;
+    Ok(rows
+        .first()
+        .and_then(|r| r.get_value(0))
+        .map(|v| vformat!("{v}"))
+        .unwrap_or_default())
+}
+
+fn status_rows(statuses: &[DbStatus]) -> Vec<Vec<String>> {
+    statuses
+        .iter()
+        .map(|s| {
+            let (status, tables) = match &s.result {
+                Ok(count) => (style::green("reachable"), count.clone()),
+                Err(e) => (style::red(&vformat!("unreachable: {e}")), String::from("-")),
+            };
+            crate::vvec![s.name.clone(), s.dialect.clone(), status, tables]
+        })
+        .collect()
+}
+
+fn first_line(s: &str) -> String {
+    String::from(s.lines().next().unwrap_or(""))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::cli::output::strip_ansi;
+    use crate::core::volkiwithstds::net::TcpListener;
 
     #[test]
     fn name_is_db() {
@@ -89,4 +241,67 @@ mod tests {
     fn description_nonempty() {
         assert!(!DbHubCommand.description().is_empty());
     }
+
+    #[test]
+    fn probe_all_renders_failures_per_database() {
+        // Port 1 refuses at once; nothing listens there
+        let table = crate::core::config::parser::parse(
+            "[db.local]\ndialect = \"sqlite\"\npath = \":memory:\"\n\
+             [db.main]\ndialect = \"postgres\"\nhost = \"127.0.0.1\"\nport = 1\n\
+             user = \"app\"\ndatabase = \"app\"\n\
+             [db.broken]\ndialect = \"postgres\"\ncredentials = \"bogus\"\n",
+        )
+        .unwrap();
+        let statuses = probe_all(&table, Duration::from_millis(500), PROBE_DEADLINE);
+        let rows = status_rows(&statuses);
+        let row = |name: &str| {
+            rows.iter()
+                .find(|r| r[0].as_str() == name)
+                .map(|r| r.iter().map(|c| strip_ansi(c)).collect::<Vec<String>>())
+                .unwrap()
+        };
+
+        assert_eq!(rows.len(), 3);
+        let local = row("local");
+        assert_eq!(local[2], "reachable");
+        assert_eq!(local[3], "0");
+        let main = row("main");
+        assert_eq!(main[1], "postgres");
+        assert!(main[2].starts_with("unreachable:"));
+        assert_eq!(main[3], "-");
+        let broken = row("broken");
+        assert!(broken[2].contains("invalid credentials mode"));
+        assert!(!broken[2].contains("\n"));
+    }
+
+    #[test]
+    fn probe_does_not_create_missing_sqlite_file() {
+        let path = crate::core::volkiwithstds::env::temp_dir()
+            .join(&vformat!("volki-probe-missing-{}.db", crate::core::volkiwithstds::process::id()));
+        let table = crate::core::config::parser::parse(&vformat!(
+            "[db.local]\ndialect = \"sqlite\"\npath = \"{}\"\n",
+            path.as_str()
+        ))
+        .unwrap();
+        let statuses = probe_all(&table, PROBE_TIMEOUT, PROBE_DEADLINE);
+        assert!(statuses[0].result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn probe_times_out_on_stalled_server() {
+        // Accepts the connection but never answers the SSLRequest
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.as_str().rsplit(':').next().unwrap();
+        let table = crate::core::config::parser::parse(&vformat!(
+            "[db.stalled]\ndialect = \"postgres\"\nhost = \"127.0.0.1\"\nport = {port}\n\
+             user = \"app\"\ndatabase = \"app\"\n"
+        ))
+        .unwrap();
+        let started = Instant::now();
+        let statuses = probe_all(&table, PROBE_TIMEOUT, Duration::from_millis(300));
+        assert_eq!(statuses[0].result, Err(String::from("timed out")));
+        assert!(started.elapsed() < Duration::from_millis(2000));
+    }
 }
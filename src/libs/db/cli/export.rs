@@ -0,0 +1,299 @@
+use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::collections::json;
+use crate::core::volkiwithstds::io::{self, IoError, Write};
+use crate::libs::db::langs::postgres::lib::types::{Column, Row, RowSink, Value};
+use crate::vformat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Writes a result set to `out` row by row as the driver reads it.
+///
+/// CSV is RFC 4180 with a header line and CRLF line endings; NULL is an
+/// empty cell. JSON is an array with one object per row, keys in column
+/// order. The header comes from the column description, so an empty table
+/// still gets one.
+pub struct Exporter<'a, W: Write> {
+    out: &'a mut W,
+    format: ExportFormat,
+    started: bool,
+    rows: u64,
+    error: Option<IoError>,
+}
+
+impl<'a, W: Write> Exporter<'a, W> {
+    pub fn new(out: &'a mut W, format: ExportFormat) -> Self {
+        Self {
+            out,
+            format,
+            started: false,
+            rows: 0,
+            error: None,
+        }
+    }
+
+    /// Close the output and return the number of rows written, or the
+    /// first write error.
+    pub fn finish(mut self) -> io::Result<u64> {
+        if self.format == ExportFormat::Json {
+            let mut tail = String::new();
+            if !self.started {
+                tail.push('[');
+            }
+            if self.rows > 0 {
+                tail.push('\n');
+            }
+            tail.push_str("]\n");
+            self.write(&tail);
+        }
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok(self.rows)
+    }
+
+    /// Write `s`, keeping the first error for `finish`. The driver keeps
+    /// reading after a failed write so the connection stays usable.
+    fn write(&mut self, s: &str) {
+        if self.error.is_none()
+            && let Err(e) = self.out.write_all(s.as_bytes())
+        {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl<W: Write> RowSink for Exporter<'_, W> {
+    fn columns(&mut self, columns: &[Column]) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        let mut line = String::new();
+        match self.format {
+            ExportFormat::Csv => {
+                for (i, col) in columns.iter().enumerate() {
+                    if i > 0 {
+                        line.push(',');
+                    }
+                    push_csv_field(&mut line, &col.name);
+                }
+                line.push_str("\r\n");
+            }
+            ExportFormat::Json => line.push('['),
+        }
+        self.write(&line);
+    }
+
+    fn row(&mut self, row: Row) {
+        let mut line = String::new();
+        match self.format {
+            ExportFormat::Csv => {
+                for i in 0..row.len() {
+                    if i > 0 {
+                        line.push(',');
+                    }
+                    match row.get_value(i) {
+                        Some(Value::Null) | None => {}
+                        Some(Value::Bytes(b)) => line.push_str(&bytea_hex(b)),
+                        Some(v) => push_csv_field(&mut line, &vformat!("{v}")),
+                    }
+                }
+                line.push_str("\r\n");
+            }
+            ExportFormat::Json => {
+                line.push_str(if self.rows == 0 { "\n  {" } else { ",\n  {" });
+                for (i, col) in row.columns().iter().enumerate() {
+                    if i > 0 {
+                        line.push_str(", ");
+                    }
+                    json::write_string(&mut line, &col.name);
+                    line.push_str(": ");
+                    push_json_value(&mut line, row.get_value(i).unwrap_or(&Value::Null));
+                }
+                line.push('}');
+            }
+        }
+        self.rows += 1;
+        self.write(&line);
+    }
+}
+
+fn push_csv_field(out: &mut String, cell: &str) {
+    if cell.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        for c in cell.chars() {
+            if c == '"' {
+                out.push('"');
+            }
+            out.push(c);
+        }
+        out.push('"');
+    } else {
+        out.push_str(cell);
+    }
+}
+
+fn push_json_value(out: &mut String, val: &Value) {
+    match val {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Int(n) => out.push_str(&vformat!("{n}")),
+        // JSON has no NaN or Infinity
+        Value::Float(f) if !f.is_finite() => out.push_str("null"),
+        Value::Float(f) => out.push_str(&vformat!("{f}")),
+        Value::Bytes(b) => json::write_string(out, &bytea_hex(b)),
+        other => json::write_string(out, &vformat!("{other}")),
+    }
+}
+
+/// Postgres `bytea` hex output, e.g. `\xcafe`.
+fn bytea_hex(bytes: &[u8]) -> String {
+    let mut hex = String::from("\\x");
+    for byte in bytes {
+        hex.push_str(&vformat!("{byte:02x}"));
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::Vec;
+    use crate::core::volkiwithstds::io::Cursor;
+    use crate::vvec;
+
+    fn columns() -> Vec<Column> {
+        ["id", "name"]
+            .iter()
+            .map(|n| Column {
+                name: String::from(*n),
+                type_oid: 0,
+                format: 0,
+            })
+            .collect()
+    }
+
+    /// Feed the columns and `values` through an exporter, as a driver would.
+    fn export(format: ExportFormat, values: Vec<Vec<Value>>) -> String {
+        let columns = columns();
+        let mut out = Cursor::new(Vec::new());
+        let mut exporter = Exporter::new(&mut out, format);
+        exporter.columns(&columns);
+        for v in values {
+            exporter.row(Row::new(columns.clone(), v));
+        }
+        exporter.finish().unwrap();
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    fn to_csv(values: Vec<Vec<Value>>) -> String {
+        export(ExportFormat::Csv, values)
+    }
+
+    fn to_json(values: Vec<Vec<Value>>) -> String {
+        export(ExportFormat::Json, values)
+    }
+
+    #[test]
+    fn format_parse() {
+        assert_eq!(ExportFormat::parse("csv"), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::parse("json"), Some(ExportFormat::Json));
+        assert_eq!(ExportFormat::parse("xml"), None);
+    }
+
+    #[test]
+    fn csv_plain_rows() {
+        let rows = vvec![
+            vvec![Value::Int(1), Value::Text("alice".into())],
+            vvec![Value::Int(2), Value::Text("bob".into())],
+        ];
+        assert_eq!(to_csv(rows), "id,name\r\n1,alice\r\n2,bob\r\n");
+    }
+
+    #[test]
+    fn csv_quotes_special_cells() {
+        let rows = vvec![
+            vvec![Value::Int(1), Value::Text("a,b".into())],
+            vvec![Value::Int(2), Value::Text("say \"hi\"".into())],
+            vvec![Value::Int(3), Value::Text("two\nlines".into())],
+        ];
+        assert_eq!(
+            to_csv(rows),
+            "id,name\r\n1,\"a,b\"\r\n2,\"say \"\"hi\"\"\"\r\n3,\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_null_is_empty() {
+        let rows = vvec![vvec![Value::Int(1), Value::Null]];
+        assert_eq!(to_csv(rows), "id,name\r\n1,\r\n");
+    }
+
+    #[test]
+    fn csv_bytea_is_hex() {
+        let rows = vvec![vvec![Value::Int(1), Value::Bytes(vvec![0xCA, 0xFE])]];
+        assert_eq!(to_csv(rows), "id,name\r\n1,\\xcafe\r\n");
+    }
+
+    #[test]
+    fn csv_empty_result() {
+        assert_eq!(to_csv(Vec::new()), "id,name\r\n");
+    }
+
+    #[test]
+    fn json_rows() {
+        let rows = vvec![
+            vvec![Value::Int(1), Value::Text("a \"q\"".into())],
+            vvec![Value::Int(2), Value::Null],
+        ];
+        assert_eq!(
+            to_json(rows),
+            "[\n  {\"id\": 1, \"name\": \"a \\\"q\\\"\"},\n  {\"id\": 2, \"name\": null}\n]\n"
+        );
+    }
+
+    #[test]
+    fn json_scalar_types() {
+        let mut out = String::new();
+        push_json_value(&mut out, &Value::Bool(true));
+        out.push(' ');
+        push_json_value(&mut out, &Value::Float(1.5));
+        out.push(' ');
+        push_json_value(&mut out, &Value::Float(f64::NAN));
+        out.push(' ');
+        push_json_value(&mut out, &Value::Bytes(vvec![0xCA, 0xFE]));
+        assert_eq!(out, "true 1.5 null \"\\\\xcafe\"");
+    }
+
+    #[test]
+    fn json_empty_result() {
+        assert_eq!(to_json(Vec::new()), "[]\n");
+    }
+
+    #[test]
+    fn json_without_result_set() {
+        let mut out = Cursor::new(Vec::new());
+        assert_eq!(
+            Exporter::new(&mut out, ExportFormat::Json)
+                .finish()
+                .unwrap(),
+            0
+        );
+        assert_eq!(out.into_inner().as_slice(), b"[]\n");
+    }
+}
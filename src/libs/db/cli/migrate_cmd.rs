@@ -0,0 +1,384 @@
+use super::{
+    DbConnection, connect_db, db_option, load_db_config, no_truncate_option, table_options,
+};
+use crate::core::cli::command::{Command, OptionSpec};
+use crate::core::cli::error::CliError;
+use crate::core::cli::output;
+use crate::core::cli::parser::ParsedArgs;
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::fs;
+use crate::core::volkiwithstds::fs::FileType;
+use crate::core::volkiwithstds::path::{Path, PathBuf};
+use crate::libs::db::langs::postgres::lib::types::Value;
+use crate::{veprintln, vformat, vvec};
+
+const MIGRATIONS_TABLE: &str = "__volki_migrations";
+
+pub struct MigrateCommand;
+
+impl Command for MigrateCommand {
+    fn name(&self) -> &str {
+        "db:migrate"
+    }
+
+    fn description(&self) -> &str {
+        "Apply schema migrations"
+    }
+
+    fn long_description(&self) -> &str {
+        "Apply pending .sql files from the migrations directory in filename order, each in its own transaction. Applied versions are tracked in the __volki_migrations table. Subcommands: up (default), status."
+    }
+
+    fn options(&self) -> Vec<OptionSpec> {
+        vvec![
+            db_option(),
+            no_truncate_option(),
+            OptionSpec {
+                name: "dir",
+                description: "Directory containing migration .sql files",
+                takes_value: true,
+                required: false,
+                default_value: Some("migrations"),
+                short: None,
+            },
+        ]
+    }
+
+    fn execute(&self, args: &ParsedArgs) -> Result<(), CliError> {
+        let sub = args
+            .positional()
+            .first()
+            .map(|s| s.as_str())
+            .unwrap_or("up");
+        let db_name = args.get_option("db");
+
+        match sub {
+            "up" => self.up(args, db_name),
+            "status" => self.status(args, db_name),
+            other => Err(CliError::InvalidUsage(vformat!(
+                "unknown subcommand '{other}' for db:migrate (available: up, status)"
+            ))),
+        }
+    }
+}
+
+impl MigrateCommand {
+    fn up(&self, args: &ParsedArgs, db_name: Option<&str>) -> Result<(), CliError> {
+        let migrations = discover(&migrations_dir(args))?;
+
+        let config = load_db_config(db_name)?;
+        let mut conn = connect_db(&config)?;
+        let applied = applied_versions(&mut conn)?;
+
+        let pending: Vec<&Migration> = migrations
+            .iter()
+            .filter(|m| !applied.contains(&m.version))
+            .collect();
+        if pending.is_empty() {
+            veprintln!("  no pending migrations");
+            veprintln!();
+            return Ok(());
+        }
+
+        for (done, migration) in pending.iter().enumerate() {
+            apply(&mut conn, migration).map_err(|e| {
+                CliError::InvalidUsage(vformat!(
+                    "migration {} failed and was rolled back\n\n  \
+                     error: {e}\n\n  \
+                     {done} earlier migration(s) in this run were committed",
+                    migration.version,
+                ))
+            })?;
+            veprintln!("  applied {}", migration.version);
+        }
+        veprintln!();
+        Ok(())
+    }
+
+    fn status(&self, args: &ParsedArgs, db_name: Option<&str>) -> Result<(), CliError> {
+        let migrations = discover(&migrations_dir(args))?;
+
+        let config = load_db_config(db_name)?;
+        let mut conn = connect_db(&config)?;
+        let applied = applied_versions(&mut conn)?;
+
+        let rows = status_rows(&migrations, &applied);
+        output::print_table_with(
+            &["Version", "Status"],
+            &rows,
+            &['l', 'l'],
+            &table_options(args),
+        );
+        veprintln!();
+        Ok(())
+    }
+}
+
+/// A migration file; `version` is the file name without `.sql`.
+#[derive(Debug)]
+struct Migration {
+    version: String,
+    path: PathBuf,
+}
+
+fn migrations_dir(args: &ParsedArgs) -> PathBuf {
+    PathBuf::from(args.get_option("dir").unwrap_or("migrations"))
+}
+
+/// List `*.sql` files in `dir`, sorted by version.
+fn discover(dir: &Path) -> Result<Vec<Migration>, CliError> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        CliError::InvalidUsage(vformat!(
+            "cannot read migrations directory {}\n\n  \
+             error: {e}\n\n  \
+             create it and add files like 0001_create_users.sql",
+            dir.as_str(),
+        ))
+    })?;
+
+    let mut migrations = Vec::new();
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| CliError::InvalidUsage(vformat!("cannot read {}: {e}", dir.as_str())))?;
+        if entry.file_type() != FileType::File {
+            continue;
+        }
+        let Some(version) = entry.file_name().strip_suffix(".sql") else {
+            continue;
+        };
+        validate_version(version)?;
+        migrations.push(Migration {
+            version: String::from(version),
+            path: PathBuf::from(entry.path().as_str()),
+        });
+    }
+    migrations.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(migrations)
+}
+
+/// Versions are stored as SQL literals, so keep them to a safe alphabet.
+fn validate_version(version: &str) -> Result<(), CliError> {
+    let ok = !version.is_empty()
+        && version
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+    if ok {
+        Ok(())
+    } else {
+        Err(CliError::InvalidUsage(vformat!(
+            "invalid migration file name '{version}.sql'\n\n  \
+             use letters, digits, '_' and '-', e.g. 0001_create_users.sql"
+        )))
+    }
+}
+
+/// Create the tracking table if needed and return applied versions.
+fn applied_versions(conn: &mut DbConnection) -> Result<Vec<String>, CliError> {
+    let create = vformat!(
+        "CREATE TABLE IF NOT EXISTS {MIGRATIONS_TABLE} (\
+         version TEXT PRIMARY KEY, \
+         applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)"
+    );
+    conn.execute(&create).map_err(|e| {
+        CliError::InvalidUsage(vformat!("failed to create {MIGRATIONS_TABLE}: {e}"))
+    })?;
+
+    let rows = conn
+        .query(&vformat!(
+            "SELECT version FROM {MIGRATIONS_TABLE} ORDER BY version"
+        ))
+        .map_err(|e| CliError::InvalidUsage(vformat!("failed to read {MIGRATIONS_TABLE}: {e}")))?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| match r.get_value(0) {
+            Some(Value::Text(v)) => Some(v.clone()),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Run one migration and record it in the same transaction.
+fn apply(conn: &mut DbConnection, migration: &Migration) -> Result<(), CliError> {
+    let sql = fs::read_to_string(&migration.path).map_err(|e| {
+        CliError::InvalidUsage(vformat!("cannot read {}: {e}", migration.path.as_str()))
+    })?;
+    let record = vformat!(
+        "INSERT INTO {MIGRATIONS_TABLE} (version) VALUES ('{}')",
+        migration.version
+    );
+    conn.execute_in_transaction(&[sql.as_str(), record.as_str()])
+        .map_err(|e| CliError::InvalidUsage(vformat!("{e}")))
+}
+
+/// One `[version, status]` row per known migration, including applied
+/// versions whose file no longer exists.
+fn status_rows(migrations: &[Migration], applied: &[String]) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = migrations
+        .iter()
+        .map(|m| {
+            let status = if applied.contains(&m.version) {
+                "applied"
+            } else {
+                "pending"
+            };
+            vvec![m.version.clone(), String::from(status)]
+        })
+        .collect();
+    for version in applied {
+        if !migrations.iter().any(|m| &m.version == version) {
+            rows.push(vvec![
+                version.clone(),
+                String::from("applied (file missing)")
+            ]);
+        }
+    }
+    rows.sort_by(|a, b| a[0].cmp(&b[0]));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::db::langs::sqlite::lib::Connection as SqliteConnection;
+
+    use core::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_migrations(files: &[(&str, &str)]) -> PathBuf {
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = crate::core::volkiwithstds::env::temp_dir().join(&vformat!(
+            "volki_test_migrate_{}_{}",
+            crate::core::volkiwithstds::process::id(),
+            id
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let _ = fs::create_dir_all(&dir);
+        for (name, sql) in files {
+            fs::write(dir.join(name), sql).unwrap();
+        }
+        dir
+    }
+
+    fn memory_conn() -> DbConnection {
+        DbConnection::Sqlite(SqliteConnection::open(":memory:").unwrap())
+    }
+
+    #[test]
+    fn name_is_db_migrate() {
+        assert_eq!(MigrateCommand.name(), "db:migrate");
+    }
+
+    #[test]
+    fn requires_config() {
+        assert!(MigrateCommand.requires_config());
+    }
+
+    #[test]
+    fn discover_sorts_and_skips_non_sql() {
+        let dir = temp_migrations(&[
+            ("0002_posts.sql", "CREATE TABLE posts (id INTEGER);"),
+            ("0001_users.sql", "CREATE TABLE users (id INTEGER);"),
+            ("README.md", "notes"),
+        ]);
+        let found = discover(&dir).unwrap();
+        let versions: Vec<&str> = found.iter().map(|m| m.version.as_str()).collect();
+        assert_eq!(versions, vvec!["0001_users", "0002_posts"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn discover_missing_dir() {
+        let err = discover(Path::new("/nonexistent/volki/migrations")).unwrap_err();
+        assert!(vformat!("{err}").contains("cannot read migrations directory"));
+    }
+
+    #[test]
+    fn discover_rejects_unsafe_names() {
+        let dir = temp_migrations(&[("0001_it's.sql", "SELECT 1;")]);
+        let err = discover(&dir).unwrap_err();
+        assert!(vformat!("{err}").contains("invalid migration file name"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn apply_records_versions() {
+        let dir = temp_migrations(&[
+            (
+                "0001_users.sql",
+                "CREATE TABLE users (id INTEGER);\nINSERT INTO users VALUES (1);",
+            ),
+            ("0002_posts.sql", "CREATE TABLE posts (id INTEGER);"),
+        ]);
+        let mut conn = memory_conn();
+        assert!(applied_versions(&mut conn).unwrap().is_empty());
+
+        for m in &discover(&dir).unwrap() {
+            apply(&mut conn, m).unwrap();
+        }
+
+        assert_eq!(
+            applied_versions(&mut conn).unwrap(),
+            vvec![String::from("0001_users"), String::from("0002_posts")]
+        );
+        let rows = conn.query("SELECT id FROM users").unwrap();
+        assert_eq!(rows[0].get_int(0), Some(1));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_keeps_earlier() {
+        let dir = temp_migrations(&[
+            ("0001_users.sql", "CREATE TABLE users (id INTEGER);"),
+            (
+                "0002_broken.sql",
+                "CREATE TABLE half (id INTEGER);\nINSERT INTO missing VALUES (1);",
+            ),
+        ]);
+        let mut conn = memory_conn();
+        applied_versions(&mut conn).unwrap();
+        let migrations = discover(&dir).unwrap();
+
+        apply(&mut conn, &migrations[0]).unwrap();
+        assert!(apply(&mut conn, &migrations[1]).is_err());
+
+        assert_eq!(
+            applied_versions(&mut conn).unwrap(),
+            vvec![String::from("0001_users")]
+        );
+        // the partial CREATE TABLE from the failed migration is rolled back
+        assert!(conn.query("SELECT * FROM half").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn status_marks_applied_pending_and_missing() {
+        let migrations = vvec![
+            Migration {
+                version: String::from("0001_a"),
+                path: PathBuf::from("0001_a.sql"),
+            },
+            Migration {
+                version: String::from("0003_c"),
+                path: PathBuf::from("0003_c.sql"),
+            },
+        ];
+        let applied = vvec![String::from("0001_a"), String::from("0002_b")];
+        let rows = status_rows(&migrations, &applied);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            vvec![String::from("0001_a"), String::from("applied")]
+        );
+        assert_eq!(
+            rows[1],
+            vvec![
+                String::from("0002_b"),
+                String::from("applied (file missing)")
+            ]
+        );
+        assert_eq!(
+            rows[2],
+            vvec![String::from("0003_c"), String::from("pending")]
+        );
+    }
+}
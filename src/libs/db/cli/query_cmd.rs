@@ -0,0 +1,230 @@
+use super::{connect_db, db_option, load_db_config, no_truncate_option, table_options, Dialect};
+use crate::core::cli::command::{Command, OptionSpec};
+use crate::core::cli::error::CliError;
+use crate::core::cli::output;
+use crate::core::cli::parser::ParsedArgs;
+use crate::core::cli::style;
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::time::{Duration, Instant};
+use crate::libs::db::langs::postgres::lib::types::{Row, Value};
+use crate::{veprintln, vformat, vvec};
+
+pub struct QueryCommand;
+
+impl Command for QueryCommand {
+    fn name(&self) -> &str {
+        "db:query"
+    }
+
+    fn description(&self) -> &str {
+        "Run a SQL query and print the result"
+    }
+
+    fn long_description(&self) -> &str {
+        "Run a SQL statement and print any rows as a table. --explain shows the query plan \
+         (EXPLAIN ANALYZE on PostgreSQL, EXPLAIN QUERY PLAN on SQLite) and only accepts a \
+         single SELECT. --timing reports how long the query took."
+    }
+
+    fn options(&self) -> Vec<OptionSpec> {
+        vvec![
+            db_option(),
+            no_truncate_option(),
+            OptionSpec {
+                name: "explain",
+                description: "Show the query plan instead of the rows (SELECT only)",
+                takes_value: false,
+                required: false,
+                default_value: None,
+                short: None,
+            },
+            OptionSpec {
+                name: "timing",
+                description: "Print the wall-clock time of the query",
+                takes_value: false,
+                required: false,
+                default_value: None,
+                short: None,
+            },
+        ]
+    }
+
+    fn execute(&self, args: &ParsedArgs) -> Result<(), CliError> {
+        let sql = args
+            .positional()
+            .first()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| {
+                CliError::InvalidUsage(String::from(
+                    "missing SQL to run\n\n  usage: volki db:query \"SELECT ...\"",
+                ))
+            })?;
+        let explain = args.get_flag("explain");
+        // Refuse before connecting so nothing runs under EXPLAIN ANALYZE by accident
+        if explain && !is_single_select(sql) {
+            return Err(CliError::InvalidUsage(String::from(
+                "--explain only accepts a single SELECT statement",
+            )));
+        }
+
+        let config = load_db_config(args.get_option("db"))?;
+        let mut conn = connect_db(&config)?;
+        let sql = if explain {
+            explain_sql(sql, &config.dialect)
+        } else {
+            String::from(sql)
+        };
+
+        let (rows, elapsed) = timed(|| conn.query(&sql));
+        let rows = rows.map_err(|e| CliError::InvalidUsage(vformat!("query failed: {e}")))?;
+
+        if explain {
+            print_plan(&rows);
+        } else {
+            print_rows(&rows, args);
+        }
+        if args.get_flag("timing") {
+            output::print_hint(&timing_line(elapsed));
+        }
+        veprintln!();
+        Ok(())
+    }
+}
+
+/// Whether `sql` is one read-only SELECT (optionally behind a WITH), with at
+/// most a trailing semicolon.
+fn is_single_select(sql: &str) -> bool {
+    let body = sql.trim().trim_end_matches(';').trim_end();
+    if body.contains(';') {
+        return false;
+    }
+    let upper = body.to_ascii_uppercase();
+    let mut words = upper
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty());
+    match words.next() {
+        Some("SELECT") => {}
+        Some("WITH") => {}
+        _ => return false,
+    }
+    // Data-modifying CTEs and SELECT INTO write even under EXPLAIN
+    !words.any(|w| matches!(w, "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "INTO"))
+}
+
+fn explain_sql(sql: &str, dialect: &Dialect) -> String {
+    if *dialect == Dialect::Sqlite {
+        vformat!("EXPLAIN QUERY PLAN {sql}")
+    } else {
+        vformat!("EXPLAIN ANALYZE {sql}")
+    }
+}
+
+/// Run `f` and measure its wall-clock time.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+fn timing_line(elapsed: Duration) -> String {
+    vformat!("query took {}", style::format_duration(elapsed.as_millis()))
+}
+
+/// Print the plan one line per row. SQLite's plan puts the text in the
+/// last column; Postgres returns a single `QUERY PLAN` column.
+fn print_plan(rows: &[Row]) {
+    for row in rows {
+        if let Some(line) = row.len().checked_sub(1).and_then(|i| row.get_value(i)) {
+            veprintln!("  {}", line);
+        }
+    }
+}
+
+fn print_rows(rows: &[Row], args: &ParsedArgs) {
+    let Some(first) = rows.first() else {
+        veprintln!("  {}", style::dim("(0 rows)"));
+        return;
+    };
+    let headers: Vec<&str> = first.columns().iter().map(|c| c.name.as_str()).collect();
+    let aligns: Vec<char> = (0..first.len())
+        .map(|i| match first.get_value(i) {
+            Some(Value::Int(_) | Value::Float(_)) => 'r',
+            _ => 'l',
+        })
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            (0..headers.len())
+                .map(|i| vformat!("{}", row.get_value(i).unwrap_or(&Value::Null)))
+                .collect()
+        })
+        .collect();
+
+    output::print_table_with(&headers, &cells, &aligns, &table_options(args));
+    let noun = if rows.len() == 1 { "row" } else { "rows" };
+    veprintln!("  {}", style::dim(&vformat!("({} {noun})", rows.len())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cli::parser::RawArgs;
+    use crate::core::volkiwithstds::thread;
+
+    fn run(tokens: &[&str]) -> Result<(), CliError> {
+        let raw = RawArgs {
+            subcommand: Some(String::from("db:query")),
+            tokens: tokens.iter().map(|t| String::from(*t)).collect(),
+        };
+        let parsed = ParsedArgs::resolve(&raw, &QueryCommand.options()).unwrap();
+        QueryCommand.execute(&parsed)
+    }
+
+    #[test]
+    fn name_is_db_query() {
+        assert_eq!(QueryCommand.name(), "db:query");
+        let opts = QueryCommand.options();
+        assert!(opts.iter().any(|o| o.name == "explain"));
+        assert!(opts.iter().any(|o| o.name == "timing"));
+    }
+
+    #[test]
+    fn single_select_detection() {
+        assert!(is_single_select("SELECT * FROM users"));
+        assert!(is_single_select("  select id from t where x = 1;  "));
+        assert!(is_single_select("WITH a AS (SELECT 1) SELECT * FROM a"));
+        assert!(!is_single_select("DELETE FROM users"));
+        assert!(!is_single_select("DROP TABLE users"));
+        assert!(!is_single_select("SELECT 1; DROP TABLE users"));
+        assert!(!is_single_select("WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d"));
+        assert!(!is_single_select("SELECT * INTO backup FROM users"));
+    }
+
+    #[test]
+    fn explain_rejects_non_select_before_connecting() {
+        let msg = vformat!("{}", run(&["UPDATE users SET x = 1", "--explain"]).unwrap_err());
+        assert!(msg.contains("only accepts a single SELECT"));
+        let msg = vformat!("{}", run(&["--timing"]).unwrap_err());
+        assert!(msg.contains("missing SQL"));
+    }
+
+    #[test]
+    fn explain_prefix_per_dialect() {
+        assert_eq!(explain_sql("SELECT 1", &Dialect::Postgres), "EXPLAIN ANALYZE SELECT 1");
+        assert_eq!(explain_sql("SELECT 1", &Dialect::Sqlite), "EXPLAIN QUERY PLAN SELECT 1");
+    }
+
+    #[test]
+    fn timing_reports_non_negative_duration() {
+        let (value, elapsed) = timed(|| {
+            thread::sleep(Duration::from_millis(5));
+            7
+        });
+        assert_eq!(value, 7);
+        assert!(elapsed.as_millis() >= 5);
+        assert!(timing_line(elapsed).starts_with("query took "));
+        assert_eq!(timing_line(Duration::from_millis(0)), "query took 0ms");
+    }
+}
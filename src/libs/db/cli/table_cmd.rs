@@ -1,9 +1,19 @@
-use super::{connect_db, db_option, load_db_config, query_and_print, require_name};
+use super::export::{ExportFormat, Exporter};
+use super::{
+    connect_db, db_option, load_db_config, no_truncate_option, query_and_print, query_cells,
+    require_name, table_options, DbConnection,
+};
 use crate::core::cli::command::{Command, OptionSpec};
 use crate::core::cli::confirm::{self, ConfirmResult};
 use crate::core::cli::error::CliError;
+use crate::core::cli::output;
 use crate::core::cli::parser::ParsedArgs;
-use crate::core::volkiwithstds::collections::Vec;
+use crate::core::cli::validate;
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::fs;
+use crate::core::volkiwithstds::io::{self, Read, Write};
+use crate::core::volkiwithstds::path::Path;
+use crate::libs::db::langs::postgres::lib::types::Value;
 use crate::{veprintln, vvec};
 
 pub struct TableCommand;
@@ -18,12 +28,13 @@ impl Command for TableCommand {
     }
 
     fn long_description(&self) -> &str {
-        "List, describe, drop, and truncate tables in the public schema. Subcommands: ls (default), describe, drop, truncate."
+        "List, describe, drop, truncate, and export tables in the public schema. Subcommands: ls (default), describe, drop, truncate, export."
     }
 
     fn options(&self) -> Vec<OptionSpec> {
         vvec![
             db_option(),
+            no_truncate_option(),
             OptionSpec {
                 name: "name",
                 description: "Table name (for describe/drop/truncate)",
@@ -40,6 +51,30 @@ impl Command for TableCommand {
                 default_value: None,
                 short: None,
             },
+            OptionSpec {
+                name: "format",
+                description: "Export format: csv or json",
+                takes_value: true,
+                required: false,
+                default_value: Some("csv"),
+                short: None,
+            },
+            OptionSpec {
+                name: "out",
+                description: "Write the export to a file instead of stdout",
+                takes_value: true,
+                required: false,
+                default_value: None,
+                short: None,
+            },
+            OptionSpec {
+                name: "copy",
+                description: "Stream a CSV export with postgres COPY instead of decoding rows",
+                takes_value: false,
+                required: false,
+                default_value: None,
+                short: None,
+            },
         ]
     }
 
@@ -57,15 +92,18 @@ impl Command for TableCommand {
                  FROM information_schema.tables \
                  WHERE table_schema = 'public' \
                  ORDER BY table_name",
+                &[],
                 &["Name", "Type"],
                 &['l', 'l'],
                 db_name,
+                &table_options(args),
             ),
             "describe" => self.describe(args, db_name),
             "drop" => self.drop_table(args, db_name),
             "truncate" => self.truncate_table(args, db_name),
+            "export" => self.export_table(args, db_name),
             other => Err(CliError::InvalidUsage(crate::vformat!(
-                "unknown subcommand '{other}' for db:table (available: ls, describe, drop, truncate)"
+                "unknown subcommand '{other}' for db:table (available: ls, describe, drop, truncate, export)"
             ))),
         }
     }
@@ -74,21 +112,61 @@ impl Command for TableCommand {
 impl TableCommand {
     fn describe(&self, args: &ParsedArgs, db_name: Option<&str>) -> Result<(), CliError> {
         let name = require_name(args, "Table name")?;
+        let params = [Value::Text(name)];
+        let options = table_options(args);
 
-        let sql = crate::vformat!(
+        let config = load_db_config(db_name)?;
+        let mut conn = connect_db(&config)?;
+
+        let columns = query_cells(
+            &mut conn,
             "SELECT column_name, data_type, is_nullable, column_default \
              FROM information_schema.columns \
-             WHERE table_schema = 'public' AND table_name = '{}' \
+             WHERE table_schema = 'public' AND table_name = $1 \
              ORDER BY ordinal_position",
-            name,
-        );
-
-        query_and_print(
-            &sql,
+            &params,
+            4,
+        )?;
+        output::print_table_with(
             &["Column", "Type", "Nullable", "Default"],
+            &columns,
             &['l', 'l', 'l', 'l'],
-            db_name,
-        )
+            &options,
+        );
+        veprintln!();
+
+        let indexes = query_cells(
+            &mut conn,
+            "SELECT indexname, indexdef FROM pg_indexes \
+             WHERE schemaname = 'public' AND tablename = $1 \
+             ORDER BY indexname",
+            &params,
+            2,
+        )?;
+        let foreign_keys = query_cells(
+            &mut conn,
+            // conkey/confkey pair each column with the one it references;
+            // information_schema.constraint_column_usage has no ordinal
+            "SELECT c.conname, a.attname, rt.relname, ra.attname \
+             FROM pg_constraint c \
+             JOIN pg_class t ON t.oid = c.conrelid \
+             JOIN pg_namespace n ON n.oid = t.relnamespace \
+             JOIN pg_class rt ON rt.oid = c.confrelid \
+             CROSS JOIN LATERAL unnest(c.conkey, c.confkey) WITH ORDINALITY AS k(attnum, refnum, ord) \
+             JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = k.attnum \
+             JOIN pg_attribute ra ON ra.attrelid = c.confrelid AND ra.attnum = k.refnum \
+             WHERE c.contype = 'f' AND n.nspname = 'public' AND t.relname = $1 \
+             ORDER BY c.conname, k.ord",
+            &params,
+            4,
+        )?;
+
+        for section in describe_sections(&indexes, &foreign_keys) {
+            output::print_section(section.title);
+            output::print_table_with(section.headers, &section.rows, section.aligns, &options);
+            veprintln!();
+        }
+        Ok(())
     }
 
     fn drop_table(&self, args: &ParsedArgs, db_name: Option<&str>) -> Result<(), CliError> {
@@ -135,6 +213,183 @@ impl TableCommand {
         veprintln!();
         Ok(())
     }
+
+    fn export_table(&self, args: &ParsedArgs, db_name: Option<&str>) -> Result<(), CliError> {
+        // `export <name>` or `export --name <name>`
+        let name = match args.positional().get(1) {
+            Some(n) => {
+                validate::validate_identifier(n, "Table name")?;
+                String::from(n.as_str())
+            }
+            None => require_name(args, "Table name")?,
+        };
+
+        let raw_format = args.get_option("format").unwrap_or("csv");
+        let format = ExportFormat::parse(raw_format).ok_or_else(|| {
+            CliError::InvalidUsage(crate::vformat!(
+                "unknown export format '{raw_format}' (available: csv, json)"
+            ))
+        })?;
+        let copy = args.get_flag("copy");
+        if copy && format != ExportFormat::Csv {
+            return Err(CliError::InvalidUsage(String::from(
+                "--copy only supports --format=csv",
+            )));
+        }
+
+        let config = load_db_config(db_name)?;
+        let mut conn = connect_db(&config)?;
+        if copy {
+            return copy_export(&mut conn, &name, args.get_option("out"));
+        }
+
+        let sql = crate::vformat!("SELECT * FROM {name}");
+        match args.get_option("out") {
+            Some(path) => {
+                let mut file = fs::File::create(Path::new(path)).map_err(|e| {
+                    CliError::InvalidUsage(crate::vformat!("failed to write {path}: {e}"))
+                })?;
+                let rows = export_rows(&mut conn, &sql, &mut file, path, format)?;
+                veprintln!("  exported {} rows from '{}' to {}", rows, name, path);
+                veprintln!();
+            }
+            None => {
+                export_rows(&mut conn, &sql, &mut io::stdout(), "stdout", format)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A titled table printed after the columns in `describe`.
+struct DescribeSection {
+    title: &'static str,
+    headers: &'static [&'static str],
+    aligns: &'static [char],
+    rows: Vec<Vec<String>>,
+}
+
+/// Build the "Indexes" and "Foreign keys" sections from raw query cells:
+/// `[indexname, indexdef]` and `[constraint, column, ref_table, ref_column]`,
+/// the latter one row per column pair in key order. A section with no rows
+/// is left out.
+fn describe_sections(
+    indexes: &[Vec<String>],
+    foreign_keys: &[Vec<String>],
+) -> Vec<DescribeSection> {
+    let mut sections = Vec::new();
+
+    if !indexes.is_empty() {
+        let rows = indexes
+            .iter()
+            .map(|r| {
+                let def = r[1].as_str();
+                let unique = if def.starts_with("CREATE UNIQUE INDEX") { "yes" } else { "" };
+                let using = def.find(" USING ").map_or(def, |i| &def[i + 7..]);
+                vvec![r[0].clone(), String::from(unique), String::from(using)]
+            })
+            .collect();
+        sections.push(DescribeSection {
+            title: "Indexes",
+            headers: &["Name", "Unique", "Definition"],
+            aligns: &['l', 'l', 'l'],
+            rows,
+        });
+    }
+
+    if !foreign_keys.is_empty() {
+        // Composite keys come back as one row per column pair, so group by
+        // constraint. Pairs stay in order; the i-th column references the
+        // i-th referenced column.
+        let mut groups: Vec<(String, Vec<String>, String, Vec<String>)> = Vec::new();
+        for r in foreign_keys {
+            if groups.last().is_none_or(|g| g.0 != r[0]) {
+                groups.push((r[0].clone(), Vec::new(), r[2].clone(), Vec::new()));
+            }
+            let group = groups.last_mut().unwrap();
+            group.1.push(r[1].clone());
+            group.3.push(r[3].clone());
+        }
+        let rows = groups
+            .into_iter()
+            .map(|(constraint, columns, table, ref_columns)| {
+                vvec![
+                    constraint,
+                    columns.join(", "),
+                    crate::vformat!("{}({})", table, ref_columns.join(", ")),
+                ]
+            })
+            .collect();
+        sections.push(DescribeSection {
+            title: "Foreign keys",
+            headers: &["Constraint", "Column", "References"],
+            aligns: &['l', 'l', 'l'],
+            rows,
+        });
+    }
+
+    sections
+}
+
+/// Run `sql` and stream its rows to `out` (named `dest` in errors) in
+/// `format`, returning the row count.
+fn export_rows<W: Write>(
+    conn: &mut DbConnection,
+    sql: &str,
+    out: &mut W,
+    dest: &str,
+    format: ExportFormat,
+) -> Result<u64, CliError> {
+    let mut exporter = Exporter::new(out, format);
+    conn.query_into(sql, &mut exporter)
+        .map_err(|e| CliError::InvalidUsage(crate::vformat!("failed to export table: {e}")))?;
+    exporter
+        .finish()
+        .map_err(|e| CliError::InvalidUsage(crate::vformat!("failed to write {dest}: {e}")))
+}
+
+/// `export --copy`: stream `COPY ... TO STDOUT` straight to the output.
+/// Postgres only.
+fn copy_export(conn: &mut DbConnection, name: &str, out: Option<&str>) -> Result<(), CliError> {
+    let DbConnection::Postgres(pg) = conn else {
+        return Err(CliError::InvalidUsage(String::from(
+            "--copy requires a postgres database",
+        )));
+    };
+    let export_err = |e: &dyn core::fmt::Display| {
+        CliError::InvalidUsage(crate::vformat!("failed to export table: {e}"))
+    };
+
+    let sql = crate::vformat!("COPY {name} TO STDOUT WITH (FORMAT csv, HEADER)");
+    let mut reader = pg.copy_out(&sql).map_err(|e| export_err(&e))?;
+    match out {
+        Some(path) => {
+            let mut file = fs::File::create(Path::new(path)).map_err(|e| {
+                CliError::InvalidUsage(crate::vformat!("failed to write {path}: {e}"))
+            })?;
+            let bytes = pipe(&mut reader, &mut file).map_err(|e| export_err(&e))?;
+            veprintln!("  exported {} bytes from '{}' to {}", bytes, name, path);
+            veprintln!();
+        }
+        None => {
+            pipe(&mut reader, &mut io::stdout()).map_err(|e| export_err(&e))?;
+        }
+    }
+    Ok(())
+}
+
+fn pipe<R: Read, W: Write>(reader: &mut R, writer: &mut W) -> io::Result<u64> {
+    let mut buf = [0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            writer.flush()?;
+            return Ok(total);
+        }
+        writer.write_all(&buf[..n])?;
+        total += n as u64;
+    }
 }
 
 #[cfg(test)]
@@ -173,5 +428,101 @@ mod tests {
         assert!(msg.contains("describe"));
         assert!(msg.contains("drop"));
         assert!(msg.contains("truncate"));
+        assert!(msg.contains("export"));
+    }
+
+    #[test]
+    fn export_rejects_unknown_format() {
+        let raw = crate::core::cli::parser::RawArgs {
+            subcommand: Some(String::from("db:table")),
+            tokens: vvec![
+                String::from("export"),
+                String::from("users"),
+                String::from("--format=xml"),
+            ],
+        };
+        let parsed = ParsedArgs::resolve(&raw, &TableCommand.options()).unwrap();
+        let msg = crate::vformat!("{}", TableCommand.execute(&parsed).unwrap_err());
+        assert!(msg.contains("unknown export format 'xml'"));
+    }
+
+    fn cells(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|r| r.iter().map(|c| String::from(*c)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn describe_sections_format_indexes_and_foreign_keys() {
+        let indexes = cells(&[
+            &[
+                "orders_pkey",
+                "CREATE UNIQUE INDEX orders_pkey ON public.orders USING btree (id)",
+            ],
+            &[
+                "orders_user_idx",
+                "CREATE INDEX orders_user_idx ON public.orders USING btree (user_id)",
+            ],
+        ]);
+        let fks = cells(&[
+            &["orders_user_fk", "user_id", "users", "id"],
+            &["orders_item_fk", "shop_id", "items", "shop_id"],
+            &["orders_item_fk", "sku", "items", "sku"],
+        ]);
+
+        let sections = describe_sections(&indexes, &fks);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].title, "Indexes");
+        assert_eq!(
+            sections[0].rows,
+            cells(&[
+                &["orders_pkey", "yes", "btree (id)"],
+                &["orders_user_idx", "", "btree (user_id)"],
+            ])
+        );
+        assert_eq!(sections[1].title, "Foreign keys");
+        assert_eq!(
+            sections[1].rows,
+            cells(&[
+                &["orders_user_fk", "user_id", "users(id)"],
+                &["orders_item_fk", "shop_id, sku", "items(shop_id, sku)"],
+            ])
+        );
+    }
+
+    #[test]
+    fn describe_sections_keep_composite_key_pairing() {
+        // (a, b) -> items(y, x): a references y, b references x
+        let fks = cells(&[
+            &["pair_fk", "a", "items", "y"],
+            &["pair_fk", "b", "items", "x"],
+        ]);
+        let sections = describe_sections(&[], &fks);
+        assert_eq!(sections[0].rows, cells(&[&["pair_fk", "a, b", "items(y, x)"]]));
+    }
+
+    #[test]
+    fn describe_sections_omit_empty() {
+        assert!(describe_sections(&[], &[]).is_empty());
+        let fks = cells(&[&["fk", "a", "t", "b"]]);
+        let sections = describe_sections(&[], &fks);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].title, "Foreign keys");
+    }
+
+    #[test]
+    fn export_copy_requires_csv() {
+        let raw = crate::core::cli::parser::RawArgs {
+            subcommand: Some(String::from("db:table")),
+            tokens: vvec![
+                String::from("export"),
+                String::from("users"),
+                String::from("--copy"),
+                String::from("--format=json"),
+            ],
+        };
+        let parsed = ParsedArgs::resolve(&raw, &TableCommand.options()).unwrap();
+        let msg = crate::vformat!("{}", TableCommand.execute(&parsed).unwrap_err());
+        assert!(msg.contains("--copy only supports"));
     }
 }
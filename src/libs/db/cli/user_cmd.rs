@@ -1,5 +1,6 @@
 use super::{
-    connect_db, db_option, load_db_config, query_and_print, require_name, require_password,
+    connect_db, db_option, load_db_config, no_truncate_option, query_and_print, require_name,
+    require_password, table_options,
 };
 use crate::core::cli::command::{Command, OptionSpec};
 use crate::core::cli::confirm::{self, ConfirmResult};
@@ -26,6 +27,7 @@ impl Command for UserCommand {
     fn options(&self) -> Vec<OptionSpec> {
         vvec![
             db_option(),
+            no_truncate_option(),
             OptionSpec {
                 name: "name",
                 description: "Role name (for add/drop)",
@@ -66,9 +68,11 @@ impl Command for UserCommand {
                 "SELECT rolname, rolsuper, rolcreatedb, rolcanlogin \
                  FROM pg_roles \
                  ORDER BY rolname",
+                &[],
                 &["Role", "Super", "CreateDB", "Login"],
                 &['l', 'l', 'l', 'l'],
                 db_name,
+                &table_options(args),
             ),
             "add" => self.add(args, db_name),
             "drop" => self.drop_role(args, db_name),
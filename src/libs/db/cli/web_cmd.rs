@@ -74,6 +74,8 @@ impl Command for WebEditorCommand {
             empty_routes: EmptyRoutesPolicy::Error(
                 "no page.volki routes found under src/libs/db/web_editor/app",
             ),
+            watch_interval: None,
+            max_body_size: None,
         })
     }
 }
@@ -1,19 +1,27 @@
-use crate::core::volkiwithstds::collections::{HashMap, String, Vec};
-use crate::core::volkiwithstds::net::TcpStream;
+use crate::core::volkiwithstds::collections::{HashMap, String, Vec, VecDeque};
+use crate::core::volkiwithstds::io::{self, IoError, IoErrorKind, Read};
+use crate::core::volkiwithstds::sync::Arc;
+use crate::core::volkiwithstds::time::{Duration, Instant};
 
 use crate::libs::db::langs::postgres::lib::error::PgError;
-use crate::libs::db::langs::postgres::lib::protocol;
-use crate::libs::db::langs::postgres::lib::types::Row;
+use crate::libs::db::langs::postgres::lib::protocol::{self, Notification};
+use crate::libs::db::langs::postgres::lib::tls::{PgStream, SslMode};
+use crate::libs::db::langs::postgres::lib::transaction::Transaction;
+use crate::libs::db::langs::postgres::lib::types::{Row, RowSchema, RowSink, Value};
 
 pub struct Connection {
-    stream: TcpStream,
+    stream: PgStream,
     params: HashMap<String, String>,
     backend_pid: i32,
     backend_key: i32,
+    /// Notifications that arrived while a query was being answered, kept
+    /// for `poll_notification`.
+    notifications: VecDeque<Notification>,
 }
 
 impl Connection {
     /// Connect to a Postgres server and complete authentication.
+    /// TLS is used when the server offers it (`sslmode=prefer`).
     pub fn connect(
         host: &str,
         port: u16,
@@ -21,7 +29,46 @@ impl Connection {
         database: &str,
         password: &str,
     ) -> Result<Self, PgError> {
-        let mut stream = TcpStream::connect((host, port))?;
+        Self::connect_with_ssl(host, port, user, database, password, SslMode::default())
+    }
+
+    /// Connect with an explicit `sslmode`, negotiating TLS before startup.
+    pub fn connect_with_ssl(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: &str,
+        sslmode: SslMode,
+    ) -> Result<Self, PgError> {
+        Self::establish(host, port, user, database, password, sslmode, None)
+    }
+
+    /// Like [`connect_with_ssl`](Self::connect_with_ssl), but gives up when
+    /// the TCP connect takes longer than `timeout`. Used for quick health
+    /// probes.
+    pub fn connect_timeout(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: &str,
+        sslmode: SslMode,
+        timeout: Duration,
+    ) -> Result<Self, PgError> {
+        Self::establish(host, port, user, database, password, sslmode, Some(timeout))
+    }
+
+    fn establish(
+        host: &str,
+        port: u16,
+        user: &str,
+        database: &str,
+        password: &str,
+        sslmode: SslMode,
+        connect_timeout: Option<Duration>,
+    ) -> Result<Self, PgError> {
+        let mut stream = PgStream::open(host, port, sslmode, connect_timeout)?;
 
         protocol::write_startup(&mut stream, user, database)?;
 
@@ -107,69 +154,42 @@ impl Connection {
             params,
             backend_pid,
             backend_key,
+            notifications: VecDeque::new(),
         })
     }
 
     /// Execute a simple query and return result rows.
     pub fn query(&mut self, sql: &str) -> Result<Vec<Row>, PgError> {
         protocol::write_query(&mut self.stream, sql)?;
+        read_rows(&mut self.stream, &mut self.notifications, "query")
+    }
 
-        let mut columns = Vec::new();
-        let mut rows = Vec::new();
-
-        loop {
-            let (tag, payload) = protocol::read_message(&mut self.stream)?;
-            match tag {
-                b'T' => {
-                    columns = protocol::parse_row_description(&payload)?;
-                }
-                b'D' => {
-                    let row = protocol::parse_data_row(&payload, &columns)?;
-                    rows.push(row);
-                }
-                b'C' => {
-                    // CommandComplete — query done
-                }
-                b'Z' => {
-                    // ReadyForQuery
-                    break;
-                }
-                b'E' => {
-                    // Drain until ReadyForQuery then return error
-                    let err = protocol::parse_error_response(&payload);
-                    self.drain_until_ready()?;
-                    return Err(err);
-                }
-                b'N' => {
-                    // NoticeResponse — ignore
-                }
-                b'I' => {
-                    // EmptyQueryResponse
-                }
-                _ => {
-                    return Err(PgError::Protocol(crate::vformat!(
-                        "unexpected message in query: 0x{tag:02x}"
-                    )));
-                }
-            }
-        }
-
-        Ok(rows)
+    /// Execute a simple query, handing each row to `sink` as it arrives.
+    pub fn query_into(&mut self, sql: &str, sink: &mut dyn RowSink) -> Result<(), PgError> {
+        protocol::write_query(&mut self.stream, sql)?;
+        read_rows_into(&mut self.stream, &mut self.notifications, "query", sink)
     }
 
     /// Execute a statement that doesn't return rows (INSERT, UPDATE, DELETE, DDL).
     /// Returns the number of affected rows.
     pub fn execute(&mut self, sql: &str) -> Result<u64, PgError> {
+        let tag = self.execute_tag(sql)?;
+        Ok(protocol::parse_command_complete(&tag))
+    }
+
+    /// Like `execute`, but returns the raw CommandComplete tag of the last
+    /// statement, e.g. `INSERT 0 5` or `ROLLBACK`.
+    pub(crate) fn execute_tag(&mut self, sql: &str) -> Result<Vec<u8>, PgError> {
         protocol::write_query(&mut self.stream, sql)?;
 
-        let mut affected = 0u64;
+        let mut tag_payload = Vec::new();
 
         loop {
             let (tag, payload) = protocol::read_message(&mut self.stream)?;
             match tag {
                 b'T' | b'D' => {}
                 b'C' => {
-                    affected = protocol::parse_command_complete(&payload);
+                    tag_payload = payload;
                 }
                 b'Z' => {
                     break;
@@ -179,6 +199,10 @@ impl Connection {
                     self.drain_until_ready()?;
                     return Err(err);
                 }
+                b'A' => {
+                    self.notifications
+                        .push_back(protocol::parse_notification_response(&payload)?);
+                }
                 b'N' | b'I' => {}
                 _ => {
                     return Err(PgError::Protocol(crate::vformat!(
@@ -188,11 +212,14 @@ impl Connection {
             }
         }
 
-        Ok(affected)
+        Ok(tag_payload)
     }
 
     /// Execute a parameterized query using the extended query protocol.
-    pub fn query_params(&mut self, sql: &str, params: &[&str]) -> Result<Vec<Row>, PgError> {
+    ///
+    /// Placeholders are `$1`, `$2`, ... in `sql`; values are sent separately
+    /// from the statement text, so they are never interpolated into SQL.
+    pub fn query_params(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Row>, PgError> {
         let stmt = "";
         let portal = "";
 
@@ -203,49 +230,67 @@ impl Connection {
         protocol::write_execute(&mut self.stream, portal, 0)?;
         protocol::write_sync(&mut self.stream)?;
 
-        let mut columns = Vec::new();
-        let mut rows = Vec::new();
+        read_rows(&mut self.stream, &mut self.notifications, "query_params")
+    }
+
+    /// Run a `COPY ... TO STDOUT` statement and stream its output.
+    ///
+    /// The reader yields the raw `CopyData` bytes with no row decoding.
+    /// Read it to the end, or drop it, before using the connection again.
+    pub fn copy_out(&mut self, sql: &str) -> Result<CopyOut<'_, PgStream>, PgError> {
+        protocol::write_query(&mut self.stream, sql)?;
+        CopyOut::start(&mut self.stream, &mut self.notifications)
+    }
 
+    /// Subscribe this session to `NOTIFY` on `channel`.
+    pub fn listen(&mut self, channel: &str) -> Result<(), PgError> {
+        self.execute(&crate::vformat!("LISTEN {}", quote_ident(channel)))
+            .map(|_| ())
+    }
+
+    /// Stop receiving notifications on `channel`.
+    pub fn unlisten(&mut self, channel: &str) -> Result<(), PgError> {
+        self.execute(&crate::vformat!("UNLISTEN {}", quote_ident(channel)))
+            .map(|_| ())
+    }
+
+    /// Next notification on a listened channel, waiting up to `timeout`.
+    ///
+    /// Notifications that arrived during an earlier query are returned
+    /// first. Returns `None` when nothing arrives in time.
+    pub fn poll_notification(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<Notification>, PgError> {
+        if let Some(n) = self.notifications.pop_front() {
+            return Ok(Some(n));
+        }
+        let start = Instant::now();
         loop {
+            let left = timeout.as_millis().saturating_sub(start.elapsed().as_millis());
+            if !self.stream.wait_readable(left.min(i32::MAX as u128) as i32)? {
+                return Ok(None);
+            }
             let (tag, payload) = protocol::read_message(&mut self.stream)?;
             match tag {
-                b'1' => {
-                    // ParseComplete
-                }
-                b'2' => {
-                    // BindComplete
-                }
-                b'T' => {
-                    columns = protocol::parse_row_description(&payload)?;
-                }
-                b'D' => {
-                    let row = protocol::parse_data_row(&payload, &columns)?;
-                    rows.push(row);
-                }
-                b'C' => {
-                    // CommandComplete
-                }
-                b'Z' => {
-                    break;
-                }
-                b'n' => {
-                    // NoData (for queries returning no columns)
-                }
-                b'E' => {
-                    let err = protocol::parse_error_response(&payload);
-                    self.drain_until_ready()?;
-                    return Err(err);
+                b'A' => return Ok(Some(protocol::parse_notification_response(&payload)?)),
+                b'E' => return Err(protocol::parse_error_response(&payload)),
+                b'N' | b'S' => {
+                    // NoticeResponse / ParameterStatus can arrive between queries
                 }
-                b'N' => {}
                 _ => {
                     return Err(PgError::Protocol(crate::vformat!(
-                        "unexpected message in query_params: 0x{tag:02x}"
+                        "unexpected message while idle: 0x{tag:02x}"
                     )));
                 }
             }
         }
+    }
 
-        Ok(rows)
+    /// Start a transaction. Statements run through the returned `Transaction`
+    /// are committed together, or rolled back if it is dropped uncommitted.
+    pub fn begin(&mut self) -> Result<Transaction<'_>, PgError> {
+        Transaction::begin(self)
     }
 
     /// Explicitly close the connection (sends Terminate).
@@ -269,21 +314,270 @@ impl Connection {
         self.backend_key
     }
 
+    /// Whether the session is encrypted with TLS.
+    pub fn is_tls(&self) -> bool {
+        self.stream.is_tls()
+    }
+
+    /// A connection whose server side replays `replies`, for tests that
+    /// script a whole exchange.
+    #[cfg(test)]
+    pub(crate) fn mock(replies: Vec<u8>) -> Connection {
+        Connection {
+            stream: PgStream::Mock {
+                replies: io::Cursor::new(replies),
+                sent: Vec::new(),
+            },
+            params: HashMap::new(),
+            backend_pid: 0,
+            backend_key: 0,
+            notifications: VecDeque::new(),
+        }
+    }
+
+    /// Everything the client has written to a `mock` connection.
+    #[cfg(test)]
+    pub(crate) fn sent(&self) -> &[u8] {
+        match &self.stream {
+            PgStream::Mock { sent, .. } => sent,
+            _ => &[],
+        }
+    }
+
     /// Drain messages until ReadyForQuery, used after receiving an error.
     fn drain_until_ready(&mut self) -> Result<(), PgError> {
+        drain_until_ready(&mut self.stream, &mut self.notifications)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = protocol::write_terminate(&mut self.stream);
+    }
+}
+
+/// Collect result rows until ReadyForQuery.
+///
+/// Handles both simple-query responses and the extended-protocol
+/// ParseComplete/BindComplete/NoData messages. On ErrorResponse the rest of
+/// the cycle is drained so the connection stays usable. Notifications that
+/// arrive mid-cycle are queued rather than treated as part of the result.
+fn read_rows<S: Read>(
+    stream: &mut S,
+    notifications: &mut VecDeque<Notification>,
+    context: &str,
+) -> Result<Vec<Row>, PgError> {
+    let mut rows = Vec::new();
+    read_rows_into(stream, notifications, context, &mut rows)?;
+    Ok(rows)
+}
+
+/// Like [`read_rows`], but hands the columns and each row to `sink`.
+fn read_rows_into<S: Read>(
+    stream: &mut S,
+    notifications: &mut VecDeque<Notification>,
+    context: &str,
+    sink: &mut dyn RowSink,
+) -> Result<(), PgError> {
+    let mut schema = Arc::new(RowSchema::new(Vec::new()));
+
+    loop {
+        let (tag, payload) = protocol::read_message(stream)?;
+        match tag {
+            b'1' | b'2' => {
+                // ParseComplete / BindComplete
+            }
+            b'T' => {
+                schema = Arc::new(RowSchema::new(protocol::parse_row_description(&payload)?));
+                sink.columns(schema.columns());
+            }
+            b'D' => {
+                sink.row(protocol::parse_data_row(&payload, &schema)?);
+            }
+            b'C' => {
+                // CommandComplete — query done
+            }
+            b'Z' => {
+                // ReadyForQuery
+                break;
+            }
+            b'E' => {
+                // Drain until ReadyForQuery then return error
+                let err = protocol::parse_error_response(&payload);
+                drain_until_ready(stream, notifications)?;
+                return Err(err);
+            }
+            b'A' => {
+                notifications.push_back(protocol::parse_notification_response(&payload)?);
+            }
+            b'N' | b'I' | b'n' => {
+                // NoticeResponse / EmptyQueryResponse / NoData
+            }
+            _ => {
+                return Err(PgError::Protocol(crate::vformat!(
+                    "unexpected message in {context}: 0x{tag:02x}"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Data of a `COPY ... TO STDOUT`, returned by [`Connection::copy_out`].
+/// Dropping it before the end drains the rest so the connection stays
+/// usable.
+pub struct CopyOut<'a, S: Read> {
+    stream: &'a mut S,
+    notifications: &'a mut VecDeque<Notification>,
+    chunk: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<'a, S: Read> CopyOut<'a, S> {
+    /// Read up to CopyOutResponse. A statement that does not start a COPY
+    /// is an error, after its reply has been drained.
+    fn start(
+        stream: &'a mut S,
+        notifications: &'a mut VecDeque<Notification>,
+    ) -> Result<Self, PgError> {
+        loop {
+            let (tag, payload) = protocol::read_message(stream)?;
+            match tag {
+                b'H' => {
+                    protocol::parse_copy_out_response(&payload)?;
+                    return Ok(CopyOut {
+                        stream,
+                        notifications,
+                        chunk: Vec::new(),
+                        pos: 0,
+                        done: false,
+                    });
+                }
+                b'E' => {
+                    let err = protocol::parse_error_response(&payload);
+                    drain_until_ready(stream, notifications)?;
+                    return Err(err);
+                }
+                b'A' => {
+                    notifications.push_back(protocol::parse_notification_response(&payload)?);
+                }
+                b'N' => {}
+                b'Z' => {
+                    return Err(PgError::Protocol("statement did not start COPY TO STDOUT".into()));
+                }
+                _ => {
+                    drain_until_ready(stream, notifications)?;
+                    return Err(PgError::Protocol("statement did not start COPY TO STDOUT".into()));
+                }
+            }
+        }
+    }
+
+    /// Load the next CopyData chunk. Returns false once ReadyForQuery ends
+    /// the copy.
+    fn next_chunk(&mut self) -> Result<bool, PgError> {
         loop {
-            let (tag, _) = protocol::read_message(&mut self.stream)?;
-            if tag == b'Z' {
-                return Ok(());
+            let (tag, payload) = match protocol::read_message(self.stream) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    self.done = true;
+                    return Err(e);
+                }
+            };
+            match tag {
+                b'd' => {
+                    self.chunk = payload;
+                    self.pos = 0;
+                    return Ok(true);
+                }
+                b'c' | b'C' | b'N' => {
+                    // CopyDone / CommandComplete / NoticeResponse
+                }
+                b'A' => {
+                    self.notifications
+                        .push_back(protocol::parse_notification_response(&payload)?);
+                }
+                b'Z' => {
+                    self.done = true;
+                    return Ok(false);
+                }
+                b'E' => {
+                    self.done = true;
+                    let err = protocol::parse_error_response(&payload);
+                    drain_until_ready(self.stream, self.notifications)?;
+                    return Err(err);
+                }
+                _ => {
+                    self.done = true;
+                    return Err(PgError::Protocol(crate::vformat!(
+                        "unexpected message in copy_out: 0x{tag:02x}"
+                    )));
+                }
             }
         }
     }
 }
 
-impl Drop for Connection {
+impl<S: Read> Read for CopyOut<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos >= self.chunk.len() {
+            if self.done {
+                return Ok(0);
+            }
+            match self.next_chunk() {
+                Ok(true) => {}
+                Ok(false) => return Ok(0),
+                Err(PgError::Io(e)) => return Err(e),
+                Err(e) => {
+                    return Err(IoError::new(IoErrorKind::Other, &crate::vformat!("{e}")));
+                }
+            }
+        }
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<S: Read> Drop for CopyOut<'_, S> {
     fn drop(&mut self) {
-        let _ = protocol::write_terminate(&mut self.stream);
+        while !self.done {
+            if self.next_chunk().is_err() {
+                break;
+            }
+        }
+    }
+}
+
+fn drain_until_ready<S: Read>(
+    stream: &mut S,
+    notifications: &mut VecDeque<Notification>,
+) -> Result<(), PgError> {
+    loop {
+        let (tag, payload) = protocol::read_message(stream)?;
+        match tag {
+            b'Z' => return Ok(()),
+            b'A' => notifications.push_back(protocol::parse_notification_response(&payload)?),
+            _ => {}
+        }
+    }
+}
+
+/// Quote an identifier such as a channel name, doubling embedded quotes.
+fn quote_ident(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 2);
+    out.push('"');
+    for c in name.chars() {
+        if c == '"' {
+            out.push('"');
+        }
+        out.push(c);
     }
+    out.push('"');
+    out
 }
 
 /// Read a null-terminated string from a byte slice.
@@ -299,3 +593,184 @@ fn read_cstring_from(data: &[u8], offset: &mut usize) -> Result<String, PgError>
     *offset += 1;
     Ok(s)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::io::Cursor;
+
+    fn msg(buf: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+        buf.push(tag);
+        buf.extend_from_slice(&((payload.len() as i32) + 4).to_be_bytes());
+        buf.extend_from_slice(payload);
+    }
+
+    fn int4_row_description(name: &str) -> Vec<u8> {
+        let mut d = Vec::new();
+        d.extend_from_slice(&1i16.to_be_bytes());
+        d.extend_from_slice(name.as_bytes());
+        d.push(0);
+        d.extend_from_slice(&0i32.to_be_bytes());
+        d.extend_from_slice(&0i16.to_be_bytes());
+        d.extend_from_slice(&23i32.to_be_bytes());
+        d.extend_from_slice(&4i16.to_be_bytes());
+        d.extend_from_slice(&(-1i32).to_be_bytes());
+        d.extend_from_slice(&0i16.to_be_bytes());
+        d
+    }
+
+    fn data_row(value: Option<&[u8]>) -> Vec<u8> {
+        let mut d = Vec::new();
+        d.extend_from_slice(&1i16.to_be_bytes());
+        match value {
+            Some(v) => {
+                d.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                d.extend_from_slice(v);
+            }
+            None => d.extend_from_slice(&(-1i32).to_be_bytes()),
+        }
+        d
+    }
+
+    #[test]
+    fn read_rows_extended_select_int_roundtrip() {
+        // Server reply to Parse/Bind/Describe/Execute/Sync for `SELECT $1::int` with $1 = 42
+        let mut wire = Vec::new();
+        msg(&mut wire, b'1', &[]);
+        msg(&mut wire, b'2', &[]);
+        msg(&mut wire, b'T', &int4_row_description("int4"));
+        msg(&mut wire, b'D', &data_row(Some(b"42")));
+        msg(&mut wire, b'C', b"SELECT 1\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let rows = read_rows(&mut stream, &mut VecDeque::new(), "query_params").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_int(0), Some(42));
+    }
+
+    #[test]
+    fn read_rows_null_param_roundtrip() {
+        let mut wire = Vec::new();
+        msg(&mut wire, b'1', &[]);
+        msg(&mut wire, b'2', &[]);
+        msg(&mut wire, b'T', &int4_row_description("int4"));
+        msg(&mut wire, b'D', &data_row(None));
+        msg(&mut wire, b'C', b"SELECT 1\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let rows = read_rows(&mut stream, &mut VecDeque::new(), "query_params").unwrap();
+        assert_eq!(rows[0].get_value(0), Some(&Value::Null));
+    }
+
+    #[test]
+    fn read_rows_error_drains_to_ready() {
+        let mut wire = Vec::new();
+        msg(&mut wire, b'E', b"SERROR\0C22P02\0Minvalid input\0\0");
+        msg(&mut wire, b'Z', b"I");
+        // A following cycle must still be readable
+        msg(&mut wire, b'n', &[]);
+        msg(&mut wire, b'C', b"SET\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let err = read_rows(&mut stream, &mut VecDeque::new(), "query_params").unwrap_err();
+        assert!(matches!(err, PgError::Server { ref code, .. } if code == "22P02"));
+        let rows = read_rows(&mut stream, &mut VecDeque::new(), "query").unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn read_rows_queues_interleaved_notifications() {
+        let mut notify = Vec::new();
+        notify.extend_from_slice(&99i32.to_be_bytes());
+        notify.extend_from_slice(b"jobs\0done\0");
+
+        let mut wire = Vec::new();
+        msg(&mut wire, b'T', &int4_row_description("n"));
+        msg(&mut wire, b'A', &notify);
+        msg(&mut wire, b'D', &data_row(Some(b"1")));
+        msg(&mut wire, b'C', b"SELECT 1\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let mut queued = VecDeque::new();
+        let rows = read_rows(&mut stream, &mut queued, "query").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_int(0), Some(1));
+        let n = queued.pop_front().unwrap();
+        assert_eq!((n.process_id, n.channel.as_str(), n.payload.as_str()), (99, "jobs", "done"));
+        assert!(queued.is_empty());
+    }
+
+    fn copy_out_reply(wire: &mut Vec<u8>, chunks: &[&[u8]]) {
+        msg(wire, b'H', b"\0\0\x01\0\0");
+        for chunk in chunks {
+            msg(wire, b'd', chunk);
+        }
+        msg(wire, b'c', &[]);
+        msg(wire, b'C', b"COPY 2\0");
+        msg(wire, b'Z', b"I");
+    }
+
+    #[test]
+    fn copy_out_streams_copy_data() {
+        let mut wire = Vec::new();
+        copy_out_reply(&mut wire, &[b"id\n", b"1\n", b"", b"2\n"]);
+        // The next query on the connection must still line up
+        msg(&mut wire, b'C', b"SET\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let mut queued = VecDeque::new();
+        let mut out = Vec::new();
+        let mut reader = CopyOut::start(&mut stream, &mut queued).unwrap();
+        let mut small = [0u8; 2];
+        let n = reader.read(&mut small).unwrap();
+        assert_eq!(&small[..n], b"id");
+        out.extend_from_slice(&small[..n]);
+        reader.read_to_end(&mut out).unwrap();
+        drop(reader);
+        assert_eq!(out.as_slice(), b"id\n1\n2\n");
+
+        assert!(read_rows(&mut stream, &mut queued, "query").unwrap().is_empty());
+    }
+
+    #[test]
+    fn copy_out_dropped_early_drains_reply() {
+        let mut wire = Vec::new();
+        copy_out_reply(&mut wire, &[b"a\n", b"b\n"]);
+        msg(&mut wire, b'C', b"SET\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let mut queued = VecDeque::new();
+        let mut reader = CopyOut::start(&mut stream, &mut queued).unwrap();
+        let mut one = [0u8; 1];
+        reader.read(&mut one).unwrap();
+        drop(reader);
+        assert!(read_rows(&mut stream, &mut queued, "query").unwrap().is_empty());
+    }
+
+    #[test]
+    fn copy_out_rejects_non_copy_statement() {
+        let mut wire = Vec::new();
+        msg(&mut wire, b'T', &int4_row_description("n"));
+        msg(&mut wire, b'D', &data_row(Some(b"1")));
+        msg(&mut wire, b'C', b"SELECT 1\0");
+        msg(&mut wire, b'Z', b"I");
+
+        let mut stream = Cursor::new(wire);
+        let mut queued = VecDeque::new();
+        let err = CopyOut::start(&mut stream, &mut queued).err().unwrap();
+        assert!(matches!(err, PgError::Protocol(_)));
+        assert_eq!(stream.position() as usize, stream.get_ref().len());
+    }
+
+    #[test]
+    fn quote_ident_escapes_quotes() {
+        assert_eq!(quote_ident("jobs"), "\"jobs\"");
+        assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -7,6 +7,7 @@ pub enum PgError {
     Io(io::IoError),
     Auth(String),
     Protocol(String),
+    Tls(String),
     Server {
         code: String,
         message: String,
@@ -20,6 +21,7 @@ impl fmt::Display for PgError {
             PgError::Io(e) => write!(f, "I/O error: {e}"),
             PgError::Auth(msg) => write!(f, "authentication error: {msg}"),
             PgError::Protocol(msg) => write!(f, "protocol error: {msg}"),
+            PgError::Tls(msg) => write!(f, "TLS error: {msg}"),
             PgError::Server {
                 severity,
                 code,
@@ -68,6 +70,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_tls_error() {
+        let err = PgError::Tls("server does not support SSL".into());
+        assert_eq!(
+            crate::vformat!("{err}").as_str(),
+            "TLS error: server does not support SSL"
+        );
+    }
+
     #[test]
     fn display_server_error() {
         let err = PgError::Server {
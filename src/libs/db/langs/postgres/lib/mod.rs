@@ -1,8 +1,15 @@
 pub mod connection;
 pub mod error;
+pub mod pool;
 pub mod protocol;
+pub mod tls;
+pub mod transaction;
 pub mod types;
 
-pub use connection::Connection;
+pub use connection::{Connection, CopyOut};
 pub use error::PgError;
-pub use types::{Column, Row, Value};
+pub use pool::{Pool, PoolOptions, PooledConnection};
+pub use protocol::Notification;
+pub use tls::SslMode;
+pub use transaction::Transaction;
+pub use types::{Column, Row, RowSchema, Value};
@@ -0,0 +1,322 @@
+//! Bounded pool of Postgres connections.
+//!
+//! `Pool::get` hands out an idle connection when one is available, opening a
+//! new one only while fewer than `max_size` exist, and otherwise waits up to
+//! `acquire_timeout` for a connection to be returned. Idle connections are
+//! pinged before reuse so a connection the server dropped is replaced rather
+//! than handed out.
+
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::io::{IoError, IoErrorKind};
+use crate::core::volkiwithstds::sync::Mutex;
+use crate::core::volkiwithstds::thread;
+use crate::core::volkiwithstds::time::{Duration, Instant};
+use crate::libs::db::langs::postgres::lib::connection::Connection;
+use crate::libs::db::langs::postgres::lib::error::PgError;
+use crate::libs::db::langs::postgres::lib::tls::SslMode;
+use core::ops::{Deref, DerefMut};
+
+/// Default `max_size` when volki.toml doesn't set `pool_size`.
+pub const DEFAULT_POOL_SIZE: usize = 10;
+
+/// Default `acquire_timeout`: how long `get` waits for a connection to be
+/// returned before giving up.
+pub const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long `get` sleeps between checks while the pool is exhausted.
+const WAIT_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Connection parameters and sizing for a [`Pool`].
+#[derive(Debug, Clone)]
+pub struct PoolOptions {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub database: String,
+    pub password: String,
+    pub sslmode: SslMode,
+    pub max_size: usize,
+    /// How long `get` waits while all `max_size` connections are checked out.
+    pub acquire_timeout: Duration,
+}
+
+struct PoolState {
+    idle: Vec<Connection>,
+    /// Connections that exist, idle or checked out (or being opened).
+    open: usize,
+}
+
+pub struct Pool {
+    options: PoolOptions,
+    state: Mutex<PoolState>,
+}
+
+/// What `get` decided to do while holding the lock.
+enum Claim {
+    Idle(Connection),
+    Open,
+    Wait,
+}
+
+impl Pool {
+    pub fn new(options: PoolOptions) -> Self {
+        Self {
+            options,
+            state: Mutex::new(PoolState {
+                idle: Vec::new(),
+                open: 0,
+            }),
+        }
+    }
+
+    /// Check out a connection, returned to the pool when the guard drops.
+    /// Fails with a `TimedOut` I/O error if none is returned within
+    /// `acquire_timeout`.
+    pub fn get(&self) -> Result<PooledConnection<'_>, PgError> {
+        let start = Instant::now();
+        loop {
+            let claim = {
+                let mut state = self.state.lock();
+                if let Some(conn) = state.idle.pop() {
+                    Claim::Idle(conn)
+                } else if state.open < self.options.max_size.max(1) {
+                    state.open += 1;
+                    Claim::Open
+                } else {
+                    Claim::Wait
+                }
+            };
+
+            match claim {
+                Claim::Idle(mut conn) => {
+                    if is_healthy(&mut conn) {
+                        return Ok(self.guard(conn));
+                    }
+                    drop(conn);
+                    self.state.lock().open -= 1;
+                }
+                Claim::Open => {
+                    return match self.connect() {
+                        Ok(conn) => Ok(self.guard(conn)),
+                        Err(e) => {
+                            self.state.lock().open -= 1;
+                            Err(e)
+                        }
+                    };
+                }
+                Claim::Wait if start.elapsed() >= self.options.acquire_timeout => {
+                    return Err(PgError::Io(IoError::new(
+                        IoErrorKind::TimedOut,
+                        "timed out waiting for a pooled connection",
+                    )));
+                }
+                Claim::Wait => thread::sleep(WAIT_INTERVAL),
+            }
+        }
+    }
+
+    /// Connections currently waiting in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.state.lock().idle.len()
+    }
+
+    /// Connections that exist, idle or checked out.
+    pub fn open_count(&self) -> usize {
+        self.state.lock().open
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.options.max_size
+    }
+
+    fn connect(&self) -> Result<Connection, PgError> {
+        let o = &self.options;
+        Connection::connect_with_ssl(&o.host, o.port, &o.user, &o.database, &o.password, o.sslmode)
+    }
+
+    fn guard(&self, conn: Connection) -> PooledConnection<'_> {
+        PooledConnection {
+            pool: self,
+            conn: Some(conn),
+        }
+    }
+}
+
+/// Round-trip a trivial statement to confirm the server is still there.
+fn is_healthy(conn: &mut Connection) -> bool {
+    conn.execute("SELECT 1").is_ok()
+}
+
+/// A checked-out connection. Derefs to [`Connection`].
+pub struct PooledConnection<'a> {
+    pool: &'a Pool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.state.lock().idle.push(conn);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::io::{Read, Write};
+    use crate::core::volkiwithstds::net::{TcpListener, TcpStream};
+    use crate::core::volkiwithstds::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn send(stream: &mut TcpStream, tag: u8, payload: &[u8]) {
+        let mut msg = Vec::new();
+        msg.push(tag);
+        msg.extend_from_slice(&((payload.len() as i32) + 4).to_be_bytes());
+        msg.extend_from_slice(payload);
+        stream.write_all(&msg).unwrap();
+    }
+
+    fn zeroed(len: usize) -> Vec<u8> {
+        (0..len).map(|_| 0).collect()
+    }
+
+    /// Minimal server: trusts every startup, answers each simple query with
+    /// `SELECT 1`, and hangs up on a query containing `bye`.
+    fn serve(mut stream: TcpStream) {
+        let mut len = [0u8; 4];
+        if stream.read_exact(&mut len).is_err() {
+            return;
+        }
+        let mut startup = zeroed(i32::from_be_bytes(len) as usize - 4);
+        if stream.read_exact(&mut startup).is_err() {
+            return;
+        }
+        send(&mut stream, b'R', &0i32.to_be_bytes());
+        send(&mut stream, b'Z', b"I");
+        loop {
+            let mut header = [0u8; 5];
+            if stream.read_exact(&mut header).is_err() {
+                return;
+            }
+            let len = i32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+            let mut body = zeroed(len as usize - 4);
+            if stream.read_exact(&mut body).is_err() || header[0] != b'Q' {
+                return;
+            }
+            if body.windows(3).any(|w| w == b"bye") {
+                return;
+            }
+            send(&mut stream, b'C', b"SELECT 1\0");
+            send(&mut stream, b'Z', b"I");
+        }
+    }
+
+    /// Start a mock server; returns its port and a count of accepted connections.
+    fn mock_server() -> (u16, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().rsplit(':').next().unwrap().parse().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let count = accepted.clone();
+        thread::spawn(move || {
+            while let Ok(stream) = listener.accept() {
+                count.fetch_add(1, Ordering::SeqCst);
+                thread::spawn(move || serve(stream));
+            }
+        });
+        (port, accepted)
+    }
+
+    fn pool(port: u16, max_size: usize) -> Pool {
+        Pool::new(PoolOptions {
+            host: String::from("127.0.0.1"),
+            port,
+            user: String::from("app"),
+            database: String::from("app"),
+            password: String::new(),
+            sslmode: SslMode::Disable,
+            max_size,
+            acquire_timeout: Duration::from_millis(50),
+        })
+    }
+
+    #[test]
+    fn test_sequential_gets_reuse_connection() {
+        let (port, accepted) = mock_server();
+        let pool = pool(port, 4);
+
+        for _ in 0..5 {
+            let mut conn = pool.get().unwrap();
+            assert_eq!(conn.execute("SELECT 1").unwrap(), 1);
+        }
+
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.open_count(), 1);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_checkouts_open_separate_connections() {
+        let (port, accepted) = mock_server();
+        let pool = pool(port, 2);
+
+        let a = pool.get().unwrap();
+        let b = pool.get().unwrap();
+        assert_eq!(pool.open_count(), 2);
+        assert_eq!(pool.idle_count(), 0);
+        drop(a);
+        drop(b);
+
+        let _c = pool.get().unwrap();
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.open_count(), 2);
+    }
+
+    #[test]
+    fn test_exhausted_pool_times_out() {
+        let (port, accepted) = mock_server();
+        let pool = pool(port, 1);
+
+        let held = pool.get().unwrap();
+        match pool.get() {
+            Err(PgError::Io(e)) => assert_eq!(e.kind(), IoErrorKind::TimedOut),
+            Err(e) => panic!("expected a timeout, got {e}"),
+            Ok(_) => panic!("expected a timeout, got a connection"),
+        }
+        assert_eq!(pool.open_count(), 1);
+
+        drop(held);
+        assert!(pool.get().is_ok());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_dead_connection_is_replaced() {
+        let (port, accepted) = mock_server();
+        let pool = pool(port, 1);
+
+        {
+            let mut conn = pool.get().unwrap();
+            // Server hangs up; the broken connection still goes back idle
+            assert!(conn.execute("SELECT 'bye'").is_err());
+        }
+
+        let mut conn = pool.get().unwrap();
+        assert_eq!(conn.execute("SELECT 1").unwrap(), 1);
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.open_count(), 1);
+    }
+}
@@ -2,7 +2,8 @@ use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::io::{self, Read, Write};
 
 use crate::libs::db::langs::postgres::lib::error::PgError;
-use crate::libs::db::langs::postgres::lib::types::{Column, Row, Value};
+use crate::core::volkiwithstds::sync::Arc;
+use crate::libs::db::langs::postgres::lib::types::{Column, Row, RowSchema, Value};
 // --- MD5 implementation (RFC 1321) ---
 
 const S: [u32; 64] = [
@@ -174,6 +175,19 @@ pub fn write_startup<W: Write>(stream: &mut W, user: &str, database: &str) -> io
     stream.flush()
 }
 
+/// SSLRequest code: 1234 in the high 16 bits, 5679 in the low 16 bits.
+pub const SSL_REQUEST_CODE: i32 = 80877103;
+
+/// Send SSLRequest (no tag byte). The server answers with a single
+/// `S` (proceed with TLS) or `N` (TLS not available) byte.
+pub fn write_ssl_request<W: Write>(stream: &mut W) -> io::Result<()> {
+    let mut msg = Vec::with_capacity(8);
+    write_i32(&mut msg, 8);
+    write_i32(&mut msg, SSL_REQUEST_CODE);
+    stream.write_all(&msg)?;
+    stream.flush()
+}
+
 /// Read one Postgres message: returns (tag, payload).
 pub fn read_message<R: Read>(stream: &mut R) -> Result<(u8, Vec<u8>), PgError> {
     let mut tag_buf = [0u8; 1];
@@ -262,22 +276,34 @@ pub fn write_parse<W: Write>(
 }
 
 /// Send Bind message: bind parameters to a portal.
+///
+/// Each parameter is sent with its own format code (see `encode_param`);
+/// results are always requested in text format.
 pub fn write_bind<W: Write>(
     stream: &mut W,
     portal: &str,
     stmt_name: &str,
-    params: &[&str],
+    params: &[Value],
 ) -> io::Result<()> {
     let mut body = Vec::new();
     write_cstring(&mut body, portal);
     write_cstring(&mut body, stmt_name);
 
-    write_i16(&mut body, 0);
-    write_i16(&mut body, params.len() as i16);
-    for &p in params {
-        let bytes = p.as_bytes();
-        write_i32(&mut body, bytes.len() as i32);
-        body.extend_from_slice(bytes);
+    let encoded: Vec<(i16, Option<Vec<u8>>)> = params.iter().map(encode_param).collect();
+
+    write_i16(&mut body, encoded.len() as i16);
+    for (format, _) in &encoded {
+        write_i16(&mut body, *format);
+    }
+    write_i16(&mut body, encoded.len() as i16);
+    for (_, bytes) in &encoded {
+        match bytes {
+            Some(bytes) => {
+                write_i32(&mut body, bytes.len() as i32);
+                body.extend_from_slice(bytes);
+            }
+            None => write_i32(&mut body, -1),
+        }
     }
 
     write_i16(&mut body, 0);
@@ -290,6 +316,31 @@ pub fn write_bind<W: Write>(
     stream.write_all(&msg)
 }
 
+/// Encode a bind parameter as `(format code, bytes)`; `None` bytes means SQL NULL.
+///
+/// Scalars use the text format (code 0) so the server can coerce them to the
+/// placeholder's inferred type; `Bytes` use the binary format (code 1).
+pub fn encode_param(value: &Value) -> (i16, Option<Vec<u8>>) {
+    let text = match value {
+        Value::Null => return (0, None),
+        Value::Bytes(b) => {
+            let mut out = Vec::with_capacity(b.len());
+            out.extend_from_slice(b);
+            return (1, Some(out));
+        }
+        Value::Text(s) => s.clone(),
+        Value::Int(n) => crate::vformat!("{n}"),
+        Value::Float(f) if f.is_nan() => String::from("NaN"),
+        Value::Float(f) if f.is_infinite() && *f > 0.0 => String::from("Infinity"),
+        Value::Float(f) if f.is_infinite() => String::from("-Infinity"),
+        Value::Float(f) => crate::vformat!("{f}"),
+        Value::Bool(b) => String::from(if *b { "t" } else { "f" }),
+        Value::Date(d) => crate::vformat!("{d}"),
+        Value::Timestamp(ts) => crate::vformat!("{ts}"),
+    };
+    (0, Some(text.into_bytes()))
+}
+
 /// Send Describe message for a portal.
 pub fn write_describe_portal<W: Write>(stream: &mut W, portal: &str) -> io::Result<()> {
     let mut body = Vec::new();
@@ -340,15 +391,20 @@ pub fn parse_row_description(data: &[u8]) -> Result<Vec<Column>, PgError> {
         let type_oid = read_i32(data, &mut offset)? as u32;
         let _type_size = read_i16(data, &mut offset)?;
         let _type_mod = read_i32(data, &mut offset)?;
-        let _format_code = read_i16(data, &mut offset)?;
-        columns.push(Column { name, type_oid });
+        let format = read_i16(data, &mut offset)?;
+        columns.push(Column {
+            name,
+            type_oid,
+            format,
+        });
     }
 
     Ok(columns)
 }
 
 /// Parse DataRow payload into a `Row`.
-pub fn parse_data_row(data: &[u8], columns: &[Column]) -> Result<Row, PgError> {
+pub fn parse_data_row(data: &[u8], schema: &Arc<RowSchema>) -> Result<Row, PgError> {
+    let columns = schema.columns();
     let mut offset = 0;
     let num_cols = read_i16(data, &mut offset)? as usize;
     let mut values = Vec::with_capacity(num_cols);
@@ -362,16 +418,23 @@ pub fn parse_data_row(data: &[u8], columns: &[Column]) -> Result<Row, PgError> {
             if offset + len > data.len() {
                 return Err(PgError::Protocol("truncated data row".into()));
             }
-            let text = String::from_utf8_lossy(&data[offset..offset + len]).to_owned();
+            let raw = &data[offset..offset + len];
             offset += len;
 
-            let type_oid = columns.get(i).map(|c| c.type_oid).unwrap_or(0);
-            values.push(Value::from_text(&text, type_oid));
+            let (type_oid, format) = columns
+                .get(i)
+                .map(|c| (c.type_oid, c.format))
+                .unwrap_or((0, 0));
+            if format == 1 {
+                values.push(Value::from_binary(raw, type_oid));
+            } else {
+                let text = String::from_utf8_lossy(raw).to_owned();
+                values.push(Value::from_text(&text, type_oid));
+            }
         }
     }
 
-    let cols: Vec<Column> = columns.iter().cloned().collect();
-    Ok(Row::new(cols, values))
+    Ok(Row::with_schema(schema.clone(), values))
 }
 
 /// Parse ErrorResponse/NoticeResponse payload into field map.
@@ -421,6 +484,52 @@ pub fn parse_command_complete(data: &[u8]) -> u64 {
         .unwrap_or(0)
 }
 
+/// An asynchronous `NOTIFY` delivered on a channel this session listens to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Backend process that sent the notification.
+    pub process_id: i32,
+    pub channel: String,
+    pub payload: String,
+}
+
+/// Parse a NotificationResponse (`'A'`) payload.
+pub fn parse_notification_response(data: &[u8]) -> Result<Notification, PgError> {
+    let mut offset = 0;
+    let process_id = read_i32(data, &mut offset)?;
+    let channel = read_cstring(data, &mut offset)?;
+    let payload = read_cstring(data, &mut offset)?;
+    Ok(Notification {
+        process_id,
+        channel,
+        payload,
+    })
+}
+
+/// Column layout announced by CopyOutResponse (`'H'`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyOutResponse {
+    /// Binary COPY format rather than text or CSV.
+    pub binary: bool,
+    pub columns: usize,
+}
+
+/// Parse a CopyOutResponse (`'H'`) payload.
+pub fn parse_copy_out_response(data: &[u8]) -> Result<CopyOutResponse, PgError> {
+    let Some(&format) = data.first() else {
+        return Err(PgError::Protocol("truncated CopyOutResponse".into()));
+    };
+    let mut offset = 1;
+    let columns = read_i16(data, &mut offset)?.max(0) as usize;
+    if data.len() < offset + 2 * columns {
+        return Err(PgError::Protocol("truncated CopyOutResponse".into()));
+    }
+    Ok(CopyOutResponse {
+        binary: format == 1,
+        columns,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -503,6 +612,13 @@ mod tests {
         assert_eq!(*body.last().unwrap(), 0);
     }
 
+    #[test]
+    fn ssl_request_encoding() {
+        let mut buf = Vec::new();
+        write_ssl_request(&mut buf).unwrap();
+        assert_eq!(buf.as_slice(), &[0, 0, 0, 8, 0x04, 0xD2, 0x16, 0x2F]);
+    }
+
     // --- Frame reading/writing ---
 
     #[test]
@@ -575,10 +691,12 @@ mod tests {
             Column {
                 name: "id".into(),
                 type_oid: 23,
+                format: 0,
             },
             Column {
                 name: "name".into(),
                 type_oid: 25,
+                format: 0,
             },
         ];
 
@@ -595,9 +713,10 @@ mod tests {
         data.extend_from_slice(&(val2.len() as i32).to_be_bytes());
         data.extend_from_slice(val2);
 
-        let row = parse_data_row(&data, &columns).unwrap();
+        let row = parse_data_row(&data, &Arc::new(RowSchema::new(columns))).unwrap();
         assert_eq!(row.get_int(0), Some(42));
         assert_eq!(row.get_str(1), Some("alice"));
+        assert_eq!(row.get_by_name("name"), Some(&Value::Text("alice".into())));
     }
 
     #[test]
@@ -605,13 +724,14 @@ mod tests {
         let columns = vvec![Column {
             name: "val".into(),
             type_oid: 25,
+            format: 0,
         }];
 
         let mut data = Vec::new();
         data.extend_from_slice(&1i16.to_be_bytes());
         data.extend_from_slice(&(-1i32).to_be_bytes()); // NULL
 
-        let row = parse_data_row(&data, &columns).unwrap();
+        let row = parse_data_row(&data, &Arc::new(RowSchema::new(columns))).unwrap();
         assert_eq!(row.get_value(0), Some(&Value::Null));
     }
 
@@ -665,6 +785,44 @@ mod tests {
         assert_eq!(parse_command_complete(b"DELETE 0\0"), 0);
     }
 
+    // --- NotificationResponse parsing ---
+
+    #[test]
+    fn parse_notification_response_frame() {
+        let mut wire = Vec::new();
+        wire.push(b'A');
+        let body = b"\0\0\x30\x39jobs\0{\"id\":7}\0";
+        wire.extend_from_slice(&((body.len() as i32) + 4).to_be_bytes());
+        wire.extend_from_slice(body);
+
+        let mut cursor = io::Cursor::new(wire);
+        let (tag, payload) = read_message(&mut cursor).unwrap();
+        assert_eq!(tag, b'A');
+        let n = parse_notification_response(&payload).unwrap();
+        assert_eq!(n.process_id, 12345);
+        assert_eq!(n.channel, "jobs");
+        assert_eq!(n.payload, "{\"id\":7}");
+
+        assert!(parse_notification_response(b"\0\0\0\x01jobs").is_err());
+    }
+
+    // --- CopyOutResponse parsing ---
+
+    #[test]
+    fn parse_copy_out_response_text() {
+        // text format, 2 columns, both text
+        let r = parse_copy_out_response(b"\0\0\x02\0\0\0\0").unwrap();
+        assert_eq!(
+            r,
+            CopyOutResponse {
+                binary: false,
+                columns: 2
+            }
+        );
+        assert!(parse_copy_out_response(b"\0\0\x02\0\0").is_err());
+        assert!(parse_copy_out_response(b"").is_err());
+    }
+
     // --- Password message ---
 
     #[test]
@@ -690,6 +848,67 @@ mod tests {
         assert_eq!(&buf[5..], b"SELECT 1\0");
     }
 
+    // --- Extended query ---
+
+    #[test]
+    fn encode_param_variants() {
+        assert_eq!(encode_param(&Value::Null), (0, None));
+        assert_eq!(
+            encode_param(&Value::Int(-7)),
+            (0, Some(String::from("-7").into_bytes()))
+        );
+        assert_eq!(
+            encode_param(&Value::Bool(true)),
+            (0, Some(String::from("t").into_bytes()))
+        );
+        assert_eq!(
+            encode_param(&Value::Float(1.5)),
+            (0, Some(String::from("1.5").into_bytes()))
+        );
+        assert_eq!(
+            encode_param(&Value::Float(f64::NEG_INFINITY)),
+            (0, Some(String::from("-Infinity").into_bytes()))
+        );
+        assert_eq!(
+            encode_param(&Value::Text("it's".into())),
+            (0, Some(String::from("it's").into_bytes()))
+        );
+        assert_eq!(
+            encode_param(&Value::Bytes(vvec![0xDE, 0xAD])),
+            (1, Some(vvec![0xDE, 0xAD]))
+        );
+    }
+
+    #[test]
+    fn write_bind_message_layout() {
+        let mut buf = Vec::new();
+        write_bind(
+            &mut buf,
+            "",
+            "",
+            &[Value::Int(42), Value::Null, Value::Bytes(vvec![0x01])],
+        )
+        .unwrap();
+        assert_eq!(buf[0], b'B');
+        let len = i32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]);
+        assert_eq!(len as usize, buf.len() - 1);
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(b"\0\0"); // portal, statement
+        expected.extend_from_slice(&3i16.to_be_bytes()); // format codes
+        expected.extend_from_slice(&0i16.to_be_bytes());
+        expected.extend_from_slice(&0i16.to_be_bytes());
+        expected.extend_from_slice(&1i16.to_be_bytes());
+        expected.extend_from_slice(&3i16.to_be_bytes()); // values
+        expected.extend_from_slice(&2i32.to_be_bytes());
+        expected.extend_from_slice(b"42");
+        expected.extend_from_slice(&(-1i32).to_be_bytes());
+        expected.extend_from_slice(&1i32.to_be_bytes());
+        expected.push(0x01);
+        expected.extend_from_slice(&0i16.to_be_bytes()); // result formats
+        assert_eq!(&buf[5..], expected.as_slice());
+    }
+
     // --- Terminate message ---
 
     #[test]
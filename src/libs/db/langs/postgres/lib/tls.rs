@@ -0,0 +1,273 @@
+use crate::core::security::tls::context::SslContext;
+use crate::core::security::tls::error::TlsError;
+use crate::core::security::tls::stream;
+use crate::core::volkiwithstds::io::{self, IoError, IoErrorKind, Read, Write};
+use crate::core::volkiwithstds::net::TcpStream;
+use crate::core::volkiwithstds::time::Duration;
+use crate::core::volkiwithstds::sys::openssl::SSL;
+
+use crate::libs::db::langs::postgres::lib::error::PgError;
+use crate::libs::db::langs::postgres::lib::protocol;
+
+/// How hard to insist on TLS, mirroring libpq's `sslmode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SslMode {
+    /// Plaintext only.
+    Disable,
+    /// Use TLS when the server offers it, otherwise fall back to plaintext.
+    #[default]
+    Prefer,
+    /// Fail unless the connection is encrypted. Certificates are not verified.
+    Require,
+}
+
+impl SslMode {
+    pub fn parse(s: &str) -> Option<SslMode> {
+        match s {
+            "disable" => Some(SslMode::Disable),
+            "prefer" => Some(SslMode::Prefer),
+            "require" => Some(SslMode::Require),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SslMode::Disable => "disable",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require",
+        }
+    }
+}
+
+/// Transport under a postgres `Connection`.
+pub enum PgStream {
+    Plain(TcpStream),
+    Tls(TlsStream),
+    /// Replays scripted server replies and records what the client sends.
+    #[cfg(test)]
+    Mock {
+        replies: io::Cursor<crate::core::volkiwithstds::collections::Vec<u8>>,
+        sent: crate::core::volkiwithstds::collections::Vec<u8>,
+    },
+}
+
+impl PgStream {
+    /// Open a TCP connection and negotiate TLS according to `mode`.
+    /// `connect_timeout` bounds each TCP connect, not the TLS handshake.
+    pub fn open(
+        host: &str,
+        port: u16,
+        mode: SslMode,
+        connect_timeout: Option<Duration>,
+    ) -> Result<PgStream, PgError> {
+        let tcp_connect = || match connect_timeout {
+            Some(t) => TcpStream::connect_timeout(
+                (host, port),
+                t.as_millis().min(i32::MAX as u128) as i32,
+            ),
+            None => TcpStream::connect((host, port)),
+        };
+        let mut tcp = tcp_connect()?;
+        if mode == SslMode::Disable {
+            return Ok(PgStream::Plain(tcp));
+        }
+
+        protocol::write_ssl_request(&mut tcp)?;
+        let mut answer = [0u8; 1];
+        tcp.read_exact(&mut answer)?;
+
+        if !upgrade_to_tls(answer[0], mode)? {
+            return Ok(PgStream::Plain(tcp));
+        }
+        match TlsStream::connect(tcp, host) {
+            Ok(tls) => Ok(PgStream::Tls(tls)),
+            // libpq retries in plaintext when a preferred handshake fails
+            Err(_) if mode == SslMode::Prefer => Ok(PgStream::Plain(tcp_connect()?)),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn is_tls(&self) -> bool {
+        matches!(self, PgStream::Tls(_))
+    }
+
+    /// Wait up to `timeout_ms` for incoming data. Bytes OpenSSL has already
+    /// decrypted count, since the socket alone would not report them.
+    pub fn wait_readable(&self, timeout_ms: i32) -> io::Result<bool> {
+        match self {
+            PgStream::Plain(s) => s.wait_readable(timeout_ms),
+            PgStream::Tls(s) => {
+                // SAFETY: `s.ssl` lives as long as the `TlsStream`.
+                if unsafe { stream::ssl_pending(s.ssl) } > 0 {
+                    return Ok(true);
+                }
+                s._tcp.wait_readable(timeout_ms)
+            }
+            #[cfg(test)]
+            PgStream::Mock { replies, .. } => Ok(replies.position() < replies.get_ref().len()),
+        }
+    }
+}
+
+/// Decide from the server's one-byte SSLRequest answer whether to start TLS.
+fn upgrade_to_tls(answer: u8, mode: SslMode) -> Result<bool, PgError> {
+    match (answer, mode) {
+        (b'S', _) => Ok(true),
+        (b'N', SslMode::Require) => Err(PgError::Tls(
+            "server does not support SSL, but sslmode=require".into(),
+        )),
+        (b'N', _) => Ok(false),
+        (other, _) => Err(PgError::Protocol(crate::vformat!(
+            "unexpected SSLRequest response: 0x{other:02x}"
+        ))),
+    }
+}
+
+impl Read for PgStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            PgStream::Plain(s) => s.read(buf),
+            PgStream::Tls(s) => s.read(buf),
+            #[cfg(test)]
+            PgStream::Mock { replies, .. } => replies.read(buf),
+        }
+    }
+}
+
+impl Write for PgStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            PgStream::Plain(s) => s.write(buf),
+            PgStream::Tls(s) => s.write(buf),
+            #[cfg(test)]
+            PgStream::Mock { sent, .. } => {
+                sent.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            PgStream::Plain(s) => s.flush(),
+            PgStream::Tls(s) => s.flush(),
+            #[cfg(test)]
+            PgStream::Mock { .. } => Ok(()),
+        }
+    }
+}
+
+/// Blocking client-side TLS session over a `TcpStream`.
+pub struct TlsStream {
+    ssl: *mut SSL,
+    // Dropped after `ssl` is freed in `Drop`.
+    _ctx: SslContext,
+    _tcp: TcpStream,
+}
+
+unsafe impl Send for TlsStream {}
+
+impl TlsStream {
+    fn connect(tcp: TcpStream, host: &str) -> Result<TlsStream, PgError> {
+        let ctx = SslContext::new_client().map_err(tls_err)?;
+        let ssl = ctx.new_ssl().map_err(tls_err)?;
+        let stream = TlsStream {
+            ssl,
+            _ctx: ctx,
+            _tcp: tcp,
+        };
+        stream::ssl_set_fd(ssl, stream._tcp.as_raw_fd()).map_err(tls_err)?;
+        // SAFETY: `ssl` is owned by `stream`, which keeps it alive and bound
+        // to its socket until dropped.
+        unsafe {
+            stream::ssl_set_hostname(ssl, host).map_err(tls_err)?;
+            stream::ssl_connect(ssl).map_err(tls_err)?;
+        }
+        Ok(stream)
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match stream::ssl_read(self.ssl, buf) {
+                Ok(n) => return Ok(n),
+                Err(TlsError::WantRead | TlsError::WantWrite) => continue,
+                Err(TlsError::ConnectionClosed) => return Ok(0),
+                Err(e) => return Err(tls_io_err(e)),
+            }
+        }
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            match stream::ssl_write(self.ssl, buf) {
+                Ok(n) => return Ok(n),
+                Err(TlsError::WantRead | TlsError::WantWrite) => continue,
+                Err(e) => return Err(tls_io_err(e)),
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for TlsStream {
+    fn drop(&mut self) {
+        stream::ssl_shutdown(self.ssl);
+        stream::ssl_free(self.ssl);
+    }
+}
+
+fn tls_err(e: TlsError) -> PgError {
+    PgError::Tls(crate::vformat!("{e}"))
+}
+
+fn tls_io_err(e: TlsError) -> IoError {
+    match e {
+        TlsError::SyscallError(io) => io,
+        other => IoError::new(IoErrorKind::Other, &crate::vformat!("{other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sslmode_parse_roundtrip() {
+        for mode in [SslMode::Disable, SslMode::Prefer, SslMode::Require] {
+            assert_eq!(SslMode::parse(mode.as_str()), Some(mode));
+        }
+        assert_eq!(SslMode::parse("verify-full"), None);
+        assert_eq!(SslMode::default(), SslMode::Prefer);
+    }
+
+    #[test]
+    fn upgrade_when_server_accepts() {
+        assert!(upgrade_to_tls(b'S', SslMode::Prefer).unwrap());
+        assert!(upgrade_to_tls(b'S', SslMode::Require).unwrap());
+    }
+
+    #[test]
+    fn prefer_falls_back_when_server_declines() {
+        assert!(!upgrade_to_tls(b'N', SslMode::Prefer).unwrap());
+    }
+
+    #[test]
+    fn require_fails_when_server_declines() {
+        let err = upgrade_to_tls(b'N', SslMode::Require).unwrap_err();
+        assert!(matches!(err, PgError::Tls(_)));
+    }
+
+    #[test]
+    fn unexpected_answer_is_protocol_error() {
+        let err = upgrade_to_tls(b'E', SslMode::Prefer).unwrap_err();
+        assert!(matches!(err, PgError::Protocol(_)));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::core::volkiwithstds::collections::{String, Vec};
+
+use crate::libs::db::langs::postgres::lib::connection::Connection;
+use crate::libs::db::langs::postgres::lib::error::PgError;
+use crate::libs::db::langs::postgres::lib::types::{Row, Value};
+
+/// An open `BEGIN ... COMMIT` block on a borrowed connection.
+///
+/// Dropping a transaction without calling `commit` or `rollback` rolls it back.
+pub struct Transaction<'a> {
+    conn: &'a mut Connection,
+    finished: bool,
+}
+
+impl<'a> Transaction<'a> {
+    pub(crate) fn begin(conn: &'a mut Connection) -> Result<Self, PgError> {
+        conn.execute("BEGIN")?;
+        Ok(Transaction {
+            conn,
+            finished: false,
+        })
+    }
+
+    /// Execute a simple query inside the transaction.
+    pub fn query(&mut self, sql: &str) -> Result<Vec<Row>, PgError> {
+        self.conn.query(sql)
+    }
+
+    /// Execute a parameterized query inside the transaction.
+    pub fn query_params(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Row>, PgError> {
+        self.conn.query_params(sql, params)
+    }
+
+    /// Execute a statement inside the transaction, returning affected rows.
+    pub fn execute(&mut self, sql: &str) -> Result<u64, PgError> {
+        self.conn.execute(sql)
+    }
+
+    /// Commit the transaction. If an earlier statement failed, the server
+    /// turns the COMMIT into a rollback, which is reported as an error.
+    pub fn commit(mut self) -> Result<(), PgError> {
+        self.finished = true;
+        let tag = self.conn.execute_tag("COMMIT")?;
+        if tag.starts_with(b"ROLLBACK") {
+            return Err(PgError::Server {
+                severity: String::from("ERROR"),
+                code: String::from("25P02"),
+                message: String::from("transaction was aborted; COMMIT rolled it back"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Roll back every statement issued since `begin`.
+    pub fn rollback(mut self) -> Result<(), PgError> {
+        self.finished = true;
+        self.conn.execute("ROLLBACK").map(|_| ())
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.conn.execute("ROLLBACK");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(buf: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+        buf.push(tag);
+        buf.extend_from_slice(&((payload.len() as i32) + 4).to_be_bytes());
+        buf.extend_from_slice(payload);
+    }
+
+    fn complete(buf: &mut Vec<u8>, tag: &str, status: u8) {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(tag.as_bytes());
+        payload.push(0);
+        msg(buf, b'C', &payload);
+        msg(buf, b'Z', &[status]);
+    }
+
+    fn failed(buf: &mut Vec<u8>) {
+        msg(buf, b'E', b"SERROR\0C23505\0Mduplicate key\0\0");
+        msg(buf, b'Z', b"E");
+    }
+
+    fn sent_query(conn: &Connection, sql: &str) -> bool {
+        let mut needle = Vec::new();
+        needle.extend_from_slice(sql.as_bytes());
+        needle.push(0);
+        conn.sent()
+            .windows(needle.len())
+            .any(|w| w == needle.as_slice())
+    }
+
+    #[test]
+    fn failed_statement_then_rollback_leaves_connection_usable() {
+        let mut wire = Vec::new();
+        complete(&mut wire, "BEGIN", b'T');
+        failed(&mut wire);
+        complete(&mut wire, "ROLLBACK", b'I');
+        complete(&mut wire, "INSERT 0 1", b'I');
+
+        let mut conn = Connection::mock(wire);
+        let mut tx = conn.begin().unwrap();
+        let err = tx.execute("INSERT INTO t VALUES (1)").unwrap_err();
+        assert!(matches!(err, PgError::Server { ref code, .. } if code == "23505"));
+        tx.rollback().unwrap();
+
+        assert_eq!(conn.execute("INSERT INTO t VALUES (2)").unwrap(), 1);
+    }
+
+    #[test]
+    fn commit_of_aborted_transaction_is_an_error() {
+        let mut wire = Vec::new();
+        complete(&mut wire, "BEGIN", b'T');
+        failed(&mut wire);
+        complete(&mut wire, "ROLLBACK", b'I');
+
+        let mut conn = Connection::mock(wire);
+        let mut tx = conn.begin().unwrap();
+        assert!(tx.execute("INSERT INTO t VALUES (1)").is_err());
+        let err = tx.commit().unwrap_err();
+        assert!(matches!(err, PgError::Server { ref code, .. } if code == "25P02"));
+        assert!(sent_query(&conn, "COMMIT"));
+    }
+
+    #[test]
+    fn commit_succeeds_on_commit_tag() {
+        let mut wire = Vec::new();
+        complete(&mut wire, "BEGIN", b'T');
+        complete(&mut wire, "COMMIT", b'I');
+
+        let mut conn = Connection::mock(wire);
+        conn.begin().unwrap().commit().unwrap();
+        assert!(!sent_query(&conn, "ROLLBACK"));
+    }
+
+    #[test]
+    fn drop_without_commit_rolls_back() {
+        let mut wire = Vec::new();
+        complete(&mut wire, "BEGIN", b'T');
+        complete(&mut wire, "ROLLBACK", b'I');
+
+        let mut conn = Connection::mock(wire);
+        drop(conn.begin().unwrap());
+        assert!(sent_query(&conn, "ROLLBACK"));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::collections::{HashMap, String, Vec};
+use crate::core::volkiwithstds::fmt;
+use crate::core::volkiwithstds::sync::Arc;
 
 /// Known Postgres type OIDs for text-format conversion.
 const OID_BOOL: u32 = 16;
@@ -10,6 +12,14 @@ const OID_FLOAT4: u32 = 700;
 const OID_FLOAT8: u32 = 701;
 const OID_TEXT: u32 = 25;
 const OID_VARCHAR: u32 = 1043;
+const OID_DATE: u32 = 1082;
+const OID_TIMESTAMP: u32 = 1114;
+const OID_TIMESTAMPTZ: u32 = 1184;
+
+/// Days from 0000-03-01 to the Postgres epoch (2000-01-01), used by the
+/// civil-date conversions below.
+const PG_EPOCH_DAYS: i64 = 730_425;
+const MICROS_PER_DAY: i64 = 86_400_000_000;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
@@ -19,9 +29,239 @@ pub enum Value {
     Float(f64),
     Bool(bool),
     Bytes(Vec<u8>),
+    Date(Date),
+    Timestamp(Timestamp),
+}
+
+/// Calendar date (proleptic Gregorian).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Date and time of day with microsecond precision.
+/// `offset` is seconds east of UTC for `timestamptz`, `None` for `timestamp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub date: Date,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub micros: u32,
+    pub offset: Option<i32>,
+}
+
+impl Date {
+    /// Parse `YYYY-MM-DD`.
+    pub fn parse(text: &str) -> Option<Date> {
+        let mut parts = text.splitn(3, '-');
+        let year = parse_digits(parts.next()?)? as i32;
+        let month = parse_digits(parts.next()?)? as u8;
+        let day = parse_digits(parts.next()?)? as u8;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    /// Build a date from a day count relative to 2000-01-01.
+    pub fn from_pg_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days, shifted to the Postgres epoch.
+        let z = days + PG_EPOCH_DAYS;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+}
+
+impl Timestamp {
+    /// Parse `YYYY-MM-DD HH:MM:SS[.ffffff][+HH[:MM]]` as sent by Postgres;
+    /// a `T` separator and trailing `Z` are also accepted.
+    pub fn parse(text: &str) -> Option<Timestamp> {
+        let sep = text.find([' ', 'T'])?;
+        let date = Date::parse(&text[..sep])?;
+        let rest = &text[sep + 1..];
+
+        let (time, offset) = match rest.find(['+', '-', 'Z']) {
+            Some(i) => (&rest[..i], Some(parse_offset(&rest[i..])?)),
+            None => (rest, None),
+        };
+
+        let (hms, frac) = time.split_once('.').unwrap_or((time, ""));
+        let mut parts = hms.splitn(3, ':');
+        let hour = parse_digits(parts.next()?)? as u8;
+        let minute = parse_digits(parts.next()?)? as u8;
+        let second = parse_digits(parts.next()?)? as u8;
+        if hour > 24 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let micros = if frac.is_empty() {
+            0
+        } else {
+            if frac.len() > 6 {
+                return None;
+            }
+            let mut n = parse_digits(frac)? as u32;
+            for _ in frac.len()..6 {
+                n *= 10;
+            }
+            n
+        };
+
+        Some(Timestamp {
+            date,
+            hour,
+            minute,
+            second,
+            micros,
+            offset,
+        })
+    }
+
+    /// Build a timestamp from microseconds relative to 2000-01-01 00:00:00.
+    pub fn from_pg_micros(micros: i64, offset: Option<i32>) -> Timestamp {
+        let days = micros.div_euclid(MICROS_PER_DAY);
+        let rem = micros.rem_euclid(MICROS_PER_DAY);
+        let secs = rem / 1_000_000;
+        Timestamp {
+            date: Date::from_pg_days(days),
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+            micros: (rem % 1_000_000) as u32,
+            offset,
+        }
+    }
+}
+
+/// Parse a non-empty run of ASCII digits.
+fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse::<u64>().ok()
+}
+
+/// Parse a UTC offset: `Z`, `+HH`, `+HH:MM` or `+HH:MM:SS` (or `-`).
+fn parse_offset(s: &str) -> Option<i32> {
+    if s == "Z" {
+        return Some(0);
+    }
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let mut secs = 0i32;
+    let mut unit = 3600;
+    for part in s[1..].split(':') {
+        if unit == 0 {
+            return None;
+        }
+        secs += parse_digits(part)? as i32 * unit;
+        unit /= 60;
+    }
+    Some(sign * secs)
+}
+
+/// ISO-8601 `YYYY-MM-DD`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// ISO-8601 `YYYY-MM-DDTHH:MM:SS[.ffffff][Z|±HH:MM]`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date, self.hour, self.minute, self.second
+        )?;
+        if self.micros != 0 {
+            write!(f, ".{:06}", self.micros)?;
+        }
+        match self.offset {
+            None => Ok(()),
+            Some(0) => f.write_str("Z"),
+            Some(off) => {
+                let sign = if off < 0 { '-' } else { '+' };
+                let off = off.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", off / 3600, off / 60 % 60)
+            }
+        }
+    }
+}
+
+/// Renders values the way the CLI tables show them: `NULL`, `t`/`f` for
+/// booleans, `<bytes>` for binary data.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("NULL"),
+            Value::Text(s) => f.write_str(s.as_str()),
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Float(n) => write!(f, "{n}"),
+            Value::Bool(b) => f.write_str(if *b { "t" } else { "f" }),
+            Value::Bytes(_) => f.write_str("<bytes>"),
+            Value::Date(d) => write!(f, "{d}"),
+            Value::Timestamp(ts) => write!(f, "{ts}"),
+        }
+    }
 }
 
 impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Floats as-is; integers are widened so numeric columns read uniformly.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(n) => Some(*n),
+            Value::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Text(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        }
+    }
+
     /// Convert a text-format Postgres value to a typed `Value` based on OID.
     pub fn from_text(text: &str, type_oid: u32) -> Value {
         match type_oid {
@@ -38,10 +278,55 @@ impl Value {
                 Err(_) => Value::Text(String::from(text)),
             },
             OID_BYTEA => Value::Bytes(decode_bytea_hex(text)),
+            OID_DATE => match Date::parse(text) {
+                Some(d) => Value::Date(d),
+                None => Value::Text(String::from(text)),
+            },
+            OID_TIMESTAMP | OID_TIMESTAMPTZ => match Timestamp::parse(text) {
+                Some(ts) => Value::Timestamp(ts),
+                None => Value::Text(String::from(text)),
+            },
             OID_TEXT | OID_VARCHAR => Value::Text(String::from(text)),
             _ => Value::Text(String::from(text)),
         }
     }
+
+    /// Convert a binary-format Postgres value to a typed `Value` based on OID.
+    /// Unknown types and malformed payloads are returned as `Bytes`.
+    pub fn from_binary(data: &[u8], type_oid: u32) -> Value {
+        match (type_oid, data.len()) {
+            (OID_BOOL, 1) => Value::Bool(data[0] != 0),
+            (OID_INT2, 2) => Value::Int(i16::from_be_bytes([data[0], data[1]]) as i64),
+            (OID_INT4, 4) => Value::Int(be_i32(data) as i64),
+            (OID_INT8, 8) => Value::Int(be_i64(data)),
+            (OID_FLOAT4, 4) => Value::Float(f32::from_bits(be_i32(data) as u32) as f64),
+            (OID_FLOAT8, 8) => Value::Float(f64::from_bits(be_i64(data) as u64)),
+            (OID_TEXT | OID_VARCHAR, _) => Value::Text(String::from_utf8_lossy(data).to_owned()),
+            (OID_DATE, 4) => match be_i32(data) {
+                i32::MAX => Value::Text(String::from("infinity")),
+                i32::MIN => Value::Text(String::from("-infinity")),
+                days => Value::Date(Date::from_pg_days(days as i64)),
+            },
+            (OID_TIMESTAMP | OID_TIMESTAMPTZ, 8) => match be_i64(data) {
+                i64::MAX => Value::Text(String::from("infinity")),
+                i64::MIN => Value::Text(String::from("-infinity")),
+                // timestamptz is always sent as UTC in binary format
+                micros => Value::Timestamp(Timestamp::from_pg_micros(
+                    micros,
+                    (type_oid == OID_TIMESTAMPTZ).then_some(0),
+                )),
+            },
+            _ => Value::Bytes(data.iter().copied().collect()),
+        }
+    }
+}
+
+fn be_i32(b: &[u8]) -> i32 {
+    i32::from_be_bytes([b[0], b[1], b[2], b[3]])
+}
+
+fn be_i64(b: &[u8]) -> i64 {
+    i64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])
 }
 
 /// Decode Postgres hex-format bytea (`\x...`) into bytes.
@@ -76,17 +361,51 @@ fn hex_nibble(b: u8) -> u8 {
 pub struct Column {
     pub name: String,
     pub type_oid: u32,
+    /// Result format code from RowDescription: 0 = text, 1 = binary.
+    pub format: i16,
+}
+
+/// Column metadata for one result set, shared by all of its rows.
+/// Holds a name→index map so lookups by name don't scan the columns.
+#[derive(Debug)]
+pub struct RowSchema {
+    columns: Vec<Column>,
+    index: HashMap<String, usize>,
+}
+
+impl RowSchema {
+    pub fn new(columns: Vec<Column>) -> Self {
+        let mut index = HashMap::with_capacity(columns.len());
+        for (i, col) in columns.iter().enumerate() {
+            // Duplicate names (e.g. from a join) resolve to the first column
+            index.entry(col.name.clone()).or_insert(i);
+        }
+        Self { columns, index }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Row {
-    columns: Vec<Column>,
+    schema: Arc<RowSchema>,
     values: Vec<Value>,
 }
 
 impl Row {
     pub fn new(columns: Vec<Column>, values: Vec<Value>) -> Self {
-        Self { columns, values }
+        Self::with_schema(Arc::new(RowSchema::new(columns)), values)
+    }
+
+    /// Build a row that shares its result set's schema.
+    pub fn with_schema(schema: Arc<RowSchema>, values: Vec<Value>) -> Self {
+        Self { schema, values }
     }
 
     pub fn len(&self) -> usize {
@@ -98,7 +417,7 @@ impl Row {
     }
 
     pub fn columns(&self) -> &[Column] {
-        &self.columns
+        self.schema.columns()
     }
 
     pub fn get_value(&self, idx: usize) -> Option<&Value> {
@@ -106,17 +425,11 @@ impl Row {
     }
 
     pub fn get_str(&self, idx: usize) -> Option<&str> {
-        match self.values.get(idx)? {
-            Value::Text(s) => Some(s.as_str()),
-            _ => None,
-        }
+        self.values.get(idx)?.as_str()
     }
 
     pub fn get_int(&self, idx: usize) -> Option<i64> {
-        match self.values.get(idx)? {
-            Value::Int(n) => Some(*n),
-            _ => None,
-        }
+        self.values.get(idx)?.as_i64()
     }
 
     pub fn get_float(&self, idx: usize) -> Option<f64> {
@@ -127,23 +440,35 @@ impl Row {
     }
 
     pub fn get_bool(&self, idx: usize) -> Option<bool> {
-        match self.values.get(idx)? {
-            Value::Bool(b) => Some(*b),
-            _ => None,
-        }
+        self.values.get(idx)?.as_bool()
     }
 
     /// Look up a value by column name, returning a reference.
     pub fn get_by_name(&self, name: &str) -> Option<&Value> {
-        let idx = self.columns.iter().position(|c| c.name == name)?;
-        self.values.get(idx)
+        self.values.get(self.schema.index_of(name)?)
+    }
+}
+
+/// Receives a result set as the driver reads it, so rows can be handled
+/// one at a time instead of being collected first.
+pub trait RowSink {
+    /// Called for each result set before its rows, even when it has none.
+    fn columns(&mut self, columns: &[Column]);
+    fn row(&mut self, row: Row);
+}
+
+impl RowSink for Vec<Row> {
+    fn columns(&mut self, _columns: &[Column]) {}
+
+    fn row(&mut self, row: Row) {
+        self.push(row);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::vvec;
+    use crate::{vformat, vvec};
 
     #[test]
     fn value_from_text_bool() {
@@ -199,20 +524,293 @@ mod tests {
         );
     }
 
+    #[test]
+    fn value_from_text_date() {
+        assert_eq!(
+            Value::from_text("2024-02-29", OID_DATE),
+            Value::Date(Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            })
+        );
+        assert_eq!(
+            Value::from_text("infinity", OID_DATE),
+            Value::Text("infinity".into())
+        );
+    }
+
+    #[test]
+    fn value_from_text_timestamp() {
+        let v = Value::from_text("2024-01-02 03:04:05.25", OID_TIMESTAMP);
+        assert_eq!(
+            v,
+            Value::Timestamp(Timestamp {
+                date: Date {
+                    year: 2024,
+                    month: 1,
+                    day: 2
+                },
+                hour: 3,
+                minute: 4,
+                second: 5,
+                micros: 250_000,
+                offset: None,
+            })
+        );
+    }
+
+    #[test]
+    fn value_from_text_timestamptz_offsets() {
+        let Value::Timestamp(ts) = Value::from_text("2024-01-02 03:04:05+00", OID_TIMESTAMPTZ)
+        else {
+            panic!("expected timestamp");
+        };
+        assert_eq!(ts.offset, Some(0));
+
+        let Value::Timestamp(ts) = Value::from_text("1999-12-31 23:59:59-05:30", OID_TIMESTAMPTZ)
+        else {
+            panic!("expected timestamp");
+        };
+        assert_eq!(ts.offset, Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(
+            ts.date,
+            Date {
+                year: 1999,
+                month: 12,
+                day: 31
+            }
+        );
+    }
+
+    #[test]
+    fn value_from_binary_date() {
+        // 2000-01-01 is day 0
+        assert_eq!(
+            Value::from_binary(&0i32.to_be_bytes(), OID_DATE),
+            Value::Date(Date {
+                year: 2000,
+                month: 1,
+                day: 1
+            })
+        );
+        assert_eq!(
+            Value::from_binary(&(-1i32).to_be_bytes(), OID_DATE),
+            Value::Date(Date {
+                year: 1999,
+                month: 12,
+                day: 31
+            })
+        );
+        // 2024-02-29 is 8825 days after the epoch
+        assert_eq!(
+            Value::from_binary(&8825i32.to_be_bytes(), OID_DATE),
+            Value::Date(Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            })
+        );
+        assert_eq!(
+            Value::from_binary(&i32::MAX.to_be_bytes(), OID_DATE),
+            Value::Text("infinity".into())
+        );
+    }
+
+    #[test]
+    fn value_from_binary_timestamp() {
+        let micros: i64 = 8825 * MICROS_PER_DAY + (13 * 3600 + 30 * 60 + 15) * 1_000_000 + 42;
+        assert_eq!(
+            Value::from_binary(&micros.to_be_bytes(), OID_TIMESTAMP),
+            Value::Timestamp(Timestamp {
+                date: Date {
+                    year: 2024,
+                    month: 2,
+                    day: 29
+                },
+                hour: 13,
+                minute: 30,
+                second: 15,
+                micros: 42,
+                offset: None,
+            })
+        );
+
+        let Value::Timestamp(ts) = Value::from_binary(&(-1i64).to_be_bytes(), OID_TIMESTAMPTZ)
+        else {
+            panic!("expected timestamp");
+        };
+        assert_eq!(
+            ts.date,
+            Date {
+                year: 1999,
+                month: 12,
+                day: 31
+            }
+        );
+        assert_eq!(
+            (ts.hour, ts.minute, ts.second, ts.micros),
+            (23, 59, 59, 999_999)
+        );
+        assert_eq!(ts.offset, Some(0));
+    }
+
+    #[test]
+    fn value_from_binary_scalars() {
+        assert_eq!(Value::from_binary(&[1], OID_BOOL), Value::Bool(true));
+        assert_eq!(
+            Value::from_binary(&(-3i16).to_be_bytes(), OID_INT2),
+            Value::Int(-3)
+        );
+        assert_eq!(
+            Value::from_binary(&7i64.to_be_bytes(), OID_INT8),
+            Value::Int(7)
+        );
+        assert_eq!(
+            Value::from_binary(&1.5f64.to_be_bytes(), OID_FLOAT8),
+            Value::Float(1.5)
+        );
+        assert_eq!(
+            Value::from_binary(b"hi", OID_TEXT),
+            Value::Text("hi".into())
+        );
+        assert_eq!(Value::from_binary(&[1, 2], 9999), Value::Bytes(vvec![1, 2]));
+    }
+
+    fn all_variants() -> Vec<Value> {
+        vvec![
+            Value::Null,
+            Value::Text("hi".into()),
+            Value::Int(-7),
+            Value::Float(2.5),
+            Value::Bool(true),
+            Value::Bytes(vvec![0xCA, 0xFE]),
+            Value::Date(Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            }),
+            Value::Timestamp(Timestamp::parse("2024-01-02 03:04:05").unwrap()),
+        ]
+    }
+
+    #[test]
+    fn value_getters_per_variant() {
+        for v in all_variants().iter() {
+            assert_eq!(v.is_null(), matches!(v, Value::Null));
+            assert_eq!(
+                v.as_i64(),
+                match v {
+                    Value::Int(_) => Some(-7),
+                    _ => None,
+                }
+            );
+            assert_eq!(
+                v.as_f64(),
+                match v {
+                    Value::Int(_) => Some(-7.0),
+                    Value::Float(_) => Some(2.5),
+                    _ => None,
+                }
+            );
+            assert_eq!(
+                v.as_str(),
+                match v {
+                    Value::Text(_) => Some("hi"),
+                    _ => None,
+                }
+            );
+            assert_eq!(
+                v.as_bool(),
+                match v {
+                    Value::Bool(_) => Some(true),
+                    _ => None,
+                }
+            );
+            assert_eq!(
+                v.as_bytes(),
+                match v {
+                    Value::Bytes(_) => Some(&[0xCA, 0xFE][..]),
+                    _ => None,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn value_display_per_variant() {
+        let rendered: Vec<String> = all_variants().iter().map(|v| vformat!("{v}")).collect();
+        let expected = [
+            "NULL",
+            "hi",
+            "-7",
+            "2.5",
+            "t",
+            "<bytes>",
+            "2024-02-29",
+            "2024-01-02T03:04:05",
+        ];
+        assert_eq!(rendered.len(), expected.len());
+        for (got, want) in rendered.iter().zip(expected.iter()) {
+            assert_eq!(got.as_str(), *want);
+        }
+        assert_eq!(vformat!("{}", Value::Bool(false)), "f");
+    }
+
+    #[test]
+    fn date_display_iso() {
+        assert_eq!(
+            vformat!(
+                "{}",
+                Date {
+                    year: 7,
+                    month: 3,
+                    day: 9
+                }
+            ),
+            "0007-03-09"
+        );
+    }
+
+    #[test]
+    fn timestamp_display_iso() {
+        let mut ts = Timestamp::parse("2024-01-02 03:04:05").unwrap();
+        assert_eq!(vformat!("{ts}"), "2024-01-02T03:04:05");
+
+        ts.micros = 120;
+        ts.offset = Some(0);
+        assert_eq!(vformat!("{ts}"), "2024-01-02T03:04:05.000120Z");
+
+        ts.micros = 0;
+        ts.offset = Some(-(9 * 3600 + 30 * 60));
+        assert_eq!(vformat!("{ts}"), "2024-01-02T03:04:05-09:30");
+    }
+
+    #[test]
+    fn timestamp_parse_rejects_garbage() {
+        assert!(Timestamp::parse("2024-01-02").is_none());
+        assert!(Timestamp::parse("2024-01-02 25:00:00").is_none());
+        assert!(Timestamp::parse("2024-01-02 03:04:05.1234567").is_none());
+        assert!(Date::parse("2024-13-01").is_none());
+    }
+
     #[test]
     fn row_accessors() {
         let cols = vvec![
             Column {
                 name: "id".into(),
                 type_oid: OID_INT4,
+                format: 0,
             },
             Column {
                 name: "name".into(),
                 type_oid: OID_TEXT,
+                format: 0,
             },
             Column {
                 name: "active".into(),
                 type_oid: OID_BOOL,
+                format: 0,
             },
         ];
         let vals = vvec![
@@ -235,10 +833,12 @@ mod tests {
             Column {
                 name: "x".into(),
                 type_oid: OID_INT4,
+                format: 0,
             },
             Column {
                 name: "y".into(),
                 type_oid: OID_TEXT,
+                format: 0,
             },
         ];
         let vals = vvec![Value::Int(10), Value::Text("hi".into())];
@@ -249,6 +849,43 @@ mod tests {
         assert_eq!(row.get_by_name("z"), None);
     }
 
+    #[test]
+    fn rows_share_schema_for_name_lookup() {
+        let schema = Arc::new(RowSchema::new(vvec![
+            Column {
+                name: "id".into(),
+                type_oid: OID_INT4,
+                format: 0,
+            },
+            Column {
+                name: "email".into(),
+                type_oid: OID_TEXT,
+                format: 0,
+            },
+            Column {
+                name: "id".into(),
+                type_oid: OID_INT8,
+                format: 0,
+            },
+        ]));
+        let rows = vvec![
+            Row::with_schema(
+                schema.clone(),
+                vvec![Value::Int(1), Value::Text("a@x".into()), Value::Int(10)]
+            ),
+            Row::with_schema(
+                schema.clone(),
+                vvec![Value::Int(2), Value::Text("b@x".into()), Value::Int(20)]
+            ),
+        ];
+
+        assert_eq!(rows[1].get_by_name("email"), Some(&Value::Text("b@x".into())));
+        // The first of two same-named columns wins
+        assert_eq!(rows[0].get_by_name("id"), Some(&Value::Int(1)));
+        assert_eq!(rows[1].columns().len(), 3);
+        assert!(core::ptr::eq(rows[0].columns(), rows[1].columns()));
+    }
+
     #[test]
     fn row_out_of_bounds() {
         let row = Row::new(vvec![], vvec![]);
@@ -265,6 +902,7 @@ mod tests {
         let cols = vvec![Column {
             name: "val".into(),
             type_oid: OID_INT4,
+            format: 0,
         }];
         let vals = vvec![Value::Int(42)];
         let row = Row::new(cols, vals);
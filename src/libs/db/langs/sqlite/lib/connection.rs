@@ -0,0 +1,430 @@
+use core::ffi::CStr;
+use core::ptr;
+
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::path::CString;
+use crate::core::volkiwithstds::sync::Arc;
+use crate::core::volkiwithstds::sys::sqlite3::{self as ffi, c_void, sqlite3, sqlite3_stmt};
+
+use crate::libs::db::langs::sqlite::lib::error::SqliteError;
+use crate::libs::db::langs::sqlite::lib::{Column, Row, RowSchema, RowSink, Value};
+
+/// An open SQLite database handle.
+pub struct Connection {
+    db: *mut sqlite3,
+}
+
+// SQLite's default (serialized) threading mode allows a handle to move
+// between threads.
+unsafe impl Send for Connection {}
+
+impl Connection {
+    /// Open a database file, creating it if it does not exist.
+    /// `":memory:"` opens a private in-memory database.
+    pub fn open(path: &str) -> Result<Self, SqliteError> {
+        Self::open_with_flags(
+            path,
+            ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE | ffi::SQLITE_OPEN_URI,
+        )
+    }
+
+    /// Open an existing database for reading only. A missing file is an
+    /// error rather than being created.
+    pub fn open_readonly(path: &str) -> Result<Self, SqliteError> {
+        Self::open_with_flags(path, ffi::SQLITE_OPEN_READONLY | ffi::SQLITE_OPEN_URI)
+    }
+
+    fn open_with_flags(path: &str, flags: i32) -> Result<Self, SqliteError> {
+        let c_path = CString::new(path);
+        let mut db = ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3_open_v2(c_path.as_ptr(), &mut db, flags, ptr::null()) };
+        if rc != ffi::SQLITE_OK {
+            let message = if db.is_null() {
+                String::from("out of memory")
+            } else {
+                errmsg(db)
+            };
+            // A handle is usually allocated even on failure and must be released.
+            unsafe { ffi::sqlite3_close_v2(db) };
+            return Err(SqliteError::Open {
+                path: String::from(path),
+                message,
+            });
+        }
+        Ok(Connection { db })
+    }
+
+    /// Run one or more `;`-separated statements and return every result row.
+    pub fn query(&mut self, sql: &str) -> Result<Vec<Row>, SqliteError> {
+        let mut rows = Vec::new();
+        self.query_into(sql, &mut rows)?;
+        Ok(rows)
+    }
+
+    /// Like `query`, but hands each row to `sink` as it is stepped.
+    pub fn query_into(&mut self, sql: &str, sink: &mut dyn RowSink) -> Result<(), SqliteError> {
+        self.for_each_statement(sql, |stmt| stmt.collect_rows(sink))
+    }
+
+    /// Run a single statement with positional parameters (`?` or `?NNN`).
+    pub fn query_params(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Row>, SqliteError> {
+        let c_sql = CString::new(sql);
+        let mut tail = ptr::null();
+        let stmt = self
+            .prepare(c_sql.as_ptr(), &mut tail)?
+            .ok_or_else(|| SqliteError::Param(String::from("empty query")))?;
+        if !is_blank(tail) {
+            return Err(SqliteError::Param(String::from(
+                "cannot run multiple statements with parameters",
+            )));
+        }
+
+        stmt.bind(params)?;
+        let mut rows = Vec::new();
+        stmt.collect_rows(&mut rows)?;
+        Ok(rows)
+    }
+
+    /// Run statements that don't return rows (INSERT, UPDATE, DELETE, DDL).
+    /// Returns the number of rows changed by the last statement.
+    pub fn execute(&mut self, sql: &str) -> Result<u64, SqliteError> {
+        let mut affected = 0u64;
+        let db = self.db;
+        self.for_each_statement(sql, |stmt| {
+            let mut rows = Vec::new();
+            stmt.collect_rows(&mut rows)?;
+            affected = if stmt.column_count() == 0 {
+                unsafe { ffi::sqlite3_changes(db) as u64 }
+            } else {
+                rows.len() as u64
+            };
+            Ok(())
+        })?;
+        Ok(affected)
+    }
+
+    /// Prepare and hand each statement in `sql` to `f` in order.
+    fn for_each_statement<F>(&mut self, sql: &str, mut f: F) -> Result<(), SqliteError>
+    where
+        F: FnMut(&Statement) -> Result<(), SqliteError>,
+    {
+        let c_sql = CString::new(sql);
+        let mut next = c_sql.as_ptr();
+        while !is_blank(next) {
+            let mut tail = ptr::null();
+            // Comment-only or empty segments prepare to no statement.
+            if let Some(stmt) = self.prepare(next, &mut tail)? {
+                f(&stmt)?;
+            }
+            if tail == next {
+                break;
+            }
+            next = tail;
+        }
+        Ok(())
+    }
+
+    fn prepare(
+        &self,
+        sql: *const ffi::c_char,
+        tail: &mut *const ffi::c_char,
+    ) -> Result<Option<Statement>, SqliteError> {
+        let mut stmt = ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3_prepare_v2(self.db, sql, -1, &mut stmt, tail) };
+        if rc != ffi::SQLITE_OK {
+            return Err(self.error(rc));
+        }
+        if stmt.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(Statement { db: self.db, stmt }))
+    }
+
+    fn error(&self, code: i32) -> SqliteError {
+        sqlite_error(self.db, code)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_close_v2(self.db) };
+    }
+}
+
+/// A prepared statement, finalized on drop.
+struct Statement {
+    db: *mut sqlite3,
+    stmt: *mut sqlite3_stmt,
+}
+
+impl Statement {
+    fn column_count(&self) -> usize {
+        unsafe { ffi::sqlite3_column_count(self.stmt) as usize }
+    }
+
+    fn bind(&self, params: &[Value]) -> Result<(), SqliteError> {
+        let expected = unsafe { ffi::sqlite3_bind_parameter_count(self.stmt) } as usize;
+        if expected != params.len() {
+            return Err(SqliteError::Param(crate::vformat!(
+                "expected {expected} parameters, got {}",
+                params.len()
+            )));
+        }
+
+        for (i, param) in params.iter().enumerate() {
+            let idx = i as i32 + 1;
+            let rc = unsafe {
+                match param {
+                    Value::Null => ffi::sqlite3_bind_null(self.stmt, idx),
+                    Value::Int(n) => ffi::sqlite3_bind_int64(self.stmt, idx, *n),
+                    Value::Bool(b) => ffi::sqlite3_bind_int64(self.stmt, idx, *b as i64),
+                    Value::Float(f) => ffi::sqlite3_bind_double(self.stmt, idx, *f),
+                    Value::Text(s) => bind_text(self.stmt, idx, s.as_str()),
+                    Value::Date(d) => bind_text(self.stmt, idx, &crate::vformat!("{d}")),
+                    Value::Timestamp(ts) => bind_text(self.stmt, idx, &crate::vformat!("{ts}")),
+                    Value::Bytes(b) => ffi::sqlite3_bind_blob(
+                        self.stmt,
+                        idx,
+                        b.as_ptr() as *const c_void,
+                        b.len() as i32,
+                        ffi::SQLITE_TRANSIENT,
+                    ),
+                }
+            };
+            if rc != ffi::SQLITE_OK {
+                return Err(sqlite_error(self.db, rc));
+            }
+        }
+        Ok(())
+    }
+
+    /// Step to completion, appending each result row to `rows`.
+    fn collect_rows(&self, sink: &mut dyn RowSink) -> Result<(), SqliteError> {
+        let count = self.column_count();
+        let columns: Vec<Column> = (0..count)
+            .map(|i| Column {
+                name: cstr_to_string(unsafe { ffi::sqlite3_column_name(self.stmt, i as i32) }),
+                type_oid: 0,
+                format: 0,
+            })
+            .collect();
+        let schema = Arc::new(RowSchema::new(columns));
+        sink.columns(schema.columns());
+
+        loop {
+            match unsafe { ffi::sqlite3_step(self.stmt) } {
+                ffi::SQLITE_ROW => {
+                    let values = (0..count).map(|i| self.column_value(i as i32)).collect();
+                    sink.row(Row::with_schema(schema.clone(), values));
+                }
+                ffi::SQLITE_DONE => return Ok(()),
+                rc => return Err(sqlite_error(self.db, rc)),
+            }
+        }
+    }
+
+    fn column_value(&self, idx: i32) -> Value {
+        unsafe {
+            match ffi::sqlite3_column_type(self.stmt, idx) {
+                ffi::SQLITE_INTEGER => Value::Int(ffi::sqlite3_column_int64(self.stmt, idx)),
+                ffi::SQLITE_FLOAT => Value::Float(ffi::sqlite3_column_double(self.stmt, idx)),
+                ffi::SQLITE_TEXT => {
+                    // column_text must be called before column_bytes.
+                    let ptr = ffi::sqlite3_column_text(self.stmt, idx);
+                    let len = ffi::sqlite3_column_bytes(self.stmt, idx) as usize;
+                    Value::Text(String::from_utf8_lossy(raw_slice(ptr, len)).to_owned())
+                }
+                ffi::SQLITE_BLOB => {
+                    let ptr = ffi::sqlite3_column_blob(self.stmt, idx) as *const u8;
+                    let len = ffi::sqlite3_column_bytes(self.stmt, idx) as usize;
+                    Value::Bytes(raw_slice(ptr, len).iter().copied().collect())
+                }
+                _ => Value::Null,
+            }
+        }
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_finalize(self.stmt) };
+    }
+}
+
+unsafe fn bind_text(stmt: *mut sqlite3_stmt, idx: i32, s: &str) -> i32 {
+    unsafe {
+        ffi::sqlite3_bind_text(
+            stmt,
+            idx,
+            s.as_ptr() as *const ffi::c_char,
+            s.len() as i32,
+            ffi::SQLITE_TRANSIENT,
+        )
+    }
+}
+
+/// View `len` bytes at `ptr`; SQLite returns null for zero-length values.
+unsafe fn raw_slice<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        unsafe { core::slice::from_raw_parts(ptr, len) }
+    }
+}
+
+fn sqlite_error(db: *mut sqlite3, code: i32) -> SqliteError {
+    SqliteError::Sqlite {
+        code,
+        message: errmsg(db),
+    }
+}
+
+fn errmsg(db: *mut sqlite3) -> String {
+    cstr_to_string(unsafe { ffi::sqlite3_errmsg(db) })
+}
+
+fn cstr_to_string(ptr: *const ffi::c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+    String::from_utf8_lossy(bytes).to_owned()
+}
+
+/// True when the rest of a SQL buffer is only whitespace.
+fn is_blank(ptr: *const ffi::c_char) -> bool {
+    if ptr.is_null() {
+        return true;
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_bytes()
+        .iter()
+        .all(|b| b.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vvec;
+
+    fn memory_db() -> Connection {
+        let mut conn = Connection::open(":memory:").unwrap();
+        conn.execute(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, score REAL, avatar BLOB);
+             INSERT INTO users (name, score, avatar) VALUES ('alice', 9.5, x'CAFE');
+             INSERT INTO users (name, score) VALUES ('bob', NULL);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn create_insert_select() {
+        let mut conn = memory_db();
+        let rows = conn
+            .query("SELECT id, name, score, avatar FROM users ORDER BY id")
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_int(0), Some(1));
+        assert_eq!(rows[0].get_str(1), Some("alice"));
+        assert_eq!(rows[0].get_float(2), Some(9.5));
+        assert_eq!(rows[0].get_value(3), Some(&Value::Bytes(vvec![0xCA, 0xFE])));
+        assert_eq!(rows[1].get_str(1), Some("bob"));
+        assert_eq!(rows[1].get_value(2), Some(&Value::Null));
+        assert_eq!(
+            rows[1].get_by_name("name"),
+            Some(&Value::Text("bob".into()))
+        );
+    }
+
+    #[test]
+    fn get_by_name_ignores_column_order() {
+        let mut conn = memory_db();
+        let a = conn.query("SELECT id, name, score FROM users ORDER BY id").unwrap();
+        let b = conn.query("SELECT score, name, id FROM users ORDER BY id").unwrap();
+        for (x, y) in a.iter().zip(b.iter()) {
+            for col in ["id", "name", "score"] {
+                assert_eq!(x.get_by_name(col), y.get_by_name(col));
+            }
+        }
+        assert_eq!(b[0].get_by_name("id"), Some(&Value::Int(1)));
+        assert_eq!(b[0].get_by_name("avatar"), None);
+    }
+
+    #[test]
+    fn execute_returns_changed_rows() {
+        let mut conn = memory_db();
+        let n = conn.execute("UPDATE users SET score = 1.0").unwrap();
+        assert_eq!(n, 2);
+        let n = conn
+            .execute("DELETE FROM users WHERE name = 'bob'")
+            .unwrap();
+        assert_eq!(n, 1);
+    }
+
+    #[test]
+    fn query_params_binds_values() {
+        let mut conn = memory_db();
+        conn.query_params(
+            "INSERT INTO users (name, score, avatar) VALUES (?1, ?2, ?3)",
+            &[
+                Value::Text("carol".into()),
+                Value::Float(7.25),
+                Value::Bytes(vvec![1, 2]),
+            ],
+        )
+        .unwrap();
+
+        let rows = conn
+            .query_params(
+                "SELECT name, score FROM users WHERE score > ? ORDER BY score",
+                &[Value::Int(7)],
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_str(0), Some("carol"));
+        assert_eq!(rows[1].get_str(0), Some("alice"));
+    }
+
+    #[test]
+    fn query_params_count_mismatch() {
+        let mut conn = memory_db();
+        let err = conn
+            .query_params("SELECT * FROM users WHERE id = ?", &[])
+            .unwrap_err();
+        assert!(matches!(err, SqliteError::Param(_)));
+    }
+
+    #[test]
+    fn query_params_rejects_multiple_statements() {
+        let mut conn = memory_db();
+        let err = conn
+            .query_params("SELECT ?; SELECT 2", &[Value::Int(1)])
+            .unwrap_err();
+        assert!(matches!(err, SqliteError::Param(_)));
+    }
+
+    #[test]
+    fn sql_error_reports_message() {
+        let mut conn = memory_db();
+        let err = conn.query("SELECT * FROM missing").unwrap_err();
+        let msg = crate::vformat!("{err}");
+        assert!(msg.contains("no such table: missing"));
+    }
+
+    #[test]
+    fn trailing_whitespace_and_empty_statements() {
+        let mut conn = memory_db();
+        let rows = conn.query("SELECT 1;  ; \n").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get_int(0), Some(1));
+        assert_eq!(rows[0].columns()[0].name, "1");
+    }
+
+    #[test]
+    fn open_invalid_path_fails() {
+        let result = Connection::open("/nonexistent-dir/volki/test.db");
+        assert!(matches!(result, Err(SqliteError::Open { .. })));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::fmt;
+
+#[derive(Debug)]
+pub enum SqliteError {
+    Open { path: String, message: String },
+    Sqlite { code: i32, message: String },
+    Param(String),
+}
+
+impl fmt::Display for SqliteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SqliteError::Open { path, message } => {
+                write!(f, "cannot open database '{path}': {message}")
+            }
+            SqliteError::Sqlite { code, message } => write!(f, "sqlite error ({code}): {message}"),
+            SqliteError::Param(msg) => write!(f, "parameter error: {msg}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_open_error() {
+        let err = SqliteError::Open {
+            path: "/nope/app.db".into(),
+            message: "unable to open database file".into(),
+        };
+        assert_eq!(
+            crate::vformat!("{err}").as_str(),
+            "cannot open database '/nope/app.db': unable to open database file"
+        );
+    }
+
+    #[test]
+    fn display_sqlite_error() {
+        let err = SqliteError::Sqlite {
+            code: 1,
+            message: "no such table: users".into(),
+        };
+        assert_eq!(
+            crate::vformat!("{err}").as_str(),
+            "sqlite error (1): no such table: users"
+        );
+    }
+
+    #[test]
+    fn display_param_error() {
+        let err = SqliteError::Param("expected 2 parameters, got 1".into());
+        assert_eq!(
+            crate::vformat!("{err}").as_str(),
+            "parameter error: expected 2 parameters, got 1"
+        );
+    }
+}
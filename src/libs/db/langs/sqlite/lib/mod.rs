@@ -0,0 +1,7 @@
+pub mod connection;
+pub mod error;
+
+pub use connection::Connection;
+pub use error::SqliteError;
+// Rows share the postgres representation so callers can treat drivers alike.
+pub use crate::libs::db::langs::postgres::lib::types::{Column, Row, RowSchema, RowSink, Value};
@@ -1,3 +1,11 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuoteStyle {
+    Single,
+    Double,
+    /// Leave every string literal exactly as written.
+    Preserve,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum QuoteProps {
     AsNeeded,
@@ -43,7 +51,7 @@ pub struct FormatConfig {
     pub tab_width: usize,
     pub use_tabs: bool,
     pub semi: bool,
-    pub single_quote: bool,
+    pub quotes: QuoteStyle,
     pub quote_props: QuoteProps,
     pub jsx_single_quote: bool,
     pub trailing_comma: TrailingComma,
@@ -52,6 +60,9 @@ pub struct FormatConfig {
     pub arrow_parens: ArrowParens,
     pub end_of_line: EndOfLine,
     pub single_attribute_per_line: bool,
+    /// Group and alphabetize the leading `import` block. Off by default since
+    /// Prettier itself never reorders statements.
+    pub sort_imports: bool,
 }
 
 impl Default for FormatConfig {
@@ -61,7 +72,7 @@ impl Default for FormatConfig {
             tab_width: 2,
             use_tabs: false,
             semi: true,
-            single_quote: false,
+            quotes: QuoteStyle::Double,
             quote_props: QuoteProps::AsNeeded,
             jsx_single_quote: false,
             trailing_comma: TrailingComma::All,
@@ -70,6 +81,7 @@ impl Default for FormatConfig {
             arrow_parens: ArrowParens::Always,
             end_of_line: EndOfLine::Lf,
             single_attribute_per_line: false,
+            sort_imports: false,
         }
     }
 }
@@ -85,7 +97,7 @@ mod tests {
         assert_eq!(c.tab_width, 2);
         assert!(!c.use_tabs);
         assert!(c.semi);
-        assert!(!c.single_quote);
+        assert_eq!(c.quotes, QuoteStyle::Double);
         assert_eq!(c.quote_props, QuoteProps::AsNeeded);
         assert!(!c.jsx_single_quote);
         assert_eq!(c.trailing_comma, TrailingComma::All);
@@ -94,6 +106,7 @@ mod tests {
         assert_eq!(c.arrow_parens, ArrowParens::Always);
         assert_eq!(c.end_of_line, EndOfLine::Lf);
         assert!(!c.single_attribute_per_line);
+        assert!(!c.sort_imports);
     }
 
     #[test]
@@ -2,7 +2,7 @@ use crate::core::volkiwithstds::collections::ToString;
 use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::fmt;
 
-use super::config::{ArrowParens, EndOfLine, FormatConfig, TrailingComma};
+use super::config::{ArrowParens, EndOfLine, FormatConfig, QuoteStyle, TrailingComma};
 use super::plugin_bridge;
 use super::tokenizer::{Token, TokenKind, tokenize};
 
@@ -40,6 +40,7 @@ pub fn format_source(
 
     run_plugin_hook(&mut tokens, config, plugins, "formatter.after_normalize");
 
+    sort_imports(&mut tokens, config);
     normalize_semicolons(&mut tokens, config);
     normalize_bracket_spacing(&mut tokens, config);
     normalize_trailing_commas(&mut tokens, config);
@@ -116,69 +117,298 @@ fn normalize_line_endings(tokens: &mut Vec<Token>, config: &FormatConfig) {
 }
 
 // Pass 2: Normalize quote style
+//
+// Template literals are a separate token kind and are never touched.
 fn normalize_quotes(tokens: &mut Vec<Token>, config: &FormatConfig) {
-    let target_quote = if config.single_quote { '\'' } else { '"' };
+    let target_quote = match config.quotes {
+        QuoteStyle::Single => '\'',
+        QuoteStyle::Double => '"',
+        QuoteStyle::Preserve => return,
+    };
+    let jsx_quote = if config.jsx_single_quote { '\'' } else { '"' };
     for token in tokens.iter_mut() {
         if token.kind == TokenKind::StringLiteral && token.text.len() >= 2 {
-            let current_quote = token.text.as_bytes()[0] as char;
-            if current_quote != target_quote {
-                try_swap_quotes(token, current_quote, target_quote);
-            }
+            requote(token, target_quote);
+        } else if token.kind == TokenKind::JsxAttribute {
+            requote_jsx_attribute(token, jsx_quote);
         }
     }
 }
 
-fn try_swap_quotes(token: &mut Token, from: char, to: char) {
-    let inner = &token.text[1..token.text.len() - 1];
+/// JSX attribute strings have no escapes, so the quote is only swapped when
+/// the value doesn't contain the target quote.
+fn requote_jsx_attribute(token: &mut Token, quote: char) {
+    let Some(eq) = token.text.find("=") else {
+        return;
+    };
+    let value = &token.text[eq + 1..];
+    if value.len() < 2 {
+        return;
+    }
+    let inner = &value[1..value.len() - 1];
+    if value.starts_with(quote) || inner.contains(quote) {
+        return;
+    }
+    let mut text = String::from(&token.text[..=eq]);
+    text.push(quote);
+    text.push_str(inner);
+    text.push(quote);
+    token.text = text;
+}
 
-    let mut target_count = 0usize;
-    let mut from_escaped = 0usize;
+/// Rewrite a string literal with `preferred` quotes, unless the content has
+/// more of that quote than the alternate, in which case the alternate wins
+/// since it needs fewer escapes. Either way the chosen quote is escaped and
+/// needless escapes of the other quote are dropped.
+fn requote(token: &mut Token, preferred: char) {
+    let inner = &token.text[1..token.text.len() - 1];
     let chars: Vec<char> = inner.chars().collect();
+    let alternate = if preferred == '"' { '\'' } else { '"' };
+
+    let mut preferred_count = 0usize;
+    let mut alternate_count = 0usize;
     let mut i = 0;
     while i < chars.len() {
-        if chars[i] == '\\' {
-            if i + 1 < chars.len() && chars[i + 1] == from {
-                from_escaped += 1;
-            }
-            i += 2;
-        } else {
-            if chars[i] == to {
-                target_count += 1;
-            }
+        let c = if chars[i] == '\\' && i + 1 < chars.len() {
             i += 1;
+            chars[i]
+        } else {
+            chars[i]
+        };
+        if c == preferred {
+            preferred_count += 1;
+        } else if c == alternate {
+            alternate_count += 1;
         }
+        i += 1;
     }
-
-    // Skip swap if it would add more escapes than it removes
-    if target_count > from_escaped {
-        return;
-    }
+    let quote = if preferred_count > alternate_count {
+        alternate
+    } else {
+        preferred
+    };
 
     let mut result = String::with_capacity(token.text.len());
-    result.push(to);
+    result.push(quote);
     let mut i = 0;
     while i < chars.len() {
         if chars[i] == '\\' && i + 1 < chars.len() {
-            if chars[i + 1] == from {
-                result.push(from);
+            let next = chars[i + 1];
+            if next == '\'' || next == '"' {
+                if next == quote {
+                    result.push('\\');
+                }
+                result.push(next);
             } else {
                 result.push('\\');
-                result.push(chars[i + 1]);
+                result.push(next);
             }
             i += 2;
-        } else if chars[i] == to {
-            result.push('\\');
-            result.push(to);
-            i += 1;
         } else {
+            if chars[i] == quote {
+                result.push('\\');
+            }
             result.push(chars[i]);
             i += 1;
         }
     }
-    result.push(to);
+    result.push(quote);
     token.text = result;
 }
 
+// Pass 2b: Sort the leading import block
+//
+// Only the run of `import` statements at the top of the module is touched.
+// Side-effect imports (`import "x"`) stay where they are and split the block
+// into runs that are sorted independently, since moving them could change
+// evaluation order. Comments directly above an import travel with it.
+struct ImportChunk {
+    tokens: Vec<Token>,
+    source: String,
+    side_effect: bool,
+}
+
+fn sort_imports(tokens: &mut Vec<Token>, config: &FormatConfig) {
+    if !config.sort_imports {
+        return;
+    }
+
+    let mut chunks: Vec<ImportChunk> = Vec::new();
+    let mut block_start = 0;
+    let mut block_end = 0;
+    let mut pos = 0;
+    loop {
+        let mut j = pos;
+        let mut comment_start = None;
+        let mut newlines = 0;
+        while j < tokens.len() {
+            match tokens[j].kind {
+                TokenKind::Whitespace => {}
+                TokenKind::Newline => {
+                    newlines += 1;
+                    // A comment separated from the first import by a blank
+                    // line is a file header, not part of the import.
+                    if newlines > 1 && chunks.is_empty() {
+                        comment_start = None;
+                    }
+                }
+                TokenKind::LineComment | TokenKind::BlockComment => {
+                    comment_start.get_or_insert(j);
+                    newlines = 0;
+                }
+                _ => break,
+            }
+            j += 1;
+        }
+        let Some((end, source, side_effect)) = parse_import(tokens, j) else {
+            break;
+        };
+        let start = comment_start.unwrap_or(j);
+        if chunks.is_empty() {
+            block_start = start;
+        }
+        chunks.push(ImportChunk {
+            tokens: tokens[start..end].iter().cloned().collect(),
+            source,
+            side_effect,
+        });
+        block_end = end;
+        pos = end;
+    }
+    if chunks.len() < 2 {
+        return;
+    }
+
+    let eol = config.end_of_line.as_str();
+    let mut sorted: Vec<Token> = Vec::new();
+    let mut run: Vec<ImportChunk> = Vec::new();
+    for chunk in chunks {
+        if chunk.side_effect {
+            emit_import_run(&mut sorted, &mut run, eol);
+            push_import_tokens(&mut sorted, chunk.tokens, eol);
+        } else {
+            run.push(chunk);
+        }
+    }
+    emit_import_run(&mut sorted, &mut run, eol);
+
+    let tail: Vec<Token> = tokens[block_end..].iter().cloned().collect();
+    tokens.truncate(block_start);
+    tokens.extend(sorted);
+    tokens.extend(tail);
+}
+
+/// External sources sort before relative ones, then case-insensitively.
+fn emit_import_run(out: &mut Vec<Token>, run: &mut Vec<ImportChunk>, eol: &str) {
+    run.sort_by(|a, b| {
+        is_relative_source(&a.source)
+            .cmp(&is_relative_source(&b.source))
+            .then_with(|| a.source.to_lowercase().cmp(&b.source.to_lowercase()))
+    });
+    let mut prev_relative = None;
+    for chunk in run.drain(..) {
+        let relative = is_relative_source(&chunk.source);
+        if prev_relative == Some(false) && relative {
+            out.push(newline_token(eol));
+        }
+        prev_relative = Some(relative);
+        push_import_tokens(out, chunk.tokens, eol);
+    }
+}
+
+fn push_import_tokens(out: &mut Vec<Token>, chunk: Vec<Token>, eol: &str) {
+    if !out.is_empty() {
+        out.push(newline_token(eol));
+    }
+    out.extend(chunk);
+}
+
+fn newline_token(eol: &str) -> Token {
+    Token {
+        kind: TokenKind::Newline,
+        text: eol.to_vstring(),
+        line: 0,
+        col: 0,
+    }
+}
+
+fn is_relative_source(source: &str) -> bool {
+    source.starts_with('.') || source.starts_with('/')
+}
+
+/// Recognize an import declaration starting at `pos`. Returns the index just
+/// past it (including a trailing `;` and same-line comment), the module
+/// source without quotes, and whether it is a side-effect-only import.
+fn parse_import(tokens: &[Token], pos: usize) -> Option<(usize, String, bool)> {
+    let tok = tokens.get(pos)?;
+    if tok.kind != TokenKind::Identifier || tok.text != "import" {
+        return None;
+    }
+
+    let mut i = find_significant_after(tokens, pos)?;
+    let side_effect = match tokens[i].kind {
+        TokenKind::StringLiteral => true,
+        // `import(...)` and `import.meta` are expressions
+        TokenKind::OpenParen | TokenKind::Dot => return None,
+        _ => {
+            let mut depth = 0usize;
+            loop {
+                let t = &tokens[i];
+                match t.kind {
+                    TokenKind::OpenBrace => depth += 1,
+                    TokenKind::CloseBrace => depth = depth.saturating_sub(1),
+                    TokenKind::Semicolon | TokenKind::Eof => return None,
+                    TokenKind::Identifier if depth == 0 && t.text == "from" => break,
+                    _ => {}
+                }
+                i = find_significant_after(tokens, i)?;
+            }
+            i = find_significant_after(tokens, i)?;
+            if tokens[i].kind != TokenKind::StringLiteral {
+                return None;
+            }
+            false
+        }
+    };
+
+    let quoted = tokens[i].text.as_str();
+    let source = String::from(&quoted[1..quoted.len() - 1]);
+    let mut end = i + 1;
+
+    // Import attributes: `with { type: "json" }` / `assert { ... }`
+    if let Some(n) = find_significant_after(tokens, i)
+        && tokens[n].kind == TokenKind::Identifier
+        && (tokens[n].text == "with" || tokens[n].text == "assert")
+        && let Some(open) = find_significant_after(tokens, n)
+        && tokens[open].kind == TokenKind::OpenBrace
+    {
+        end = find_matching_close_brace(tokens, open)? + 1;
+    }
+
+    let mut j = end;
+    while j < tokens.len() && tokens[j].kind == TokenKind::Whitespace {
+        j += 1;
+    }
+    if j < tokens.len() && tokens[j].kind == TokenKind::Semicolon {
+        end = j + 1;
+        j = end;
+        while j < tokens.len() && tokens[j].kind == TokenKind::Whitespace {
+            j += 1;
+        }
+    }
+    if j < tokens.len()
+        && matches!(
+            tokens[j].kind,
+            TokenKind::LineComment | TokenKind::BlockComment
+        )
+        && !tokens[j].text.contains("\n")
+    {
+        end = j + 1;
+    }
+
+    Some((end, source, side_effect))
+}
+
 // Pass 3: Normalize semicolons
 fn normalize_semicolons(tokens: &mut Vec<Token>, config: &FormatConfig) {
     if config.semi {
@@ -199,6 +429,19 @@ fn is_statement_end(kind: &TokenKind) -> bool {
             | TokenKind::CloseParen
             | TokenKind::CloseBracket
             | TokenKind::RegexLiteral
+            | TokenKind::JsxTagEnd
+    )
+}
+
+/// A tag end followed by more JSX is inside an element, not the end of one.
+fn continues_jsx(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::JsxTagStart
+            | TokenKind::JsxTagEnd
+            | TokenKind::JsxAttribute
+            | TokenKind::JsxText
+            | TokenKind::OpenBrace
     )
 }
 
@@ -252,6 +495,9 @@ fn insert_semicolons(tokens: &mut Vec<Token>) {
                 {
                     continue;
                 }
+                if tokens[pi].kind == TokenKind::JsxTagEnd && continues_jsx(&tokens[ni].kind) {
+                    continue;
+                }
             }
             if tokens[pi].kind == TokenKind::Semicolon {
                 continue;
@@ -348,6 +594,8 @@ fn is_object_literal_brace(tokens: &[Token], pos: usize) -> bool {
     let prev = find_significant_before(tokens, pos);
     match prev {
         None => true,
+        // `style={{ color: "red" }}`
+        Some(pi) if tokens[pi].kind == TokenKind::OpenBrace => is_jsx_container(tokens, pi),
         Some(pi) => {
             matches!(
                 tokens[pi].kind,
@@ -367,6 +615,26 @@ fn is_object_literal_brace(tokens: &[Token], pos: usize) -> bool {
     }
 }
 
+/// Whether the `{` at `open` is a JSX expression container: it follows a tag,
+/// attribute or text, or directly follows another container (`{a}{b}`).
+fn is_jsx_container(tokens: &[Token], open: usize) -> bool {
+    let mut pos = open;
+    while let Some(pi) = find_significant_before(tokens, pos) {
+        match tokens[pi].kind {
+            TokenKind::JsxTagStart
+            | TokenKind::JsxTagEnd
+            | TokenKind::JsxAttribute
+            | TokenKind::JsxText => return true,
+            TokenKind::CloseBrace => match find_matching_open(tokens, pi) {
+                Some(oi) => pos = oi,
+                None => return false,
+            },
+            _ => return false,
+        }
+    }
+    false
+}
+
 fn find_matching_close_brace(tokens: &[Token], open: usize) -> Option<usize> {
     let mut depth = 1;
     for i in (open + 1)..tokens.len() {
@@ -433,6 +701,10 @@ fn normalize_trailing_commas(tokens: &mut Vec<Token>, config: &FormatConfig) {
             continue;
         }
         let open_idx = matching_open.unwrap();
+        if !allows_trailing_comma(classify_group_kind(tokens, open_idx)) {
+            i += 1;
+            continue;
+        }
         let multi_line = !is_single_line_range(tokens, open_idx, i);
         let prev_sig = find_significant_before(tokens, i);
 
@@ -481,6 +753,18 @@ fn normalize_trailing_commas(tokens: &mut Vec<Token>, config: &FormatConfig) {
     }
 }
 
+/// Grouping parens and blocks never take a trailing comma; `(a,)` is a
+/// syntax error.
+fn allows_trailing_comma(kind: GroupKind) -> bool {
+    matches!(
+        kind,
+        GroupKind::FunctionCall
+            | GroupKind::FunctionParams
+            | GroupKind::ArrayLiteral
+            | GroupKind::ObjectLiteral
+    )
+}
+
 fn is_comma_eligible(token: &Token) -> bool {
     matches!(
         token.kind,
@@ -650,9 +934,19 @@ fn remove_arrow_parens(tokens: &mut Vec<Token>) {
 fn normalize_whitespace(tokens: &mut Vec<Token>, config: &FormatConfig) {
     collapse_blank_lines(tokens);
     normalize_spacing(tokens);
+    normalize_jsx_spacing(tokens);
     reindent(tokens, config);
-    wrap_long_lines(tokens, config);
-    reindent(tokens, config);
+    // Groups are measured at the columns of the previous layout, so an inner
+    // group may break before its parent does; a further round collapses it
+    // again at its new column.
+    for _ in 0..3 {
+        let before = serialize(tokens, config);
+        wrap_long_lines(tokens, config);
+        reindent(tokens, config);
+        if serialize(tokens, config) == before {
+            break;
+        }
+    }
 }
 
 fn collapse_blank_lines(tokens: &mut Vec<Token>) {
@@ -714,6 +1008,34 @@ fn normalize_spacing(tokens: &mut Vec<Token>) {
     }
 }
 
+// `<div >` becomes `<div>`, `<br/>` becomes `<br />`.
+fn normalize_jsx_spacing(tokens: &mut Vec<Token>) {
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i].kind == TokenKind::JsxTagEnd && i > 0 && !is_after_newline(tokens, i) {
+            let has_space = tokens[i - 1].kind == TokenKind::Whitespace;
+            let self_closing = tokens[i].text == "/>";
+            if has_space && !self_closing && !is_after_newline(tokens, i - 1) {
+                tokens.remove(i - 1);
+                continue;
+            }
+            if !has_space && self_closing && tokens[i - 1].kind != TokenKind::Newline {
+                tokens.insert(
+                    i,
+                    Token {
+                        kind: TokenKind::Whitespace,
+                        text: " ".into(),
+                        line: 0,
+                        col: 0,
+                    },
+                );
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+}
+
 fn is_after_newline(tokens: &[Token], pos: usize) -> bool {
     pos > 0 && tokens[pos - 1].kind == TokenKind::Newline
 }
@@ -725,18 +1047,31 @@ fn reindent(tokens: &mut Vec<Token>, config: &FormatConfig) {
         vstr!(" ").repeat(config.tab_width)
     };
 
-    let mut depth: usize = 0;
+    // Each open delimiter indents one level past the line it sits on, so
+    // `foo(() => {` indents its body once, not twice.
+    let mut levels: Vec<usize> = Vec::new();
+    let mut line_level: usize = 0;
     let mut i = 0;
     while i < tokens.len() {
         match tokens[i].kind {
             TokenKind::OpenBrace | TokenKind::OpenBracket | TokenKind::OpenParen => {
-                depth += 1;
+                levels.push(line_level + 1);
             }
             TokenKind::CloseBrace | TokenKind::CloseBracket | TokenKind::CloseParen => {
-                depth = depth.saturating_sub(1);
+                levels.pop();
+            }
+            // `<div` opens a level that carries through `>` into the
+            // children; `</div` and `/>` close it again.
+            TokenKind::JsxTagStart if tokens[i].text.starts_with("</") => {
+                levels.pop();
+            }
+            TokenKind::JsxTagStart => levels.push(line_level + 1),
+            TokenKind::JsxTagEnd if tokens[i].text == "/>" => {
+                levels.pop();
             }
             _ => {}
         }
+        let depth = levels.last().copied().unwrap_or(0);
 
         // After a newline, set indentation whitespace
         if tokens[i].kind == TokenKind::Newline {
@@ -746,9 +1081,20 @@ fn reindent(tokens: &mut Vec<Token>, config: &FormatConfig) {
                 let effective_depth = if let Some(close_idx) = find_first_non_ws(tokens, next_idx) {
                     if matches!(
                         tokens[close_idx].kind,
-                        TokenKind::CloseBrace | TokenKind::CloseBracket | TokenKind::CloseParen
-                    ) {
+                        TokenKind::CloseBrace
+                            | TokenKind::CloseBracket
+                            | TokenKind::CloseParen
+                            | TokenKind::JsxTagEnd
+                    ) || (tokens[close_idx].kind == TokenKind::JsxTagStart
+                        && tokens[close_idx].text.starts_with("</"))
+                    {
                         depth.saturating_sub(1)
+                    } else if tokens[close_idx].kind == TokenKind::Dot
+                        || (tokens[close_idx].kind == TokenKind::Operator
+                            && tokens[close_idx].text == "?.")
+                    {
+                        // Broken method chains hang one level in
+                        depth + 1
                     } else {
                         depth
                     }
@@ -756,6 +1102,7 @@ fn reindent(tokens: &mut Vec<Token>, config: &FormatConfig) {
                     depth
                 };
 
+                line_level = effective_depth;
                 let new_indent = indent_str.repeat(effective_depth);
                 if tokens[next_idx].kind == TokenKind::Whitespace {
                     tokens[next_idx].text = new_indent;
@@ -797,6 +1144,7 @@ enum GroupKind {
     ArrayLiteral,
     ObjectLiteral,
     Block,
+    JsxContainer,
     Other,
 }
 
@@ -816,6 +1164,9 @@ enum BreakDecision {
     Flat,
     Expanded,
     LastArgExpanded,
+    /// `useEffect(() => { ... }, [deps])`: hug a leading callback when the
+    /// rest of the call stays flat.
+    FirstArgExpanded,
 }
 
 fn find_matching_close(tokens: &[Token], open: usize) -> Option<usize> {
@@ -839,11 +1190,24 @@ fn find_matching_close(tokens: &[Token], open: usize) -> Option<usize> {
     None
 }
 
+/// Length of `from..=to` once flattened: a line break becomes a space except
+/// next to a delimiter, and a trailing comma disappears.
 fn measure_flat_length(tokens: &[Token], from: usize, to: usize) -> usize {
     let mut len = 0;
     for i in from..=to {
         match tokens[i].kind {
-            TokenKind::Newline => {}
+            TokenKind::Newline => {
+                let after_open = find_significant_before(tokens, i).is_some_and(|p| {
+                    matches!(
+                        tokens[p].kind,
+                        TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace
+                    )
+                });
+                if !after_open && !next_is_close(tokens, i) {
+                    len += 1;
+                }
+            }
+            TokenKind::Comma if next_is_close(tokens, i) => {}
             TokenKind::Whitespace => {
                 if i > 0 && tokens[i - 1].kind == TokenKind::Newline {
                 } else {
@@ -858,6 +1222,15 @@ fn measure_flat_length(tokens: &[Token], from: usize, to: usize) -> usize {
     len
 }
 
+fn next_is_close(tokens: &[Token], pos: usize) -> bool {
+    find_significant_after(tokens, pos).is_some_and(|n| {
+        matches!(
+            tokens[n].kind,
+            TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace
+        )
+    })
+}
+
 fn has_newline_in_range(tokens: &[Token], from: usize, to: usize) -> bool {
     for i in from..=to {
         if tokens[i].kind == TokenKind::Newline {
@@ -873,6 +1246,8 @@ fn classify_group_kind(tokens: &[Token], open_idx: usize) -> GroupKind {
         TokenKind::OpenBrace => {
             if is_object_literal_brace(tokens, open_idx) {
                 GroupKind::ObjectLiteral
+            } else if is_jsx_container(tokens, open_idx) {
+                GroupKind::JsxContainer
             } else {
                 GroupKind::Block
             }
@@ -936,6 +1311,11 @@ fn build_group_tree(tokens: &[Token]) -> Vec<DelimiterGroup> {
     let mut stack: Vec<usize> = Vec::new(); // stack of group indices
 
     for i in 0..tokens.len() {
+        // JSX containers keep their source layout; groups inside them are
+        // still wrapped as usual.
+        if tokens[i].kind == TokenKind::OpenBrace && is_jsx_container(tokens, i) {
+            continue;
+        }
         if matches!(
             tokens[i].kind,
             TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace
@@ -1073,13 +1453,27 @@ fn find_first_significant(tokens: &[Token], from: usize, to: usize) -> Option<us
     None
 }
 
-fn find_last_arg_range(tokens: &[Token], group: &DelimiterGroup) -> Option<(usize, usize)> {
-    if group.comma_indices.is_empty() {
-        return None;
+/// The group's depth-0 commas without a trailing one, i.e. the commas that
+/// separate arguments.
+fn arg_comma_indices(tokens: &[Token], group: &DelimiterGroup) -> Vec<usize> {
+    let mut commas = group.comma_indices.clone();
+    if let Some(&last) = commas.last()
+        && find_significant_after(tokens, last) == Some(group.close_idx)
+    {
+        commas.pop();
     }
-    let last_comma = *group.comma_indices.last().unwrap();
-    let start = last_comma + 1;
-    let end = group.close_idx.saturating_sub(1);
+    commas
+}
+
+fn find_last_arg_range(tokens: &[Token], group: &DelimiterGroup) -> Option<(usize, usize)> {
+    let start = match arg_comma_indices(tokens, group).last() {
+        Some(&last_comma) => last_comma + 1,
+        None => group.open_idx + 1,
+    };
+    let end = match group.comma_indices.last() {
+        Some(&c) if c >= start => c - 1,
+        _ => group.close_idx.saturating_sub(1),
+    };
     if start > end {
         return None;
     }
@@ -1091,10 +1485,10 @@ fn find_last_arg_range(tokens: &[Token], group: &DelimiterGroup) -> Option<(usiz
 }
 
 fn measure_prefix_flat_len(tokens: &[Token], group: &DelimiterGroup) -> usize {
-    if group.comma_indices.is_empty() {
-        return group.flat_len;
-    }
-    let last_comma = *group.comma_indices.last().unwrap();
+    let Some(&last_comma) = arg_comma_indices(tokens, group).last() else {
+        // Just the open delimiter
+        return 1;
+    };
     // prefix = open delim through last comma + space
     measure_flat_length(tokens, group.open_idx, last_comma) + 1
 }
@@ -1124,13 +1518,26 @@ fn decide_breaks(
             continue;
         }
 
-        if group.has_source_newline {
+        // Object and array literals keep the author's line breaks, as do
+        // groups holding JSX or line comments; argument lists and parens
+        // collapse again when they fit.
+        if group.has_source_newline
+            && (matches!(
+                group.kind,
+                GroupKind::ObjectLiteral | GroupKind::ArrayLiteral
+            ) || keeps_source_breaks(tokens, group.open_idx, group.close_idx))
+        {
             decisions[gi] = BreakDecision::Expanded;
             continue;
         }
 
+        let children_flat = group
+            .children
+            .iter()
+            .all(|&c| decisions[c] == BreakDecision::Flat);
         let col = column_at(tokens, group.open_idx);
-        if col + group.flat_len <= config.print_width {
+        let width = col + group.flat_len + trailing_punct_len(tokens, group.close_idx);
+        if children_flat && width <= config.print_width {
             decisions[gi] = BreakDecision::Flat;
             continue;
         }
@@ -1138,7 +1545,11 @@ fn decide_breaks(
         // Try LastArgExpanded for function calls
         if group.kind == GroupKind::FunctionCall {
             if let Some((start, end)) = find_last_arg_range(tokens, group) {
-                if is_expandable_last_arg(tokens, start, end) {
+                let leading_flat = group
+                    .children
+                    .iter()
+                    .all(|&c| groups[c].open_idx >= start || decisions[c] == BreakDecision::Flat);
+                if leading_flat && is_expandable_last_arg(tokens, start, end) {
                     let prefix_len = col + measure_prefix_flat_len(tokens, group);
                     if prefix_len <= config.print_width {
                         decisions[gi] = BreakDecision::LastArgExpanded;
@@ -1146,6 +1557,17 @@ fn decide_breaks(
                     }
                 }
             }
+            let arg_commas = arg_comma_indices(tokens, group);
+            if arg_commas.len() == 1 {
+                let first_end = arg_commas[0];
+                let rest_flat = group.children.iter().all(|&c| {
+                    groups[c].open_idx < first_end || decisions[c] == BreakDecision::Flat
+                });
+                if rest_flat && is_expandable_last_arg(tokens, group.open_idx + 1, first_end - 1) {
+                    decisions[gi] = BreakDecision::FirstArgExpanded;
+                    continue;
+                }
+            }
         }
 
         decisions[gi] = BreakDecision::Expanded;
@@ -1154,6 +1576,37 @@ fn decide_breaks(
     decisions
 }
 
+fn keeps_source_breaks(tokens: &[Token], open: usize, close: usize) -> bool {
+    tokens[open..=close].iter().any(|t| {
+        matches!(
+            t.kind,
+            TokenKind::LineComment
+                | TokenKind::JsxTagStart
+                | TokenKind::JsxTagEnd
+                | TokenKind::JsxText
+        )
+    })
+}
+
+/// Closing delimiters, `;` and `,` glued to the end of a group sit on the
+/// same line, so they count toward its width.
+fn trailing_punct_len(tokens: &[Token], close: usize) -> usize {
+    tokens[close + 1..]
+        .iter()
+        .take_while(|t| {
+            matches!(
+                t.kind,
+                TokenKind::CloseParen
+                    | TokenKind::CloseBracket
+                    | TokenKind::CloseBrace
+                    | TokenKind::Semicolon
+                    | TokenKind::Comma
+            )
+        })
+        .map(|t| t.text.len())
+        .sum()
+}
+
 fn is_empty_body_range(tokens: &[Token], open: usize, close: usize) -> bool {
     for i in (open + 1)..close {
         if !matches!(tokens[i].kind, TokenKind::Whitespace | TokenKind::Newline) {
@@ -1164,6 +1617,11 @@ fn is_empty_body_range(tokens: &[Token], open: usize, close: usize) -> bool {
 }
 
 fn wrap_long_lines(tokens: &mut Vec<Token>, config: &FormatConfig) {
+    // Break chains first so argument groups are measured at the column they
+    // end up on, then re-indent the new `.` lines before measuring.
+    apply_method_chain_breaks(tokens, config);
+    reindent(tokens, config);
+
     let groups = build_group_tree(tokens);
     if groups.is_empty() {
         return;
@@ -1172,12 +1630,6 @@ fn wrap_long_lines(tokens: &mut Vec<Token>, config: &FormatConfig) {
     let decisions = decide_breaks(tokens, &groups, config);
 
     apply_wrap_decisions(tokens, &groups, &decisions, config);
-
-    // Method chain breaking
-    apply_method_chain_breaks(tokens, config);
-
-    // Trailing comma fixup
-    fixup_trailing_commas(tokens, &groups, &decisions, config);
 }
 
 fn apply_wrap_decisions(
@@ -1217,9 +1669,20 @@ fn apply_wrap_decisions(
                 expand_group(tokens, open_idx, close_idx, &eol);
             }
             BreakDecision::LastArgExpanded => {
-                apply_last_arg_expansion(tokens, open_idx, close_idx, &eol, config);
+                flatten_group(tokens, open_idx, close_idx, &eol);
+                apply_arg_expansion(tokens, open_idx, false, &eol);
+            }
+            BreakDecision::FirstArgExpanded => {
+                flatten_group(tokens, open_idx, close_idx, &eol);
+                apply_arg_expansion(tokens, open_idx, true, &eol);
             }
         }
+
+        // Everything this touched lies right of `open_idx`, so it is still
+        // valid for the trailing comma fixup.
+        if allows_trailing_comma(group.kind) {
+            fixup_trailing_comma(tokens, open_idx, decision, config);
+        }
     }
 }
 
@@ -1333,9 +1796,23 @@ fn expand_group(tokens: &mut Vec<Token>, open: usize, _close: usize, eol: &str)
     let current_close = find_matching_close(tokens, open).unwrap();
     insert_newline_before(tokens, current_close, eol);
 
-    // 2. After each comma (reverse order)
+    // 2. After each comma (reverse order), except a trailing one which
+    //    already sits before the newline inserted above
     let commas = collect_depth0_commas(tokens, open);
     for &ci in commas.iter().rev() {
+        if find_significant_after(tokens, ci)
+            .is_some_and(|n| find_matching_close(tokens, open) == Some(n))
+        {
+            continue;
+        }
+        // A same-line `// comment` after the comma already ends the line
+        let mut next = ci + 1;
+        while next < tokens.len() && tokens[next].kind == TokenKind::Whitespace {
+            next += 1;
+        }
+        if next < tokens.len() && tokens[next].kind == TokenKind::LineComment {
+            continue;
+        }
         // Remove whitespace after comma
         if ci + 1 < tokens.len() && tokens[ci + 1].kind == TokenKind::Whitespace {
             tokens.remove(ci + 1);
@@ -1374,8 +1851,13 @@ fn ensure_commas_have_newlines(tokens: &mut Vec<Token>, open: usize, eol: &str)
         let after = ci + 1;
         if after < tokens.len() {
             if tokens[after].kind == TokenKind::Whitespace {
-                // Check if there's a newline after the whitespace
-                if after + 1 < tokens.len() && tokens[after + 1].kind == TokenKind::Newline {
+                // Check if there's a newline or same-line comment after the whitespace
+                if after + 1 < tokens.len()
+                    && matches!(
+                        tokens[after + 1].kind,
+                        TokenKind::Newline | TokenKind::LineComment
+                    )
+                {
                     continue;
                 }
                 // Replace whitespace with newline
@@ -1455,24 +1937,23 @@ fn insert_newline_before(tokens: &mut Vec<Token>, pos: usize, eol: &str) {
     }
 }
 
-fn apply_last_arg_expansion(
-    tokens: &mut Vec<Token>,
-    open: usize,
-    _close: usize,
-    eol: &str,
-    _config: &FormatConfig,
-) {
-    let commas = collect_depth0_commas(tokens, open);
-    if commas.is_empty() {
+/// Expand the function body, object or array inside the first or last
+/// argument, leaving the rest of the call on one line.
+fn apply_arg_expansion(tokens: &mut Vec<Token>, open: usize, first: bool, eol: &str) {
+    let Some(group_close) = find_matching_close(tokens, open) else {
         return;
-    }
-
-    let last_comma = *commas.last().unwrap();
-
-    // Find the expandable group within the last arg
-    let close = match find_matching_close(tokens, open) {
-        Some(c) => c,
-        None => return,
+    };
+    let mut commas = collect_depth0_commas(tokens, open);
+    if commas
+        .last()
+        .is_some_and(|&c| find_significant_after(tokens, c) == Some(group_close))
+    {
+        commas.pop();
+    }
+    let (last_comma, close) = if first {
+        (open, commas.first().copied().unwrap_or(group_close))
+    } else {
+        (commas.last().copied().unwrap_or(open), group_close)
     };
 
     // First pass: look for arrow fn body or function body (these take priority)
@@ -1652,76 +2133,63 @@ fn find_chain_end(tokens: &[Token], last_dot: usize) -> usize {
     }
 }
 
-fn fixup_trailing_commas(
+fn fixup_trailing_comma(
     tokens: &mut Vec<Token>,
-    groups: &[DelimiterGroup],
-    decisions: &[BreakDecision],
+    open_idx: usize,
+    decision: BreakDecision,
     config: &FormatConfig,
 ) {
-    // Process right-to-left to keep indices stable
-    let mut order: Vec<usize> = (0..groups.len()).collect();
-    order.sort_by(|a, b| groups[*b].open_idx.cmp(&groups[*a].open_idx));
-
-    for &gi in &order {
-        let decision = decisions[gi];
-        let group = &groups[gi];
-
-        let close_idx = match find_matching_close(tokens, group.open_idx) {
-            Some(c) => c,
-            None => continue,
-        };
-
-        let prev_sig = find_significant_before(tokens, close_idx);
-        let open_idx = group.open_idx;
+    let close_idx = match find_matching_close(tokens, open_idx) {
+        Some(c) => c,
+        None => return,
+    };
+    let prev_sig = find_significant_before(tokens, close_idx);
 
-        match decision {
-            BreakDecision::Expanded => {
-                if !matches!(config.trailing_comma, TrailingComma::None) {
-                    if let Some(pi) = prev_sig {
-                        if pi > open_idx
-                            && tokens[pi].kind != TokenKind::Comma
-                            && is_comma_eligible(&tokens[pi])
-                            && !is_empty_body_range(tokens, open_idx, close_idx)
-                        {
-                            tokens.insert(
-                                pi + 1,
-                                Token {
-                                    kind: TokenKind::Comma,
-                                    text: ",".into(),
-                                    line: 0,
-                                    col: 0,
-                                },
-                            );
-                        }
+    match decision {
+        BreakDecision::Expanded => {
+            if !matches!(config.trailing_comma, TrailingComma::None) {
+                if let Some(pi) = prev_sig {
+                    if pi > open_idx
+                        && tokens[pi].kind != TokenKind::Comma
+                        && is_comma_eligible(&tokens[pi])
+                        && !is_empty_body_range(tokens, open_idx, close_idx)
+                    {
+                        tokens.insert(
+                            pi + 1,
+                            Token {
+                                kind: TokenKind::Comma,
+                                text: ",".into(),
+                                line: 0,
+                                col: 0,
+                            },
+                        );
                     }
                 }
             }
-            BreakDecision::LastArgExpanded => {
-                // Remove trailing comma after the expandable last arg
-                if let Some(pi) = prev_sig {
-                    if tokens[pi].kind == TokenKind::Comma && pi > open_idx {
-                        // Check the token before the comma - if it's a close delim of the
-                        // expanded arg, remove the trailing comma
-                        let before_comma = find_significant_before(tokens, pi);
-                        if let Some(bi) = before_comma {
-                            if matches!(
-                                tokens[bi].kind,
-                                TokenKind::CloseBrace
-                                    | TokenKind::CloseBracket
-                                    | TokenKind::CloseParen
-                            ) {
-                                tokens.remove(pi);
-                            }
+        }
+        BreakDecision::LastArgExpanded | BreakDecision::FirstArgExpanded => {
+            // Remove trailing comma after the expandable last arg
+            if let Some(pi) = prev_sig {
+                if tokens[pi].kind == TokenKind::Comma && pi > open_idx {
+                    // Check the token before the comma - if it's a close delim of the
+                    // expanded arg, remove the trailing comma
+                    let before_comma = find_significant_before(tokens, pi);
+                    if let Some(bi) = before_comma {
+                        if matches!(
+                            tokens[bi].kind,
+                            TokenKind::CloseBrace | TokenKind::CloseBracket | TokenKind::CloseParen
+                        ) {
+                            tokens.remove(pi);
                         }
                     }
                 }
             }
-            BreakDecision::Flat => {
-                // Remove trailing commas on flat groups
-                if let Some(pi) = prev_sig {
-                    if tokens[pi].kind == TokenKind::Comma && pi > open_idx {
-                        tokens.remove(pi);
-                    }
+        }
+        BreakDecision::Flat => {
+            // Remove trailing commas on flat groups
+            if let Some(pi) = prev_sig {
+                if tokens[pi].kind == TokenKind::Comma && pi > open_idx {
+                    tokens.remove(pi);
                 }
             }
         }
@@ -1793,7 +2261,7 @@ mod tests {
     #[test]
     fn double_to_single_quotes() {
         let mut config = FormatConfig::default();
-        config.single_quote = true;
+        config.quotes = QuoteStyle::Single;
         let result = fmt_with(r#"const x = "hello""#, &config);
         assert!(result.contains("'hello'"));
     }
@@ -1807,11 +2275,51 @@ mod tests {
     #[test]
     fn skip_swap_if_more_escapes() {
         let mut config = FormatConfig::default();
-        config.single_quote = true;
+        config.quotes = QuoteStyle::Single;
         let result = fmt_with(r#"const x = "it's here""#, &config);
         assert!(result.contains("\"it's here\""));
     }
 
+    #[test]
+    fn single_to_double_round_trip() {
+        let mut config = FormatConfig::default();
+        config.quotes = QuoteStyle::Single;
+        assert_eq!(fmt_with(r#"const x = "a";"#, &config), "const x = 'a';\n");
+        config.quotes = QuoteStyle::Double;
+        assert_eq!(fmt_with("const x = 'a';", &config), "const x = \"a\";\n");
+    }
+
+    #[test]
+    fn preserve_leaves_quotes_alone() {
+        let mut config = FormatConfig::default();
+        config.quotes = QuoteStyle::Preserve;
+        let input = "const a = 'x';\nconst b = \"y\";\nconst c = 'it\\'s';\n";
+        assert_eq!(fmt_with(input, &config), input);
+    }
+
+    #[test]
+    fn requote_escapes_target_and_unescapes_other() {
+        // Both quote types: more doubles than singles, so single quotes stay
+        // and only the single quote is escaped.
+        let result = fmt(r#"const x = 'it\'s a "b"';"#);
+        assert_eq!(result, "const x = 'it\\'s a \"b\"';\n");
+
+        let result = fmt(r#"const x = 'say "hi" \'you\'';"#);
+        assert_eq!(result, "const x = \"say \\\"hi\\\" 'you'\";\n");
+
+        // Needless escape of the other quote is dropped.
+        let result = fmt(r#"const x = "it\'s";"#);
+        assert_eq!(result, "const x = \"it's\";\n");
+    }
+
+    #[test]
+    fn template_literals_untouched() {
+        let mut config = FormatConfig::default();
+        config.quotes = QuoteStyle::Single;
+        let result = fmt_with("const x = `a \"b\" 'c'`;", &config);
+        assert!(result.contains("`a \"b\" 'c'`"));
+    }
+
     // Semicolons
     #[test]
     fn insert_semicolons() {
@@ -2127,4 +2635,177 @@ mod tests {
             "Long params should expand, got: {result}"
         );
     }
+
+    // Import sorting
+    fn sorted(input: &str) -> String {
+        let mut config = FormatConfig::default();
+        config.sort_imports = true;
+        fmt_with(input, &config)
+    }
+
+    #[test]
+    fn sort_imports_off_by_default() {
+        let input = "import b from \"b\";\nimport a from \"a\";\n";
+        assert_eq!(fmt(input), input);
+    }
+
+    #[test]
+    fn sort_imports_mixed_named_and_default() {
+        let input = "import { z } from \"./z\";\nimport React from \"react\";\nimport { b, a } from \"../b\";\nimport Axios from \"axios\";\nimport { useState } from \"React-dom\";\n\nconst x = 1;\n";
+        let result = sorted(input);
+        let order: Vec<usize> = [
+            "\"axios\"",
+            "\"react\"",
+            "\"React-dom\"",
+            "\"../b\"",
+            "\"./z\"",
+        ]
+        .iter()
+        .map(|src| result.find(src).unwrap())
+        .collect();
+        assert!(
+            order.windows(2).all(|w| w[0] < w[1]),
+            "imports out of order, got: {result}"
+        );
+        assert!(result.contains("from \"React-dom\";\n\nimport"));
+        assert!(result.ends_with("\n\nconst x = 1;\n"));
+    }
+
+    #[test]
+    fn sort_imports_keeps_comments_attached() {
+        let input = "// header\n\n// pick z\nimport z from \"z\";\nimport a from \"a\"; // first\n";
+        assert_eq!(
+            sorted(input),
+            "// header\n\nimport a from \"a\"; // first\n// pick z\nimport z from \"z\";\n"
+        );
+    }
+
+    #[test]
+    fn sort_imports_side_effect_stays_in_place() {
+        let input = "import b from \"b\";\nimport a from \"a\";\nimport \"./polyfill\";\nimport d from \"d\";\nimport c from \"c\";\n";
+        assert_eq!(
+            sorted(input),
+            "import a from \"a\";\nimport b from \"b\";\nimport \"./polyfill\";\nimport c from \"c\";\nimport d from \"d\";\n"
+        );
+    }
+
+    #[test]
+    fn sort_imports_stops_at_code() {
+        let input = "import b from \"b\";\nconst x = import(\"z\");\nimport a from \"a\";\n";
+        assert_eq!(sorted(input), input);
+    }
+
+    // JSX
+    #[test]
+    fn jsx_nested_tree_canonical_indentation() {
+        let input = "function App() {\n  return (\n<div className='app'   id=\"x\" >\n        <Header title={title}/>\n   <ul>\n{items.map(item => <li key={item.id}>{item.name}</li>)}\n      </ul>\n<>\n<span>Don't stop</span>\n</>\n  <input\n type=\"text\"\n      disabled\n  />\n</div>\n  )\n}\n";
+        let expected = "function App() {\n  return (\n    <div className=\"app\" id=\"x\">\n      <Header title={title} />\n      <ul>\n        {items.map((item) => <li key={item.id}>{item.name}</li>)}\n      </ul>\n      <>\n        <span>Don't stop</span>\n      </>\n      <input\n        type=\"text\"\n        disabled\n      />\n    </div>\n  );\n}\n";
+        let result = fmt(input);
+        assert_eq!(result, expected);
+        assert_eq!(fmt(&result), result, "JSX formatting should be idempotent");
+    }
+
+    #[test]
+    fn jsx_self_closing_and_style_object() {
+        let result = fmt("const el = <Foo style={{  color: 'red' }}/>\n");
+        assert_eq!(result, "const el = <Foo style={{ color: \"red\" }} />;\n");
+    }
+
+    #[test]
+    fn jsx_single_quote_attributes() {
+        let mut config = FormatConfig::default();
+        config.jsx_single_quote = true;
+        let result = fmt_with("const el = <a href=\"/x\" title=\"it's\">x</a>;\n", &config);
+        assert_eq!(result, "const el = <a href='/x' title=\"it's\">x</a>;\n");
+    }
+
+    #[test]
+    fn jsx_does_not_capture_generics() {
+        let result = fmt("const f = <T,>(x: T) => x < 3\n");
+        assert_eq!(result, "const f = <T,>(x: T) => x < 3;\n");
+    }
+
+    // Print width boundary
+    fn fmt_width(input: &str, width: usize) -> String {
+        let mut config = FormatConfig::default();
+        config.print_width = width;
+        let result = fmt_with(input, &config);
+        assert_eq!(fmt_with(&result, &config), result, "not idempotent");
+        result
+    }
+
+    #[test]
+    fn width_call_under_and_at_boundary_stays_flat() {
+        // 39 and exactly 40 columns including `);`
+        let under = "foo(alpha, beta, gamma, delta, e12345);\n";
+        let at = "foo(alpha, beta, gamma, delta, e123456);\n";
+        assert_eq!(at.len(), 41);
+        assert_eq!(fmt_width(under, 40), under);
+        assert_eq!(fmt_width(at, 40), at);
+    }
+
+    #[test]
+    fn width_call_over_boundary_breaks() {
+        let result = fmt_width("foo(alpha, beta, gamma, delta, e1234567);\n", 40);
+        assert_eq!(
+            result,
+            "foo(\n  alpha,\n  beta,\n  gamma,\n  delta,\n  e1234567,\n);\n"
+        );
+    }
+
+    #[test]
+    fn width_short_call_collapses() {
+        assert_eq!(fmt_width("foo(\n  a,\n  b,\n);\n", 40), "foo(a, b);\n");
+    }
+
+    #[test]
+    fn width_method_chain_breaks_and_indents() {
+        let result = fmt_width(
+            "const x = promise.then(handleTheResult).catch(handleErr).finally(done);\n",
+            40,
+        );
+        assert_eq!(
+            result,
+            "const x = promise\n  .then(handleTheResult)\n  .catch(handleErr)\n  .finally(done);\n"
+        );
+    }
+
+    #[test]
+    fn width_never_splits_strings() {
+        let result = fmt_width("call(\"a very long string literal that exceeds\");\n", 40);
+        assert_eq!(
+            result,
+            "call(\n  \"a very long string literal that exceeds\",\n);\n"
+        );
+    }
+
+    #[test]
+    fn width_inner_group_measured_after_outer_breaks() {
+        let result = fmt_width(
+            "const v = compute(firstArgument, secondArgument(inner, more), third);\n",
+            40,
+        );
+        assert_eq!(
+            result,
+            "const v = compute(\n  firstArgument,\n  secondArgument(inner, more),\n  third,\n);\n"
+        );
+    }
+
+    #[test]
+    fn width_hugs_first_and_last_callbacks() {
+        let input = "useEffect(() => {\n  run();\n}, [dep]);\ndoSomething(argumentNumberOne, function () {\n  return 1;\n});\n";
+        assert_eq!(fmt_width(input, 40), input);
+    }
+
+    #[test]
+    fn width_grouping_parens_get_no_trailing_comma() {
+        let result = fmt_width("items.map(item => (\n  foo(item)\n));\n", 40);
+        assert_eq!(result, "items.map((item) => (foo(item)));\n");
+    }
+
+    #[test]
+    fn width_keeps_same_line_comment_after_comma() {
+        let result = fmt_width("function f(a, // first\n  b) {}\n", 40);
+        assert_eq!(result, "function f(\n  a, // first\n  b,\n) {}\n");
+    }
 }
@@ -1,4 +1,4 @@
-use super::config::FormatConfig;
+use super::config::{FormatConfig, QuoteStyle};
 use super::tokenizer::{Token, TokenKind};
 use crate::core::plugins::protocol::JsonOut;
 use crate::core::volkiwithstds::collections::ToString;
@@ -35,6 +35,10 @@ pub fn token_kind_str(kind: &TokenKind) -> &'static str {
         TokenKind::BlockComment => "BlockComment",
         TokenKind::Whitespace => "Whitespace",
         TokenKind::Newline => "Newline",
+        TokenKind::JsxTagStart => "JsxTagStart",
+        TokenKind::JsxTagEnd => "JsxTagEnd",
+        TokenKind::JsxAttribute => "JsxAttribute",
+        TokenKind::JsxText => "JsxText",
         TokenKind::Eof => "Eof",
     }
 }
@@ -68,6 +72,10 @@ pub fn token_kind_from_str(s: &str) -> Option<TokenKind> {
         "BlockComment" => Some(TokenKind::BlockComment),
         "Whitespace" => Some(TokenKind::Whitespace),
         "Newline" => Some(TokenKind::Newline),
+        "JsxTagStart" => Some(TokenKind::JsxTagStart),
+        "JsxTagEnd" => Some(TokenKind::JsxTagEnd),
+        "JsxAttribute" => Some(TokenKind::JsxAttribute),
+        "JsxText" => Some(TokenKind::JsxText),
         "Eof" => Some(TokenKind::Eof),
         _ => None,
     }
@@ -98,7 +106,10 @@ pub fn config_to_json(config: &FormatConfig) -> JsonOut {
         ("tab_width".into(), JsonOut::Int(config.tab_width as i64)),
         ("use_tabs".into(), JsonOut::Bool(config.use_tabs)),
         ("semi".into(), JsonOut::Bool(config.semi)),
-        ("single_quote".into(), JsonOut::Bool(config.single_quote)),
+        (
+            "single_quote".into(),
+            JsonOut::Bool(config.quotes == QuoteStyle::Single)
+        ),
         (
             "bracket_spacing".into(),
             JsonOut::Bool(config.bracket_spacing)
@@ -158,6 +169,10 @@ mod tests {
             TokenKind::BlockComment,
             TokenKind::Whitespace,
             TokenKind::Newline,
+            TokenKind::JsxTagStart,
+            TokenKind::JsxTagEnd,
+            TokenKind::JsxAttribute,
+            TokenKind::JsxText,
             TokenKind::Eof,
         ];
         for kind in &kinds {
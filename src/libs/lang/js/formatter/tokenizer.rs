@@ -31,6 +31,14 @@ pub enum TokenKind {
     BlockComment,
     Whitespace,
     Newline,
+    /// `<name`, `</name`, or a bare `<` / `</` for fragments.
+    JsxTagStart,
+    /// `>` or `/>` closing a JSX tag.
+    JsxTagEnd,
+    /// `name`, `name="value"`, or `name=` when the value is `{...}`.
+    JsxAttribute,
+    /// Text between JSX tags, excluding surrounding whitespace.
+    JsxText,
     Eof,
 }
 
@@ -61,6 +69,18 @@ impl fmt::Display for TokenizeError {
 
 impl core::error::Error for TokenizeError {}
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsxMode {
+    /// Inside `<name ...>` or `</name>`.
+    Tag { closing: bool },
+    /// Between an element's opening and closing tags.
+    Children,
+    /// Inside a `{...}` container. `depth` counts nested braces; `templates`
+    /// records the template nesting at entry so a `}` ending an inner `${}`
+    /// isn't taken as the end of the container.
+    Expr { depth: usize, templates: usize },
+}
+
 struct Tokenizer {
     chars: Vec<char>,
     pos: usize,
@@ -68,6 +88,7 @@ struct Tokenizer {
     col: usize,
     tokens: Vec<Token>,
     template_depth: Vec<usize>,
+    jsx: Vec<JsxMode>,
 }
 
 impl Tokenizer {
@@ -79,6 +100,7 @@ impl Tokenizer {
             col: 1,
             tokens: Vec::new(),
             template_depth: Vec::new(),
+            jsx: Vec::new(),
         }
     }
 
@@ -188,6 +210,12 @@ impl Tokenizer {
             let line = self.line;
             let col = self.col;
 
+            match self.jsx.last().copied() {
+                Some(JsxMode::Tag { .. }) if self.read_jsx_tag_part(ch, line, col) => continue,
+                Some(JsxMode::Children) if self.read_jsx_child(ch, line, col) => continue,
+                _ => {}
+            }
+
             match ch {
                 '\n' => {
                     self.advance();
@@ -256,12 +284,36 @@ impl Tokenizer {
                 }
                 '{' => {
                     self.advance();
+                    let templates = self.template_depth.len();
+                    if let Some(JsxMode::Expr {
+                        depth,
+                        templates: t,
+                    }) = self.jsx.last_mut()
+                        && *t == templates
+                    {
+                        *depth += 1;
+                    }
                     if let Some(depth) = self.template_depth.last_mut() {
                         *depth += 1;
                     }
                     self.emit(TokenKind::OpenBrace, "{".into(), line, col);
                 }
                 '}' => {
+                    let templates = self.template_depth.len();
+                    if let Some(JsxMode::Expr {
+                        depth,
+                        templates: t,
+                    }) = self.jsx.last_mut()
+                        && *t == templates
+                    {
+                        if *depth == 0 {
+                            self.jsx.pop();
+                            self.advance();
+                            self.emit(TokenKind::CloseBrace, "}".into(), line, col);
+                            continue;
+                        }
+                        *depth -= 1;
+                    }
                     if let Some(depth) = self.template_depth.last_mut() {
                         if *depth == 0 {
                             self.template_depth.pop();
@@ -348,6 +400,9 @@ impl Tokenizer {
                         self.emit(TokenKind::Operator, "!".into(), line, col);
                     }
                 }
+                '<' if self.slash_is_regex() && self.looks_like_jsx() => {
+                    self.read_jsx_tag_start(line, col);
+                }
                 '<' | '>' => {
                     self.read_comparison(line, col);
                 }
@@ -732,6 +787,198 @@ impl Tokenizer {
         self.emit(TokenKind::Identifier, text, line, col);
     }
 
+    /// A `<` in expression position starts JSX only if the tag is
+    /// self-closing or its closing tag appears later, which keeps TypeScript
+    /// generics like `<T>(x: T) => x` on the comparison path.
+    fn looks_like_jsx(&self) -> bool {
+        let mut i = self.pos + 1;
+        let mut name = String::new();
+        while let Some(&c) = self.chars.get(i) {
+            if is_jsx_name_char(c) {
+                name.push(c);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return self.chars.get(i) == Some(&'>') && self.contains_from(i, "</>");
+        }
+        if !name.chars().next().is_some_and(is_ident_start) {
+            return false;
+        }
+
+        let mut braces = 0usize;
+        while let Some(&c) = self.chars.get(i) {
+            match c {
+                '"' | '\'' if braces == 0 => {
+                    i += 1;
+                    while self.chars.get(i).is_some_and(|&q| q != c) {
+                        i += 1;
+                    }
+                }
+                '{' => braces += 1,
+                '}' => braces = braces.saturating_sub(1),
+                '/' if braces == 0 && self.chars.get(i + 1) == Some(&'>') => return true,
+                '>' if braces == 0 => return self.has_closing_tag(i, &name),
+                ',' | ';' | '(' | ')' if braces == 0 => return false,
+                _ => {}
+            }
+            i += 1;
+        }
+        false
+    }
+
+    fn contains_from(&self, from: usize, pat: &str) -> bool {
+        let pat: Vec<char> = pat.chars().collect();
+        from < self.chars.len() && self.chars[from..].windows(pat.len()).any(|w| w == &pat[..])
+    }
+
+    /// Whether `</name` appears after `from` as a whole tag name, so `<T>`
+    /// isn't taken for JSX because of a later `</Table>`.
+    fn has_closing_tag(&self, from: usize, name: &str) -> bool {
+        let pat: Vec<char> = crate::vformat!("</{name}").chars().collect();
+        if from >= self.chars.len() {
+            return false;
+        }
+        let rest = &self.chars[from..];
+        rest.windows(pat.len()).enumerate().any(|(at, w)| {
+            w == &pat[..]
+                && rest
+                    .get(at + pat.len())
+                    .is_some_and(|&c| c == '>' || c == '/' || c.is_whitespace())
+        })
+    }
+
+    fn read_jsx_tag_start(&mut self, line: usize, col: usize) {
+        let mut text = String::new();
+        text.push(self.advance().unwrap()); // <
+        let closing = self.peek() == Some('/');
+        if closing {
+            text.push(self.advance().unwrap());
+        }
+        while let Some(c) = self.peek() {
+            if is_jsx_name_char(c) {
+                text.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.emit(TokenKind::JsxTagStart, text, line, col);
+        self.jsx.push(JsxMode::Tag { closing });
+    }
+
+    /// Handle one token inside a JSX tag. Whitespace and comments return
+    /// false and fall through to the regular tokenizer.
+    fn read_jsx_tag_part(&mut self, ch: char, line: usize, col: usize) -> bool {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => false,
+            '/' if matches!(self.peek_at(1), Some('/') | Some('*')) => false,
+            '/' if self.peek_at(1) == Some('>') => {
+                self.advance();
+                self.advance();
+                self.emit(TokenKind::JsxTagEnd, "/>".into(), line, col);
+                self.jsx.pop();
+                true
+            }
+            '>' => {
+                self.advance();
+                self.emit(TokenKind::JsxTagEnd, ">".into(), line, col);
+                if let Some(JsxMode::Tag { closing }) = self.jsx.pop() {
+                    if closing {
+                        if self.jsx.last() == Some(&JsxMode::Children) {
+                            self.jsx.pop();
+                        }
+                    } else {
+                        self.jsx.push(JsxMode::Children);
+                    }
+                }
+                true
+            }
+            '{' => {
+                self.advance();
+                self.emit(TokenKind::OpenBrace, "{".into(), line, col);
+                self.push_jsx_expr();
+                true
+            }
+            _ if is_ident_start(ch) => {
+                let mut text = String::new();
+                while let Some(c) = self.peek() {
+                    if is_jsx_name_char(c) {
+                        text.push(c);
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                if self.peek() == Some('=') {
+                    text.push(self.advance().unwrap());
+                    if let Some(quote @ ('"' | '\'')) = self.peek() {
+                        text.push(self.advance().unwrap());
+                        while let Some(c) = self.advance() {
+                            text.push(c);
+                            if c == quote {
+                                break;
+                            }
+                        }
+                    }
+                }
+                self.emit(TokenKind::JsxAttribute, text, line, col);
+                true
+            }
+            _ => {
+                self.advance();
+                self.emit(TokenKind::Operator, ch.to_vstring(), line, col);
+                true
+            }
+        }
+    }
+
+    /// Handle one token between JSX tags. Whitespace returns false and falls
+    /// through to the regular tokenizer; everything else is text, a tag or
+    /// an expression container.
+    fn read_jsx_child(&mut self, ch: char, line: usize, col: usize) -> bool {
+        match ch {
+            ' ' | '\t' | '\n' | '\r' => false,
+            '<' => {
+                self.read_jsx_tag_start(line, col);
+                true
+            }
+            '{' => {
+                self.advance();
+                self.emit(TokenKind::OpenBrace, "{".into(), line, col);
+                self.push_jsx_expr();
+                true
+            }
+            _ => {
+                let mut text = String::new();
+                while let Some(c) = self.peek() {
+                    if matches!(c, '<' | '{' | '\n' | '\r') {
+                        break;
+                    }
+                    text.push(c);
+                    self.advance();
+                }
+                let trimmed = text.trim_end();
+                let trailing = String::from(&text[trimmed.len()..]);
+                self.emit(TokenKind::JsxText, String::from(trimmed), line, col);
+                if !trailing.is_empty() {
+                    let ws_col = col + trimmed.chars().count();
+                    self.emit(TokenKind::Whitespace, trailing, line, ws_col);
+                }
+                true
+            }
+        }
+    }
+
+    fn push_jsx_expr(&mut self) {
+        self.jsx.push(JsxMode::Expr {
+            depth: 0,
+            templates: self.template_depth.len(),
+        });
+    }
+
     fn read_comparison(&mut self, line: usize, col: usize) {
         let ch = self.advance().unwrap();
         let mut text = String::new();
@@ -764,6 +1011,12 @@ fn is_ident_continue(ch: char) -> bool {
     ch.is_ascii_alphanumeric() || ch == '_' || ch == '$' || ch > '\x7f'
 }
 
+/// JSX tag and attribute names also allow `-` (`aria-label`), `:`
+/// (namespaces) and `.` (member expressions like `Foo.Bar`).
+fn is_jsx_name_char(ch: char) -> bool {
+    is_ident_continue(ch) || matches!(ch, '-' | ':' | '.')
+}
+
 pub fn tokenize(input: &str) -> Result<Vec<Token>, TokenizeError> {
     Tokenizer::new(input).tokenize()
 }
@@ -934,4 +1187,107 @@ mod tests {
         assert_eq!(tokens[0].line, 1);
         assert_eq!(tokens[2].line, 2);
     }
+
+    #[test]
+    fn jsx_element_with_attributes() {
+        let t = texts(r#"x = <a href="/" {...p} on={f}>hi {name}</a>"#);
+        let t: Vec<&str> = t.iter().map(|s| s.as_str()).collect();
+        assert_eq!(
+            t,
+            vvec![
+                "x",
+                " ",
+                "=",
+                " ",
+                "<a",
+                " ",
+                "href=\"/\"",
+                " ",
+                "{",
+                "...",
+                "p",
+                "}",
+                " ",
+                "on=",
+                "{",
+                "f",
+                "}",
+                ">",
+                "hi",
+                " ",
+                "{",
+                "name",
+                "}",
+                "</a",
+                ">",
+                ""
+            ]
+        );
+        let k = kinds(r#"x = <a href="/">hi</a>"#);
+        assert_eq!(
+            &k[4..],
+            &[
+                TokenKind::JsxTagStart,
+                TokenKind::Whitespace,
+                TokenKind::JsxAttribute,
+                TokenKind::JsxTagEnd,
+                TokenKind::JsxText,
+                TokenKind::JsxTagStart,
+                TokenKind::JsxTagEnd,
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn jsx_fragment_and_self_closing() {
+        let t = texts("return <><br/>a</>");
+        let t: Vec<&str> = t.iter().map(|s| s.as_str()).collect();
+        assert_eq!(
+            t,
+            vvec!["return", " ", "<", ">", "<br", "/>", "a", "</", ">", ""]
+        );
+    }
+
+    #[test]
+    fn jsx_text_keeps_comment_like_content() {
+        let k = kinds("(<p>// not a comment</p>)");
+        assert!(k.contains(&TokenKind::JsxText));
+        assert!(!k.contains(&TokenKind::LineComment));
+    }
+
+    #[test]
+    fn jsx_container_with_template() {
+        let t = texts("(<p>{`a${b}c`}</p>)");
+        let t: Vec<&str> = t.iter().map(|s| s.as_str()).collect();
+        assert_eq!(
+            t,
+            vvec![
+                "(", "<p", ">", "{", "`a${", "b", "}c`", "}", "</p", ">", ")", ""
+            ]
+        );
+    }
+
+    #[test]
+    fn generic_arrow_is_not_jsx() {
+        let k = kinds("f = <T>(x) => x");
+        assert!(!k.contains(&TokenKind::JsxTagStart));
+        let k = kinds("a < b > c");
+        assert!(!k.contains(&TokenKind::JsxTagStart));
+    }
+
+    #[test]
+    fn generic_is_not_jsx_before_longer_closing_tag() {
+        let k = kinds("f = <T>(x) => x;\nconst t = <Table></Table>;");
+        let tags: Vec<usize> = k
+            .iter()
+            .enumerate()
+            .filter(|(_, k)| **k == TokenKind::JsxTagStart)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(tags.len(), 2);
+        let t = texts("f = <T>(x) => x;\nconst t = <Table></Table>;");
+        assert_eq!(t[tags[0]].as_str(), "<Table");
+        assert_eq!(t[tags[1]].as_str(), "</Table");
+    }
 }
@@ -4,6 +4,7 @@ use crate::core::cli::command::{Command, OptionSpec};
 use crate::core::cli::error::CliError;
 use crate::core::cli::parser::ParsedArgs;
 use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::core::volkiwithstds::time::Duration;
 use crate::libs::web::compiler;
 use super::dynamic_runtime::{run_dynamic_runtime, DynamicRuntimeOptions, EmptyRoutesPolicy};
 
@@ -19,7 +20,7 @@ impl Command for WebDevCommand {
     }
 
     fn long_description(&self) -> &str {
-        "Starts a development server that reads and interprets .volki files at runtime without a cargo build step. Pages are parsed and served immediately. Fragment functions are resolved, CSS is generated, metadata is extracted, and client interactivity is compiled to WASM at startup. For production builds, use web:build + web:start."
+        "Starts a development server that reads and interprets .volki files at runtime without a cargo build step. Pages are parsed and served immediately, and edits under app/ are picked up without a restart: the tree is polled every --watch-interval milliseconds (0 disables watching) and open pages reload themselves. Fragment functions are resolved, CSS is generated, metadata is extracted, and client interactivity is compiled to WASM at startup. For production builds, use web:build + web:start."
     }
 
     fn options(&self) -> Vec<OptionSpec> {
@@ -40,6 +41,14 @@ impl Command for WebDevCommand {
             default_value: Some("127.0.0.1"),
             short: None,
         });
+        opts.push(OptionSpec {
+            name: "watch-interval",
+            description: "Milliseconds between file change checks (0 disables)",
+            takes_value: true,
+            required: false,
+            default_value: Some("500"),
+            short: None,
+        });
         opts
     }
 
@@ -55,6 +64,15 @@ impl Command for WebDevCommand {
         let port: u16 = port_str.parse().map_err(|_| {
             CliError::InvalidUsage(String::from("invalid port number"))
         })?;
+        let interval_str = args.get_option("watch-interval").unwrap_or("500");
+        let interval_ms: u64 = interval_str.parse().map_err(|_| {
+            CliError::InvalidUsage(String::from("invalid watch interval"))
+        })?;
+        let watch_interval = if interval_ms == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(interval_ms))
+        };
 
         // Find project root (where volki.toml is)
         let cwd = crate::core::volkiwithstds::env::current_dir().map_err(|e| {
@@ -79,6 +97,8 @@ impl Command for WebDevCommand {
             show_summary: true,
             show_source_dir: false,
             empty_routes: EmptyRoutesPolicy::WarnAndReturn,
+            watch_interval,
+            max_body_size: compiler::read_max_body_size_config(cwd.as_path()),
         })
     }
 }
@@ -108,4 +128,11 @@ mod tests {
         let opts = WebDevCommand.options();
         assert!(opts.iter().any(|o| o.name == "host"));
     }
+
+    #[test]
+    fn test_dev_has_watch_interval_option() {
+        let opts = WebDevCommand.options();
+        let opt = opts.iter().find(|o| o.name == "watch-interval").unwrap();
+        assert_eq!(opt.default_value, Some("500"));
+    }
 }
@@ -2,11 +2,15 @@
 
 use crate::core::cli::error::CliError;
 use crate::core::cli::style;
-use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::fs;
 use crate::core::volkiwithstds::path::{Path, PathBuf};
-use crate::libs::web::interpreter::scanner::{DynamicRouteKind, discover_dynamic_routes};
+use crate::core::volkiwithstds::sync::{Arc, Mutex};
+use crate::core::volkiwithstds::time::Duration;
+use crate::libs::web::interpreter::scanner::{DynamicRoute, DynamicRouteKind, discover_dynamic_routes};
+use crate::libs::web::router::{Router, RouterSlot};
 use crate::libs::web::server::Server;
+use super::hot_reload;
 use crate::veprintln;
 
 pub enum EmptyRoutesPolicy<'a> {
@@ -24,6 +28,10 @@ pub struct DynamicRuntimeOptions<'a> {
     pub show_summary: bool,
     pub show_source_dir: bool,
     pub empty_routes: EmptyRoutesPolicy<'a>,
+    /// Poll `app/` at this interval and hot-swap rebuilt routes; `None` disables.
+    pub watch_interval: Option<Duration>,
+    /// Request body limit in bytes; `None` keeps the server default.
+    pub max_body_size: Option<usize>,
 }
 
 pub fn run_dynamic_runtime(opts: DynamicRuntimeOptions<'_>) -> Result<(), CliError> {
@@ -78,28 +86,23 @@ pub fn run_dynamic_runtime(opts: DynamicRuntimeOptions<'_>) -> Result<(), CliErr
         .host(opts.host)
         .port(opts.port)
         .public_dir(runtime_public_dir.as_path().as_str());
+    if let Some(bytes) = opts.max_body_size {
+        server = server.max_body_size(bytes);
+    }
 
-    let mut page_count: usize = 0;
-    let mut has_not_found = false;
-
-    for route in routes {
-        match route.kind {
-            DynamicRouteKind::Page => {
-                if opts.show_routes {
-                    veprintln!("  {} {}", style::green("page"), route.url_path);
-                }
-                server = server.dynamic_page(route.url_path.as_str(), route.data);
-                page_count += 1;
-            }
-            DynamicRouteKind::NotFound => {
-                if opts.show_routes {
-                    veprintln!("  {} 404 handler", style::cyan("page"));
-                }
-                server = server.not_found_dynamic_page(route.data);
-                has_not_found = true;
-            }
-        }
+    let mut router = Router::new();
+    let (page_count, has_not_found) = register_routes(&mut router, routes, opts.show_routes);
+
+    if let Some(interval) = opts.watch_interval {
+        router.use_middleware(hot_reload::live_reload);
+        let slot: RouterSlot = Arc::new(Mutex::new(None));
+        let source_dir = opts.source_dir.to_path_buf();
+        hot_reload::watch(source_dir.join("app"), interval, slot.clone(), move || {
+            rebuild_router(source_dir.as_path())
+        });
+        server = server.reload_from(slot);
     }
+    server = server.router(router);
 
     veprintln!();
     veprintln!("  {}", opts.title);
@@ -118,13 +121,70 @@ pub fn run_dynamic_runtime(opts: DynamicRuntimeOptions<'_>) -> Result<(), CliErr
         );
         veprintln!("  {}", style::dim(summary.as_str()));
         veprintln!("  {}", style::dim("note: complex expressions may show placeholders"));
-        veprintln!("  {}", style::dim("      restart to pick up file changes"));
+        if opts.watch_interval.is_some() {
+            veprintln!("  {}", style::dim("      watching app/ for changes"));
+        } else {
+            veprintln!("  {}", style::dim("      restart to pick up file changes"));
+        }
         veprintln!();
     }
 
     server.listen();
 }
 
+/// Registers discovered routes on `router`, returning the page count and
+/// whether any 404 handler was found.
+fn register_routes(router: &mut Router, routes: Vec<DynamicRoute>, show_routes: bool) -> (usize, bool) {
+    let mut page_count: usize = 0;
+    let mut has_not_found = false;
+
+    for route in routes {
+        match route.kind {
+            DynamicRouteKind::Page => {
+                if show_routes {
+                    veprintln!("  {} {}", style::green("page"), route.url_path);
+                }
+                router.dynamic_page_route(route.url_path.as_str(), route.data);
+                page_count += 1;
+            }
+            DynamicRouteKind::NotFound => {
+                if route.url_path.as_str() == "/" {
+                    if show_routes {
+                        veprintln!("  {} 404 handler", style::cyan("page"));
+                    }
+                    router.not_found_dynamic_page(route.data);
+                } else {
+                    if show_routes {
+                        veprintln!("  {} 404 handler for {}", style::cyan("page"), route.url_path);
+                    }
+                    router.not_found_dynamic_page_at(route.url_path.as_str(), route.data);
+                }
+                has_not_found = true;
+            }
+        }
+    }
+
+    (page_count, has_not_found)
+}
+
+/// Re-scans `source_dir` after a change. On error the message is printed and
+/// the server keeps its current routes.
+fn rebuild_router(source_dir: &Path) -> Option<Router> {
+    match discover_dynamic_routes(source_dir) {
+        Ok(routes) => {
+            let mut router = Router::new();
+            let (page_count, _) = register_routes(&mut router, routes, false);
+            router.use_middleware(hot_reload::live_reload);
+            veprintln!("  {} reloaded {} page(s)", style::green("dev"), page_count);
+            Some(router)
+        }
+        Err(e) => {
+            veprintln!("  {} reload failed: {}", style::red("error"), e);
+            None
+        }
+    }
+}
+
 fn copy_tree(src: &Path, dst: &Path) -> Result<(), String> {
     fs::create_dir_all(dst).map_err(|e| crate::vformat!("create_dir_all {}: {}", dst, e))?;
 
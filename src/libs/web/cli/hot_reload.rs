@@ -0,0 +1,208 @@
+//! Dev-server hot reload — watches `app/` and swaps in a rebuilt router.
+//!
+//! There is no inotify/kqueue wrapper, so a background thread polls
+//! `fs::metadata` for every file under `app/` and folds path, size and mtime
+//! into one fingerprint. A change is applied only after the fingerprint has
+//! held for a whole interval, so an editor's burst of writes rebuilds once.
+//! The event loop picks the new router out of a [`RouterSlot`] between polls;
+//! requests already dispatched keep their `Arc<DynamicPageData>` alive.
+
+use crate::core::volkiwithstds::fs;
+use crate::core::volkiwithstds::path::{Path, PathBuf};
+use crate::core::volkiwithstds::thread;
+use crate::core::volkiwithstds::time::Duration;
+use crate::libs::web::http::request::Request;
+use crate::libs::web::http::response::Response;
+use crate::libs::web::router::middleware::Next;
+use crate::libs::web::router::{Router, RouterSlot};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Endpoint the injected dev script polls for the current reload version.
+pub const RELOAD_PATH: &str = "/__volki/reload";
+
+/// Bumped after every successful rebuild; open pages reload when it changes.
+static VERSION: AtomicUsize = AtomicUsize::new(0);
+
+const RELOAD_SCRIPT: &str = "<script>(function(){var v=null;setInterval(function(){\
+fetch(\"/__volki/reload\").then(function(r){return r.text()}).then(function(t){\
+if(v===null){v=t}else if(t!==v){location.reload()}}).catch(function(){})},1000)})();</script>";
+
+/// Fingerprint of every file under `dir`. Order-independent, so it does not
+/// depend on the order `read_dir` yields entries in.
+pub fn fingerprint(dir: &Path) -> u64 {
+    let mut sum: u64 = 0;
+    add_fingerprint(dir, &mut sum);
+    sum
+}
+
+fn add_fingerprint(dir: &Path, sum: &mut u64) {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        match entry.file_type() {
+            fs::FileType::Directory => add_fingerprint(entry.path(), sum),
+            fs::FileType::File => {
+                if let Ok(meta) = fs::metadata(entry.path()) {
+                    let mut h = fnv(0xcbf29ce484222325, entry.path().as_str().as_bytes());
+                    h = fnv(h, &meta.len().to_le_bytes());
+                    h = fnv(h, &meta.modified().as_nanos().to_le_bytes());
+                    *sum = sum.wrapping_add(h);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn fnv(mut h: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h
+}
+
+/// Tracks the applied fingerprint and holds back a change until it settles.
+pub struct Debounce {
+    applied: u64,
+    pending: Option<u64>,
+}
+
+impl Debounce {
+    pub fn new(initial: u64) -> Self {
+        Self {
+            applied: initial,
+            pending: None,
+        }
+    }
+
+    /// Feeds one poll's fingerprint. Returns true when a change has been
+    /// seen unchanged on two consecutive polls and should be applied.
+    pub fn observe(&mut self, fingerprint: u64) -> bool {
+        if fingerprint == self.applied {
+            self.pending = None;
+            return false;
+        }
+        if self.pending == Some(fingerprint) {
+            self.applied = fingerprint;
+            self.pending = None;
+            return true;
+        }
+        self.pending = Some(fingerprint);
+        false
+    }
+}
+
+/// Spawns the watcher thread. `rebuild` runs after each settled change; a
+/// `None` (e.g. a parse error) keeps the current router in place.
+pub fn watch<F>(dir: PathBuf, interval: Duration, slot: RouterSlot, rebuild: F)
+where
+    F: Fn() -> Option<Router> + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut debounce = Debounce::new(fingerprint(dir.as_path()));
+        loop {
+            thread::sleep(interval);
+            if !debounce.observe(fingerprint(dir.as_path())) {
+                continue;
+            }
+            if let Some(router) = rebuild() {
+                *slot.lock() = Some(router);
+                VERSION.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+}
+
+/// Middleware that answers [`RELOAD_PATH`] and injects the polling script
+/// into HTML responses.
+pub fn live_reload(req: &Request, next: Next<'_>) -> Response {
+    if req.route_path.as_str() == RELOAD_PATH {
+        let version = crate::vformat!("{}", VERSION.load(Ordering::SeqCst));
+        return Response::ok()
+            .text(version.as_str())
+            .header("Cache-Control", "no-store");
+    }
+    inject_reload_script(next(req))
+}
+
+fn inject_reload_script(mut resp: Response) -> Response {
+    let is_html = resp
+        .headers
+        .get("content-type")
+        .is_some_and(|ct| ct.starts_with("text/html"));
+    if !is_html {
+        return resp;
+    }
+    let needle = b"</body>";
+    let at = resp
+        .body
+        .windows(needle.len())
+        .rposition(|w| w == needle)
+        .unwrap_or(resp.body.len());
+    let mut body = crate::core::volkiwithstds::collections::Vec::with_capacity(
+        resp.body.len() + RELOAD_SCRIPT.len(),
+    );
+    body.extend_from_slice(&resp.body[..at]);
+    body.extend_from_slice(RELOAD_SCRIPT.as_bytes());
+    body.extend_from_slice(&resp.body[at..]);
+    resp.body = body;
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debounce_waits_for_stable_fingerprint() {
+        let mut d = Debounce::new(1);
+        assert!(!d.observe(1));
+        assert!(!d.observe(2));
+        // Still being written — restart the wait
+        assert!(!d.observe(3));
+        assert!(d.observe(3));
+        assert!(!d.observe(3));
+    }
+
+    #[test]
+    fn test_debounce_ignores_revert() {
+        let mut d = Debounce::new(1);
+        assert!(!d.observe(2));
+        assert!(!d.observe(1));
+        assert!(!d.observe(1));
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_file_changes() {
+        let dir = crate::core::volkiwithstds::env::temp_dir().join("volki_hot_reload_test");
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.join("posts").as_path()).unwrap();
+        fs::write_str(dir.join("page.volki").as_path(), "a").unwrap();
+
+        let before = fingerprint(dir.as_path());
+        assert_eq!(fingerprint(dir.as_path()), before);
+
+        fs::write_str(dir.join("posts/page.volki").as_path(), "b").unwrap();
+        let added = fingerprint(dir.as_path());
+        assert_ne!(added, before);
+
+        fs::write_str(dir.join("page.volki").as_path(), "abc").unwrap();
+        assert_ne!(fingerprint(dir.as_path()), added);
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+
+    #[test]
+    fn test_inject_reload_script_into_html() {
+        let resp = inject_reload_script(Response::ok().html("<html><body><p>hi</p></body></html>"));
+        let body = core::str::from_utf8(&resp.body).unwrap();
+        assert!(body.contains("<p>hi</p><script>"));
+        assert!(body.ends_with("</script></body></html>"));
+
+        let resp = inject_reload_script(Response::ok().text("plain"));
+        assert_eq!(resp.body.as_slice(), b"plain");
+    }
+}
@@ -1,6 +1,7 @@
 pub mod build_cmd;
 pub mod dev_cmd;
 pub mod dynamic_runtime;
+pub mod hot_reload;
 pub mod hub_cmd;
 pub mod start_cmd;
 
@@ -59,7 +59,7 @@ impl Command for WebStartCommand {
         });
         opts.push(OptionSpec {
             name: "max-body-size",
-            description: "Max request body size in bytes",
+            description: "Max request body size in bytes (overrides [web].max_body_size)",
             takes_value: true,
             required: false,
             default_value: None,
@@ -107,6 +107,11 @@ impl Command for WebStartCommand {
                 CliError::InvalidUsage(String::from("invalid --max-body-size value"))
             })?;
             server = server.max_body_size(bytes);
+        } else if let Some(bytes) = crate::core::volkiwithstds::env::current_dir()
+            .ok()
+            .and_then(|cwd| crate::libs::web::compiler::read_max_body_size_config(cwd.as_path()))
+        {
+            server = server.max_body_size(bytes);
         }
         if let Some(timeout_str) = args.get_option("read-timeout") {
             let secs: u64 = timeout_str.parse().map_err(|_| {
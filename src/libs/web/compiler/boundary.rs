@@ -1,8 +1,11 @@
 //! Server/client boundary validation — ensures APIs are used in the correct
 //! function context (server vs client vs component).
+//!
+//! `-> Shared` helpers are compiled on both sides, so they may use neither
+//! client-only nor server-only APIs.
 
 use crate::core::volkiwithstds::collections::{String, Vec};
-use super::scanner::{RsxFunction, RsxReturnType};
+use super::scanner::{self, RsxFunction, RsxReturnType};
 
 /// A single boundary violation found during validation.
 pub struct BoundaryViolation {
@@ -17,6 +20,10 @@ pub struct BoundaryViolation {
 
 // ── Pattern lists ──────────────────────────────────────────────────────
 
+/// `el.remove()` — only client-only when `el` is a DOM handle, so that
+/// `map.remove()` and friends stay usable on the server.
+const DOM_REMOVE: &str = ".remove()";
+
 /// Client/browser-only APIs — forbidden in `-> Html` and `-> Fragment`.
 const CLIENT_ONLY: &[(&str, &str)] = &[
     ("dom::query(", "dom::query"),
@@ -43,8 +50,19 @@ const CLIENT_ONLY: &[(&str, &str)] = &[
     (".toggle_class(", ".toggle_class"),
     (".get_attr(", ".get_attr"),
     (".remove_attr(", ".remove_attr"),
+    (".append_html(", ".append_html"),
+    (DOM_REMOVE, ".remove"),
     ("dom::query_all_count(", "dom::query_all_count"),
     ("dom::query_all_get(", "dom::query_all_get"),
+    // localStorage
+    ("storage::get(", "storage::get"),
+    ("storage::set(", "storage::set"),
+    ("storage::remove(", "storage::remove"),
+    // HTTP
+    ("net::fetch(", "net::fetch"),
+    // Event control
+    ("event::prevent_default(", "event::prevent_default"),
+    ("event::stop_propagation(", "event::stop_propagation"),
     // Ref operations
     ("use_ref(", "use_ref"),
     ("use_ref_el(", "use_ref_el"),
@@ -77,6 +95,13 @@ const COMPONENT_ONLY: &[(&str, &str)] = &[
     ("use_memo_f32(", "use_memo_f32"),
 ];
 
+/// Event-handler APIs — forbidden in `-> Component`, which renders outside
+/// of any DOM event.
+const HANDLER_ONLY: &[(&str, &str)] = &[
+    ("event::prevent_default(", "event::prevent_default"),
+    ("event::stop_propagation(", "event::stop_propagation"),
+];
+
 // ── Public API ─────────────────────────────────────────────────────────
 
 /// Validate that no function uses APIs from the wrong side of the boundary.
@@ -104,6 +129,21 @@ pub fn validate_boundaries(
                 );
             }
             RsxReturnType::Client => {
+                let ret = &source[func.return_type_span.0..func.return_type_span.1];
+                if ret != "Client" && !func.returns_string {
+                    let (line, col) = line_col_at(source, func.return_type_span.0);
+                    let (message, help) =
+                        build_message(ret, "Client", fn_name, &ViolationKind::UnsupportedClientReturn);
+                    violations.push(BoundaryViolation {
+                        line,
+                        col,
+                        pattern: String::from(ret),
+                        fn_type: String::from("Client"),
+                        fn_name: fn_name.map(String::from),
+                        message,
+                        help,
+                    });
+                }
                 scan_body(
                     body, SERVER_ONLY, func.body_span.0, source,
                     "Client", fn_name, ViolationKind::ServerInClient,
@@ -121,6 +161,23 @@ pub fn validate_boundaries(
                     "Component", fn_name, ViolationKind::ServerInClient,
                     &mut violations,
                 );
+                scan_body(
+                    body, HANDLER_ONLY, func.body_span.0, source,
+                    "Component", fn_name, ViolationKind::HandlerOnlyInComponent,
+                    &mut violations,
+                );
+            }
+            RsxReturnType::Shared => {
+                scan_body(
+                    body, CLIENT_ONLY, func.body_span.0, source,
+                    "Shared", fn_name, ViolationKind::ClientInShared,
+                    &mut violations,
+                );
+                scan_body(
+                    body, SERVER_ONLY, func.body_span.0, source,
+                    "Shared", fn_name, ViolationKind::ServerInShared,
+                    &mut violations,
+                );
             }
         }
     }
@@ -208,7 +265,11 @@ enum ViolationKind {
     ClientInServer,
     ServerInClient,
     ComponentOnlyInClient,
+    HandlerOnlyInComponent,
+    ClientInShared,
+    ServerInShared,
     TopLevelForbidden,
+    UnsupportedClientReturn,
 }
 
 fn scan_body(
@@ -246,6 +307,9 @@ fn scan_body(
         for &(pattern, display) in patterns {
             let pat = pattern.as_bytes();
             if i + pat.len() <= len && &bytes[i..i + pat.len()] == pat {
+                if pattern == DOM_REMOVE && !scanner::is_dom_handle_receiver(body, i) {
+                    continue;
+                }
                 let abs_offset = body_offset + i;
                 let (line, col) = line_col_at(source, abs_offset);
 
@@ -317,6 +381,45 @@ fn build_message(
             );
             (msg, help)
         }
+        ViolationKind::HandlerOnlyInComponent => {
+            let msg = crate::vformat!(
+                "`{}` can only be used in `-> Client` event handlers, not in component{}",
+                display, name_part
+            );
+            let help = String::from(
+                "Components render outside of any DOM event, so there is no event to act on.\n           Call it from the `-> Client` function bound with `on*={handler}`."
+            );
+            (msg, help)
+        }
+        ViolationKind::ClientInShared | ViolationKind::ServerInShared => {
+            let side = match kind {
+                ViolationKind::ClientInShared => "client-only",
+                _ => "server-only",
+            };
+            let msg = crate::vformat!(
+                "{} API `{}` used in shared function{} (-> Shared)",
+                side, display, name_part
+            );
+            let help = crate::vformat!(
+                "Shared functions are compiled into both the server and the client module,\n           so they may only use plain Rust. Move `{}` to a {} function.",
+                display,
+                match kind {
+                    ViolationKind::ClientInShared => "`-> Client` or `-> Component`",
+                    _ => "server (-> Html, -> Fragment)",
+                }
+            );
+            (msg, help)
+        }
+        ViolationKind::UnsupportedClientReturn => {
+            let msg = crate::vformat!(
+                "client function{} returns unsupported type `{}`",
+                name_part, display
+            );
+            let help = String::from(
+                "Client functions may return nothing or `Client<&str>`.\n           The WASM module has no allocator, so an owned `String` cannot be handed to JS;\n           return a string literal or a slice of a static buffer instead."
+            );
+            (msg, help)
+        }
         ViolationKind::TopLevelForbidden => {
             let msg = crate::vformat!("`{}` cannot be used at the top level of a .volki file", display);
             let help = crate::vformat!(
@@ -403,6 +506,80 @@ pub fn page(_req: &Request) -> Html {
         assert!(violations[0].message.as_str().contains("Html"));
     }
 
+    #[test]
+    fn test_dom_mutation_api_in_html_detected() {
+        let source = r##"
+pub fn page(_req: &Request) -> Html {
+    let row = dom::create("tr");
+    row.append_html("<b>x</b>");
+    row.remove();
+}
+
+pub fn delete_row() -> Client {
+    let row = dom::query("#row");
+    row.append_html("<b>x</b>");
+    row.remove();
+}
+"##;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[0].pattern.as_str(), "dom::create");
+        assert_eq!(violations[1].pattern.as_str(), ".append_html");
+        assert_eq!(violations[2].pattern.as_str(), ".remove");
+        assert_eq!(violations[2].fn_name.as_ref().unwrap().as_str(), "page");
+    }
+
+    #[test]
+    fn test_non_dom_remove_in_html_allowed() {
+        let source = r##"
+pub fn page(_req: &Request) -> Html {
+    let mut cache = HashMap::new();
+    cache.entry("k").or_insert(1);
+    entry.remove();
+    cache.remove();
+}
+"##;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_storage_api_in_html_detected() {
+        let source = r##"
+pub fn page(_req: &Request) -> Html {
+    storage::set("theme", "dark");
+}
+
+pub fn remember() -> Client {
+    storage::set("theme", "dark");
+    let theme = storage::get("theme");
+    storage::remove("theme");
+}
+"##;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pattern.as_str(), "storage::set");
+        assert_eq!(violations[0].fn_name.as_ref().unwrap().as_str(), "page");
+        assert!(violations[0].message.as_str().contains("client-only API"));
+    }
+
+    #[test]
+    fn test_net_fetch_in_fragment_detected() {
+        let source = r##"
+pub fn sidebar() -> Fragment {
+    net::fetch("/api/menu", "on_menu");
+}
+"##;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pattern.as_str(), "net::fetch");
+        assert!(violations[0].message.as_str().contains("client-only API"));
+    }
+
     #[test]
     fn test_use_state_in_html_detected() {
         let source = r#"
@@ -447,6 +624,28 @@ pub fn on_click() -> Client {
         assert!(violations[0].message.as_str().contains("not `-> Client`"));
     }
 
+    #[test]
+    fn test_client_string_return_rejected() {
+        let source = r#"
+pub fn label(n: i32) -> Client<String> {
+    let mut s = String::new();
+    s.push_str(if n > 1 { "items" } else { "item" });
+    s
+}
+
+pub fn status() -> Client<&str> {
+    "ok"
+}
+"#;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pattern.as_str(), "Client<String>");
+        assert_eq!(violations[0].line, 2);
+        assert!(violations[0].message.as_str().contains("function `label` returns unsupported type"));
+        assert!(violations[0].help.as_str().contains("Client<&str>"));
+    }
+
     #[test]
     fn test_valid_html_no_violations() {
         let source = r#"
@@ -537,6 +736,32 @@ pub fn counter() -> Component {
         assert!(violations[0].message.as_str().contains("Component"));
     }
 
+    #[test]
+    fn test_event_api_only_in_client() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    event::prevent_default();
+}
+
+pub fn counter() -> Component {
+    event::stop_propagation();
+}
+
+pub fn on_link(_e: Event) -> Client {
+    event::prevent_default();
+    event::stop_propagation();
+}
+"#;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].pattern.as_str(), "event::prevent_default");
+        assert!(violations[0].message.as_str().contains("client-only API"));
+        assert_eq!(violations[1].pattern.as_str(), "event::stop_propagation");
+        assert!(violations[1].message.as_str().contains("event handlers"));
+        assert_eq!(violations[1].fn_name.as_ref().unwrap().as_str(), "counter");
+    }
+
     // ── Top-level validation tests ──────────────────────────────────
 
     #[test]
@@ -686,4 +911,34 @@ let count = use_state(0_i32);
         assert!(violations[0].help.as_str().contains("runtime API"));
         assert!(violations[0].help.as_str().contains("Component"));
     }
+
+    #[test]
+    fn test_pure_shared_function_allowed() {
+        let source = r##"
+fn format_price(cents: i64) -> Shared<i64> {
+    // dom::query("#x") in a comment is fine
+    cents / 100
+}
+"##;
+        let fns = scan_functions(source);
+        assert!(validate_boundaries(&fns, source).is_empty());
+    }
+
+    #[test]
+    fn test_client_and_server_api_in_shared_detected() {
+        let source = r##"
+fn helper() -> Shared {
+    let el = dom::query("#btn");
+    let r = Response::ok();
+}
+"##;
+        let fns = scan_functions(source);
+        let violations = validate_boundaries(&fns, source);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].pattern.as_str(), "dom::query");
+        assert!(violations[0].message.as_str().contains("client-only API `dom::query` used in shared function `helper`"));
+        assert_eq!(violations[1].pattern.as_str(), "Response::");
+        assert!(violations[1].message.as_str().contains("server-only API"));
+        assert!(violations[1].help.as_str().contains("both the server and the client"));
+    }
 }
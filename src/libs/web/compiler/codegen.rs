@@ -1,6 +1,6 @@
 //! Code Generator — transforms AST nodes into Rust source code.
 
-use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::collections::{String, Vec};
 
 use super::parser::{RsxAttr, RsxAttrValue, RsxNode};
 
@@ -18,13 +18,11 @@ pub fn generate_html_fn_with_client(nodes: &[RsxNode], glue_url: Option<&str>) -
     for node in nodes {
         match node {
             // Special <Style>{expr}</Style> element
-            RsxNode::Element { tag, children, self_closing: false, .. }
+            RsxNode::Element { tag, attrs, children, self_closing: false }
                 if is_special_tag(tag.as_str(), "Style") =>
             {
                 if let Some(RsxNode::Expr(expr)) = children.first() {
-                    out.push_str("        .inline_style(");
-                    out.push_str(expr.as_str());
-                    out.push_str(")\n");
+                    push_inline_style(attrs, expr.as_str(), &mut out);
                 }
             }
             // Special <Head>...</Head> element
@@ -221,6 +219,48 @@ fn find_attr<'a>(attrs: &'a [RsxAttr], name: &str) -> Option<&'a String> {
     None
 }
 
+/// Close the `({ let mut __el = ...` block opened in `generate_element` with
+/// one loop per `{...expr}`, skipping keys that are set explicitly.
+fn generate_spreads(attrs: &[RsxAttr], out: &mut String) {
+    let mut names: Vec<&str> = Vec::new();
+    for attr in attrs {
+        let name = match attr.value {
+            RsxAttrValue::Spread(_) => continue,
+            RsxAttrValue::ClassToggle(_) => "class",
+            _ => attr.name.as_str(),
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    let mut explicit = String::new();
+    for name in names {
+        if !explicit.is_empty() {
+            explicit.push_str(" | ");
+        }
+        explicit.push('"');
+        explicit.push_str(name);
+        explicit.push('"');
+    }
+    out.push_str("; ");
+    for attr in attrs {
+        let RsxAttrValue::Spread(expr) = &attr.value else {
+            continue;
+        };
+        out.push_str("for (__k, __v) in ");
+        out.push_str(expr.as_str());
+        out.push_str(" { let (__k, __v): (&str, &str) = (__k.as_ref(), __v.as_ref()); ");
+        if explicit.is_empty() {
+            out.push_str("__el = __el.attr(__k, __v); } ");
+        } else {
+            out.push_str("if !matches!(__k, ");
+            out.push_str(explicit.as_str());
+            out.push_str(") { __el = __el.attr(__k, __v); } } ");
+        }
+    }
+    out.push_str("__el })");
+}
+
 /// Generate Rust code for a single RSX node.
 fn generate_node(node: &RsxNode, out: &mut String, _depth: usize) {
     match node {
@@ -300,6 +340,14 @@ fn generate_element(
     _self_closing: bool,
     out: &mut String,
 ) {
+    // Spreads are applied after every named attribute and skip any key that
+    // is set explicitly, so `<a href="/x" {...extra}>` keeps `href="/x"`
+    // regardless of where the spread appears.
+    let has_spread = attrs.iter().any(|a| matches!(a.value, RsxAttrValue::Spread(_)));
+    if has_spread {
+        out.push_str("({ let mut __el = ");
+    }
+
     // Element constructor
     out.push_str(tag);
     out.push_str("()");
@@ -337,9 +385,25 @@ fn generate_element(
                     out.push_str("\")");
                 }
             }
+            RsxAttrValue::Spread(_) | RsxAttrValue::ClassToggle(_) => {}
         }
     }
 
+    // Class directives append after the static `class`, wherever they appear
+    for attr in attrs {
+        if let (Some(class), RsxAttrValue::ClassToggle(cond)) = (attr.toggled_class(), &attr.value) {
+            out.push_str(".class_if(\"");
+            out.push_str(class);
+            out.push_str("\", ");
+            out.push_str(cond.as_str());
+            out.push(')');
+        }
+    }
+
+    if has_spread {
+        generate_spreads(attrs, out);
+    }
+
     // Children
     for child in children {
         match child {
@@ -384,6 +448,26 @@ fn is_event_attr(name: &str) -> bool {
     name.starts_with("on") && name.len() > 2
 }
 
+/// `.inline_style(expr)` for a `<Style>` element. A `<Style scoped>` that
+/// the compiler has stamped carries the scope attribute as its value, and
+/// the CSS is rewritten against it when the page renders.
+fn push_inline_style(attrs: &[RsxAttr], expr: &str, out: &mut String) {
+    match find_attr(attrs, "scoped") {
+        Some(attr) if !attr.is_empty() => {
+            out.push_str("        .inline_style(crate::libs::web::volkistyle::scope::scope_css(&(");
+            out.push_str(expr);
+            out.push_str("), \"");
+            out.push_str(attr.as_str());
+            out.push_str("\").as_str())\n");
+        }
+        _ => {
+            out.push_str("        .inline_style(");
+            out.push_str(expr);
+            out.push_str(")\n");
+        }
+    }
+}
+
 /// Generate code for a `-> Html` function body with utility CSS injected.
 /// Collects all utility classes, generates CSS, and injects `.inline_style("...")`.
 pub fn generate_html_fn_styled(nodes: &[RsxNode], css: &str, glue_url: Option<&str>) -> String {
@@ -408,13 +492,11 @@ pub fn generate_html_fn_styled(nodes: &[RsxNode], css: &str, glue_url: Option<&s
     for node in nodes {
         match node {
             // Special <Style>{expr}</Style> element
-            RsxNode::Element { tag, children, self_closing: false, .. }
+            RsxNode::Element { tag, attrs, children, self_closing: false }
                 if is_special_tag(tag.as_str(), "Style") =>
             {
                 if let Some(RsxNode::Expr(expr)) = children.first() {
-                    out.push_str("        .inline_style(");
-                    out.push_str(expr.as_str());
-                    out.push_str(")\n");
+                    push_inline_style(attrs, expr.as_str(), &mut out);
                 }
             }
             // Special <Head>...</Head> element
@@ -913,4 +995,64 @@ mod tests {
         assert!(code.contains(".inline_style(\".flex{display:flex;}\")"));
         assert!(code.contains("div().class(\"flex\").into_node()"));
     }
+
+    #[test]
+    fn test_codegen_attr_spread_explicit_wins() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("a"),
+            attrs: vvec![
+                RsxAttr { name: s(""), value: RsxAttrValue::Spread(s("extra")) },
+                RsxAttr { name: s("href"), value: RsxAttrValue::Literal(s("/x")) },
+            ],
+            children: vvec![RsxNode::Text(s("go"))],
+            self_closing: false,
+        }];
+        let code = generate_fragment_fn(&nodes);
+        assert!(code.contains("({ let mut __el = a().attr(\"href\", \"/x\"); for (__k, __v) in extra {"));
+        assert!(code.contains("if !matches!(__k, \"href\") { __el = __el.attr(__k, __v); }"));
+        assert!(code.contains("__el }).text(\"go\").into_node()"));
+    }
+
+    #[test]
+    fn test_codegen_attr_spread_only() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("br"),
+            attrs: vvec![RsxAttr { name: s(""), value: RsxAttrValue::Spread(s("&attrs")) }],
+            children: empty_nodes(),
+            self_closing: true,
+        }];
+        let code = generate_fragment_fn(&nodes);
+        assert!(code.contains("for (__k, __v) in &attrs {"));
+        assert!(code.contains("__el = __el.attr(__k, __v); } __el }).into_node()"));
+        assert!(!code.contains("matches!"));
+    }
+
+    #[test]
+    fn test_codegen_class_directive() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("li"),
+            attrs: vvec![
+                RsxAttr { name: s("class:active"), value: RsxAttrValue::ClassToggle(s("is_active")) },
+                RsxAttr { name: s("class"), value: RsxAttrValue::Literal(s("tab")) },
+            ],
+            children: empty_nodes(),
+            self_closing: false,
+        }];
+        let code = generate_fragment_fn(&nodes);
+        assert!(code.contains("li().class(\"tab\").class_if(\"active\", is_active).into_node()"));
+    }
+
+    #[test]
+    fn test_codegen_fragment_shorthand_has_no_wrapper() {
+        let file = crate::core::volkiwithstds::path::PathBuf::from("<test>");
+        let tokens = crate::libs::web::compiler::tokenizer::tokenize(
+            r#"<dl><><dt>"k"</dt><dd>"v"</dd></></dl>"#,
+            file.clone(),
+        )
+        .unwrap();
+        let nodes = crate::libs::web::compiler::parser::parse(&tokens, file).unwrap();
+        let code = generate_fragment_fn(&nodes);
+        assert!(code.contains("dl().child(dt().text(\"k\").into_node()).child(dd().text(\"v\").into_node()).into_node()"));
+        assert!(!code.contains("div()"));
+    }
 }
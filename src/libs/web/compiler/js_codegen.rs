@@ -8,8 +8,8 @@
 //! 5. Registers per-function handlers and auto-binds `data-volki-on*` attributes
 
 use crate::core::volkiwithstds::collections::{String, Vec};
-use super::scanner::{RsxFunction, FnParam};
-use crate::libs::web::wasm::types::{WasmAbi, rust_type_to_wasm};
+use super::scanner::{self, RsxFunction, FnParam};
+use crate::libs::web::wasm::types::{WasmAbi, WasmType, rust_type_to_wasm};
 
 /// Generate the complete JS glue file.
 ///
@@ -62,6 +62,16 @@ pub fn generate_js_glue(
     out.push_str("  return __decoder.decode(new Uint8Array(__wasm.exports.memory.buffer, ptr, len));\n");
     out.push_str("}\n\n");
 
+    if all_fns.iter().any(|f| source[f.body_span.0..f.body_span.1].contains("storage::get(")) {
+        out.push_str("function __storage_read(key) {\n");
+        out.push_str("  try {\n");
+        out.push_str("    return window.localStorage.getItem(key) ?? '';\n");
+        out.push_str("  } catch (_) {\n");
+        out.push_str("    return '';\n");
+        out.push_str("  }\n");
+        out.push_str("}\n\n");
+    }
+
     // Component state infrastructure (emitted if any Component functions exist)
     if has_components {
         out.push_str("const __components = new Map();\n");
@@ -69,8 +79,31 @@ pub fn generate_js_glue(
         out.push_str("let __rerender_scheduled = false;\n");
         out.push_str("let __rerender_queue = new Set();\n\n");
 
-        out.push_str("function __register_component(id, name, renderFn) {\n");
-        out.push_str("  __components.set(id, { name, slots: [], renderFn, mounted: false });\n");
+        out.push_str("function __register_component(id, name, renderFn, props) {\n");
+        out.push_str("  __components.set(id, { name, slots: [], renderFn, props: props || [], mounted: false });\n");
+        out.push_str("}\n\n");
+
+        // Props travel as data-volki-prop-<name> attributes on the mount
+        // point and are passed to the render export in declaration order.
+        // Call after __volki_dealloc(): string props are copied into WASM memory.
+        out.push_str("function __component_args(id) {\n");
+        out.push_str("  const comp = __components.get(id);\n");
+        out.push_str("  if (!comp || comp.props.length === 0) return [];\n");
+        out.push_str("  const el = document.querySelector(`[data-volki-component=\"${comp.name}\"]`);\n");
+        out.push_str("  const args = [];\n");
+        out.push_str("  for (const [name, kind] of comp.props) {\n");
+        out.push_str("    const raw = el ? el.getAttribute(`data-volki-prop-${name}`) : null;\n");
+        out.push_str("    if (kind === \"string\") {\n");
+        out.push_str("      args.push(...__pass_string(raw ?? \"\"));\n");
+        out.push_str("    } else if (kind === \"bool\") {\n");
+        out.push_str("      args.push(raw === \"true\" || raw === \"1\" ? 1 : 0);\n");
+        out.push_str("    } else {\n");
+        out.push_str("      const n = Number(raw ?? 0);\n");
+        out.push_str("      const v = Number.isFinite(n) ? n : 0;\n");
+        out.push_str("      args.push(kind === \"bigint\" ? BigInt(Math.trunc(v)) : v);\n");
+        out.push_str("    }\n");
+        out.push_str("  }\n");
+        out.push_str("  return args;\n");
         out.push_str("}\n\n");
 
         out.push_str("function __schedule_rerender(comp_id) {\n");
@@ -85,7 +118,7 @@ pub fn generate_js_glue(
         out.push_str("        const comp = __components.get(id);\n");
         out.push_str("        if (comp) {\n");
         out.push_str("          __wasm.exports.__volki_dealloc();\n");
-        out.push_str("          __wasm.exports[comp.renderFn]();\n");
+        out.push_str("          __wasm.exports[comp.renderFn](...__component_args(id));\n");
         out.push_str("          __run_effects(id);\n");
         out.push_str("        }\n");
         out.push_str("      }\n");
@@ -102,14 +135,14 @@ pub fn generate_js_glue(
         out.push_str("    const depsChanged = !eff.prevDeps || eff.deps.some((d, j) => d !== eff.prevDeps[j]);\n");
         out.push_str("    if (depsChanged) {\n");
         out.push_str("      const cleanupFn = `__effect_cleanup_${comp.name}_${i}`;\n");
-        out.push_str("      if (typeof __wasm.exports[cleanupFn] === 'function') {\n");
+        out.push_str("      if (eff.prevDeps && typeof __wasm.exports[cleanupFn] === 'function') {\n");
         out.push_str("        __wasm.exports.__volki_dealloc();\n");
-        out.push_str("        __wasm.exports[cleanupFn]();\n");
+        out.push_str("        __wasm.exports[cleanupFn](...eff.prevDeps);\n");
         out.push_str("      }\n");
         out.push_str("      const effectFn = `__effect_${comp.name}_${i}`;\n");
         out.push_str("      if (typeof __wasm.exports[effectFn] === 'function') {\n");
         out.push_str("        __wasm.exports.__volki_dealloc();\n");
-        out.push_str("        __wasm.exports[effectFn]();\n");
+        out.push_str("        __wasm.exports[effectFn](...eff.deps);\n");
         out.push_str("      }\n");
         out.push_str("      eff.prevDeps = [...eff.deps];\n");
         out.push_str("    }\n");
@@ -465,12 +498,22 @@ pub fn generate_js_glue(
     generate_dom_import("__volki_dom_append", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_remove", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_set_html", &all_fns, source, &mut out);
+    generate_dom_import("__volki_dom_append_html", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_toggle_class", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_get_attr", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_get_attr_len", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_remove_attr", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_query_all_count", &all_fns, source, &mut out);
     generate_dom_import("__volki_dom_query_all_get", &all_fns, source, &mut out);
+    // localStorage
+    generate_dom_import("__volki_storage_get", &all_fns, source, &mut out);
+    generate_dom_import("__volki_storage_get_len", &all_fns, source, &mut out);
+    generate_dom_import("__volki_storage_set", &all_fns, source, &mut out);
+    generate_dom_import("__volki_storage_remove", &all_fns, source, &mut out);
+    generate_dom_import("__volki_fetch", &all_fns, source, &mut out);
+    // Event control
+    generate_dom_import("__volki_event_prevent_default", &all_fns, source, &mut out);
+    generate_dom_import("__volki_event_stop_propagation", &all_fns, source, &mut out);
 
     // RSX component externs
     if has_rsx_components {
@@ -485,6 +528,17 @@ pub fn generate_js_glue(
         out.push_str("      return (comp && comp.mounted) ? 1 : 0;\n");
         out.push_str("    },\n");
 
+        // Keys are only recorded on the node for now (nothing diffs by key
+        // yet); data-volki-key mirrors it for debugging and CSS hooks.
+        out.push_str("    __volki_dom_set_key(handle, key_ptr, key_len) {\n");
+        out.push_str("      const el = __handles.get(handle);\n");
+        out.push_str("      if (!el) return;\n");
+        out.push_str("      const key = __read_string(key_ptr, key_len);\n");
+        out.push_str("      if (el.__volkiKey === key) return;\n");
+        out.push_str("      el.__volkiKey = key;\n");
+        out.push_str("      el.setAttribute(\"data-volki-key\", key);\n");
+        out.push_str("    },\n");
+
         out.push_str("    __volki_component_mount_point(id) {\n");
         out.push_str("      const comp = __components.get(id);\n");
         out.push_str("      if (!comp) return 0;\n");
@@ -508,7 +562,9 @@ pub fn generate_js_glue(
     out.push_str("  }\n");
     out.push_str("};\n\n");
 
-    out.push_str("const __volki_handlers = Object.create(null);\n\n");
+    out.push_str("const __volki_handlers = Object.create(null);\n");
+    // The DOM event a bound handler is running for (`event::*` APIs)
+    out.push_str("let __volki_event = null;\n\n");
 
     out.push_str("function __coerce_event_arg(event, paramType) {\n");
     out.push_str("  const target = event && event.target ? event.target : null;\n");
@@ -547,6 +603,8 @@ pub fn generate_js_glue(
     out.push_str("        if (eventName === \"click\" && el.tagName === \"A\") {\n");
     out.push_str("          event.preventDefault();\n");
     out.push_str("        }\n");
+    out.push_str("        const outer = __volki_event;\n");
+    out.push_str("        __volki_event = event;\n");
     out.push_str("        try {\n");
     out.push_str("          if (handler.arity === 0) {\n");
     out.push_str("            handler.call();\n");
@@ -556,6 +614,8 @@ pub fn generate_js_glue(
     out.push_str("          handler.call(__coerce_event_arg(event, kind));\n");
     out.push_str("        } catch (e) {\n");
     out.push_str("          console.error(`volki: handler '${handlerName}' failed for event '${eventName}'`, e);\n");
+    out.push_str("        } finally {\n");
+    out.push_str("          __volki_event = outer;\n");
     out.push_str("        }\n");
     out.push_str("      });\n");
     out.push_str("    }\n");
@@ -581,7 +641,18 @@ pub fn generate_js_glue(
             out.push_str(name.as_str());
             out.push_str("\", \"");
             out.push_str(export_name.as_str());
-            out.push_str("\");\n");
+            out.push('"');
+            if !func.params.is_empty() {
+                out.push_str(", [");
+                for (j, p) in func.params.iter().enumerate() {
+                    if j > 0 {
+                        out.push_str(", ");
+                    }
+                    out.push_str(crate::vformat!("[\"{}\", \"{}\"]", p.name, js_prop_kind(p.ty.as_str())).as_str());
+                }
+                out.push(']');
+            }
+            out.push_str(");\n");
         }
     }
 
@@ -592,7 +663,11 @@ pub fn generate_js_glue(
             if let Some(name) = &func.name {
                 out.push_str("  __wasm.exports.__volki_component_");
                 out.push_str(name.as_str());
-                out.push_str("();\n");
+                if func.params.is_empty() {
+                    out.push_str("();\n");
+                } else {
+                    out.push_str(crate::vformat!("(...__component_args({}));\n", i).as_str());
+                }
                 out.push_str("  __run_effects(");
                 out.push_str(crate::vformat!("{}", i).as_str());
                 out.push_str(");\n");
@@ -616,7 +691,7 @@ pub fn generate_js_glue(
         if name.starts_with("__effect_") {
             continue;
         }
-        generate_js_handler(name, &func.params, &mut out);
+        generate_js_handler(name, &func.params, func.returns_string, &mut out);
     }
 
     // Auto-init on load
@@ -669,7 +744,11 @@ fn generate_dom_import(
             source[f.body_span.0..f.body_span.1].contains("dom::append(")
         }),
         "__volki_dom_remove" => functions.iter().any(|f| {
-            source[f.body_span.0..f.body_span.1].contains("dom::remove(")
+            let b = &source[f.body_span.0..f.body_span.1];
+            b.contains("dom::remove(") || scanner::has_dom_remove(b)
+        }),
+        "__volki_dom_append_html" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains(".append_html(")
         }),
         "__volki_dom_set_html" => functions.iter().any(|f| {
             source[f.body_span.0..f.body_span.1].contains("dom::set_html(")
@@ -692,6 +771,24 @@ fn generate_dom_import(
         "__volki_dom_query_all_get" => functions.iter().any(|f| {
             source[f.body_span.0..f.body_span.1].contains("dom::query_all_get(")
         }),
+        "__volki_storage_get" | "__volki_storage_get_len" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("storage::get(")
+        }),
+        "__volki_storage_set" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("storage::set(")
+        }),
+        "__volki_storage_remove" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("storage::remove(")
+        }),
+        "__volki_fetch" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("net::fetch(")
+        }),
+        "__volki_event_prevent_default" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("event::prevent_default(")
+        }),
+        "__volki_event_stop_propagation" => functions.iter().any(|f| {
+            source[f.body_span.0..f.body_span.1].contains("event::stop_propagation(")
+        }),
         _ => false,
     };
 
@@ -782,6 +879,12 @@ fn generate_dom_import(
             out.push_str("      if (el) el.innerHTML = __read_string(html_ptr, html_len);\n");
             out.push_str("    },\n");
         }
+        "__volki_dom_append_html" => {
+            out.push_str("    __volki_dom_append_html(handle, html_ptr, html_len) {\n");
+            out.push_str("      const el = __handles.get(handle);\n");
+            out.push_str("      if (el) el.insertAdjacentHTML(\"beforeend\", __read_string(html_ptr, html_len));\n");
+            out.push_str("    },\n");
+        }
         "__volki_dom_toggle_class" => {
             out.push_str("    __volki_dom_toggle_class(handle, cls_ptr, cls_len) {\n");
             out.push_str("      const el = __handles.get(handle);\n");
@@ -824,6 +927,66 @@ fn generate_dom_import(
             out.push_str("      return __new_handle(els[idx]);\n");
             out.push_str("    },\n");
         }
+        // localStorage can throw (disabled storage, quota); treat that as a miss
+        "__volki_storage_get" => {
+            out.push_str("    __volki_storage_get(key_ptr, key_len) {\n");
+            out.push_str("      const val = __storage_read(__read_string(key_ptr, key_len));\n");
+            out.push_str("      const [ptr, _] = __pass_string(val);\n");
+            out.push_str("      return ptr;\n");
+            out.push_str("    },\n");
+        }
+        "__volki_storage_get_len" => {
+            out.push_str("    __volki_storage_get_len(key_ptr, key_len) {\n");
+            out.push_str("      return __encoder.encode(__storage_read(__read_string(key_ptr, key_len))).length;\n");
+            out.push_str("    },\n");
+        }
+        "__volki_storage_set" => {
+            out.push_str("    __volki_storage_set(key_ptr, key_len, val_ptr, val_len) {\n");
+            out.push_str("      try {\n");
+            out.push_str("        window.localStorage.setItem(__read_string(key_ptr, key_len), __read_string(val_ptr, val_len));\n");
+            out.push_str("      } catch (e) {\n");
+            out.push_str("        console.error('volki: localStorage write failed', e);\n");
+            out.push_str("      }\n");
+            out.push_str("    },\n");
+        }
+        "__volki_storage_remove" => {
+            out.push_str("    __volki_storage_remove(key_ptr, key_len) {\n");
+            out.push_str("      try { window.localStorage.removeItem(__read_string(key_ptr, key_len)); } catch (_) {}\n");
+            out.push_str("    },\n");
+        }
+        // Fire and forget: the body goes to the named Client handler as
+        // (status, body). A network failure is delivered as (0, "").
+        "__volki_fetch" => {
+            out.push_str("    __volki_fetch(url_ptr, url_len, cb_ptr, cb_len) {\n");
+            out.push_str("      const url = __read_string(url_ptr, url_len);\n");
+            out.push_str("      const cb = __read_string(cb_ptr, cb_len);\n");
+            out.push_str("      const deliver = (status, body) => {\n");
+            out.push_str("        const handler = __volki_handlers[cb];\n");
+            out.push_str("        if (!handler) {\n");
+            out.push_str("          console.error(`volki: missing fetch callback '${cb}'`);\n");
+            out.push_str("          return;\n");
+            out.push_str("        }\n");
+            out.push_str("        handler.call(status, body);\n");
+            out.push_str("      };\n");
+            out.push_str("      fetch(url)\n");
+            out.push_str("        .then(async (res) => deliver(res.status, await res.text()))\n");
+            out.push_str("        .catch((e) => {\n");
+            out.push_str("          console.error(`volki: fetch ${url} failed`, e);\n");
+            out.push_str("          deliver(0, \"\");\n");
+            out.push_str("        });\n");
+            out.push_str("    },\n");
+        }
+        // Act on the event being dispatched to a bound handler, if any
+        "__volki_event_prevent_default" => {
+            out.push_str("    __volki_event_prevent_default() {\n");
+            out.push_str("      if (__volki_event) __volki_event.preventDefault();\n");
+            out.push_str("    },\n");
+        }
+        "__volki_event_stop_propagation" => {
+            out.push_str("    __volki_event_stop_propagation() {\n");
+            out.push_str("      if (__volki_event) __volki_event.stopPropagation();\n");
+            out.push_str("    },\n");
+        }
         _ => {}
     }
 }
@@ -834,16 +997,40 @@ fn js_param_kind(ty: &str) -> &'static str {
         "bool"
     } else if ty == "&str" || ty == "str" || ty == "String" {
         "string"
-    } else if ty.contains("i32") || ty.contains("i64") || ty.contains("u32") || ty.contains("u64")
-        || ty.contains("f32") || ty.contains("f64")
-    {
+    } else if matches!(
+        ty,
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize" | "usize" | "f32" | "f64"
+    ) {
         "number"
     } else {
         "event"
     }
 }
 
-fn generate_js_handler(name: &str, params: &[FnParam], out: &mut String) {
+/// How `__component_args` decodes a prop attribute for a param of type `ty`.
+fn js_prop_kind(ty: &str) -> &'static str {
+    match rust_type_to_wasm(ty) {
+        WasmAbi::StringPair => "string",
+        WasmAbi::Direct(WasmType::I64) => "bigint",
+        _ if ty == "bool" => "bool",
+        _ => "number",
+    }
+}
+
+/// Marshal a scalar JS argument for a WASM export. Floats are JS numbers
+/// already and go through untouched; `bool` becomes `0`/`1` and 64-bit
+/// integers must be handed over as `BigInt`.
+fn js_direct_arg(param: &FnParam, wt: WasmType) -> String {
+    match wt {
+        WasmType::I32 if param.ty.as_str() == "bool" => {
+            crate::vformat!("({} ? 1 : 0)", param.name)
+        }
+        WasmType::I64 => crate::vformat!("BigInt(Math.trunc({}))", param.name),
+        _ => param.name.clone(),
+    }
+}
+
+fn generate_js_handler(name: &str, params: &[FnParam], returns_string: bool, out: &mut String) {
     out.push_str("__volki_handlers[\"");
     out.push_str(name);
     out.push_str("\"] = {\n");
@@ -892,20 +1079,13 @@ fn generate_js_handler(name: &str, params: &[FnParam], out: &mut String) {
                 wasm_args.push(ptr_var);
                 wasm_args.push(crate::vformat!("__{}_l", param.name));
             }
-            WasmAbi::Direct(_) => {
-                let arg = if param.ty.as_str() == "bool" {
-                    crate::vformat!("({} ? 1 : 0)", param.name)
-                } else {
-                    param.name.clone()
-                };
-                wasm_args.push(arg);
-            }
+            WasmAbi::Direct(wt) => wasm_args.push(js_direct_arg(param, wt)),
             WasmAbi::Void => {}
         }
     }
 
     // Call WASM export
-    out.push_str("  __wasm.exports.");
+    out.push_str(if returns_string { "  const __ret = __wasm.exports." } else { "  __wasm.exports." });
     out.push_str(name);
     out.push('(');
     for (i, arg) in wasm_args.iter().enumerate() {
@@ -914,6 +1094,13 @@ fn generate_js_handler(name: &str, params: &[FnParam], out: &mut String) {
     }
     out.push_str(");\n");
 
+    // Unpack the `len << 32 | ptr` fat value (an i64, so a BigInt in JS)
+    if returns_string {
+        out.push_str("  const __ptr = Number(__ret & 0xffffffffn);\n");
+        out.push_str("  const __len = Number(__ret >> 32n);\n");
+        out.push_str("  return __read_string(__ptr, __len);\n");
+    }
+
     out.push_str("  }\n");
     out.push_str("};\n\n");
 }
@@ -1005,6 +1192,120 @@ pub fn init() -> Client {
         assert!(js.contains("__volki_console_log(msg_ptr, msg_len)"));
     }
 
+    #[test]
+    fn test_generate_js_glue_string_return() {
+        let source = r#"
+pub fn format_price(cents: i32) -> Client<&str> {
+    "0.00"
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/price.wasm", false);
+        assert!(js.contains("const __ret = __wasm.exports.format_price(cents);"));
+        assert!(js.contains("const __ptr = Number(__ret & 0xffffffffn);"));
+        assert!(js.contains("const __len = Number(__ret >> 32n);"));
+        assert!(js.contains("return __read_string(__ptr, __len);"));
+    }
+
+    #[test]
+    fn test_generate_js_glue_dom_mutations() {
+        let source = r##"
+pub fn delete_row() -> Client {
+    let row = dom::query("#row");
+    row.set_attr("disabled", "true");
+    row.remove_attr("disabled");
+    row.append_html("<em>gone</em>");
+    row.remove();
+}
+"##;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/page.wasm", false);
+        assert!(js.contains("__volki_dom_set_attr("));
+        assert!(js.contains("__volki_dom_remove_attr("));
+        assert!(js.contains("__volki_dom_remove(handle) {"));
+        assert!(js.contains("el.insertAdjacentHTML(\"beforeend\", __read_string(html_ptr, html_len));"));
+    }
+
+    #[test]
+    fn test_generate_js_glue_event_control() {
+        let source = r#"
+pub fn insert_row() -> Client {
+    event::prevent_default();
+    dom::log("insert");
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/page.wasm", false);
+        assert!(js.contains("let __volki_event = null;"));
+        assert!(js.contains("__volki_event_prevent_default() {"));
+        assert!(js.contains("if (__volki_event) __volki_event.preventDefault();"));
+        assert!(js.contains("__volki_event = event;"));
+        assert!(js.contains("__volki_event = outer;"));
+        assert!(!js.contains("__volki_event_stop_propagation"));
+    }
+
+    #[test]
+    fn test_generate_js_glue_storage() {
+        let source = r#"
+pub fn remember(value: &str) -> Client {
+    storage::set("last", value);
+    let prev = storage::get("last");
+    storage::remove("stale");
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/page.wasm", false);
+        assert!(js.contains("function __storage_read(key)"));
+        assert!(js.contains("window.localStorage.getItem(key) ?? ''"));
+        assert!(js.contains("__volki_storage_get(key_ptr, key_len)"));
+        assert!(js.contains("__volki_storage_get_len(key_ptr, key_len)"));
+        assert!(js.contains("window.localStorage.setItem(__read_string(key_ptr, key_len), __read_string(val_ptr, val_len))"));
+        assert!(js.contains("window.localStorage.removeItem(__read_string(key_ptr, key_len))"));
+    }
+
+    #[test]
+    fn test_generate_js_glue_net_fetch() {
+        let source = r#"
+pub fn refresh() -> Client {
+    net::fetch("/api/items", "on_items");
+}
+
+pub fn on_items(status: i32, body: &str) -> Client {
+    dom::log(body);
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/page.wasm", false);
+        assert!(js.contains("__volki_fetch(url_ptr, url_len, cb_ptr, cb_len)"));
+        assert!(js.contains("fetch(url)"));
+        assert!(js.contains("deliver(res.status, await res.text())"));
+        assert!(js.contains("const handler = __volki_handlers[cb];"));
+        assert!(js.contains("handler.call(status, body);"));
+        // The callback's wrapper marshals the body into WASM memory
+        assert!(js.contains("__volki_handlers[\"on_items\"]"));
+        assert!(js.contains("__wasm.exports.on_items(status, __body_p, __body_l);"));
+    }
+
     #[test]
     fn test_generate_js_glue_mixed_params() {
         let source = r#"
@@ -1023,6 +1324,23 @@ pub fn update(id: i32, text: &str, flag: bool) -> Client {
         assert!(js.contains("(flag ? 1 : 0)"));
     }
 
+    #[test]
+    fn test_generate_js_glue_i64_param_is_bigint() {
+        let source = r#"
+pub fn seek(pos: i64, count: u16) -> Client {
+    dom::log("seek");
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let js = generate_js_glue(&client_fns, &empty_components(), source, "/wasm/x.wasm", false);
+        assert!(js.contains("paramTypes: [\"number\", \"number\"]"));
+        assert!(js.contains("__wasm.exports.seek(BigInt(Math.trunc(pos)), count);"));
+    }
+
     #[test]
     fn test_generate_js_only_needed_imports() {
         let source = r#"
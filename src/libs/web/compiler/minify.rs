@@ -1,9 +1,10 @@
 //! Generated output compactors (Rust/JS).
 //!
 //! These compactors are intentionally conservative:
-//! - preserve string/raw-string/template literal contents
-//! - remove comments
-//! - collapse whitespace runs to a single ASCII space
+//! - preserve string/raw-string/char/template literal contents
+//! - remove comments (including `//!` banners; `#![...]` attributes are code)
+//! - collapse whitespace runs to a single ASCII space; in JS a run that
+//!   holds a line break stays a line break wherever ASI could depend on it
 //! - return structured errors on unterminated tokens
 
 use crate::core::volkiwithstds::collections::String;
@@ -161,6 +162,18 @@ pub fn minify_rust_generated(input: &str) -> Result<String, MinifyError> {
             }
         }
 
+        // char literal: '"', '\'', '\u{..}' — but not a lifetime like 'a
+        if b == b'\''
+            && let Some(end) = char_literal_end(bytes, i)
+        {
+            emit_pending_space(&mut out, &mut pending_ws);
+            for &ch in &bytes[i..end] {
+                out.push(ch);
+            }
+            i = end;
+            continue;
+        }
+
         // normal string
         if b == b'"' {
             emit_pending_space(&mut out, &mut pending_ws);
@@ -198,17 +211,115 @@ pub fn minify_rust_generated(input: &str) -> Result<String, MinifyError> {
     Ok(String::from(s.trim()))
 }
 
+/// If a Rust char literal starts at `start` (a `'`), return the offset just
+/// past its closing quote. Lifetimes and labels return `None`.
+fn char_literal_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut k = start + 1;
+    match bytes.get(k)? {
+        b'\\' => {
+            k += 2;
+            while k < bytes.len() && bytes[k] != b'\'' && bytes[k] != b'\n' {
+                k += 1;
+            }
+        }
+        &c => k += utf8_len(c),
+    }
+    (bytes.get(k) == Some(&b'\'')).then_some(k + 1)
+}
+
+fn utf8_len(first: u8) -> usize {
+    match first {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    }
+}
+
+/// Words after which a line break always ends the statement
+/// (ES "restricted productions"), e.g. `return\nvalue` returns undefined.
+const JS_RESTRICTED_WORDS: &[&[u8]] = &[b"return", b"throw", b"break", b"continue", b"yield", b"async"];
+
+/// Words after which a `/` starts a regular expression, not a division.
+const JS_REGEX_PREFIX_WORDS: &[&[u8]] = &[
+    b"return", b"typeof", b"case", b"do", b"else", b"in", b"of", b"void", b"delete", b"new", b"throw", b"yield",
+    b"await",
+];
+
+fn is_js_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+fn trailing_js_word(out: &[u8]) -> &[u8] {
+    let start = out.iter().rposition(|&b| !is_js_ident_byte(b)).map_or(0, |p| p + 1);
+    &out[start..]
+}
+
+/// Whether a line break between `out` and the token starting `next` may
+/// change how the code parses if it became a space. A break is only
+/// dropped when the previous token cannot end a statement or the next one
+/// continues it, which is exactly when ASI would not insert a semicolon.
+fn js_line_break_matters(out: &[u8], next: &[u8]) -> bool {
+    if next.starts_with(b"++") || next.starts_with(b"--") || out.ends_with(b"++") || out.ends_with(b"--") {
+        return true;
+    }
+    if JS_RESTRICTED_WORDS.contains(&trailing_js_word(out)) {
+        return true;
+    }
+    // `/` may close a regex literal, so it is not treated as an operator
+    if let Some(last) = out.last()
+        && b"{([,;:=+-*%&|^!~?<>.".contains(last)
+    {
+        return false;
+    }
+    if next.starts_with(b"!=") {
+        return false;
+    }
+    !matches!(
+        next.first(),
+        Some(b'(' | b'[' | b'.' | b',' | b';' | b':' | b'?' | b'=' | b'*' | b'%' | b'&' | b'|' | b'^' | b'<' | b'>')
+            | Some(b')' | b']' | b'}' | b'+' | b'-' | b'/' | b'`')
+    )
+}
+
+/// Whether a `/` after `out` would start a regular expression literal.
+fn js_slash_starts_regex(out: &[u8]) -> bool {
+    match out.last() {
+        None => true,
+        Some(last) if b"(,=:[!&|?{};+-*%<>~^".contains(last) => true,
+        Some(_) => JS_REGEX_PREFIX_WORDS.contains(&trailing_js_word(out)),
+    }
+}
+
+fn emit_js_separator(
+    out: &mut crate::core::volkiwithstds::collections::Vec<u8>,
+    pending_ws: &mut bool,
+    pending_nl: &mut bool,
+    next: &[u8],
+) {
+    if *pending_ws && !out.is_empty() {
+        let sep = if *pending_nl && js_line_break_matters(out.as_slice(), next) { b'\n' } else { b' ' };
+        out.push(sep);
+    }
+    *pending_ws = false;
+    *pending_nl = false;
+}
+
+/// Regex literals are not tokenized, so input that contains one is
+/// rejected and callers fall back to the unminified source.
 pub fn minify_js_generated(input: &str) -> Result<String, MinifyError> {
     let bytes = input.as_bytes();
     let mut out = crate::core::volkiwithstds::collections::Vec::with_capacity(bytes.len());
     let mut i = 0usize;
     let mut pending_ws = false;
+    let mut pending_nl = false;
 
     while i < bytes.len() {
         let b = bytes[i];
 
         if is_ws(b) {
             pending_ws = true;
+            pending_nl |= b == b'\n' || b == b'\r';
             i += 1;
             continue;
         }
@@ -233,6 +344,8 @@ pub fn minify_js_generated(input: &str) -> Result<String, MinifyError> {
                     closed = true;
                     break;
                 }
+                // a comment spanning lines counts as a line break for ASI
+                pending_nl |= bytes[i] == b'\n';
                 i += 1;
             }
             if !closed {
@@ -242,10 +355,14 @@ pub fn minify_js_generated(input: &str) -> Result<String, MinifyError> {
             continue;
         }
 
+        if b == b'/' && js_slash_starts_regex(out.as_slice()) {
+            return Err(minify_err(input, i, "regex literal"));
+        }
+
         // single or double quote strings
         if b == b'"' || b == b'\'' {
             let q = b;
-            emit_pending_space(&mut out, &mut pending_ws);
+            emit_js_separator(&mut out, &mut pending_ws, &mut pending_nl, &bytes[i..]);
             out.push(q);
             i += 1;
             let mut closed = false;
@@ -275,7 +392,7 @@ pub fn minify_js_generated(input: &str) -> Result<String, MinifyError> {
 
         // template literals
         if b == b'`' {
-            emit_pending_space(&mut out, &mut pending_ws);
+            emit_js_separator(&mut out, &mut pending_ws, &mut pending_nl, &bytes[i..]);
             out.push(b'`');
             i += 1;
             let mut closed = false;
@@ -303,7 +420,7 @@ pub fn minify_js_generated(input: &str) -> Result<String, MinifyError> {
             continue;
         }
 
-        emit_pending_space(&mut out, &mut pending_ws);
+        emit_js_separator(&mut out, &mut pending_ws, &mut pending_nl, &bytes[i..]);
         out.push(b);
         i += 1;
     }
@@ -339,4 +456,69 @@ mod tests {
         assert!(out.contains("`x ${b}`;"));
         assert!(!out.contains("// c"));
     }
+
+    #[test]
+    fn js_minify_keeps_line_break_after_return() {
+        let out = minify_js_generated("function f() {\n  return\n  value;\n}\n").unwrap();
+        assert_eq!(out.as_str(), "function f() { return\nvalue; }");
+    }
+
+    #[test]
+    fn js_minify_keeps_line_break_around_update_operators() {
+        let out = minify_js_generated("a ++\n b").unwrap();
+        assert_eq!(out.as_str(), "a ++\nb");
+        let out = minify_js_generated("a\n++b").unwrap();
+        assert_eq!(out.as_str(), "a\n++b");
+    }
+
+    #[test]
+    fn js_minify_line_breaks_only_where_asi_applies() {
+        let out = minify_js_generated("let a = 1\nlet b = a\n(b)\nconst c = {\n  x: 1,\n};\nf(c) /* one\ntwo */ g()").unwrap();
+        assert_eq!(out.as_str(), "let a = 1\nlet b = a (b)\nconst c = { x: 1, }; f(c)\ng()");
+    }
+
+    #[test]
+    fn js_minify_rejects_regex_literals() {
+        let err = minify_js_generated("const re = /\"/g;").unwrap_err();
+        assert_eq!(err.kind, "regex literal");
+        assert!(minify_js_generated("const half = a / 2;").is_ok());
+    }
+
+    #[test]
+    fn rust_minify_keeps_comment_markers_in_strings() {
+        let src = "let a = \"// keep\"; let b = \"/* keep */\"; // drop\nlet c = b\"//x\";\n";
+        let out = minify_rust_generated(src).unwrap();
+        assert_eq!(out.as_str(), "let a = \"// keep\"; let b = \"/* keep */\"; let c = b\"//x\";");
+    }
+
+    #[test]
+    fn rust_minify_strips_banner_and_blank_lines() {
+        let src = "//! @generated by volki compiler — do not edit.\n\n\n#![allow(unused)]\n\n\n/// doc\nfn a() {}\n";
+        let out = minify_rust_generated(src).unwrap();
+        assert_eq!(out.as_str(), "#![allow(unused)] fn a() {}");
+    }
+
+    #[test]
+    fn rust_minify_preserves_char_literals() {
+        let src = "let q = '\"'; let s = '\\''; let u = '\\u{2014}'; let e = '—';\nlet t = \"a  b\";";
+        let out = minify_rust_generated(src).unwrap();
+        assert_eq!(
+            out.as_str(),
+            "let q = '\"'; let s = '\\''; let u = '\\u{2014}'; let e = '—'; let t = \"a  b\";"
+        );
+    }
+
+    #[test]
+    fn rust_minify_lifetimes_are_not_chars() {
+        let src = "fn f<'a>(x: &'a str) -> &'a str { 'outer: loop { break 'outer; } x }";
+        let out = minify_rust_generated(src).unwrap();
+        assert_eq!(out.as_str(), src);
+    }
+
+    #[test]
+    fn rust_minify_error_has_position() {
+        let err = minify_rust_generated("fn a() {\n  let s = \"open;\n").unwrap_err();
+        assert_eq!(err.kind, "unterminated string");
+        assert_eq!(err.line, 2);
+    }
 }
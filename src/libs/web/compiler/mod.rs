@@ -108,6 +108,15 @@ pub fn read_entrypoint_config(dir: &Path) -> String {
     }
 }
 
+/// Read the `[web].max_body_size` value (bytes) from `volki.toml` in the
+/// given directory. Returns `None` if not found or not a non-negative integer.
+pub fn read_max_body_size_config(dir: &Path) -> Option<usize> {
+    let content = fs::read_to_string(dir.join("volki.toml").as_path()).ok()?;
+    let table = crate::core::config::parser::parse(content.as_str()).ok()?;
+    let bytes = table.get("web", "max_body_size")?.as_int()?;
+    usize::try_from(bytes).ok()
+}
+
 /// Result of compiling a single `.volki` source string.
 #[derive(Debug)]
 pub struct SourceOutput {
@@ -177,7 +186,8 @@ pub fn compile_source_full(source: &str, file: &Path) -> Result<SourceOutput, Co
 
     for func in &functions {
         if func.return_type == RsxReturnType::Client
-            || func.return_type == RsxReturnType::Component {
+            || func.return_type == RsxReturnType::Component
+            || func.return_type == RsxReturnType::Shared {
             parsed_bodies.push(None);
             continue;
         }
@@ -244,10 +254,24 @@ pub fn compile_source_full(source: &str, file: &Path) -> Result<SourceOutput, Co
         }
     }
 
+    // `<Style scoped>` stamps every element in the file with one attribute
+    let scoped = parsed_bodies.iter().chain(component_rsx_bodies.iter())
+        .flatten()
+        .any(|nodes| volkistyle::scope::has_scoped_style(nodes));
+    if scoped {
+        let attr = volkistyle::scope::scope_attr(file.as_str());
+        for body in parsed_bodies.iter_mut().chain(component_rsx_bodies.iter_mut()).flatten() {
+            *body = volkistyle::scope::stamp_scope(body, attr.as_str());
+        }
+    }
+
     // Generate CSS from all collected classes
     let style_cfg = volkistyle::config::load_for_source_file(file);
     let style_report = volkistyle::generate_css_with_config(&all_classes, &style_cfg);
     let mut warnings = compile_warnings_from_style(file, source, &style_report);
+    for w in semantic::find_unused_components(source, file, &functions) {
+        warnings.push(w);
+    }
     if style_cfg.unknown_class_policy == volkistyle::config::UnknownClassPolicy::Error
         && !style_report.diagnostics.is_empty()
     {
@@ -282,10 +306,17 @@ pub fn compile_source_full(source: &str, file: &Path) -> Result<SourceOutput, Co
         let before = &source[last_pos..func.return_type_span.0];
         output.push_str(before);
 
+        // Shared helpers keep their body; only `Shared<T>` becomes `T`
+        if func.return_type == RsxReturnType::Shared {
+            output.push_str(scanner::shared_return_type(source, func));
+            last_pos = func.return_type_span.1;
+            continue;
+        }
+
         match func.return_type {
             RsxReturnType::Html => output.push_str("HtmlDocument"),
             RsxReturnType::Fragment => output.push_str("Vec<HtmlNode>"),
-            RsxReturnType::Client | RsxReturnType::Component => unreachable!(),
+            RsxReturnType::Client | RsxReturnType::Component | RsxReturnType::Shared => unreachable!(),
         }
 
         let between = &source[func.return_type_span.1..func.body_span.0];
@@ -309,7 +340,7 @@ pub fn compile_source_full(source: &str, file: &Path) -> Result<SourceOutput, Co
                 )
             }
             RsxReturnType::Fragment => codegen::generate_fragment_fn(nodes),
-            RsxReturnType::Client | RsxReturnType::Component => unreachable!(),
+            RsxReturnType::Client | RsxReturnType::Component | RsxReturnType::Shared => unreachable!(),
         };
 
         output.push_str("\n    ");
@@ -414,12 +445,26 @@ fn resolve_components(
                 if is_component_tag(tag.as_str()) {
                     let snake = pascal_to_snake(tag.as_str());
 
-                    // RSX Component → mount-point div
+                    // RSX Component → mount-point div, props as data-volki-prop-* attrs
                     if rsx_components.iter().any(|n| n.as_str() == snake.as_str()) {
-                        let mount_expr = crate::vformat!(
-                            "div().attr(\"data-volki-component\", \"{}\").into_node()",
+                        let mut mount_expr = crate::vformat!(
+                            "div().attr(\"data-volki-component\", \"{}\")",
                             snake
                         );
+                        for attr in attrs {
+                            let value = match &attr.value {
+                                parser::RsxAttrValue::Literal(v) => crate::vformat!("\"{}\"", v),
+                                parser::RsxAttrValue::Expr(e) => {
+                                    crate::vformat!("crate::vformat!(\"{{}}\", {}).as_str()", e)
+                                }
+                                // Rejected on component tags by semantic validation
+                                parser::RsxAttrValue::Spread(_) | parser::RsxAttrValue::ClassToggle(_) => continue,
+                            };
+                            mount_expr.push_str(
+                                crate::vformat!(".attr(\"data-volki-prop-{}\", {})", attr.name, value).as_str(),
+                            );
+                        }
+                        mount_expr.push_str(".into_node()");
                         out.push(parser::RsxNode::Expr(mount_expr));
                         continue;
                     }
@@ -511,6 +556,8 @@ fn build_component_call(
                 parser::RsxAttrValue::Expr(e) => {
                     call.push_str(e.as_str());
                 }
+                // Rejected on component tags by semantic validation
+                parser::RsxAttrValue::Spread(_) | parser::RsxAttrValue::ClassToggle(_) => {}
             }
         }
     }
@@ -590,8 +637,10 @@ fn compile_file_to_dist(
     let full_output = compile_source_full(source.as_str(), path)?;
 
     // Mirror source path into dist
+    // Outside source_root the whole path is mirrored; keep it relative so
+    // join doesn't treat it as absolute and write outside dist
     let relative = path.strip_prefix(source_root.as_str()).unwrap_or(path.as_str());
-    let out_path = dist_dir.join(relative);
+    let out_path = dist_dir.join(relative.trim_start_matches('/'));
     let out_path = out_path.with_extension("rs");
 
     // Ensure parent directory exists
@@ -676,7 +725,7 @@ fn copy_rs_to_dist(
     })?;
 
     let relative = path.strip_prefix(source_root.as_str()).unwrap_or(path.as_str());
-    let out_path = dist_dir.join(relative);
+    let out_path = dist_dir.join(relative.trim_start_matches('/'));
 
     if let Some(parent) = out_path.as_path().parent() {
         fs::create_dir_all(parent).map_err(|e| CompileError {
@@ -714,7 +763,7 @@ fn copy_asset_to_public(
     dist_dir: &Path,
 ) -> Result<(), CompileError> {
     let relative = path.strip_prefix(source_root.as_str()).unwrap_or(path.as_str());
-    let out_path = dist_dir.join("public").join(relative);
+    let out_path = dist_dir.join("public").join(relative.trim_start_matches('/'));
 
     if let Some(parent) = out_path.as_path().parent() {
         fs::create_dir_all(parent).map_err(|e| CompileError {
@@ -741,8 +790,38 @@ fn copy_asset_to_public(
     Ok(())
 }
 
-/// Recursively copy a directory's contents to a destination.
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), CompileError> {
+/// Recursively copy `src` into `dst`. Symlinks are recreated as links when
+/// `preserve_symlinks` is set, otherwise their targets are copied; a
+/// directory link that points back into its own ancestors is an error.
+fn copy_dir_recursive(src: &Path, dst: &Path, preserve_symlinks: bool) -> Result<(), CompileError> {
+    copy_dir_inner(src, dst, preserve_symlinks, &mut Vec::new())
+}
+
+/// `ancestors` holds the (dev, inode) of each directory being copied on the
+/// way down to `src`, so following a link into one of them is caught.
+fn copy_dir_inner(
+    src: &Path,
+    dst: &Path,
+    preserve_symlinks: bool,
+    ancestors: &mut Vec<(u64, u64)>,
+) -> Result<(), CompileError> {
+    let meta = fs::metadata(src).map_err(|e| CompileError {
+        file: src.to_path_buf(),
+        line: 0,
+        col: 0,
+        message: crate::vformat!("failed to read directory: {}", e),
+    })?;
+    let id = (meta.dev(), meta.ino());
+    if ancestors.contains(&id) {
+        return Err(CompileError {
+            file: src.to_path_buf(),
+            line: 0,
+            col: 0,
+            message: String::from("symlink cycle: directory links back to one of its parents"),
+        });
+    }
+    ancestors.push(id);
+
     fs::create_dir_all(dst).map_err(|e| CompileError {
         file: src.to_path_buf(),
         line: 0,
@@ -768,8 +847,24 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), CompileError> {
         let src_path = entry.path().to_path_buf();
         let dst_path = dst.join(entry.file_name());
 
-        if entry.file_type() == fs::FileType::Directory {
-            copy_dir_recursive(src_path.as_path(), dst_path.as_path())?;
+        let file_type = entry.file_type();
+        if file_type == fs::FileType::Symlink && preserve_symlinks {
+            let target = fs::read_link(src_path.as_path()).map_err(|e| CompileError {
+                file: src_path.to_path_buf(),
+                line: 0,
+                col: 0,
+                message: crate::vformat!("failed to read symlink: {}", e),
+            })?;
+            fs::symlink(target.as_path(), dst_path.as_path()).map_err(|e| CompileError {
+                file: src_path.to_path_buf(),
+                line: 0,
+                col: 0,
+                message: crate::vformat!("failed to create symlink: {}", e),
+            })?;
+        } else if file_type == fs::FileType::Directory
+            || (file_type == fs::FileType::Symlink && fs::is_dir(src_path.as_path()))
+        {
+            copy_dir_inner(src_path.as_path(), dst_path.as_path(), preserve_symlinks, ancestors)?;
         } else {
             let content = fs::read(src_path.as_path()).map_err(|e| CompileError {
                 file: src_path.to_path_buf(),
@@ -786,6 +881,7 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), CompileError> {
         }
     }
 
+    ancestors.pop();
     Ok(())
 }
 
@@ -825,7 +921,7 @@ pub fn compile_dir(source_dir: &Path, dist_name: &str) -> Result<Vec<CompileResu
     let public_src = source_dir.join("public");
     if public_src.as_path().exists() {
         let public_dst = dist_dir.join("public");
-        copy_dir_recursive(public_src.as_path(), public_dst.as_path())?;
+        copy_dir_recursive(public_src.as_path(), public_dst.as_path(), false)?;
     }
 
     // Walk source tree: compile .volki, copy .rs
@@ -963,6 +1059,66 @@ fn generate_sub_mod_files(dir: &Path) -> Result<(), CompileError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_copy_dir_recursive_symlinks() {
+        let root = crate::core::volkiwithstds::env::temp_dir().join("volki_copy_symlink_test");
+        let _ = fs::remove_dir_all(root.as_path());
+        let src = root.join("src");
+        fs::create_dir_all(src.join("assets").as_path()).unwrap();
+        fs::write_str(src.join("assets/logo.svg").as_path(), "<svg/>").unwrap();
+        fs::symlink(Path::new("assets/logo.svg"), src.join("logo.svg").as_path()).unwrap();
+        fs::symlink(Path::new("assets"), src.join("img").as_path()).unwrap();
+
+        let followed = root.join("followed");
+        copy_dir_recursive(src.as_path(), followed.as_path(), false).unwrap();
+        let meta = fs::symlink_metadata(followed.join("logo.svg").as_path()).unwrap();
+        assert!(meta.is_file());
+        assert!(fs::symlink_metadata(followed.join("img").as_path()).unwrap().is_dir());
+        assert_eq!(fs::read_to_string(followed.join("img/logo.svg").as_path()).unwrap().as_str(), "<svg/>");
+
+        let kept = root.join("kept");
+        copy_dir_recursive(src.as_path(), kept.as_path(), true).unwrap();
+        assert!(fs::symlink_metadata(kept.join("img").as_path()).unwrap().is_symlink());
+        assert_eq!(fs::read_link(kept.join("logo.svg").as_path()).unwrap().as_str(), "assets/logo.svg");
+        assert_eq!(fs::read_to_string(kept.join("logo.svg").as_path()).unwrap().as_str(), "<svg/>");
+
+        let _ = fs::remove_dir_all(root.as_path());
+    }
+
+    #[test]
+    fn test_copy_asset_outside_root_stays_in_dist() {
+        let root = crate::core::volkiwithstds::env::temp_dir().join("volki_copy_asset_test");
+        let _ = fs::remove_dir_all(root.as_path());
+        let asset = root.join("shared/logo.svg");
+        fs::create_dir_all(root.join("shared").as_path()).unwrap();
+        fs::write_str(asset.as_path(), "<svg/>").unwrap();
+        let dist = root.join("dist");
+
+        copy_asset_to_public(asset.as_path(), root.join("app").as_path(), dist.as_path()).unwrap();
+        let mirrored = dist.join("public").join(asset.as_str().trim_start_matches('/'));
+        assert_eq!(fs::read_to_string(mirrored.as_path()).unwrap().as_str(), "<svg/>");
+
+        let _ = fs::remove_dir_all(root.as_path());
+    }
+
+    #[test]
+    fn test_copy_dir_recursive_symlink_cycle() {
+        let root = crate::core::volkiwithstds::env::temp_dir().join("volki_copy_cycle_test");
+        let _ = fs::remove_dir_all(root.as_path());
+        let src = root.join("src");
+        fs::create_dir_all(src.join("a").as_path()).unwrap();
+        fs::symlink(Path::new(".."), src.join("a/loop").as_path()).unwrap();
+
+        let err = copy_dir_recursive(src.as_path(), root.join("followed").as_path(), false).unwrap_err();
+        assert!(err.message.contains("symlink cycle"));
+
+        let kept = root.join("kept");
+        copy_dir_recursive(src.as_path(), kept.as_path(), true).unwrap();
+        assert!(fs::symlink_metadata(kept.join("a/loop").as_path()).unwrap().is_symlink());
+
+        let _ = fs::remove_dir_all(root.as_path());
+    }
+
     #[test]
     fn test_compile_full_file() {
         let source = r##"use crate::libs::web::prelude::*;
@@ -1040,6 +1196,21 @@ pub fn page(_req: &Request) -> Html {
         assert!(result.contains("use crate::libs::db::web_editor::shared::CSS;"));
     }
 
+    #[test]
+    fn test_read_max_body_size_config() {
+        let dir = crate::core::volkiwithstds::env::temp_dir().join("volki_max_body_config_test");
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.as_path()).unwrap();
+        assert_eq!(read_max_body_size_config(dir.as_path()), None);
+
+        fs::write_str(dir.join("volki.toml").as_path(), "[web]\nmax_body_size = 4096\n").unwrap();
+        assert_eq!(read_max_body_size_config(dir.as_path()), Some(4096));
+
+        fs::write_str(dir.join("volki.toml").as_path(), "[web]\nmax_body_size = -1\n").unwrap();
+        assert_eq!(read_max_body_size_config(dir.as_path()), None);
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+
     #[test]
     fn test_read_dist_config_default() {
         // Non-existent directory returns default
@@ -1105,6 +1276,33 @@ pub fn on_click(target: &str) -> Client {
         assert!(out.client.is_none());
     }
 
+    #[test]
+    fn test_compile_scoped_style() {
+        let source = r#"pub fn page(_req: &Request) -> Html {
+    <Style scoped>{CSS}</Style>
+    <div class="card"><p>"hi"</p></div>
+}
+"#;
+        let path = Path::new("app/page.volki");
+        let attr = crate::libs::web::volkistyle::scope::scope_attr("app/page.volki");
+        let out = compile_source_full(source, path).unwrap();
+
+        let scoped = crate::vformat!("scope_css(&(CSS), \"{}\")", attr);
+        assert!(out.server_rs.contains(scoped.as_str()));
+        let stamped = crate::vformat!(".attr(\"{}\", \"\")", attr);
+        assert_eq!(out.server_rs.matches(stamped.as_str()).count(), 2);
+
+        // Without `scoped` nothing is stamped
+        let source = r#"pub fn page(_req: &Request) -> Html {
+    <Style>{CSS}</Style>
+    <div class="card"><p>"hi"</p></div>
+}
+"#;
+        let plain = compile_source_full(source, path).unwrap();
+        assert!(plain.server_rs.contains(".inline_style(CSS)"));
+        assert!(!plain.server_rs.contains("data-v-"));
+    }
+
     #[test]
     fn test_compile_db_editor_page_with_client() {
         // Simulates the db web editor page.volki — mixes server-side Html/Fragment
@@ -1198,6 +1396,39 @@ pub fn insert_row() -> Client {
         assert!(client.glue_js.contains("(checked ? 1 : 0)"));
     }
 
+    #[test]
+    fn test_compile_client_float_params() {
+        let source = r##"pub fn page(_req: &Request) -> Html {
+    <input type="range" oninput={on_slider} />
+    <span id="volume">"0"</span>
+}
+
+pub fn on_slider(value: f64) -> Client {
+    dom::log("slider moved");
+}
+
+pub fn on_zoom(factor: f32, label: &str) -> Client {
+    dom::log(label);
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+        let client = out.client.as_ref().unwrap();
+
+        // Floats cross the boundary as native WASM floats
+        assert!(client.wasm_rs.contains("pub extern \"C\" fn on_slider(value: f64)"));
+        assert!(client.wasm_rs.contains("pub extern \"C\" fn on_zoom(factor: f32, label_ptr: i32, label_len: i32)"));
+
+        // JS glue hands the number over untouched
+        assert!(client.glue_js.contains("paramTypes: [\"number\"]"));
+        assert!(client.glue_js.contains("__wasm.exports.on_slider(value);"));
+        assert!(client.glue_js.contains("__wasm.exports.on_zoom(factor, __label_p, __label_l);"));
+        assert!(!client.glue_js.contains("value | 0"));
+        assert!(!client.glue_js.contains("Math.trunc(value)"));
+        assert!(!client.glue_js.contains("(value ? 1 : 0)"));
+        assert!(!client.glue_js.contains("BigInt(Math.trunc(value))"));
+    }
+
     #[test]
     fn test_client_functions_stripped_from_server() {
         let source = r#"
@@ -1324,6 +1555,21 @@ pub fn page(_req: &Request) -> Html {
         assert!(err.message.contains("Html"));
     }
 
+    #[test]
+    fn test_storage_boundary_error_on_compile() {
+        let source = r##"
+pub fn page(_req: &Request) -> Html {
+    storage::set("visited", "1");
+    <div>"hello"</div>
+}
+"##;
+        let path = Path::new("page.volki");
+        let err = compile_source_full(source, path).unwrap_err();
+        assert!(err.message.contains("client-only API"));
+        assert!(err.message.contains("storage::set"));
+        assert!(err.message.contains("Html"));
+    }
+
     #[test]
     fn test_legacy_volki_handler_syntax_errors() {
         let source = r#"
@@ -1796,6 +2042,90 @@ pub fn on_increment() -> Client {
         assert!(client.glue_js.contains("__volki_component_mount_point("));
     }
 
+    #[test]
+    fn test_rsx_component_use_effect_closure() {
+        let source = r##"use crate::libs::web::prelude::*;
+
+pub fn page(_req: &Request) -> Html {
+    <div><p id="log">"0"</p></div>
+}
+
+pub fn counter() -> Component {
+    let (count, set_count) = use_state(0_i32);
+    let _ = set_count;
+    use_effect(&[], || {
+        dom::log("mounted");
+    });
+    use_effect(&[count], move || {
+        dom::log(count);
+        move || {
+            dom::log("cleanup");
+        }
+    });
+
+    return (
+        <span>{state::fmt_i32(count)}</span>
+    )
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+        let client = out.client.as_ref().unwrap();
+        let wasm = client.wasm_rs.as_str();
+
+        // Registration stays in the component body, closures are hoisted out
+        assert!(wasm.contains("__volki_effect_register(0, 0);"));
+        assert!(wasm.contains("__volki_effect_register(1, 1);"));
+        assert!(wasm.contains("__volki_effect_set_dep(1, 0, count);"));
+        assert!(!wasm.contains("|| {"));
+
+        // Empty deps: no params, runs once after mount
+        assert!(wasm.contains("pub extern \"C\" fn __effect_counter_0() {"));
+        assert!(wasm.contains("= \"mounted\";"));
+
+        // Deps arrive as arguments; the returned closure becomes the cleanup
+        assert!(wasm.contains("pub extern \"C\" fn __effect_counter_1(count: i32) {"));
+        assert!(wasm.contains("__volki_log_i32(count)"));
+        assert!(wasm.contains("pub extern \"C\" fn __effect_cleanup_counter_1(count: i32) {"));
+        assert!(wasm.contains("= \"cleanup\";"));
+        assert!(!wasm.contains("__effect_cleanup_counter_0"));
+
+        // Glue: register import, run after render, cleanup only after a previous run
+        let js = client.glue_js.as_str();
+        assert!(js.contains("__volki_effect_register(slot, dep_count)"));
+        assert!(js.contains("__run_effects(0);"));
+        assert!(js.contains("if (eff.prevDeps && typeof __wasm.exports[cleanupFn] === 'function')"));
+        assert!(js.contains("__wasm.exports[cleanupFn](...eff.prevDeps);"));
+        assert!(js.contains("__wasm.exports[effectFn](...eff.deps);"));
+    }
+
+    #[test]
+    fn test_rsx_component_keyed_element() {
+        let source = r##"use crate::libs::web::prelude::*;
+
+pub fn page(_req: &Request) -> Html {
+    <ul id="list"></ul>
+}
+
+pub fn row() -> Component {
+    let (id, set_id) = use_state(7_i32);
+    let _ = set_id;
+
+    return (
+        <li key={state::fmt_i32(id)}>"row"</li>
+    )
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+        let client = out.client.as_ref().unwrap();
+
+        assert!(client.wasm_rs.contains("fn __volki_dom_set_key(handle: i32, key_ptr: i32, key_len: i32);"));
+        assert!(client.wasm_rs.contains("__volki_dom_set_key(__key0, __kfb0, __kfl0);"));
+        assert!(client.glue_js.contains("__volki_dom_set_key(handle, key_ptr, key_len)"));
+        assert!(client.glue_js.contains("el.__volkiKey = key;"));
+    }
+
     #[test]
     fn test_rsx_component_imperative_backward_compat() {
         // Old-style imperative Component should still work unchanged
@@ -1855,6 +2185,37 @@ pub fn counter() -> Component {
         assert!(out.server_rs.contains("counter"));
     }
 
+    #[test]
+    fn test_rsx_component_props() {
+        let source = r##"use crate::libs::web::prelude::*;
+
+pub fn page(_req: &Request) -> Html {
+    <div>
+        <Counter start={5} label="Clicks" />
+    </div>
+}
+
+pub fn counter(start: i32, label: &str) -> Component {
+    let (count, set_count) = use_state(start);
+    let _ = set_count;
+
+    return (
+        <span title={label}>{state::fmt_i32(count)}</span>
+    )
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+
+        assert!(out.server_rs.contains(
+            "div().attr(\"data-volki-component\", \"counter\").attr(\"data-volki-prop-start\", crate::vformat!(\"{}\", 5).as_str()).attr(\"data-volki-prop-label\", \"Clicks\").into_node()"
+        ));
+        let client = out.client.unwrap();
+        assert!(client.wasm_rs.contains("fn __volki_component_counter(start: i32, label_ptr: i32, label_len: i32)"));
+        assert!(client.glue_js.contains("[[\"start\", \"number\"], [\"label\", \"string\"]]"));
+        assert!(client.glue_js.contains("__wasm.exports.__volki_component_counter(...__component_args(0));"));
+    }
+
     #[test]
     fn test_rsx_component_css_classes_collected() {
         // CSS classes inside Component RSX should be collected for volkistyle
@@ -1883,4 +2244,233 @@ pub fn counter() -> Component {
         assert!(out.server_rs.contains("flex"));
         assert!(out.server_rs.contains("text-red-500") || out.server_rs.contains("inline_style"));
     }
+
+    // ── Unused component warnings ──
+
+    #[test]
+    fn test_unused_fragment_warns() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div>"hello"</div>
+}
+
+fn sidebar() -> Fragment {
+    <nav>"side"</nav>
+}
+"#;
+        let path = Path::new("test.volki");
+        let out = compile_source_full(source, path).unwrap();
+        assert_eq!(out.warnings.len(), 1);
+        let w = &out.warnings[0];
+        assert!(w.message.contains("unused Fragment `sidebar`"));
+        assert!(w.message.contains("<Sidebar />"));
+        assert_eq!((w.line, w.col), (6, 4));
+        // Still compiled and emitted
+        assert!(out.server_rs.contains("fn sidebar"));
+    }
+
+    #[test]
+    fn test_used_fragment_no_warning() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div><SidebarContent /></div>
+    {footer()}
+}
+
+fn sidebar_content() -> Fragment {
+    <nav>"side"</nav>
+}
+
+fn footer() -> Fragment {
+    <footer>"bye"</footer>
+}
+"#;
+        let path = Path::new("test.volki");
+        let out = compile_source_full(source, path).unwrap();
+        assert!(out.warnings.iter().all(|w| !w.message.contains("unused")));
+    }
+
+    #[test]
+    fn test_pub_fragment_not_warned() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div>"hello"</div>
+}
+
+pub fn sidebar() -> Fragment {
+    <nav>"side"</nav>
+}
+
+pub(crate) fn header() -> Fragment {
+    <header>"top"</header>
+}
+"#;
+        let path = Path::new("test.volki");
+        let out = compile_source_full(source, path).unwrap();
+        assert!(out.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unused_component_warns() {
+        let source = r##"use crate::libs::web::prelude::*;
+
+pub fn page(_req: &Request) -> Html {
+    <div>"hello"</div>
+}
+
+fn counter() -> Component {
+    let (count, set_count) = use_state(0_i32);
+    let _ = set_count;
+
+    return (
+        <span>{state::fmt_i32(count)}</span>
+    )
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+        let unused: Vec<&CompileWarning> = out.warnings.iter()
+            .filter(|w| w.message.contains("unused"))
+            .collect();
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("unused Component `counter`"));
+        assert_eq!(unused[0].line, 7);
+    }
+
+    #[test]
+    fn test_attr_spread_compiles() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div id="main" {...extra_attrs}>"hello"</div>
+}
+"#;
+        let path = Path::new("test.volki");
+        let out = compile_source_full(source, path).unwrap();
+        assert!(out.server_rs.contains("for (__k, __v) in extra_attrs"));
+        assert!(out.server_rs.contains("matches!(__k, \"id\")"));
+    }
+
+    #[test]
+    fn test_attr_spread_on_component_error() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <Badge {...props} />
+}
+
+fn badge() -> Fragment {
+    <span>"x"</span>
+}
+"#;
+        let path = Path::new("test.volki");
+        let err = compile_source_full(source, path).unwrap_err();
+        assert!(err.message.contains("spread attributes are not supported on component `Badge`"));
+    }
+
+    #[test]
+    fn test_class_directive_css_generated() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div class="flex" class:hidden={collapsed}>"menu"</div>
+}
+"#;
+        let path = Path::new("test.volki");
+        let out = compile_source_full(source, path).unwrap();
+        assert!(out.server_rs.contains(".class_if(\"hidden\", collapsed)"));
+        // The conditional class still gets its CSS rule
+        assert!(out.server_rs.contains(".hidden{"));
+    }
+
+    // ── Shared helpers ──
+
+    #[test]
+    fn test_shared_helper_in_server_and_client() {
+        let source = r##"use crate::libs::web::prelude::*;
+
+pub fn page(_req: &Request) -> Html {
+    <button onclick={on_click}>"Buy"</button>
+    <p id="price">{price_label(1999)}</p>
+}
+
+fn dollars(cents: i64) -> Shared<i64> {
+    cents / 100
+}
+
+fn price_label(cents: i64) -> String {
+    crate::vformat!("${}", dollars(cents))
+}
+
+pub fn on_click(target: &str) -> Client {
+    let total = dollars(1999);
+    dom::log_i32(total as i32);
+}
+"##;
+        let path = Path::new("page.volki");
+        let out = compile_source_full(source, path).unwrap();
+
+        // Server keeps the helper with the wrapper removed
+        assert!(out.server_rs.contains("fn dollars(cents: i64) -> i64 { cents / 100 }"));
+        assert!(!out.server_rs.contains("Shared"));
+
+        // Client module includes it too
+        let client = out.client.unwrap();
+        assert!(client.wasm_rs.contains("#[allow(dead_code)] fn dollars(cents: i64) -> i64 { cents / 100 }"));
+        assert!(!client.wasm_rs.contains("price_label"));
+    }
+
+    #[test]
+    fn test_shared_helper_rejects_client_api() {
+        let source = r##"
+pub fn page(_req: &Request) -> Html {
+    <p>"x"</p>
+}
+
+fn helper() -> Shared {
+    storage::set("k", "v");
+}
+"##;
+        let path = Path::new("page.volki");
+        let err = compile_source_full(source, path).unwrap_err();
+        assert!(err.message.contains("client-only API `storage::set` used in shared function `helper` (-> Shared)"));
+        assert_eq!(err.line, 7);
+    }
+
+    #[test]
+    fn test_void_element_with_children_error() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div>
+        <img src="/a.png" />
+        <img>"x"</img>
+    </div>
+}
+"#;
+        let path = Path::new("test.volki");
+        let err = compile_source_full(source, path).unwrap_err();
+        assert!(err.message.contains("`<img>` is a void element and cannot have children"));
+        assert_eq!(err.line, 5);
+        assert_eq!(err.col, 9);
+    }
+
+    #[test]
+    fn test_void_element_with_close_tag_error() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <p>"a"<br></br>"b"</p>
+}
+"#;
+        let path = Path::new("test.volki");
+        let err = compile_source_full(source, path).unwrap_err();
+        assert!(err.message.contains("write it self-closing as `<br />`"));
+    }
+
+    #[test]
+    fn test_void_element_self_closing_compiles() {
+        let source = r#"
+pub fn page(_req: &Request) -> Html {
+    <div><img src="/a.png" /><hr /></div>
+}
+"#;
+        let path = Path::new("test.volki");
+        assert!(compile_source_full(source, path).is_ok());
+    }
 }
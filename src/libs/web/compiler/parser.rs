@@ -10,6 +10,12 @@ use super::CompileError;
 pub enum RsxAttrValue {
     Literal(String),
     Expr(String),
+    /// `{...expr}` — an iterable of `(name, value)` pairs. The owning
+    /// `RsxAttr` has an empty name.
+    Spread(String),
+    /// `class:name={cond}` — the condition expression. The owning `RsxAttr`
+    /// is named `class:name`; see [`RsxAttr::toggled_class`].
+    ClassToggle(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -18,6 +24,16 @@ pub struct RsxAttr {
     pub value: RsxAttrValue,
 }
 
+impl RsxAttr {
+    /// The class name of a `class:name={cond}` directive.
+    pub fn toggled_class(&self) -> Option<&str> {
+        match self.value {
+            RsxAttrValue::ClassToggle(_) => self.name.as_str().strip_prefix("class:"),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RsxNode {
     Element {
@@ -50,14 +66,14 @@ fn skip_ws(bytes: &[u8], mut pos: usize) -> usize {
     pos
 }
 
-/// Check if position starts JSX content: `<` + alpha, or `"`.
+/// Check if position starts JSX content: `<` + alpha, `<>`, or `"`.
 fn is_jsx_start(bytes: &[u8], pos: usize) -> bool {
     if pos >= bytes.len() {
         return false;
     }
     match bytes[pos] {
         b'"' => true,
-        b'<' => pos + 1 < bytes.len() && bytes[pos + 1].is_ascii_alphabetic(),
+        b'<' => pos + 1 < bytes.len() && (bytes[pos + 1].is_ascii_alphabetic() || bytes[pos + 1] == b'>'),
         _ => false,
     }
 }
@@ -228,6 +244,11 @@ fn find_ternary_colon(bytes: &[u8], len: usize) -> Option<usize> {
                     if i < len { i += 1; }
                     tag_depth -= 1;
                     continue;
+                } else if i + 1 < len && bytes[i + 1] == b'>' {
+                    // Fragment open <>
+                    i += 2;
+                    tag_depth += 1;
+                    continue;
                 } else if i + 1 < len && bytes[i + 1].is_ascii_alphabetic() {
                     // Open tag <name...> or <name.../>
                     i += 1;
@@ -322,15 +343,27 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parse all top-level nodes.
+    /// Parse all top-level nodes. A `<>...</>` fragment has no node of its
+    /// own: its children are spliced into the surrounding list.
     fn parse_nodes(&mut self) -> Result<Vec<RsxNode>, CompileError> {
         let mut nodes = Vec::new();
         while self.pos < self.tokens.len() {
-            // Stop if we see a CloseTag (means we're inside a parent element)
-            if let Some(Token::CloseTag(_)) = self.peek() {
-                break;
+            // Stop if we see a close tag (means we're inside a parent element or fragment)
+            match self.peek() {
+                Some(Token::CloseTag(_)) | Some(Token::FragmentClose) => break,
+                Some(Token::FragmentOpen) => {
+                    self.pos += 1;
+                    let children = self.parse_nodes()?;
+                    match self.advance() {
+                        Some(Token::FragmentClose) => {}
+                        _ => return Err(self.error("expected '</>' to close fragment")),
+                    }
+                    for child in children {
+                        nodes.push(child);
+                    }
+                }
+                _ => nodes.push(self.parse_node()?),
             }
-            nodes.push(self.parse_node()?);
         }
         Ok(nodes)
     }
@@ -445,11 +478,15 @@ impl<'a> Parser<'a> {
                         Some(Token::AttrName(n)) => n.clone(),
                         _ => return Err(self.error("expected attribute name")),
                     };
-                    // Expect =
-                    match self.advance() {
-                        Some(Token::AttrEquals) => {}
-                        _ => return Err(self.error("expected '=' after attribute name")),
+                    // A bare attribute (`<Style scoped>`) has no value
+                    if !matches!(self.peek(), Some(Token::AttrEquals)) {
+                        attrs.push(RsxAttr {
+                            name,
+                            value: RsxAttrValue::Literal(String::new()),
+                        });
+                        continue;
                     }
+                    self.advance();
                     // Expect value
                     let value = match self.advance() {
                         Some(Token::AttrValue(v)) => RsxAttrValue::Literal(v.clone()),
@@ -458,6 +495,31 @@ impl<'a> Parser<'a> {
                     };
                     attrs.push(RsxAttr { name, value });
                 }
+                Some(Token::ClassDirective(_)) => {
+                    let class = match self.advance() {
+                        Some(Token::ClassDirective(c)) => c.clone(),
+                        _ => return Err(self.error("expected class directive")),
+                    };
+                    match self.advance() {
+                        Some(Token::AttrEquals) => {}
+                        _ => return Err(self.error("expected '=' after class directive")),
+                    }
+                    let cond = match self.advance() {
+                        Some(Token::AttrExpr(e)) => e.clone(),
+                        _ => return Err(self.error("class directive requires an expression like class:active={is_active}")),
+                    };
+                    attrs.push(RsxAttr {
+                        name: crate::vformat!("class:{}", class),
+                        value: RsxAttrValue::ClassToggle(cond),
+                    });
+                }
+                Some(Token::AttrSpread(_)) => {
+                    let expr = match self.advance() {
+                        Some(Token::AttrSpread(e)) => e.clone(),
+                        _ => return Err(self.error("expected spread attribute")),
+                    };
+                    attrs.push(RsxAttr { name: String::new(), value: RsxAttrValue::Spread(expr) });
+                }
                 Some(Token::SelfCloseEnd) | Some(Token::TagEnd) => break,
                 _ => return Err(self.error("unexpected token in tag attributes")),
             }
@@ -573,6 +635,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_bare_attribute() {
+        let nodes = parse_rsx(r#"<Style scoped>{css()}</Style>"#);
+        match &nodes[0] {
+            RsxNode::Element { attrs, children, .. } => {
+                assert_eq!(attrs.len(), 1);
+                assert_eq!(attrs[0].name.as_str(), "scoped");
+                assert_eq!(attrs[0].value, RsxAttrValue::Literal(String::new()));
+                assert_eq!(children.len(), 1);
+            }
+            _ => panic!("expected element"),
+        }
+    }
+
     #[test]
     fn test_parse_mixed_children() {
         let nodes = parse_rsx(r#"<div>"hello"{content()}<span>"world"</span></div>"#);
@@ -857,4 +933,79 @@ mod tests {
         let err = result.unwrap_err();
         assert!(err.message.as_str().contains("conditional"));
     }
+
+    #[test]
+    fn test_parse_attr_spread() {
+        let nodes = parse_rsx(r#"<a {...link_attrs(item)} href="/x">"go"</a>"#);
+        match &nodes[0] {
+            RsxNode::Element { attrs, .. } => {
+                assert_eq!(attrs.len(), 2);
+                assert!(attrs[0].name.is_empty());
+                assert_eq!(attrs[0].value, RsxAttrValue::Spread(String::from("link_attrs(item)")));
+                assert_eq!(attrs[1].name.as_str(), "href");
+            }
+            _ => panic!("expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_class_directive() {
+        let nodes = parse_rsx(r#"<li class="tab" class:active={tab == current}>"x"</li>"#);
+        match &nodes[0] {
+            RsxNode::Element { attrs, .. } => {
+                assert_eq!(attrs.len(), 2);
+                assert_eq!(attrs[0].toggled_class(), None);
+                assert_eq!(attrs[1].name.as_str(), "class:active");
+                assert_eq!(attrs[1].value, RsxAttrValue::ClassToggle(String::from("tab == current")));
+                assert_eq!(attrs[1].toggled_class(), Some("active"));
+            }
+            _ => panic!("expected element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_class_directive_requires_expression() {
+        let file = PathBuf::from("<test>");
+        let tokens = tokenizer::tokenize(r#"<li class:active="yes" />"#, file.clone()).unwrap();
+        let err = parse(&tokens, file).unwrap_err();
+        assert!(err.message.contains("class:active={is_active}"));
+    }
+
+    #[test]
+    fn test_parse_fragment_shorthand_flattens() {
+        let nodes = parse_rsx(r#"<ul><><li>"a"</li><li>"b"</li></></ul>"#);
+        assert_eq!(nodes.len(), 1);
+        match &nodes[0] {
+            RsxNode::Element { tag, children, .. } => {
+                assert_eq!(tag.as_str(), "ul");
+                assert_eq!(children.len(), 2);
+                assert!(matches!(&children[0], RsxNode::Element { tag, .. } if tag.as_str() == "li"));
+            }
+            _ => panic!("expected element"),
+        }
+
+        let nodes = parse_rsx(r#"<>"a" <></> <b>"c"</b></>"#);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0], RsxNode::Text(String::from("a")));
+    }
+
+    #[test]
+    fn test_parse_fragment_in_ternary_branch() {
+        let nodes = parse_rsx(r#"{open ? <><dt>"k"</dt><dd>"v"</dd></> : <p>"none"</p>}"#);
+        match &nodes[0] {
+            RsxNode::Ternary { condition, if_true, if_false } => {
+                assert_eq!(condition.as_str(), "open");
+                assert_eq!(if_true.len(), 2);
+                assert_eq!(if_false.len(), 1);
+            }
+            _ => panic!("expected Ternary"),
+        }
+    }
+
+    #[test]
+    fn test_parse_unclosed_fragment_errors() {
+        let file = PathBuf::from("<test>");
+        let tokens = tokenizer::tokenize(r#"<><b>"x"</b>"#, file.clone()).unwrap();
+        assert!(parse(&tokens, file).is_err());
+    }
 }
@@ -3,9 +3,14 @@
 //! Conventions:
 //! - `app/page.volki` → page handler at `/`
 //! - `app/about/page.volki` → page handler at `/about`
-//! - `app/not_found.volki` → 404 handler
+//! - `app/not_found.volki` → 404 handler; `app/docs/not_found.volki` handles 404s under `/docs`
 //! - `app/api/tables/route.volki` or `route.rs` → API route at `/api/tables` (scans for pub fn get/post/etc.)
+//! - `app/posts/[id]/page.volki` → page at `/posts/:id`, with `id` read via `req.param("id")`
+//! - `app/docs/[...slug]/page.volki` → catch-all page at `/docs/*slug`
 //! - Other `.volki`/`.rs` files → utility modules (e.g., `shared.volki`)
+//!
+//! Bracketed directories are not valid Rust identifiers, so they become the
+//! modules `param_id` / `catchall_slug`, declared with `#[path = "[id]/mod.rs"]`.
 
 use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::fs;
@@ -82,11 +87,12 @@ fn scan_dir(
     let nf_volki = dir.join("not_found.volki");
     let nf_rs = dir.join("not_found.rs");
     if nf_volki.as_path().exists() || nf_rs.as_path().exists() {
+        let url = dir_to_url(dir, root);
         let mut module = dir_to_module(dir, root);
         module.push_str("::not_found");
         routes.push(DiscoveredRoute {
             kind: RouteKind::NotFound,
-            url_path: String::new(),
+            url_path: url,
             module_path: module,
             methods: Vec::new(),
             has_metadata: false,
@@ -192,8 +198,14 @@ pub fn generate_mod_file(dir: &Path) -> Result<String, CompileError> {
 
     let mut out = String::from("//! @generated by volki compiler \u{2014} do not edit.\n\n");
     for m in module_names.iter() {
+        let module = segment_to_module(m.as_str());
+        if module.as_str() != m.as_str() {
+            out.push_str("#[path = \"");
+            out.push_str(m.as_str());
+            out.push_str("/mod.rs\"]\n");
+        }
         out.push_str("pub mod ");
-        out.push_str(m.as_str());
+        out.push_str(module.as_str());
         out.push_str(";\n");
     }
 
@@ -398,10 +410,16 @@ pub fn generate_root_mod(
         }
     }
 
-    // Not-found handler (last)
+    // Not-found handlers (last) — `app/` is global, subdirectories are scoped
     for route in routes {
         if let RouteKind::NotFound = route.kind {
-            out.push_str("        .not_found_page(");
+            if route.url_path.as_str() == "/" {
+                out.push_str("        .not_found_page(");
+            } else {
+                out.push_str("        .not_found_page_at(\"");
+                out.push_str(route.url_path.as_str());
+                out.push_str("\", ");
+            }
             out.push_str(route.module_path.as_str());
             out.push_str("::page)\n");
         }
@@ -419,14 +437,40 @@ fn dir_to_url(dir: &Path, root: &Path) -> String {
     match dir.strip_prefix(app_path.as_path().as_str()) {
         Some(rel) if rel.is_empty() => String::from("/"),
         Some(rel) => {
-            let mut url = String::from("/");
-            url.push_str(rel);
+            let mut url = String::new();
+            for part in rel.split('/') {
+                if part.is_empty() {
+                    continue;
+                }
+                url.push('/');
+                if let Some(name) = part.strip_prefix("[...").and_then(|p| p.strip_suffix(']')) {
+                    url.push('*');
+                    url.push_str(name);
+                } else if let Some(name) = part.strip_prefix('[').and_then(|p| p.strip_suffix(']')) {
+                    url.push(':');
+                    url.push_str(name);
+                } else {
+                    url.push_str(part);
+                }
+            }
             url
         }
         None => String::from("/"),
     }
 }
 
+/// Map a directory name to its Rust module name: `[id]` → `param_id`,
+/// `[...slug]` → `catchall_slug`, anything else unchanged.
+fn segment_to_module(segment: &str) -> String {
+    if let Some(name) = segment.strip_prefix("[...").and_then(|p| p.strip_suffix(']')) {
+        crate::vformat!("catchall_{}", name)
+    } else if let Some(name) = segment.strip_prefix('[').and_then(|p| p.strip_suffix(']')) {
+        crate::vformat!("param_{}", name)
+    } else {
+        String::from(segment)
+    }
+}
+
 /// Convert directory path to Rust module path relative to root.
 fn dir_to_module(dir: &Path, root: &Path) -> String {
     match dir.strip_prefix(root.as_str()) {
@@ -440,7 +484,7 @@ fn dir_to_module(dir: &Path, root: &Path) -> String {
                 if !result.is_empty() {
                     result.push_str("::");
                 }
-                result.push_str(part);
+                result.push_str(segment_to_module(part).as_str());
             }
             result
         }
@@ -497,4 +541,59 @@ mod tests {
         add_unique(&mut v, String::from("page"));
         assert_eq!(v.len(), 2);
     }
+
+    #[test]
+    fn test_dir_to_url_params() {
+        let root = Path::new("/project");
+        let dir = Path::new("/project/app/posts/[id]/comments/[...rest]");
+        assert_eq!(dir_to_url(dir, root).as_str(), "/posts/:id/comments/*rest");
+    }
+
+    #[test]
+    fn test_dir_to_module_params() {
+        let root = Path::new("/project");
+        let dir = Path::new("/project/app/posts/[id]/[...slug]");
+        assert_eq!(dir_to_module(dir, root).as_str(), "app::posts::param_id::catchall_slug");
+    }
+
+    #[test]
+    fn test_generate_root_mod_registers_param_routes() {
+        let root = crate::core::volkiwithstds::env::temp_dir().join("volki_routes_param_test");
+        let _ = fs::remove_dir_all(root.as_path());
+        let post_dir = root.join("app/posts/[id]");
+        let docs_dir = root.join("app/docs/[...slug]");
+        fs::create_dir_all(post_dir.as_path()).unwrap();
+        fs::create_dir_all(docs_dir.as_path()).unwrap();
+        fs::write_str(post_dir.join("page.volki").as_path(), "pub fn page(req: &Request) -> Html {}").unwrap();
+        fs::write_str(docs_dir.join("page.volki").as_path(), "pub fn page(req: &Request) -> Html {}").unwrap();
+
+        let routes = discover_routes(root.as_path()).unwrap();
+        let root_mod = generate_root_mod(root.as_path(), &routes, None).unwrap();
+        assert!(root_mod.contains(".page(\"/posts/:id\", app::posts::param_id::page::page)"));
+        assert!(root_mod.contains(".page(\"/docs/*slug\", app::docs::catchall_slug::page::page)"));
+
+        let posts_mod = generate_mod_file(root.join("app/posts").as_path()).unwrap();
+        assert!(posts_mod.contains("#[path = \"[id]/mod.rs\"]\npub mod param_id;"));
+        let docs_mod = generate_mod_file(root.join("app/docs").as_path()).unwrap();
+        assert!(docs_mod.contains("#[path = \"[...slug]/mod.rs\"]\npub mod catchall_slug;"));
+
+        let _ = fs::remove_dir_all(root.as_path());
+    }
+
+    #[test]
+    fn test_generate_root_mod_scoped_not_found() {
+        let root = crate::core::volkiwithstds::env::temp_dir().join("volki_routes_not_found_test");
+        let _ = fs::remove_dir_all(root.as_path());
+        let docs_dir = root.join("app/docs");
+        fs::create_dir_all(docs_dir.as_path()).unwrap();
+        fs::write_str(root.join("app/not_found.volki").as_path(), "pub fn page(req: &Request) -> Html {}").unwrap();
+        fs::write_str(docs_dir.join("not_found.volki").as_path(), "pub fn page(req: &Request) -> Html {}").unwrap();
+
+        let routes = discover_routes(root.as_path()).unwrap();
+        let root_mod = generate_root_mod(root.as_path(), &routes, None).unwrap();
+        assert!(root_mod.contains(".not_found_page(app::not_found::page)"));
+        assert!(root_mod.contains(".not_found_page_at(\"/docs\", app::docs::not_found::page)"));
+
+        let _ = fs::remove_dir_all(root.as_path());
+    }
 }
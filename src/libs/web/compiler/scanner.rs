@@ -1,4 +1,5 @@
-//! Scanner — finds function bodies (-> Html / -> Fragment / -> Client) in source files.
+//! Scanner — finds function bodies (-> Html / -> Fragment / -> Client / -> Component /
+//! -> Shared) in source files.
 
 use crate::core::volkiwithstds::collections::{String, Vec};
 
@@ -8,6 +9,11 @@ pub enum RsxReturnType {
     Fragment,
     Client,
     Component,
+    /// `-> Shared<T>` (or `-> Shared` for `()`): a pure helper compiled into
+    /// both the server output and the client WASM module, with the wrapper
+    /// rewritten to `T`. It may not use client-only or server-only APIs, and
+    /// on the client side only `core` is available.
+    Shared,
 }
 
 /// A parameter extracted from a function signature: `(name, type)`.
@@ -28,6 +34,10 @@ pub struct RsxFunction {
     pub name: Option<String>,
     /// Function parameters (extracted for Client/Component/Fragment functions, empty for Html).
     pub params: Vec<FnParam>,
+    /// `-> Client<&str>`: the body's tail `&str` is handed back to JS. Other
+    /// type arguments are kept in `return_type_span` and rejected by
+    /// `boundary::validate_boundaries`.
+    pub returns_string: bool,
 }
 
 /// Result of splitting a Component body into logic and RSX sections.
@@ -170,7 +180,16 @@ pub fn scan_functions(source: &str) -> Vec<RsxFunction> {
             let ws_end = skip_whitespace(bytes, arrow_end);
 
             // Check for "Html", "Fragment", or "Client"
-            if let Some((ret_type, ret_end)) = match_return_type(bytes, ws_end) {
+            if let Some((ret_type, mut ret_end)) = match_return_type(bytes, ws_end) {
+                let mut returns_string = false;
+                if (ret_type == RsxReturnType::Client || ret_type == RsxReturnType::Shared)
+                    && let Some(end) = match_generic_arg(bytes, ret_end)
+                {
+                    returns_string = ret_type == RsxReturnType::Client
+                        && is_str_arg(&source[ret_end + 1..end - 1]);
+                    ret_end = end;
+                }
+
                 // Find the opening brace of the function body
                 let brace_start = skip_whitespace(bytes, ret_end);
                 if brace_start < len && bytes[brace_start] == b'{' {
@@ -179,7 +198,8 @@ pub fn scan_functions(source: &str) -> Vec<RsxFunction> {
                         // Extract name and params from the function signature
                         let (name, params) = if ret_type == RsxReturnType::Client
                             || ret_type == RsxReturnType::Component
-                            || ret_type == RsxReturnType::Fragment {
+                            || ret_type == RsxReturnType::Fragment
+                            || ret_type == RsxReturnType::Shared {
                             extract_fn_signature(source, arrow_start)
                         } else {
                             (extract_fn_name_only(source, arrow_start), Vec::new())
@@ -191,6 +211,7 @@ pub fn scan_functions(source: &str) -> Vec<RsxFunction> {
                             body_span: (brace_start + 1, brace_end),
                             name,
                             params,
+                            returns_string,
                         });
                         i = brace_end + 1;
                         continue;
@@ -228,9 +249,96 @@ fn match_return_type(bytes: &[u8], pos: usize) -> Option<(RsxReturnType, usize)>
             return Some((RsxReturnType::Component, pos + 9));
         }
     }
+    if pos + 6 <= len
+        && &bytes[pos..pos + 6] == b"Shared"
+        && (pos + 6 >= len || !is_ident_char(bytes[pos + 6]))
+    {
+        return Some((RsxReturnType::Shared, pos + 6));
+    }
+    None
+}
+
+/// Match a `<...>` type argument (with nesting) right after `Shared` or `Client`,
+/// returning the position past the closing `>`.
+fn match_generic_arg(bytes: &[u8], pos: usize) -> Option<usize> {
+    if bytes.get(pos) != Some(&b'<') {
+        return None;
+    }
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate().skip(pos) {
+        match b {
+            b'<' => depth += 1,
+            b'>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b'{' | b';' => return None,
+            _ => {}
+        }
+    }
     None
 }
 
+/// The real return type of a `-> Shared<T>` function: `T`, or `()` for a
+/// bare `-> Shared`.
+pub fn shared_return_type<'a>(source: &'a str, func: &RsxFunction) -> &'a str {
+    let span = &source[func.return_type_span.0..func.return_type_span.1];
+    match span.strip_prefix("Shared<").and_then(|s| s.strip_suffix('>')) {
+        Some(inner) => inner.trim(),
+        None => "()",
+    }
+}
+
+/// `dom::` calls that return an element handle.
+const DOM_HANDLE_CALLS: &[&str] = &["dom::query(", "dom::create(", "dom::query_all_get("];
+
+/// Names bound to a DOM element handle in `body`, i.e. the `name` in
+/// `let [mut] name[: T] = dom::query(..)` and the other handle-returning calls.
+pub fn dom_handle_vars(body: &str) -> Vec<&str> {
+    let mut vars = Vec::new();
+    for stmt in body.split(';') {
+        let Some(rest) = stmt.trim_start().strip_prefix("let ") else { continue };
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix("mut ").unwrap_or(rest).trim_start();
+        let name_len = rest.bytes().take_while(|&b| is_ident_char(b)).count();
+        let Some(eq) = rest.find('=') else { continue };
+        if name_len == 0 || eq < name_len {
+            continue;
+        }
+        let rhs = rest[eq + 1..].trim_start();
+        if DOM_HANDLE_CALLS.iter().any(|call| rhs.starts_with(call)) {
+            vars.push(&rest[..name_len]);
+        }
+    }
+    vars
+}
+
+/// Whether the method call whose `.` sits at `dot` in `body` has a DOM handle
+/// variable as its receiver (see [`dom_handle_vars`]).
+pub fn is_dom_handle_receiver(body: &str, dot: usize) -> bool {
+    let before = &body.as_bytes()[..dot];
+    let name_len = before.iter().rev().take_while(|&&b| is_ident_char(b)).count();
+    if name_len == 0 {
+        return false;
+    }
+    let name = &body[dot - name_len..dot];
+    dom_handle_vars(body).iter().any(|&v| v == name)
+}
+
+/// Whether `body` calls `.remove()` on a DOM handle. Other receivers, such
+/// as a map entry, keep their own `remove`.
+pub fn has_dom_remove(body: &str) -> bool {
+    body.match_indices(".remove()").any(|(i, _)| is_dom_handle_receiver(body, i))
+}
+
+/// Whether a `Client<...>` type argument is a borrowed string. The WASM
+/// module has no allocator, so an owned `String` cannot be returned.
+fn is_str_arg(arg: &str) -> bool {
+    matches!(arg.trim(), "&str" | "&'static str")
+}
+
 fn is_ident_char(b: u8) -> bool {
     b.is_ascii_alphanumeric() || b == b'_'
 }
@@ -509,6 +617,34 @@ pub fn on_click(target: &str) -> Client {
         assert_eq!(fns[0].params[0].ty.as_str(), "&str");
     }
 
+    #[test]
+    fn test_scan_client_string_return() {
+        let source = r#"
+pub fn greeting(name: &str) -> Client<&str> {
+    "hello"
+}
+
+pub fn label() -> Client<String> {
+    "label"
+}
+
+pub fn on_click() -> Client {
+    dom::log("clicked");
+}
+"#;
+        let fns = scan_functions(source);
+        assert_eq!(fns.len(), 3);
+        assert!(fns.iter().all(|f| f.return_type == RsxReturnType::Client));
+        let rt = &source[fns[0].return_type_span.0..fns[0].return_type_span.1];
+        assert_eq!(rt, "Client<&str>");
+        assert!(fns[0].returns_string);
+        let rt = &source[fns[1].return_type_span.0..fns[1].return_type_span.1];
+        assert_eq!(rt, "Client<String>");
+        assert!(!fns[1].returns_string);
+        assert!(!fns[2].returns_string);
+        assert_eq!(fns[0].params[0].name.as_str(), "name");
+    }
+
     #[test]
     fn test_scan_client_multiple_params() {
         let source = r#"
@@ -821,4 +957,36 @@ pub fn greeting() -> Component {
         let rsx = &source[split.rsx_span.0..split.rsx_span.1];
         assert!(rsx.contains("<div>"));
     }
+
+    #[test]
+    fn test_scan_shared_function() {
+        let source = r#"
+fn format_price(cents: i64) -> Shared<i64> {
+    cents / 100
+}
+
+pub fn clamp_all(xs: &mut [i32]) -> Shared {
+    for x in xs.iter_mut() { *x = (*x).max(0); }
+}
+
+fn pair() -> Shared<(Option<u8>, Result<u8, ()>)> {
+    (None, Ok(1))
+}
+"#;
+        let fns = scan_functions(source);
+        assert_eq!(fns.len(), 3);
+        assert!(fns.iter().all(|f| f.return_type == RsxReturnType::Shared));
+        assert_eq!(fns[0].name.as_deref(), Some("format_price"));
+        assert_eq!(fns[0].params.len(), 1);
+        assert_eq!(shared_return_type(source, &fns[0]), "i64");
+        assert_eq!(shared_return_type(source, &fns[1]), "()");
+        assert_eq!(shared_return_type(source, &fns[2]), "(Option<u8>, Result<u8, ()>)");
+        assert!(source[fns[2].body_span.0..fns[2].body_span.1].contains("(None, Ok(1))"));
+    }
+
+    #[test]
+    fn test_scan_shared_prefix_is_not_shared() {
+        let fns = scan_functions("fn a() -> SharedState {\n}\n");
+        assert!(fns.is_empty());
+    }
 }
@@ -4,6 +4,8 @@
 //! - Custom component tags (`<MyComponent />`) must resolve to a function.
 //! - Resolved component functions must return `Fragment`.
 //! - Component props must match function parameters.
+//! - Private Fragment/Component functions that are never referenced are
+//!   reported as warnings.
 
 use crate::core::volkiwithstds::collections::{String, Vec};
 use crate::core::volkiwithstds::fs;
@@ -11,7 +13,7 @@ use crate::core::volkiwithstds::path::{Path, PathBuf};
 
 use super::parser::{RsxAttrValue, RsxNode};
 use super::scanner::{FnParam, RsxFunction, RsxReturnType};
-use super::CompileError;
+use super::{CompileError, CompileWarning};
 
 struct UseStmt {
     module_segments: Vec<String>,
@@ -93,6 +95,7 @@ pub fn validate_component_resolution(
             nodes,
             &client_symbols,
         )?;
+        validate_void_elements(source, file, func.body_span, nodes)?;
 
         let mut component_tags = Vec::new();
         collect_component_tags(nodes, &mut component_tags);
@@ -167,6 +170,20 @@ fn validate_component_props(
                     {
                         // Check each attr has a matching param
                         for attr in attrs {
+                            if matches!(attr.value, RsxAttrValue::Spread(_)) {
+                                let offset = find_component_tag_offset(source, body_span, tag.as_str())
+                                    .unwrap_or(body_span.0);
+                                let (line, col) = line_col_at(source, offset);
+                                return Err(CompileError {
+                                    file: file.to_path_buf(),
+                                    line,
+                                    col,
+                                    message: crate::vformat!(
+                                        "spread attributes are not supported on component `{}`; pass props by name",
+                                        tag
+                                    ),
+                                });
+                            }
                             let has_param = params.iter().any(|p| p.name.as_str() == attr.name.as_str());
                             if !has_param {
                                 let offset = find_attr_offset(source, body_span, attr.name.as_str())
@@ -360,6 +377,66 @@ fn validate_node_event_bindings(
     Ok(())
 }
 
+/// HTML void elements: they never have content or a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Reject void elements written with children or a closing tag; they must
+/// be self-closing, e.g. `<img src="..." />`.
+fn validate_void_elements(
+    source: &str,
+    file: &Path,
+    body_span: (usize, usize),
+    nodes: &[RsxNode],
+) -> Result<(), CompileError> {
+    for node in nodes {
+        match node {
+            RsxNode::Element { tag, children, self_closing, .. } => {
+                if !self_closing && VOID_ELEMENTS.contains(&tag.as_str()) {
+                    let offset = find_void_misuse_offset(source, body_span, tag.as_str())
+                        .unwrap_or(body_span.0);
+                    let (line, col) = line_col_at(source, offset);
+                    let message = if children.is_empty() {
+                        crate::vformat!("`<{}>` is a void element; write it self-closing as `<{} />`", tag, tag)
+                    } else {
+                        crate::vformat!("`<{}>` is a void element and cannot have children", tag)
+                    };
+                    return Err(CompileError {
+                        file: file.to_path_buf(),
+                        line,
+                        col,
+                        message,
+                    });
+                }
+                validate_void_elements(source, file, body_span, children)?;
+            }
+            RsxNode::CondAnd { body, .. } => {
+                validate_void_elements(source, file, body_span, body)?;
+            }
+            RsxNode::Ternary { if_true, if_false, .. } => {
+                validate_void_elements(source, file, body_span, if_true)?;
+                validate_void_elements(source, file, body_span, if_false)?;
+            }
+            RsxNode::Text(_) | RsxNode::Expr(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Offset of the opening tag matched by the first `</tag>` in the body.
+fn find_void_misuse_offset(source: &str, body_span: (usize, usize), tag: &str) -> Option<usize> {
+    if body_span.1 <= body_span.0 || body_span.1 > source.len() {
+        return None;
+    }
+    let body = &source[body_span.0..body_span.1];
+    let close = crate::vformat!("</{}>", tag);
+    let close_idx = body.find(close.as_str())?;
+    let open = crate::vformat!("<{}", tag);
+    body[..close_idx].rfind(open.as_str()).map(|idx| body_span.0 + idx)
+}
+
 fn attr_error(
     source: &str,
     file: &Path,
@@ -399,6 +476,102 @@ fn is_identifier(expr: &str) -> bool {
     bytes.all(|b| b.is_ascii_alphanumeric() || b == b'_')
 }
 
+/// Warn about private Fragment/Component functions that are never used as a
+/// tag or called anywhere in the file. `pub` functions may be imported by
+/// other modules and are skipped.
+pub fn find_unused_components(
+    source: &str,
+    file: &Path,
+    functions: &[RsxFunction],
+) -> Vec<CompileWarning> {
+    let mut warnings = Vec::new();
+    for f in functions {
+        if f.return_type != RsxReturnType::Fragment && f.return_type != RsxReturnType::Component {
+            continue;
+        }
+        let Some(name) = &f.name else {
+            continue;
+        };
+        let fn_start = super::find_fn_start(source, f.return_type_span.0);
+        let line_start = source[..fn_start].rfind('\n').map_or(0, |i| i + 1);
+        let decl = &source[line_start..f.return_type_span.0];
+        if decl.trim_start().starts_with("pub") {
+            continue;
+        }
+        let (referenced, def_offset) = find_symbol_references(source, name.as_str());
+        if referenced {
+            continue;
+        }
+        let (line, col) = line_col_at(source, def_offset.unwrap_or(fn_start));
+        let pascal = snake_to_pascal(name.as_str());
+        warnings.push(CompileWarning {
+            file: file.to_path_buf(),
+            line,
+            col,
+            message: crate::vformat!(
+                "unused {} `{}`: never rendered as <{} /> or called in this file",
+                return_type_name(f.return_type),
+                name,
+                pascal
+            ),
+        });
+    }
+    warnings
+}
+
+/// Scan identifiers for uses of `name` as a call/path or as a `<PascalCase>`
+/// tag. Returns whether any use exists, plus the offset of its `fn` declaration.
+fn find_symbol_references(source: &str, name: &str) -> (bool, Option<usize>) {
+    let bytes = source.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut def_offset = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !is_ident(bytes[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && is_ident(bytes[i]) {
+            i += 1;
+        }
+        let word = &source[start..i];
+        let before = source[..start].trim_end();
+        if word == name {
+            let is_decl = before.ends_with("fn")
+                && !before[..before.len() - 2]
+                    .ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_');
+            if is_decl && def_offset.is_none() {
+                def_offset = Some(start);
+            } else if !is_decl {
+                return (true, def_offset);
+            }
+        } else if is_custom_component_tag(word)
+            && (before.ends_with('<') || before.ends_with("</"))
+            && super::pascal_to_snake(word).as_str() == name
+        {
+            return (true, def_offset);
+        }
+    }
+    (false, def_offset)
+}
+
+fn snake_to_pascal(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = true;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 fn collect_local_symbols(functions: &[RsxFunction]) -> Vec<(String, RsxReturnType)> {
     let mut symbols = Vec::new();
     for f in functions {
@@ -641,6 +814,7 @@ fn return_type_name(rt: RsxReturnType) -> &'static str {
         RsxReturnType::Fragment => "Fragment",
         RsxReturnType::Client => "Client",
         RsxReturnType::Component => "Component",
+        RsxReturnType::Shared => "Shared",
     }
 }
 
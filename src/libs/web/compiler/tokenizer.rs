@@ -23,10 +23,18 @@ pub enum Token {
     AttrValue(String),
     /// `{expr}` — brace attribute expression (without outer braces)
     AttrExpr(String),
+    /// `{...expr}` — spread of `(name, value)` pairs (without braces and `...`)
+    AttrSpread(String),
+    /// `class:name` — conditional class directive (the class name only)
+    ClassDirective(String),
     /// `"hello"` — string literal as a child node
     TextLiteral(String),
     /// `{expr()}` — Rust expression (brace-matched, without outer braces)
     Expression(String),
+    /// `<>` — opens an anonymous fragment
+    FragmentOpen,
+    /// `</>` — closes an anonymous fragment
+    FragmentClose,
 }
 
 struct Tokenizer<'a> {
@@ -125,6 +133,21 @@ impl<'a> Tokenizer<'a> {
         String::from(s)
     }
 
+    /// Read a class name for `class:name`, which may contain utility
+    /// characters like `:` and `/` (e.g. `md:hidden`, `w-1/2`).
+    fn read_class_name(&mut self) -> String {
+        let start = self.pos;
+        while self.pos < self.bytes.len() {
+            let b = self.bytes[self.pos];
+            if b == b'=' || b == b'>' || b.is_ascii_whitespace() || (b == b'/' && self.peek_at(1) == Some(b'>')) {
+                break;
+            }
+            self.pos += 1;
+        }
+        let s = unsafe { core::str::from_utf8_unchecked(&self.bytes[start..self.pos]) };
+        String::from(s)
+    }
+
     fn read_quoted_string(&mut self) -> Result<String, CompileError> {
         // Skip the opening quote
         self.advance();
@@ -219,11 +242,28 @@ impl<'a> Tokenizer<'a> {
                     }
                     b'{' => {
                         let expr = self.read_brace_expression()?;
-                        self.tokens.push(Token::AttrExpr(expr));
+                        if let Some(rest) = expr.as_str().strip_prefix("...") {
+                            let rest = rest.trim();
+                            if rest.is_empty() {
+                                return Err(self.error("expected expression after '...' in spread attribute"));
+                            }
+                            self.tokens.push(Token::AttrSpread(String::from(rest)));
+                        } else {
+                            self.tokens.push(Token::AttrExpr(expr));
+                        }
                     }
                     _ if b.is_ascii_alphabetic() || b == b'_' => {
                         let name = self.read_ident();
-                        self.tokens.push(Token::AttrName(name));
+                        if name.as_str() == "class" && self.peek() == Some(b':') {
+                            self.pos += 1;
+                            let class = self.read_class_name();
+                            if class.is_empty() {
+                                return Err(self.error("expected class name after 'class:'"));
+                            }
+                            self.tokens.push(Token::ClassDirective(class));
+                        } else {
+                            self.tokens.push(Token::AttrName(name));
+                        }
                     }
                     _ => {
                         return Err(self.error("unexpected character in tag"));
@@ -232,6 +272,14 @@ impl<'a> Tokenizer<'a> {
             } else {
                 // Outside a tag — parse children, new tags, close tags, text, expressions
                 match b {
+                    b'<' if self.peek_at(1) == Some(b'>') => {
+                        self.pos += 2;
+                        self.tokens.push(Token::FragmentOpen);
+                    }
+                    b'<' if self.peek_at(1) == Some(b'/') && self.peek_at(2) == Some(b'>') => {
+                        self.pos += 3;
+                        self.tokens.push(Token::FragmentClose);
+                    }
                     b'<' if self.peek_at(1) == Some(b'/') => {
                         // Close tag: </name>
                         self.pos += 2;
@@ -383,4 +431,50 @@ mod tests {
             Token::CloseTag(String::from("div")),
         ]);
     }
+
+    #[test]
+    fn test_tokenize_attr_spread() {
+        let tokens = tok(r#"<div class="x" {...extra_attrs} />"#);
+        assert_eq!(tokens, vvec![
+            Token::OpenTag(String::from("div")),
+            Token::AttrName(String::from("class")),
+            Token::AttrEquals,
+            Token::AttrValue(String::from("x")),
+            Token::AttrSpread(String::from("extra_attrs")),
+            Token::SelfCloseEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_tokenize_empty_spread_error() {
+        assert!(tokenize("<div {...} />", PathBuf::from("<test>")).is_err());
+    }
+
+    #[test]
+    fn test_tokenize_class_directive() {
+        let tokens = tok(r#"<li class:active={is_active} class:md:hidden={collapsed}/>"#);
+        assert_eq!(tokens, vvec![
+            Token::OpenTag(String::from("li")),
+            Token::ClassDirective(String::from("active")),
+            Token::AttrEquals,
+            Token::AttrExpr(String::from("is_active")),
+            Token::ClassDirective(String::from("md:hidden")),
+            Token::AttrEquals,
+            Token::AttrExpr(String::from("collapsed")),
+            Token::SelfCloseEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_tokenize_fragment_shorthand() {
+        let tokens = tok(r#"<><b>"x"</b></>"#);
+        assert_eq!(tokens, vvec![
+            Token::FragmentOpen,
+            Token::OpenTag(String::from("b")),
+            Token::TagEnd,
+            Token::TextLiteral(String::from("x")),
+            Token::CloseTag(String::from("b")),
+            Token::FragmentClose,
+        ]);
+    }
 }
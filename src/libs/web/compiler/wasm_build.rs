@@ -4,9 +4,14 @@
 //! ```text
 //! rustc --target wasm32-unknown-unknown --crate-type cdylib -O --edition 2024 -o output.wasm input.rs
 //! ```
+//!
+//! Builds are cached: a `<output>.wasm.hash` sidecar records a fingerprint of
+//! the `_client.rs` content, the `rustc -vV` output and the flags above, and
+//! `rustc` is skipped while the fingerprint matches and the `.wasm` exists.
 
 use crate::core::volkiwithstds::collections::String;
-use crate::core::volkiwithstds::path::Path;
+use crate::core::volkiwithstds::fs;
+use crate::core::volkiwithstds::path::{Path, PathBuf};
 use crate::core::volkiwithstds::process::Command;
 
 use super::CompileError;
@@ -16,12 +21,23 @@ use super::CompileError;
 /// - `client_rs` — path to the generated Rust source (e.g., `dist/app/page_client.rs`)
 /// - `output_wasm` — path for the output `.wasm` file (e.g., `public/wasm/page_client.wasm`)
 ///
-/// Returns `Ok(())` on success, or a `CompileError` with a helpful message if
-/// the wasm target is missing or `rustc` fails.
+/// Returns `Ok(())` on success (including a cache hit), or a `CompileError`
+/// with a helpful message if the wasm target is missing or `rustc` fails.
 pub fn compile_wasm(client_rs: &Path, output_wasm: &Path) -> Result<(), CompileError> {
+    let hash_path = hash_sidecar_path(output_wasm);
+    let fingerprint = fs::read_to_string(client_rs)
+        .ok()
+        .map(|source| build_fingerprint(source.as_str(), rustc_version().as_str()));
+    if let Some(fp) = &fingerprint
+        && fs::exists(output_wasm)
+        && fs::read_to_string(hash_path.as_path()).is_ok_and(|stored| stored.as_str().trim() == fp.as_str())
+    {
+        return Ok(());
+    }
+
     // Ensure output directory exists
     if let Some(parent) = output_wasm.parent() {
-        crate::core::volkiwithstds::fs::create_dir_all(parent).map_err(|e| CompileError {
+        fs::create_dir_all(parent).map_err(|e| CompileError {
             file: output_wasm.to_path_buf(),
             line: 0,
             col: 0,
@@ -45,8 +61,13 @@ pub fn compile_wasm(client_rs: &Path, output_wasm: &Path) -> Result<(), CompileE
     match output {
         Ok(result) => {
             if result.status.success() {
+                // The cache is best-effort: a missing sidecar only costs a rebuild
+                if let Some(fp) = &fingerprint {
+                    let _ = fs::write_str(hash_path.as_path(), fp.as_str());
+                }
                 Ok(())
             } else {
+                let _ = fs::remove_file(hash_path.as_path());
                 let stderr = from_utf8_lossy(&result.stderr);
                 // Check for common "target not installed" message
                 if stderr.as_str().contains("wasm32-unknown-unknown") && stderr.as_str().contains("target") {
@@ -84,6 +105,32 @@ pub fn compile_wasm(client_rs: &Path, output_wasm: &Path) -> Result<(), CompileE
     }
 }
 
+/// `page_client.wasm` → `page_client.wasm.hash`
+fn hash_sidecar_path(output_wasm: &Path) -> PathBuf {
+    PathBuf::from(crate::vformat!("{}.hash", output_wasm).as_str())
+}
+
+/// Verbose `rustc` version (includes the commit hash), or empty if unavailable.
+fn rustc_version() -> String {
+    match Command::new("rustc").arg("-vV").output() {
+        Ok(result) if result.status.success() => from_utf8_lossy(&result.stdout),
+        _ => String::new(),
+    }
+}
+
+/// FNV-1a over the client source, compiler version and build flags, as hex.
+fn build_fingerprint(source: &str, rustc_version: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let parts = [source, rustc_version, "wasm32-unknown-unknown cdylib -O 2024"];
+    for part in parts {
+        for &b in part.as_bytes().iter().chain(&[0u8]) {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    crate::vformat!("{:016x}", hash)
+}
+
 /// Check if the wasm32-unknown-unknown target is available.
 pub fn check_wasm_target() -> bool {
     let output = Command::new("rustup")
@@ -130,4 +177,45 @@ mod tests {
         // Just verify it doesn't panic — result depends on environment
         let _ = check_wasm_target();
     }
+
+    #[test]
+    fn test_build_fingerprint_invalidates() {
+        let base = build_fingerprint("fn a() {}", "rustc 1.90.0");
+        assert_eq!(base.len(), 16);
+        assert_eq!(base, build_fingerprint("fn a() {}", "rustc 1.90.0"));
+        assert_ne!(base, build_fingerprint("fn b() {}", "rustc 1.90.0"));
+        assert_ne!(base, build_fingerprint("fn a() {}", "rustc 1.91.0"));
+        // Part boundaries are separated, so content can't shift between parts
+        assert_ne!(build_fingerprint("ab", "c"), build_fingerprint("a", "bc"));
+    }
+
+    #[test]
+    fn test_hash_sidecar_path() {
+        let p = hash_sidecar_path(Path::new("dist/public/wasm/page_client.wasm"));
+        assert_eq!(p.as_str(), "dist/public/wasm/page_client.wasm.hash");
+    }
+
+    #[test]
+    fn test_compile_wasm_cache_hit_skips_rustc() {
+        let dir = crate::core::volkiwithstds::env::temp_dir().join("volki_wasm_cache_test");
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.as_path()).unwrap();
+        let client_rs = dir.join("page_client.rs");
+        let wasm = dir.join("page_client.wasm");
+        // Not valid Rust: a cache miss would make rustc fail
+        fs::write_str(client_rs.as_path(), "not rust").unwrap();
+        fs::write(wasm.as_path(), b"\0asm").unwrap();
+        let fp = build_fingerprint("not rust", rustc_version().as_str());
+        fs::write_str(hash_sidecar_path(wasm.as_path()).as_path(), fp.as_str()).unwrap();
+
+        assert!(compile_wasm(client_rs.as_path(), wasm.as_path()).is_ok());
+        assert_eq!(fs::read(wasm.as_path()).unwrap().as_slice(), b"\0asm");
+
+        // Changed source → stale hash → rustc runs (and fails on this input)
+        fs::write_str(client_rs.as_path(), "still not rust").unwrap();
+        assert!(compile_wasm(client_rs.as_path(), wasm.as_path()).is_err());
+        assert!(!fs::exists(hash_sidecar_path(wasm.as_path()).as_path()));
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
 }
@@ -7,7 +7,7 @@
 
 use crate::core::volkiwithstds::collections::{String, Vec};
 use super::parser::RsxNode;
-use super::scanner::{self, RsxFunction};
+use super::scanner::{self, RsxFunction, RsxReturnType};
 use super::wasm_rsx_codegen;
 use crate::libs::web::wasm::types::{WasmAbi, rust_type_to_wasm, wasm_type_str};
 
@@ -120,16 +120,24 @@ pub fn generate_wasm_module(
     let mut needs_append = false;
     let mut needs_remove = false;
     let mut needs_set_html = false;
+    let mut needs_append_html = false;
     let mut needs_toggle_class = false;
     let mut needs_get_attr = false;
     let mut needs_remove_attr = false;
     let mut needs_query_all_count = false;
     let mut needs_query_all_get = false;
+    let mut needs_storage_get = false;
+    let mut needs_storage_set = false;
+    let mut needs_storage_remove = false;
+    let mut needs_fetch = false;
+    let mut needs_prevent_default = false;
+    let mut needs_stop_propagation = false;
 
     // RSX-specific externs
     let mut needs_create_text = false;
     let mut needs_is_mounted = false;
     let mut needs_mount_point = false;
+    let mut needs_set_key = false;
 
     // Also collect user-declared extern blocks from function bodies
     let mut user_externs: Vec<String> = Vec::new();
@@ -148,13 +156,20 @@ pub fn generate_wasm_module(
         // New DOM operations
         if body.contains("dom::create(") { needs_create = true; }
         if body.contains("dom::append(") { needs_append = true; }
-        if body.contains("dom::remove(") { needs_remove = true; }
+        if body.contains("dom::remove(") || scanner::has_dom_remove(body) { needs_remove = true; }
         if body.contains("dom::set_html(") { needs_set_html = true; }
+        if body.contains(".append_html(") { needs_append_html = true; }
         if body.contains(".toggle_class(") { needs_toggle_class = true; }
         if body.contains(".get_attr(") { needs_get_attr = true; }
         if body.contains(".remove_attr(") { needs_remove_attr = true; }
         if body.contains("dom::query_all_count(") { needs_query_all_count = true; }
         if body.contains("dom::query_all_get(") { needs_query_all_get = true; }
+        if body.contains("storage::get(") { needs_storage_get = true; }
+        if body.contains("storage::set(") { needs_storage_set = true; }
+        if body.contains("storage::remove(") { needs_storage_remove = true; }
+        if body.contains("net::fetch(") { needs_fetch = true; }
+        if body.contains("event::prevent_default(") { needs_prevent_default = true; }
+        if body.contains("event::stop_propagation(") { needs_stop_propagation = true; }
 
         // State imports
         if body.contains("use_state(") {
@@ -233,6 +248,7 @@ pub fn generate_wasm_module(
             if rsx_out.needs_add_class { needs_add_class = true; }
             if rsx_out.needs_set_attr { needs_set_attr = true; }
             if rsx_out.needs_set_text { needs_set_text = true; }
+            if rsx_out.needs_set_key { needs_set_key = true; }
             if rsx_out.needs_mount_point { needs_mount_point = true; }
             if rsx_out.needs_is_mounted { needs_is_mounted = true; }
             if rsx_out.needs_ref_get_i32 { needs_ref_get_i32 = true; }
@@ -261,6 +277,9 @@ pub fn generate_wasm_module(
     if needs_mount_point {
         out.push_str("    fn __volki_component_mount_point(id: i32) -> i32;\n");
     }
+    if needs_set_key {
+        out.push_str("    fn __volki_dom_set_key(handle: i32, key_ptr: i32, key_len: i32);\n");
+    }
 
     // State init
     if needs_state_init_i32 {
@@ -387,6 +406,9 @@ pub fn generate_wasm_module(
     if needs_set_html {
         out.push_str("    fn __volki_dom_set_html(handle: i32, html_ptr: i32, html_len: i32);\n");
     }
+    if needs_append_html {
+        out.push_str("    fn __volki_dom_append_html(handle: i32, html_ptr: i32, html_len: i32);\n");
+    }
     if needs_toggle_class {
         out.push_str("    fn __volki_dom_toggle_class(handle: i32, cls_ptr: i32, cls_len: i32);\n");
     }
@@ -403,6 +425,25 @@ pub fn generate_wasm_module(
     if needs_query_all_get {
         out.push_str("    fn __volki_dom_query_all_get(sel_ptr: i32, sel_len: i32, idx: i32) -> i32;\n");
     }
+    if needs_storage_get {
+        out.push_str("    fn __volki_storage_get(key_ptr: i32, key_len: i32) -> i32;\n");
+        out.push_str("    fn __volki_storage_get_len(key_ptr: i32, key_len: i32) -> i32;\n");
+    }
+    if needs_storage_set {
+        out.push_str("    fn __volki_storage_set(key_ptr: i32, key_len: i32, val_ptr: i32, val_len: i32);\n");
+    }
+    if needs_storage_remove {
+        out.push_str("    fn __volki_storage_remove(key_ptr: i32, key_len: i32);\n");
+    }
+    if needs_fetch {
+        out.push_str("    fn __volki_fetch(url_ptr: i32, url_len: i32, cb_ptr: i32, cb_len: i32);\n");
+    }
+    if needs_prevent_default {
+        out.push_str("    fn __volki_event_prevent_default();\n");
+    }
+    if needs_stop_propagation {
+        out.push_str("    fn __volki_event_stop_propagation();\n");
+    }
     // Append user extern declarations
     for ext in &user_externs {
         out.push_str("    ");
@@ -468,6 +509,16 @@ pub fn generate_wasm_module(
         }
     }
 
+    // Shared helpers, verbatim apart from the `Shared<T>` → `T` rewrite
+    for func in scanner::scan_functions(source).iter().filter(|f| f.return_type == RsxReturnType::Shared) {
+        let fn_start = super::find_fn_start(source, func.return_type_span.0);
+        out.push_str("#[allow(dead_code)]\n");
+        out.push_str(&source[fn_start..func.return_type_span.0]);
+        out.push_str(scanner::shared_return_type(source, func));
+        out.push_str(&source[func.return_type_span.1..=func.body_span.1]);
+        out.push_str("\n\n");
+    }
+
     // Generate Component functions
     for (i, func) in component_fns.iter().enumerate() {
         let rsx_out = if i < rsx_outputs.len() {
@@ -475,7 +526,7 @@ pub fn generate_wasm_module(
         } else {
             None
         };
-        generate_component_fn(func, source, i as u32, rsx_out, &mut out);
+        generate_component_fn(func, source, i as u32, &component_ids, rsx_out, &mut out);
     }
 
     // Generate Client functions
@@ -486,26 +537,11 @@ pub fn generate_wasm_module(
     out
 }
 
-/// Generate a single `#[no_mangle] pub extern "C"` Client function.
-fn generate_client_fn(
-    func: &RsxFunction,
-    source: &str,
-    component_ids: &[(String, u32)],
-    out: &mut String,
-) {
-    let name = match &func.name {
-        Some(n) => n.as_str(),
-        None => return,
-    };
-
-    out.push_str("#[unsafe(no_mangle)]\n");
-    out.push_str("pub extern \"C\" fn ");
-    out.push_str(name);
-    out.push('(');
-
-    // Flatten params to WASM ABI
+/// Write `params` as an export's parameter list, flattened to WASM ABI
+/// types: strings become `name_ptr: i32, name_len: i32`.
+fn push_abi_params(params: &[scanner::FnParam], out: &mut String) {
     let mut first = true;
-    for param in &func.params {
+    for param in params {
         let abi = rust_type_to_wasm(param.ty.as_str());
         if !first { out.push_str(", "); }
         first = false;
@@ -525,12 +561,28 @@ fn generate_client_fn(
             WasmAbi::Void => {}
         }
     }
+}
 
-    out.push_str(") {\n");
-
-    // Type reconstruction preamble for string params
-    for param in &func.params {
+/// Rebind flattened params to the types the function body expects: strings
+/// from (ptr, len), narrowed or unsigned scalars from the WASM value type.
+fn push_param_preamble(params: &[scanner::FnParam], out: &mut String) {
+    for param in params {
         let abi = rust_type_to_wasm(param.ty.as_str());
+        if let WasmAbi::Direct(wt) = abi
+            && param.ty.as_str() != wasm_type_str(wt)
+        {
+            out.push_str("    let ");
+            out.push_str(param.name.as_str());
+            out.push_str(" = ");
+            out.push_str(param.name.as_str());
+            if param.ty.as_str() == "bool" {
+                out.push_str(" != 0;\n");
+            } else {
+                out.push_str(" as ");
+                out.push_str(param.ty.as_str());
+                out.push_str(";\n");
+            }
+        }
         if abi == WasmAbi::StringPair {
             out.push_str("    let ");
             out.push_str(param.name.as_str());
@@ -541,11 +593,36 @@ fn generate_client_fn(
             out.push_str("_len as usize)) };\n");
         }
     }
+}
+
+/// Generate a single `#[no_mangle] pub extern "C"` Client function.
+fn generate_client_fn(
+    func: &RsxFunction,
+    source: &str,
+    component_ids: &[(String, u32)],
+    out: &mut String,
+) {
+    let name = match &func.name {
+        Some(n) => n.as_str(),
+        None => return,
+    };
+
+    out.push_str("#[unsafe(no_mangle)]\n");
+    out.push_str("pub extern \"C\" fn ");
+    out.push_str(name);
+    out.push('(');
+
+    push_abi_params(&func.params, out);
+
+    // String results come back as one i64: `len << 32 | ptr`
+    out.push_str(if func.returns_string { ") -> i64 {\n" } else { ") {\n" });
+
+    push_param_preamble(&func.params, out);
 
     // Transform and emit the function body
     let body = &source[func.body_span.0..func.body_span.1];
     let transformed = transform_client_body(body, component_ids);
-    out.push_str("    unsafe {\n");
+    out.push_str(if func.returns_string { "    let __ret: &str = unsafe {\n" } else { "    unsafe {\n" });
     for line in transformed.lines() {
         let trimmed = line.trim();
         if trimmed.is_empty() { continue; }
@@ -555,7 +632,12 @@ fn generate_client_fn(
         out.push_str(trimmed);
         out.push('\n');
     }
-    out.push_str("    }\n");
+    if func.returns_string {
+        out.push_str("    };\n");
+        out.push_str("    ((__ret.len() as i64) << 32) | (__ret.as_ptr() as usize as i64)\n");
+    } else {
+        out.push_str("    }\n");
+    }
     out.push_str("}\n\n");
 }
 
@@ -570,6 +652,7 @@ fn generate_component_fn(
     func: &RsxFunction,
     source: &str,
     component_id: u32,
+    component_ids: &[(String, u32)],
     rsx_output: Option<&wasm_rsx_codegen::WasmRsxOutput>,
     out: &mut String,
 ) {
@@ -577,11 +660,16 @@ fn generate_component_fn(
         Some(n) => n.as_str(),
         None => return,
     };
+    let effects;
 
     out.push_str("#[unsafe(no_mangle)]\n");
     out.push_str("pub extern \"C\" fn __volki_component_");
     out.push_str(name);
-    out.push_str("() {\n");
+    out.push('(');
+    // Props, read by the glue from the mount point's data-volki-prop-* attrs
+    push_abi_params(&func.params, out);
+    out.push_str(") {\n");
+    push_param_preamble(&func.params, out);
 
     out.push_str("    unsafe {\n");
     out.push_str("        __volki_component_begin(");
@@ -599,7 +687,9 @@ fn generate_component_fn(
         };
 
         // Transform and emit the logic section
-        let transformed_logic = transform_component_body(logic_body, component_id);
+        let (logic_body, hoisted) = hoist_effect_closures(logic_body);
+        effects = hoisted;
+        let transformed_logic = transform_component_body(logic_body.as_str(), component_id);
         for line in transformed_logic.lines() {
             let trimmed = line.trim();
             if trimmed.is_empty() { continue; }
@@ -634,8 +724,9 @@ fn generate_component_fn(
         }
     } else {
         // Old-style imperative path (no RSX)
-        let body = &source[func.body_span.0..func.body_span.1];
-        let transformed = transform_component_body(body, component_id);
+        let (body, hoisted) = hoist_effect_closures(&source[func.body_span.0..func.body_span.1]);
+        effects = hoisted;
+        let transformed = transform_component_body(body.as_str(), component_id);
         for line in transformed.lines() {
             let trimmed = line.trim();
             if trimmed.is_empty() { continue; }
@@ -649,6 +740,160 @@ fn generate_component_fn(
     out.push_str("        __volki_component_end();\n");
     out.push_str("    }\n");
     out.push_str("}\n\n");
+
+    for effect in &effects {
+        let slot = effect.slot;
+        generate_effect_fn(&crate::vformat!("__effect_{name}_{slot}"), effect, &effect.body, component_ids, out);
+        if let Some(cleanup) = &effect.cleanup {
+            generate_effect_fn(&crate::vformat!("__effect_cleanup_{name}_{slot}"), effect, cleanup, component_ids, out);
+        }
+    }
+}
+
+/// An inline `use_effect(&[deps], || { ... })` closure, hoisted out of the
+/// component body into its own export.
+struct EffectClosure {
+    slot: u32,
+    /// Parameter name per dependency: the dep itself when it is a plain
+    /// identifier, `__dep{i}` otherwise.
+    params: Vec<String>,
+    body: String,
+    /// Body of a `|| { ... }` closure returned as the effect's tail expression.
+    cleanup: Option<String>,
+}
+
+/// Rewrite every `use_effect(&[deps], || { ... })` in a component body to the
+/// registration-only `use_effect(&[deps]);` form and return the closures.
+///
+/// Slots count every `use_effect(` in order, closure or not, so they line up
+/// with the slots `transform_use_effect` assigns and with the
+/// `__effect_{component}_{slot}` names the JS scheduler looks up.
+fn hoist_effect_closures(body: &str) -> (String, Vec<EffectClosure>) {
+    let mut out = String::with_capacity(body.len());
+    let mut effects = Vec::new();
+    let mut pos = 0;
+    let mut slot: u32 = 0;
+
+    while let Some(rel) = body[pos..].find("use_effect(") {
+        let arg_start = pos + rel + "use_effect(".len();
+        let Some(arg_end) = find_closing_paren(body, arg_start) else { break };
+        out.push_str(&body[pos..arg_start]);
+        match split_effect_args(&body[arg_start..arg_end]) {
+            Some((deps_src, closure_body)) => {
+                out.push_str(deps_src);
+                let (effect_body, cleanup) = split_cleanup_closure(closure_body);
+                let params = deps_src[2..deps_src.len() - 1]
+                    .split(',')
+                    .map(|d| d.trim())
+                    .filter(|d| !d.is_empty())
+                    .enumerate()
+                    .map(|(i, d)| {
+                        let is_ident = d.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                            && !d.as_bytes()[0].is_ascii_digit();
+                        if is_ident { String::from(d) } else { crate::vformat!("__dep{i}") }
+                    })
+                    .collect();
+                effects.push(EffectClosure {
+                    slot,
+                    params,
+                    body: String::from(effect_body),
+                    cleanup: cleanup.map(String::from),
+                });
+            }
+            None => out.push_str(&body[arg_start..arg_end]),
+        }
+        pos = arg_end;
+        slot += 1;
+    }
+    out.push_str(&body[pos..]);
+    (out, effects)
+}
+
+/// Split `&[a, b], || { body }` (optionally `move ||`) into the deps source
+/// and the closure body. Returns `None` for the plain `&[a, b]` form.
+fn split_effect_args(args: &str) -> Option<(&str, &str)> {
+    let args = args.trim();
+    if !args.starts_with("&[") {
+        return None;
+    }
+    let close = args.find(']')?;
+    let rest = args[close + 1..].trim_start().strip_prefix(',')?.trim();
+    let rest = rest.strip_prefix("move").map(|r| r.trim_start()).unwrap_or(rest);
+    let inner = rest.strip_prefix("||")?.trim().strip_prefix('{')?.strip_suffix('}')?;
+    Some((&args[..=close], inner))
+}
+
+/// Split a trailing `|| { ... }` cleanup closure off an effect body.
+fn split_cleanup_closure(body: &str) -> (&str, Option<&str>) {
+    let bytes = body.as_bytes();
+    let mut depth: i32 = 0;
+    let mut last_or = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'(' => depth += 1,
+            b'}' | b')' => depth -= 1,
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' { i += 1; }
+                    i += 1;
+                }
+            }
+            b'|' if depth == 0 && bytes.get(i + 1) == Some(&b'|') => {
+                last_or = Some(i);
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let Some(at) = last_or else { return (body, None) };
+    let tail = body[at + 2..].trim();
+    let Some(inner) = tail.strip_prefix('{').and_then(|t| t.strip_suffix('}')) else {
+        return (body, None);
+    };
+    let head = body[..at].trim_end();
+    let head = head.strip_suffix("move").unwrap_or(head);
+    (head, Some(inner))
+}
+
+/// Emit one hoisted effect (or cleanup) body as an export taking the dep values.
+fn generate_effect_fn(
+    export_name: &str,
+    effect: &EffectClosure,
+    body: &str,
+    component_ids: &[(String, u32)],
+    out: &mut String,
+) {
+    out.push_str("#[unsafe(no_mangle)]\n");
+    out.push_str("#[allow(unused_variables)]\n");
+    out.push_str("pub extern \"C\" fn ");
+    out.push_str(export_name);
+    out.push('(');
+    for (i, param) in effect.params.iter().enumerate() {
+        if i > 0 { out.push_str(", "); }
+        out.push_str(param.as_str());
+        out.push_str(": i32");
+    }
+    out.push_str(") {\n");
+    // Typed rebinding so `dom::log(dep)` is recognised as an i32
+    let mut typed = String::new();
+    for param in &effect.params {
+        typed.push_str(crate::vformat!("let {param}: i32 = {param};\n").as_str());
+    }
+    typed.push_str(body);
+    out.push_str("    unsafe {\n");
+    for line in transform_client_body(typed.as_str(), component_ids).lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() { continue; }
+        out.push_str("        ");
+        out.push_str(trimmed);
+        out.push('\n');
+    }
+    out.push_str("    }\n");
+    out.push_str("}\n\n");
 }
 
 /// Count the number of `use_ref` / `use_ref_el` calls in a component's logic section.
@@ -680,6 +925,7 @@ fn count_user_refs(func: &RsxFunction, source: &str) -> u32 {
 fn transform_client_body(body: &str, component_ids: &[(String, u32)]) -> String {
     let mut out = String::with_capacity(body.len() * 2);
     let mut var_counter: u32 = 0;
+    let dom_handles = scanner::dom_handle_vars(body);
     let mut in_extern = false;
 
     for line in body.lines() {
@@ -782,6 +1028,41 @@ fn transform_client_body(body: &str, component_ids: &[(String, u32)]) -> String
             continue;
         }
 
+        // let v = storage::get(key) → two-call ptr+len pattern
+        if let Some(transformed) = transform_storage_get(trimmed, &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // storage::set(key, value) → __volki_storage_set(kptr, klen, vptr, vlen)
+        if let Some(transformed) = transform_storage_set(trimmed, &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // storage::remove(key) → __volki_storage_remove(ptr, len)
+        if let Some(transformed) = transform_storage_remove(trimmed, &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // net::fetch(url, "callback") → __volki_fetch(uptr, ulen, cptr, clen)
+        if let Some(transformed) = transform_net_fetch(trimmed, &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // event::prevent_default() → __volki_event_prevent_default()
+        if let Some(transformed) = transform_event_call(trimmed) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
         // dom::query(expr) → __volki_dom_query(ptr, len)
         if let Some(transformed) = transform_dom_query(trimmed, &mut var_counter) {
             out.push_str(transformed.as_str());
@@ -838,6 +1119,20 @@ fn transform_client_body(body: &str, component_ids: &[(String, u32)]) -> String
             continue;
         }
 
+        // el.append_html(html)
+        if let Some(transformed) = transform_method_call(trimmed, ".append_html(", "__volki_dom_append_html", &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // el.remove()
+        if let Some(transformed) = transform_method_remove(trimmed, &dom_handles) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
         // el.set_attr(name, value)
         if let Some(transformed) = transform_set_attr(trimmed, &mut var_counter) {
             out.push_str(transformed.as_str());
@@ -873,6 +1168,7 @@ fn transform_component_body(body: &str, component_id: u32) -> String {
     let mut memo_slot_counter: u32 = 0;
     let mut fmt_counter: u32 = 0;
     let mut in_extern = false;
+    let dom_handles = scanner::dom_handle_vars(body);
 
     for line in body.lines() {
         let trimmed = line.trim();
@@ -1060,6 +1356,20 @@ fn transform_component_body(body: &str, component_id: u32) -> String {
             continue;
         }
 
+        // el.append_html(html)
+        if let Some(transformed) = transform_method_call(trimmed, ".append_html(", "__volki_dom_append_html", &mut var_counter) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
+        // el.remove()
+        if let Some(transformed) = transform_method_remove(trimmed, &dom_handles) {
+            out.push_str(transformed.as_str());
+            out.push('\n');
+            continue;
+        }
+
         // el.set_attr(name, value)
         if let Some(transformed) = transform_set_attr(trimmed, &mut var_counter) {
             out.push_str(transformed.as_str());
@@ -1645,6 +1955,11 @@ fn is_i32_arg(arg: &str, body: &str) -> bool {
     if arg.starts_with("get_") && arg.ends_with(')') {
         return true;
     }
+    // Explicitly typed binding
+    let typed_pat = crate::vformat!("let {}: i32 ", arg);
+    if body.lines().any(|l| l.trim().starts_with(typed_pat.as_str())) {
+        return true;
+    }
     // Check how the variable was declared in the function body
     let let_pat = crate::vformat!("let {} = ", arg);
     for bline in body.lines() {
@@ -1657,6 +1972,7 @@ fn is_i32_arg(arg: &str, body: &str) -> bool {
             // String-returning sources
             if rest.contains(".get_value()") { return false; }
             if rest.starts_with("state::get_str(") { return false; }
+            if rest.starts_with("storage::get(") { return false; }
             if rest.starts_with("state::fmt_") { return false; }
             if rest.starts_with('"') { return false; }
         }
@@ -1874,6 +2190,22 @@ fn transform_dom_remove(line: &str) -> Option<String> {
     Some(out)
 }
 
+/// Transform `el.remove();` → `__volki_dom_remove(el);`. Only a variable
+/// bound to a DOM handle (see `scanner::dom_handle_vars`) is accepted as the
+/// receiver; any other `x.remove()` is left as ordinary Rust.
+fn transform_method_remove(line: &str, dom_handles: &[&str]) -> Option<String> {
+    let idx = line.find(".remove()")?;
+    let obj = line[..idx].trim();
+    if !dom_handles.contains(&obj) {
+        return None;
+    }
+    let mut out = String::from("__volki_dom_remove(");
+    out.push_str(obj);
+    out.push(')');
+    out.push_str(&line[idx + ".remove()".len()..]);
+    Some(out)
+}
+
 /// Transform `dom::set_html(handle, "html");` → `__volki_dom_set_html(handle, ptr, len);`
 fn transform_dom_set_html(line: &str, counter: &mut u32) -> Option<String> {
     let idx = line.find("dom::set_html(")?;
@@ -2009,6 +2341,129 @@ fn transform_get_value(line: &str) -> Option<String> {
     Some(out)
 }
 
+/// Bind a string argument (literal or expression) to `tmp` so it can be
+/// passed as `tmp.as_ptr(), tmp.len()`.
+fn push_str_binding(out: &mut String, tmp: &str, arg: &str) {
+    out.push_str("let ");
+    out.push_str(tmp);
+    out.push_str(" = ");
+    out.push_str(arg);
+    out.push_str(";\n");
+}
+
+/// Transform `let v = storage::get("key");` → two-call ptr+len pattern for string return.
+/// A missing key reads back as `""`.
+fn transform_storage_get(line: &str, counter: &mut u32) -> Option<String> {
+    let idx = line.find("storage::get(")?;
+    let var_name = extract_let_var(&line[..idx])?;
+    let arg_start = idx + "storage::get(".len();
+    let arg_end = find_closing_paren(line, arg_start)?;
+    let key_arg = line[arg_start..arg_end].trim();
+
+    let tmp = crate::vformat!("__sk{}", counter);
+    *counter += 1;
+
+    let mut out = String::new();
+    push_str_binding(&mut out, tmp.as_str(), key_arg);
+    for (var, ext) in [("__sgptr", "__volki_storage_get"), ("__sglen", "__volki_storage_get_len")] {
+        out.push_str(crate::vformat!(
+            "let {var} = {ext}({tmp}.as_ptr() as i32, {tmp}.len() as i32);\n"
+        ).as_str());
+    }
+    out.push_str("let ");
+    out.push_str(var_name);
+    out.push_str(" = core::str::from_utf8_unchecked(core::slice::from_raw_parts(__sgptr as *const u8, __sglen as usize));");
+    Some(out)
+}
+
+/// Transform `storage::set("key", value);` → `__volki_storage_set(kptr, klen, vptr, vlen);`
+fn transform_storage_set(line: &str, counter: &mut u32) -> Option<String> {
+    let idx = line.find("storage::set(")?;
+    let arg_start = idx + "storage::set(".len();
+    let arg_end = find_closing_paren(line, arg_start)?;
+    let args_str = &line[arg_start..arg_end];
+
+    // Split on comma (simple — assumes no commas in the key literal)
+    let comma = args_str.find(',')?;
+    let key_arg = args_str[..comma].trim();
+    let val_arg = args_str[comma + 1..].trim();
+
+    let tmp_k = crate::vformat!("__sk{}", counter);
+    let tmp_v = crate::vformat!("__sv{}", counter);
+    *counter += 1;
+
+    let mut out = String::new();
+    push_str_binding(&mut out, tmp_k.as_str(), key_arg);
+    push_str_binding(&mut out, tmp_v.as_str(), val_arg);
+    out.push_str(crate::vformat!(
+        "__volki_storage_set({tmp_k}.as_ptr() as i32, {tmp_k}.len() as i32, {tmp_v}.as_ptr() as i32, {tmp_v}.len() as i32);"
+    ).as_str());
+    Some(out)
+}
+
+/// Transform `storage::remove("key");` → `__volki_storage_remove(ptr, len);`
+fn transform_storage_remove(line: &str, counter: &mut u32) -> Option<String> {
+    let idx = line.find("storage::remove(")?;
+    let arg_start = idx + "storage::remove(".len();
+    let arg_end = find_closing_paren(line, arg_start)?;
+    let key_arg = line[arg_start..arg_end].trim();
+
+    let tmp = crate::vformat!("__sk{}", counter);
+    *counter += 1;
+
+    let mut out = String::new();
+    push_str_binding(&mut out, tmp.as_str(), key_arg);
+    out.push_str(crate::vformat!(
+        "__volki_storage_remove({tmp}.as_ptr() as i32, {tmp}.len() as i32);"
+    ).as_str());
+    Some(out)
+}
+
+/// Transform `event::prevent_default();` → `__volki_event_prevent_default();`
+/// (likewise `event::stop_propagation()`).
+fn transform_event_call(line: &str) -> Option<String> {
+    for api in ["prevent_default", "stop_propagation"] {
+        let pattern = crate::vformat!("event::{}(", api);
+        if let Some(idx) = line.find(pattern.as_str()) {
+            let mut out = String::from(&line[..idx]);
+            out.push_str("__volki_event_");
+            out.push_str(api);
+            out.push('(');
+            out.push_str(&line[idx + pattern.len()..]);
+            return Some(out);
+        }
+    }
+    None
+}
+
+/// Transform `net::fetch(url, "on_loaded");` → `__volki_fetch(uptr, ulen, cptr, clen);`
+///
+/// The request runs asynchronously in JS; when it settles, the glue calls the
+/// Client function named by the second argument as `on_loaded(status, body)`.
+fn transform_net_fetch(line: &str, counter: &mut u32) -> Option<String> {
+    let idx = line.find("net::fetch(")?;
+    let arg_start = idx + "net::fetch(".len();
+    let arg_end = find_closing_paren(line, arg_start)?;
+    let args_str = &line[arg_start..arg_end];
+
+    // The callback name is a plain identifier literal, so split on the last comma
+    let comma = args_str.rfind(',')?;
+    let url_arg = args_str[..comma].trim();
+    let cb_arg = args_str[comma + 1..].trim();
+
+    let tmp_u = crate::vformat!("__fu{}", counter);
+    let tmp_c = crate::vformat!("__fc{}", counter);
+    *counter += 1;
+
+    let mut out = String::new();
+    push_str_binding(&mut out, tmp_u.as_str(), url_arg);
+    push_str_binding(&mut out, tmp_c.as_str(), cb_arg);
+    out.push_str(crate::vformat!(
+        "__volki_fetch({tmp_u}.as_ptr() as i32, {tmp_u}.len() as i32, {tmp_c}.as_ptr() as i32, {tmp_c}.len() as i32);"
+    ).as_str());
+    Some(out)
+}
+
 /// Transform `let sum = use_memo_i32(a + b, &[a, b]);` → memo begin/changed/store/load.
 fn transform_use_memo(line: &str, memo_slot_counter: &mut u32) -> Option<String> {
     let (memo_idx, is_i32) = if let Some(idx) = line.find("use_memo_i32(") {
@@ -2490,6 +2945,185 @@ pub fn update(id: i32, text: &str) -> Client {
         assert!(wasm.contains("pub extern \"C\" fn update(id: i32, text_ptr: i32, text_len: i32)"));
     }
 
+    #[test]
+    fn test_generate_integer_width_params() {
+        let source = r#"
+pub fn seek(pos: i64, count: u32, step: i8, big: u64, on: bool) -> Client {
+    dom::log("seek");
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("pub extern \"C\" fn seek(pos: i64, count: i32, step: i32, big: i64, on: i32) {"));
+        assert!(!wasm.contains("let pos = "));
+        assert!(wasm.contains("let count = count as u32;"));
+        assert!(wasm.contains("let step = step as i8;"));
+        assert!(wasm.contains("let big = big as u64;"));
+        assert!(wasm.contains("let on = on != 0;"));
+    }
+
+    #[test]
+    fn test_generate_string_return() {
+        let source = r#"
+pub fn status_label(code: i32) -> Client<&str> {
+    if code == 0 { "idle" } else { "busy" }
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("pub extern \"C\" fn status_label(code: i32) -> i64 {"));
+        assert!(wasm.contains("let __ret: &str = unsafe {"));
+        assert!(wasm.contains("((__ret.len() as i64) << 32) | (__ret.as_ptr() as usize as i64)"));
+    }
+
+    #[test]
+    fn test_generate_dom_mutation_methods() {
+        let source = r##"
+pub fn delete_row(id: &str) -> Client {
+    let row = dom::query("#row");
+    row.set_attr("disabled", "true");
+    row.remove_attr("aria-busy");
+    row.append_html("<span>deleted</span>");
+    row.remove();
+}
+"##;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("fn __volki_dom_set_attr("));
+        assert!(wasm.contains("fn __volki_dom_remove_attr("));
+        assert!(wasm.contains("fn __volki_dom_append_html(handle: i32, html_ptr: i32, html_len: i32);"));
+        assert!(wasm.contains("fn __volki_dom_remove(handle: i32);"));
+        assert!(wasm.contains("__volki_dom_append_html(row, __s"));
+        assert!(wasm.contains("__volki_dom_remove(row);"));
+        assert!(!wasm.contains(".remove()"));
+    }
+
+    #[test]
+    fn test_non_dom_remove_is_not_rewritten() {
+        let source = r##"
+pub fn drop_entry() -> Client {
+    let row = dom::query("#row");
+    row.set_attr("data-gone", "true");
+    entry.remove();
+}
+"##;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("entry.remove();"));
+        assert!(!wasm.contains("__volki_dom_remove"));
+    }
+
+    #[test]
+    fn test_generate_component_props() {
+        let source = r##"
+pub fn counter(start: i32, label: &str) -> Component {
+    let count = use_state(start);
+    let el = dom::query("#count");
+    el.set_text(label);
+}
+"##;
+        let fns = scanner::scan_functions(source);
+        let component_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Component)
+            .collect();
+
+        let wasm = generate_wasm_module(&Vec::new(), &component_fns, source, &Vec::new());
+        assert!(wasm.contains("pub extern \"C\" fn __volki_component_counter(start: i32, label_ptr: i32, label_len: i32) {"));
+        assert!(wasm.contains("let label = unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(label_ptr as *const u8, label_len as usize)) };"));
+        assert!(wasm.contains("__volki_state_init_i32(0, start)"));
+    }
+
+    #[test]
+    fn test_generate_event_calls() {
+        let source = r#"
+pub fn on_link(_e: Event) -> Client {
+    event::prevent_default();
+    if true { event::stop_propagation(); }
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("fn __volki_event_prevent_default();"));
+        assert!(wasm.contains("fn __volki_event_stop_propagation();"));
+        assert!(wasm.contains("        __volki_event_prevent_default();\n"));
+        assert!(wasm.contains("if true { __volki_event_stop_propagation(); }"));
+        assert!(!wasm.contains("event::"));
+    }
+
+    #[test]
+    fn test_generate_storage_calls() {
+        let source = r#"
+pub fn save_theme(theme: &str) -> Client {
+    storage::set("theme", theme);
+    let saved = storage::get("theme");
+    dom::log(saved);
+    storage::remove("draft");
+}
+"#;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("fn __volki_storage_get(key_ptr: i32, key_len: i32) -> i32;"));
+        assert!(wasm.contains("fn __volki_storage_get_len(key_ptr: i32, key_len: i32) -> i32;"));
+        assert!(wasm.contains("fn __volki_storage_set(key_ptr: i32, key_len: i32, val_ptr: i32, val_len: i32);"));
+        assert!(wasm.contains("fn __volki_storage_remove(key_ptr: i32, key_len: i32);"));
+        assert!(wasm.contains("__volki_storage_set(__sk0.as_ptr() as i32, __sk0.len() as i32, __sv0.as_ptr() as i32, __sv0.len() as i32);"));
+        assert!(wasm.contains("let __sv0 = theme;"));
+        assert!(wasm.contains("__volki_log_str(saved)"));
+        assert!(wasm.contains("let saved = core::str::from_utf8_unchecked(core::slice::from_raw_parts(__sgptr as *const u8, __sglen as usize));"));
+        assert!(wasm.contains(" = \"draft\";"));
+        assert!(wasm.contains("__volki_storage_remove(__sk"));
+    }
+
+    #[test]
+    fn test_generate_net_fetch() {
+        let source = r##"
+pub fn load_count() -> Client {
+    net::fetch("/api/count", "on_count");
+}
+
+pub fn on_count(status: i32, body: &str) -> Client {
+    let el = dom::query("#count");
+    el.set_text(body);
+}
+"##;
+        let fns = scanner::scan_functions(source);
+        let client_fns: Vec<&RsxFunction> = fns.iter()
+            .filter(|f| f.return_type == RsxReturnType::Client)
+            .collect();
+
+        let wasm = generate_wasm_module(&client_fns, &empty_components(), source, &Vec::new());
+        assert!(wasm.contains("fn __volki_fetch(url_ptr: i32, url_len: i32, cb_ptr: i32, cb_len: i32);"));
+        assert!(wasm.contains("let __fu0 = \"/api/count\";"));
+        assert!(wasm.contains("let __fc0 = \"on_count\";"));
+        assert!(wasm.contains("__volki_fetch(__fu0.as_ptr() as i32, __fu0.len() as i32, __fc0.as_ptr() as i32, __fc0.len() as i32);"));
+        // The callback is an ordinary export taking (status, body)
+        assert!(wasm.contains("pub extern \"C\" fn on_count(status: i32, body_ptr: i32, body_len: i32)"));
+    }
+
     #[test]
     fn test_only_needed_imports() {
         let source = r#"
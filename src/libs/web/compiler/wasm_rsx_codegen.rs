@@ -3,6 +3,12 @@
 //! Given parsed RSX nodes from a `return (RSX)` block, produces:
 //! - **Mount code**: DOM creation via `__volki_dom_create`/`__volki_dom_append` (runs once)
 //! - **Update code**: Dynamic expression slot updates via stored refs (runs every render)
+//!
+//! A `key` attribute is not rendered as a DOM attribute. It is handed to the JS
+//! side via `__volki_dom_set_key` right after the element is created, and again
+//! on every render when the key is an expression. Only the key is recorded:
+//! RSX has no list node yet, so updates still patch the mounted nodes in place
+//! and nothing is matched or reused by key.
 
 use crate::core::volkiwithstds::collections::String;
 
@@ -23,6 +29,7 @@ pub struct WasmRsxOutput {
     pub needs_add_class: bool,
     pub needs_set_attr: bool,
     pub needs_set_text: bool,
+    pub needs_set_key: bool,
     pub needs_mount_point: bool,
     pub needs_is_mounted: bool,
     pub needs_ref_get_i32: bool,
@@ -43,6 +50,7 @@ struct RsxWalker {
     needs_add_class: bool,
     needs_set_attr: bool,
     needs_set_text: bool,
+    needs_set_key: bool,
     needs_ref_get_i32: bool,
     needs_ref_set_i32: bool,
     needs_fmt_i32: bool,
@@ -71,6 +79,7 @@ pub fn generate_component_rsx(
         needs_add_class: false,
         needs_set_attr: false,
         needs_set_text: false,
+        needs_set_key: false,
         needs_ref_get_i32: false,
         needs_ref_set_i32: false,
         needs_fmt_i32: false,
@@ -100,6 +109,7 @@ pub fn generate_component_rsx(
         needs_add_class: walker.needs_add_class,
         needs_set_attr: walker.needs_set_attr,
         needs_set_text: walker.needs_set_text,
+        needs_set_key: walker.needs_set_key,
         needs_mount_point: true,
         needs_is_mounted: true,
         needs_ref_get_i32: walker.needs_ref_get_i32,
@@ -142,8 +152,13 @@ impl RsxWalker {
         self.mount.push_str(");\n");
         self.needs_create = true;
 
+        // Mount (and update, for expression keys): element key
+        if let Some(key) = attrs.iter().find(|a| a.name.as_str() == "key") {
+            self.emit_key(var.as_str(), &key.value);
+        }
+
         // Mount: set attributes
-        for attr in attrs {
+        for attr in attrs.iter().filter(|a| a.name.as_str() != "key") {
             match &attr.value {
                 RsxAttrValue::Literal(value) => {
                     if attr.name.as_str() == "class" {
@@ -172,6 +187,9 @@ impl RsxWalker {
                     }
                     // Non-event expression attrs deferred to V2
                 }
+                // Spread attrs and class directives deferred to V2, like
+                // non-event expression attrs
+                RsxAttrValue::Spread(_) | RsxAttrValue::ClassToggle(_) => {}
             }
         }
 
@@ -337,6 +355,50 @@ impl RsxWalker {
         self.needs_set_text = true;
     }
 
+    fn emit_key(&mut self, var: &str, key: &RsxAttrValue) {
+        self.needs_set_key = true;
+        let expr = match key {
+            RsxAttrValue::Literal(value) => {
+                // Escaped for the Rust literal; the length is of the key itself
+                self.mount.push_str(crate::vformat!(
+                    "__volki_dom_set_key({var}, \"{}\".as_ptr() as i32, {});\n",
+                    value.escape_default(),
+                    value.len()
+                ).as_str());
+                return;
+            }
+            RsxAttrValue::Expr(expr) => expr.as_str().trim(),
+            // Neither a spread nor a class directive carries the `key` name
+            RsxAttrValue::Spread(_) | RsxAttrValue::ClassToggle(_) => return,
+        };
+
+        // Expression keys can change between renders: keep the handle in a
+        // ref slot and re-send the key from the update phase.
+        let slot = self.dyn_slot_counter;
+        self.dyn_slot_counter += 1;
+        let ref_slot = self.ref_slot_offset + slot;
+        self.mount.push_str(crate::vformat!("__volki_ref_set_i32({ref_slot}, {var});\n").as_str());
+        self.needs_ref_set_i32 = true;
+
+        let handle = crate::vformat!("__key{slot}");
+        self.update.push_str(crate::vformat!("let {handle} = __volki_ref_get_i32({ref_slot});\n").as_str());
+        self.needs_ref_get_i32 = true;
+
+        if let Some(inner) = extract_fmt_call(expr, "state::fmt_i32(") {
+            // Integer ids are formatted into scratch memory first
+            self.update.push_str(crate::vformat!(
+                "let __kfb{slot} = __volki_alloc(20);\n\
+                 let __kfl{slot} = __volki_state_fmt_i32({inner}, __kfb{slot}, 20);\n\
+                 __volki_dom_set_key({handle}, __kfb{slot}, __kfl{slot});\n"
+            ).as_str());
+            self.needs_fmt_i32 = true;
+        } else {
+            self.update.push_str(crate::vformat!(
+                "__volki_dom_set_key({handle}, ({expr}).as_ptr() as i32, ({expr}).len() as i32);\n"
+            ).as_str());
+        }
+    }
+
     fn emit_set_attr(&mut self, var: &str, attr_name: &str, attr_value: &str) {
         self.mount.push_str("__volki_dom_set_attr(");
         self.mount.push_str(var);
@@ -455,6 +517,65 @@ mod tests {
         assert!(output.mount_code.contains("on_click"));
     }
 
+    #[test]
+    fn test_rsx_literal_key() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("li"),
+            attrs: vvec![
+                RsxAttr { name: s("key"), value: RsxAttrValue::Literal(s("row-1")) },
+                RsxAttr { name: s("id"), value: RsxAttrValue::Literal(s("first")) }
+            ],
+            children: vvec![RsxNode::Text(s("one"))],
+            self_closing: false,
+        }];
+        let output = generate_component_rsx(&nodes, 0, 0);
+
+        // Key sent right after creation, before the node is appended
+        let create = output.mount_code.find("__volki_dom_create(\"li\"").unwrap();
+        let key = output.mount_code.find("__volki_dom_set_key(__rn0, \"row-1\".as_ptr() as i32, 5);").unwrap();
+        let append = output.mount_code.find("__volki_dom_append(__rsx_mp, __rn0);").unwrap();
+        assert!(create < key && key < append);
+        // Not rendered as a plain attribute; other attributes still are
+        assert!(!output.mount_code.contains("\"key\""));
+        assert!(output.mount_code.contains("\"id\""));
+        assert!(output.update_code.is_empty());
+        assert!(output.needs_set_key);
+    }
+
+    #[test]
+    fn test_rsx_literal_key_is_escaped() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("li"),
+            attrs: vvec![RsxAttr { name: s("key"), value: RsxAttrValue::Literal(s("a\"b\\c")) }],
+            children: vvec![],
+            self_closing: false,
+        }];
+        let output = generate_component_rsx(&nodes, 0, 0);
+        assert!(output.mount_code.contains("__volki_dom_set_key(__rn0, \"a\\\"b\\\\c\".as_ptr() as i32, 5);"));
+    }
+
+    #[test]
+    fn test_rsx_expression_key_updates() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("li"),
+            attrs: vvec![RsxAttr { name: s("key"), value: RsxAttrValue::Expr(s("state::fmt_i32(item_id)")) }],
+            children: vvec![RsxNode::Expr(s("label"))],
+            self_closing: false,
+        }];
+        let output = generate_component_rsx(&nodes, 0, 2);
+
+        // Key handle takes the first ref slot, the text expression the next
+        assert!(output.mount_code.contains("__volki_ref_set_i32(2, __rn0);"));
+        assert!(output.mount_code.contains("__volki_ref_set_i32(3, "));
+        assert_eq!(output.ref_slots_used, 2);
+
+        assert!(output.update_code.contains("let __key0 = __volki_ref_get_i32(2);"));
+        assert!(output.update_code.contains("let __kfl0 = __volki_state_fmt_i32(item_id, __kfb0, 20);"));
+        assert!(output.update_code.contains("__volki_dom_set_key(__key0, __kfb0, __kfl0);"));
+        assert!(output.needs_set_key);
+        assert!(output.needs_fmt_i32);
+    }
+
     #[test]
     fn test_rsx_ref_slot_offset() {
         let nodes = vvec![RsxNode::Element {
@@ -41,12 +41,7 @@ impl Document {
 
             // Special handling for "class"
             if name == "class" {
-                el.class_list = Vec::new();
-                for cls in value.split(' ') {
-                    if !cls.is_empty() {
-                        el.class_list.push(String::from(cls));
-                    }
-                }
+                el.class_list = split_classes(value);
             }
 
             // Update or insert in attribute list
@@ -82,48 +77,72 @@ impl Document {
         self.get_attribute(id, name).is_some()
     }
 
-    /// Adds a class to the element's class list.
-    pub fn class_list_add(&mut self, id: NodeId, class: &str) {
+    /// Adds a class to the element, like `classList.add`. The class
+    /// attribute is rewritten single-space separated; duplicates are ignored.
+    pub fn add_class(&mut self, id: NodeId, class: &str) {
         if let NodeKind::Element(ref mut el) = self.nodes[id.0].kind {
-            let cls = String::from(class);
-            if !el.class_list.contains(&cls) {
-                el.class_list.push(cls);
-                self.sync_class_attribute(id);
+            let class = class.trim();
+            if class.is_empty() {
+                return;
+            }
+            if !el.class_list.iter().any(|c| c.as_str() == class) {
+                el.class_list.push(String::from(class));
             }
+            self.sync_class_attribute(id);
         }
     }
 
-    /// Removes a class from the element's class list.
-    pub fn class_list_remove(&mut self, id: NodeId, class: &str) {
+    /// Removes a class from the element, like `classList.remove`. The class
+    /// attribute is dropped entirely once no classes remain.
+    pub fn remove_class(&mut self, id: NodeId, class: &str) {
         if let NodeKind::Element(ref mut el) = self.nodes[id.0].kind {
-            let cls = String::from(class);
-            el.class_list.retain(|c| *c != cls);
+            let class = class.trim();
+            el.class_list.retain(|c| c.as_str() != class);
             self.sync_class_attribute(id);
         }
     }
 
-    /// Toggles a class on the element. Returns `true` if the class is now present.
-    pub fn class_list_toggle(&mut self, id: NodeId, class: &str) -> bool {
-        if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
-            let cls = String::from(class);
-            if el.class_list.contains(&cls) {
-                self.class_list_remove(id, class);
-                return false;
-            }
+    /// Toggles a class, like `classList.toggle`. Returns `true` if the class
+    /// is now present.
+    pub fn toggle_class(&mut self, id: NodeId, class: &str) -> bool {
+        if self.has_class(id, class) {
+            self.remove_class(id, class);
+            false
+        } else {
+            self.add_class(id, class);
+            self.has_class(id, class)
         }
-        self.class_list_add(id, class);
-        true
     }
 
-    /// Checks whether the element's class list contains a given class.
-    pub fn class_list_contains(&self, id: NodeId, class: &str) -> bool {
+    /// Checks whether the element has a given class, like `classList.contains`.
+    pub fn has_class(&self, id: NodeId, class: &str) -> bool {
         if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
-            let cls = String::from(class);
-            return el.class_list.contains(&cls);
+            let class = class.trim();
+            return el.class_list.iter().any(|c| c.as_str() == class);
         }
         false
     }
 
+    /// Adds a class to the element's class list.
+    pub fn class_list_add(&mut self, id: NodeId, class: &str) {
+        self.add_class(id, class);
+    }
+
+    /// Removes a class from the element's class list.
+    pub fn class_list_remove(&mut self, id: NodeId, class: &str) {
+        self.remove_class(id, class);
+    }
+
+    /// Toggles a class on the element. Returns `true` if the class is now present.
+    pub fn class_list_toggle(&mut self, id: NodeId, class: &str) -> bool {
+        self.toggle_class(id, class)
+    }
+
+    /// Checks whether the element's class list contains a given class.
+    pub fn class_list_contains(&self, id: NodeId, class: &str) -> bool {
+        self.has_class(id, class)
+    }
+
     /// Syncs the class_list back to the "class" attribute.
     fn sync_class_attribute(&mut self, id: NodeId) {
         if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
@@ -202,6 +221,17 @@ impl Document {
     }
 }
 
+/// Splits a class attribute value on ASCII whitespace, dropping duplicates.
+pub(crate) fn split_classes(value: &str) -> Vec<String> {
+    let mut classes: Vec<String> = Vec::new();
+    for cls in value.split_ascii_whitespace() {
+        if !classes.iter().any(|c| c.as_str() == cls) {
+            classes.push(String::from(cls));
+        }
+    }
+    classes
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::Document;
@@ -271,6 +301,42 @@ mod tests {
         assert!(doc.class_list_contains(div, "baz"));
     }
 
+    #[test]
+    fn test_toggle_class_already_present() {
+        let mut doc = Document::new();
+        let div = doc.create_element("div");
+        doc.set_attribute(div, "class", "btn active");
+        assert!(!doc.toggle_class(div, "active"));
+        assert!(!doc.has_class(div, "active"));
+        assert_eq!(doc.get_attribute(div, "class"), Some("btn"));
+        assert!(doc.toggle_class(div, "active"));
+        assert_eq!(doc.get_attribute(div, "class"), Some("btn active"));
+    }
+
+    #[test]
+    fn test_remove_class_from_multi_class_attribute() {
+        let mut doc = Document::new();
+        let div = doc.create_element("div");
+        doc.set_attribute(div, "class", "  a\tb   c ");
+        doc.remove_class(div, "b");
+        assert_eq!(doc.get_attribute(div, "class"), Some("a c"));
+        doc.remove_class(div, "a");
+        doc.remove_class(div, "c");
+        assert!(!doc.has_attribute(div, "class"));
+    }
+
+    #[test]
+    fn test_add_class_no_duplicates() {
+        let mut doc = Document::new();
+        let div = doc.create_element("div");
+        doc.set_attribute(div, "class", "a a b");
+        assert_eq!(doc.get_attribute(div, "class"), Some("a a b"));
+        doc.add_class(div, "a");
+        assert_eq!(doc.get_attribute(div, "class"), Some("a b"));
+        doc.add_class(div, " ");
+        assert_eq!(doc.get_attribute(div, "class"), Some("a b"));
+    }
+
     #[test]
     fn test_text_content() {
         let mut doc = Document::new();
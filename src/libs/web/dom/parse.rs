@@ -1,6 +1,7 @@
 //! HTML fragment parser — parses trusted HTML strings into DOM nodes.
 
 use super::{Document, NodeId};
+use super::attributes::split_classes;
 use super::node::{ElementData, NodeKind};
 use crate::core::volkiwithstds::collections::{String, Vec};
 
@@ -33,11 +34,7 @@ impl Document {
                             el_data.id = Some(value.clone());
                         }
                         if name.as_str() == "class" {
-                            for cls in value.as_str().split(' ') {
-                                if !cls.is_empty() {
-                                    el_data.class_list.push(String::from(cls));
-                                }
-                            }
+                            el_data.class_list = split_classes(value.as_str());
                         }
                         el_data.attributes.push((name.clone(), value.clone()));
                     }
@@ -95,11 +92,7 @@ impl Document {
                             el_data.id = Some(value.clone());
                         }
                         if name.as_str() == "class" {
-                            for cls in value.as_str().split(' ') {
-                                if !cls.is_empty() {
-                                    el_data.class_list.push(String::from(cls));
-                                }
-                            }
+                            el_data.class_list = split_classes(value.as_str());
                         }
                         el_data.attributes.push((name.clone(), value.clone()));
                     }
@@ -137,6 +130,10 @@ impl Document {
     }
 
     /// Sets the innerHTML of a node by parsing an HTML string.
+    ///
+    /// Existing children and their descendants are freed back to the arena
+    /// (their slots are reused by the parsed nodes), and their ids are dropped
+    /// from the id index before the new content is indexed.
     pub fn set_inner_html(&mut self, id: NodeId, html: &str) {
         // Remove all existing children
         let mut children = Vec::new();
@@ -557,4 +554,42 @@ mod tests {
 
         assert!(doc.get_element_by_id("app").is_some());
     }
+
+    #[test]
+    fn test_set_inner_html_twice_replaces() {
+        let mut doc = Document::new();
+        let div = doc.create_element("div");
+        doc.append_child(doc.root, div);
+
+        doc.set_inner_html(div, "<ul id=\"list\"><li>a</li><li>b</li></ul>tail");
+        let arena_len = doc.nodes.len();
+        assert!(doc.get_element_by_id("list").is_some());
+
+        doc.set_inner_html(div, "<p id=\"msg\">hi</p>");
+        assert_eq!(doc.inner_html(div).as_str(), "<p id=\"msg\">hi</p>");
+        assert_eq!(doc.children_count(div), 1);
+        assert!(doc.get_element_by_id("list").is_none());
+        assert_eq!(doc.get_element_by_id("msg"), doc.first_child(div));
+        // The new nodes reuse freed slots instead of growing the arena
+        assert_eq!(doc.nodes.len(), arena_len);
+        assert_eq!(doc.free_list.len(), 4);
+    }
+
+    #[test]
+    fn test_set_inner_html_keeps_other_ids() {
+        let mut doc = Document::new();
+        let a = doc.create_element("div");
+        let b = doc.create_element("div");
+        doc.append_child(doc.root, a);
+        doc.append_child(doc.root, b);
+
+        doc.set_inner_html(a, "<span id=\"x\">old</span>");
+        doc.set_inner_html(b, "<span id=\"x\">new</span>");
+        let owner = doc.get_element_by_id("x");
+        assert_eq!(owner, doc.first_child(b));
+
+        // Freeing a's stale duplicate must not unregister b's element
+        doc.set_inner_html(a, "");
+        assert_eq!(doc.get_element_by_id("x"), owner);
+    }
 }
@@ -49,7 +49,9 @@ impl Document {
         None
     }
 
-    /// Returns all descendants matching a CSS selector.
+    /// Returns all descendant elements matching a CSS selector, in document
+    /// order. A node matching several comma-separated selectors appears once.
+    /// An invalid selector yields no matches.
     pub fn query_selector_all(&self, root: NodeId, selector_str: &str) -> Vec<NodeId> {
         let mut results = Vec::new();
         if let Some(sel) = parse_selector(selector_str) {
@@ -65,7 +67,9 @@ impl Document {
 
 #[cfg(test)]
 mod tests {
-    use super::super::Document;
+    use super::super::{Document, NodeId};
+    use crate::core::volkiwithstds::collections::Vec;
+    use crate::vvec;
 
     #[test]
     fn test_get_element_by_id() {
@@ -154,4 +158,81 @@ mod tests {
         let found = doc.query_selector(doc.root, "#app ul li.active");
         assert_eq!(found, Some(li));
     }
+
+    fn build_cards(doc: &mut Document) -> (NodeId, NodeId, NodeId, NodeId) {
+        // <div class="card"><a/><p><a/></p></div><div><a data-x="y"/></div>
+        let card = doc.create_element("div");
+        doc.class_list_add(card, "card");
+        let direct = doc.create_element("a");
+        let p = doc.create_element("p");
+        let nested = doc.create_element("a");
+        let plain = doc.create_element("div");
+        let other = doc.create_element("a");
+        doc.set_attribute(other, "data-x", "y");
+        doc.append_child(doc.root, card);
+        doc.append_child(card, direct);
+        doc.append_child(card, p);
+        doc.append_child(p, nested);
+        doc.append_child(doc.root, plain);
+        doc.append_child(plain, other);
+        (card, direct, nested, other)
+    }
+
+    #[test]
+    fn test_query_selector_all_child_combinator() {
+        let mut doc = Document::new();
+        let (_, direct, _, _) = build_cards(&mut doc);
+        assert_eq!(doc.query_selector_all(doc.root, "div.card > a"), vvec![direct]);
+    }
+
+    #[test]
+    fn test_query_selector_all_descendant() {
+        let mut doc = Document::new();
+        let (_, direct, nested, _) = build_cards(&mut doc);
+        assert_eq!(doc.query_selector_all(doc.root, ".card a"), vvec![direct, nested]);
+    }
+
+    #[test]
+    fn test_query_selector_all_list_in_document_order() {
+        let mut doc = Document::new();
+        let (card, direct, nested, other) = build_cards(&mut doc);
+        // branches listed out of order still produce document order
+        assert_eq!(
+            doc.query_selector_all(doc.root, "[data-x=\"y\"], div.card"),
+            vvec![card, other]
+        );
+        // a node matched by several branches is returned once
+        assert_eq!(
+            doc.query_selector_all(doc.root, "a, .card > a, p a"),
+            vvec![direct, nested, other]
+        );
+    }
+
+    #[test]
+    fn test_query_selector_all_nth_child() {
+        let mut doc = Document::new();
+        let ul = doc.create_element("ul");
+        doc.append_child(doc.root, ul);
+        let mut items = Vec::new();
+        for _ in 0..5 {
+            let li = doc.create_element("li");
+            doc.append_child(ul, li);
+            items.push(li);
+        }
+        assert_eq!(
+            doc.query_selector_all(doc.root, "ul li:nth-child(odd)"),
+            vvec![items[0], items[2], items[4]]
+        );
+        assert_eq!(
+            doc.query_selector_all(doc.root, "li:nth-child(-n+2)"),
+            vvec![items[0], items[1]]
+        );
+    }
+
+    #[test]
+    fn test_query_selector_all_invalid_selector() {
+        let mut doc = Document::new();
+        build_cards(&mut doc);
+        assert!(doc.query_selector_all(doc.root, "div >").is_empty());
+    }
 }
@@ -1,7 +1,7 @@
 //! CSS selector parser and matcher.
 //!
-//! Supports: tag, .class, #id, [attr], [attr=val], [attr^=val], [attr$=val],
-//! [attr*=val], combinators (descendant ` `, child `>`, adjacent `+`, general `~`),
+//! Supports: tag, .class, #id, [attr], [attr=val], [attr~=val], [attr|=val],
+//! [attr^=val], [attr$=val], [attr*=val], combinators (descendant ` `, child `>`, adjacent `+`, general `~`),
 //! pseudo-classes (:first-child, :last-child, :nth-child(an+b), :not()),
 //! compound selectors, and comma-separated selector lists.
 
@@ -61,6 +61,10 @@ pub struct AttrSelector {
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AttrOp {
     Equals,
+    /// `~=` — whitespace-separated list contains the value.
+    Includes,
+    /// `|=` — equals the value or starts with `value-`.
+    DashMatch,
     StartsWith,
     EndsWith,
     Contains,
@@ -76,9 +80,16 @@ pub enum PseudoClass {
 // ── Parser ──────────────────────────────────────────────────────────────────
 
 /// Parses a CSS selector string into a `SelectorList`.
+/// Returns `None` if any part of the input is not a valid selector.
 pub fn parse_selector(input: &str) -> Option<SelectorList> {
     let mut parser = SelectorParser::new(input);
-    parser.parse_selector_list()
+    parser.skip_whitespace();
+    let list = parser.parse_selector_list()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return None;
+    }
+    Some(list)
 }
 
 struct SelectorParser<'a> {
@@ -269,6 +280,20 @@ impl<'a> SelectorParser<'a> {
                 let val = self.parse_attr_value()?;
                 (Some(AttrOp::Equals), Some(val))
             }
+            Some('~') => {
+                self.advance();
+                if self.peek() != Some('=') { return None; }
+                self.advance();
+                let val = self.parse_attr_value()?;
+                (Some(AttrOp::Includes), Some(val))
+            }
+            Some('|') => {
+                self.advance();
+                if self.peek() != Some('=') { return None; }
+                self.advance();
+                let val = self.parse_attr_value()?;
+                (Some(AttrOp::DashMatch), Some(val))
+            }
             Some('^') => {
                 self.advance();
                 if self.peek() != Some('=') { return None; }
@@ -432,68 +457,55 @@ impl Document {
         if selector.parts.is_empty() {
             return false;
         }
-
         // parts[0] is the key selector (rightmost)
-        if !self.matches_compound(id, &selector.parts[0].1) {
-            return false;
-        }
+        self.matches_compound(id, &selector.parts[0].1) && self.matches_rest(id, selector, 1)
+    }
 
-        let mut current = id;
-        let mut i = 1;
-        while i < selector.parts.len() {
-            let (ref comb, ref compound) = selector.parts[i];
-            match comb {
-                Combinator::Descendant => {
-                    let mut ancestor = self.nodes[current.0].parent;
-                    let mut found = false;
-                    while let Some(anc) = ancestor {
-                        if self.matches_compound(anc, compound) {
-                            current = anc;
-                            found = true;
-                            break;
-                        }
-                        ancestor = self.nodes[anc.0].parent;
-                    }
-                    if !found { return false; }
-                }
-                Combinator::Child => {
-                    match self.nodes[current.0].parent {
-                        Some(p) if self.matches_compound(p, compound) => {
-                            current = p;
-                        }
-                        _ => return false,
-                    }
-                }
-                Combinator::AdjacentSibling => {
-                    match self.nodes[current.0].prev_sibling {
-                        Some(s) if self.matches_compound(s, compound) => {
-                            current = s;
-                        }
-                        _ => return false,
+    /// Matches `parts[i..]` leftward from `current`, which already matched
+    /// `parts[i - 1]`. Descendant and general-sibling steps backtrack: if the
+    /// nearest candidate fails further left, farther candidates are tried.
+    fn matches_rest(&self, current: NodeId, selector: &ComplexSelector, i: usize) -> bool {
+        let Some((comb, compound)) = selector.parts.get(i) else {
+            return true;
+        };
+        match comb {
+            Combinator::Descendant => {
+                let mut ancestor = self.nodes[current.0].parent;
+                while let Some(anc) = ancestor {
+                    if self.matches_compound(anc, compound) && self.matches_rest(anc, selector, i + 1) {
+                        return true;
                     }
+                    ancestor = self.nodes[anc.0].parent;
                 }
-                Combinator::GeneralSibling => {
-                    let mut sib = self.nodes[current.0].prev_sibling;
-                    let mut found = false;
-                    while let Some(s) = sib {
-                        if self.matches_compound(s, compound) {
-                            current = s;
-                            found = true;
-                            break;
-                        }
-                        sib = self.nodes[s.0].prev_sibling;
+                false
+            }
+            Combinator::Child => match self.nodes[current.0].parent {
+                Some(p) => self.matches_compound(p, compound) && self.matches_rest(p, selector, i + 1),
+                None => false,
+            },
+            Combinator::AdjacentSibling => match self.prev_element_sibling(current) {
+                Some(s) => self.matches_compound(s, compound) && self.matches_rest(s, selector, i + 1),
+                None => false,
+            },
+            Combinator::GeneralSibling => {
+                let mut sib = self.prev_element_sibling(current);
+                while let Some(s) = sib {
+                    if self.matches_compound(s, compound) && self.matches_rest(s, selector, i + 1) {
+                        return true;
                     }
-                    if !found { return false; }
+                    sib = self.prev_element_sibling(s);
                 }
-                Combinator::None => {}
+                false
             }
-            i += 1;
+            Combinator::None => self.matches_rest(current, selector, i + 1),
         }
-
-        true
     }
 
+    /// Selectors only ever match elements, never text, comments or the document.
     fn matches_compound(&self, id: NodeId, selector: &CompoundSelector) -> bool {
+        if !matches!(self.nodes[id.0].kind, NodeKind::Element(_)) {
+            return false;
+        }
         for part in selector.parts.iter() {
             if !self.matches_simple(id, part) {
                 return false;
@@ -502,6 +514,28 @@ impl Document {
         true
     }
 
+    fn prev_element_sibling(&self, id: NodeId) -> Option<NodeId> {
+        let mut sib = self.nodes[id.0].prev_sibling;
+        while let Some(s) = sib {
+            if matches!(self.nodes[s.0].kind, NodeKind::Element(_)) {
+                return Some(s);
+            }
+            sib = self.nodes[s.0].prev_sibling;
+        }
+        None
+    }
+
+    fn next_element_sibling(&self, id: NodeId) -> Option<NodeId> {
+        let mut sib = self.nodes[id.0].next_sibling;
+        while let Some(s) = sib {
+            if matches!(self.nodes[s.0].kind, NodeKind::Element(_)) {
+                return Some(s);
+            }
+            sib = self.nodes[s.0].next_sibling;
+        }
+        None
+    }
+
     fn matches_simple(&self, id: NodeId, selector: &SimpleSelector) -> bool {
         match selector {
             SimpleSelector::Universal => true,
@@ -541,6 +575,15 @@ impl Document {
                 match (&attr.op, &attr.value) {
                     (None, _) => return true, // [attr] — just existence
                     (Some(AttrOp::Equals), Some(v)) => return value.as_str() == v.as_str(),
+                    (Some(AttrOp::Includes), Some(v)) => {
+                        return value.as_str().split_whitespace().any(|w| w == v.as_str());
+                    }
+                    (Some(AttrOp::DashMatch), Some(v)) => {
+                        let value = value.as_str();
+                        return value == v.as_str()
+                            || (value.starts_with(v.as_str())
+                                && value[v.len()..].starts_with('-'));
+                    }
                     (Some(AttrOp::StartsWith), Some(v)) => return value.as_str().starts_with(v.as_str()),
                     (Some(AttrOp::EndsWith), Some(v)) => return value.as_str().ends_with(v.as_str()),
                     (Some(AttrOp::Contains), Some(v)) => return value.as_str().contains(v.as_str()),
@@ -553,33 +596,26 @@ impl Document {
         }
     }
 
+    /// Structural pseudo-classes count element siblings only.
     fn matches_pseudo(&self, id: NodeId, pseudo: &PseudoClass) -> bool {
+        if self.nodes[id.0].parent.is_none() {
+            return false;
+        }
         match pseudo {
-            PseudoClass::FirstChild => {
-                self.nodes[id.0].prev_sibling.is_none() && self.nodes[id.0].parent.is_some()
-            }
-            PseudoClass::LastChild => {
-                self.nodes[id.0].next_sibling.is_none() && self.nodes[id.0].parent.is_some()
-            }
+            PseudoClass::FirstChild => self.prev_element_sibling(id).is_none(),
+            PseudoClass::LastChild => self.next_element_sibling(id).is_none(),
             PseudoClass::NthChild(a, b) => {
-                if let Some(parent) = self.nodes[id.0].parent {
-                    let mut idx = 1i32; // 1-based
-                    let mut sib = self.nodes[parent.0].first_child;
-                    while let Some(s) = sib {
-                        if s == id {
-                            break;
-                        }
-                        idx += 1;
-                        sib = self.nodes[s.0].next_sibling;
-                    }
-                    if *a == 0 {
-                        idx == *b
-                    } else {
-                        let diff = idx - b;
-                        diff % a == 0 && diff / a >= 0
-                    }
+                let mut idx = 1i32; // 1-based
+                let mut sib = self.prev_element_sibling(id);
+                while let Some(s) = sib {
+                    idx += 1;
+                    sib = self.prev_element_sibling(s);
+                }
+                if *a == 0 {
+                    idx == *b
                 } else {
-                    false
+                    let diff = idx - b;
+                    diff % a == 0 && diff / a >= 0
                 }
             }
         }
@@ -658,6 +694,19 @@ mod tests {
         assert_eq!(sel.selectors.len(), 1);
     }
 
+    #[test]
+    fn test_parse_rejects_trailing_garbage() {
+        assert!(parse_selector("div $").is_none());
+        assert!(parse_selector("div,").is_none());
+        assert!(parse_selector("  div > p  ").is_some());
+    }
+
+    #[test]
+    fn test_parse_attr_includes_and_dash() {
+        let sel = parse_selector("[rel~=\"next\"][lang|=en]").unwrap();
+        assert_eq!(sel.selectors[0].parts[0].1.parts.len(), 2);
+    }
+
     #[test]
     fn test_match_tag() {
         let mut doc = Document::new();
@@ -732,6 +781,60 @@ mod tests {
         assert!(!doc.matches_selector(li2, &sel));
     }
 
+    #[test]
+    fn test_match_descendant_backtracks() {
+        // .x > .y span — the nearest .y fails (its parent is .y), the outer one matches
+        let mut doc = Document::new();
+        let x = doc.create_element("div");
+        let outer = doc.create_element("div");
+        let inner = doc.create_element("div");
+        let span = doc.create_element("span");
+        doc.class_list_add(x, "x");
+        doc.class_list_add(outer, "y");
+        doc.class_list_add(inner, "y");
+        doc.append_child(doc.root, x);
+        doc.append_child(x, outer);
+        doc.append_child(outer, inner);
+        doc.append_child(inner, span);
+
+        let sel = parse_selector(".x > .y span").unwrap();
+        assert!(doc.matches_selector(span, &sel));
+    }
+
+    #[test]
+    fn test_match_siblings_skip_text() {
+        let mut doc = Document::new();
+        let ul = doc.create_element("ul");
+        let ws1 = doc.create_text("\n  ");
+        let li1 = doc.create_element("li");
+        let ws2 = doc.create_text("\n  ");
+        let li2 = doc.create_element("li");
+        doc.append_child(doc.root, ul);
+        doc.append_child(ul, ws1);
+        doc.append_child(ul, li1);
+        doc.append_child(ul, ws2);
+        doc.append_child(ul, li2);
+
+        assert!(doc.matches_selector(li1, &parse_selector("li:first-child").unwrap()));
+        assert!(doc.matches_selector(li2, &parse_selector("li:nth-child(2)").unwrap()));
+        assert!(doc.matches_selector(li2, &parse_selector("li:last-child").unwrap()));
+        assert!(doc.matches_selector(li2, &parse_selector("li + li").unwrap()));
+        assert!(!doc.matches_selector(ws1, &parse_selector("*").unwrap()));
+    }
+
+    #[test]
+    fn test_match_attr_includes_and_dash() {
+        let mut doc = Document::new();
+        let a = doc.create_element("a");
+        doc.set_attribute(a, "rel", "nofollow next");
+        doc.set_attribute(a, "lang", "en-US");
+
+        assert!(doc.matches_selector(a, &parse_selector("[rel~=next]").unwrap()));
+        assert!(!doc.matches_selector(a, &parse_selector("[rel~=nex]").unwrap()));
+        assert!(doc.matches_selector(a, &parse_selector("[lang|=en]").unwrap()));
+        assert!(!doc.matches_selector(a, &parse_selector("[lang|=e]").unwrap()));
+    }
+
     #[test]
     fn test_match_not() {
         let mut doc = Document::new();
@@ -42,6 +42,17 @@ impl Document {
             self.unlink(child);
         }
 
+        self.link_last(parent, child);
+
+        // Update id_index for the child and everything below it
+        self.index_subtree(child);
+
+        self.record_child_list_mutation(parent);
+    }
+
+    /// Links a detached `child` as the last child of `parent`. No indexing,
+    /// no mutation records.
+    fn link_last(&mut self, parent: NodeId, child: NodeId) {
         self.nodes[child.0].parent = Some(parent);
 
         let old_last = self.nodes[parent.0].last_child;
@@ -53,15 +64,20 @@ impl Document {
             self.nodes[parent.0].first_child = Some(child);
         }
         self.nodes[parent.0].last_child = Some(child);
+    }
 
-        // Update id_index if child has an id
-        if let NodeKind::Element(ref el) = self.nodes[child.0].kind {
-            if let Some(ref id) = el.id {
-                self.id_index.insert(id.clone(), child);
+    /// Registers the ids of `id` and all its descendant elements in the id index.
+    fn index_subtree(&mut self, id: NodeId) {
+        if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
+            if let Some(ref eid) = el.id {
+                self.id_index.insert(eid.clone(), id);
             }
         }
-
-        self.record_child_list_mutation(parent);
+        let mut child = self.nodes[id.0].first_child;
+        while let Some(c) = child {
+            self.index_subtree(c);
+            child = self.nodes[c.0].next_sibling;
+        }
     }
 
     /// Inserts `new_child` before `reference` under `parent`.
@@ -91,11 +107,7 @@ impl Document {
             self.nodes[parent.0].first_child = Some(new_child);
         }
 
-        if let NodeKind::Element(ref el) = self.nodes[new_child.0].kind {
-            if let Some(ref id) = el.id {
-                self.id_index.insert(id.clone(), new_child);
-            }
-        }
+        self.index_subtree(new_child);
 
         self.record_child_list_mutation(parent);
     }
@@ -106,13 +118,7 @@ impl Document {
             return;
         }
 
-        // Remove from id_index
-        if let NodeKind::Element(ref el) = self.nodes[child.0].kind {
-            if let Some(ref id) = el.id {
-                self.id_index.remove(id.as_str());
-            }
-        }
-
+        self.unindex_id(child);
         self.unlink(child);
         self.record_child_list_mutation(parent);
     }
@@ -149,26 +155,24 @@ impl Document {
         }
 
         // Clean up old_child
-        if let NodeKind::Element(ref el) = self.nodes[old_child.0].kind {
-            if let Some(ref id) = el.id {
-                self.id_index.remove(id.as_str());
-            }
-        }
+        self.unindex_id(old_child);
         self.nodes[old_child.0].parent = None;
         self.nodes[old_child.0].prev_sibling = None;
         self.nodes[old_child.0].next_sibling = None;
 
         // Update id_index for new child
-        if let NodeKind::Element(ref el) = self.nodes[new_child.0].kind {
-            if let Some(ref id) = el.id {
-                self.id_index.insert(id.clone(), new_child);
-            }
-        }
+        self.index_subtree(new_child);
 
         self.record_child_list_mutation(parent);
     }
 
-    /// Deep or shallow clone of a node. Returns the new node's id.
+    /// Deep or shallow clone of a node. Returns the new, detached node's id.
+    ///
+    /// A shallow clone copies the node itself (tag and attributes for
+    /// elements); a deep clone also copies all descendants. Event listeners
+    /// are not copied. Cloned ids are not registered in the id index until
+    /// the clone is inserted with `append_child`, `insert_before` or
+    /// `replace_child`, at which point the clone takes over the lookup.
     pub fn clone_node(&mut self, id: NodeId, deep: bool) -> NodeId {
         let kind = match &self.nodes[id.0].kind {
             NodeKind::Document => NodeKind::Document,
@@ -200,7 +204,7 @@ impl Document {
             let mut child_opt = self.nodes[id.0].first_child;
             while let Some(child) = child_opt {
                 let cloned = self.clone_node(child, true);
-                self.append_child(new_id, cloned);
+                self.link_last(new_id, cloned);
                 child_opt = self.nodes[child.0].next_sibling;
             }
         }
@@ -220,6 +224,18 @@ impl Document {
         self.free_subtree(id);
     }
 
+    /// Drops an element's id from the id index, unless the index entry has
+    /// since been taken over by another element with the same id.
+    pub(crate) fn unindex_id(&mut self, id: NodeId) {
+        if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
+            if let Some(ref eid) = el.id {
+                if self.id_index.get(eid.as_str()) == Some(&id) {
+                    self.id_index.remove(eid.as_str());
+                }
+            }
+        }
+    }
+
     /// Recursively frees a node and all descendants.
     fn free_subtree(&mut self, id: NodeId) {
         // Collect children first
@@ -234,13 +250,7 @@ impl Document {
             self.free_subtree(child);
         }
 
-        // Remove from id_index
-        if let NodeKind::Element(ref el) = self.nodes[id.0].kind {
-            if let Some(ref eid) = el.id {
-                self.id_index.remove(eid.as_str());
-            }
-        }
-
+        self.unindex_id(id);
         self.nodes[id.0].freed = true;
         self.nodes[id.0].parent = None;
         self.nodes[id.0].first_child = None;
@@ -388,4 +398,49 @@ mod tests {
         assert!(doc.nodes[grandchild.0].freed);
         assert_eq!(doc.free_list.len(), 3);
     }
+
+    #[test]
+    fn test_clone_node_independent_mutation() {
+        let mut doc = Document::new();
+        let row = doc.create_element("tr");
+        let cell = doc.create_element("td");
+        let text = doc.create_text("a");
+        doc.set_attribute(row, "class", "row");
+        doc.append_child(doc.root, row);
+        doc.append_child(row, cell);
+        doc.append_child(cell, text);
+
+        let copy = doc.clone_node(row, true);
+        let copy_cell = doc.first_child(copy).unwrap();
+        doc.set_attribute(copy, "class", "row copy");
+        doc.set_text_content(copy_cell, "b");
+        let extra = doc.create_element("td");
+        doc.append_child(copy, extra);
+
+        assert_eq!(doc.get_attribute(row, "class"), Some("row"));
+        assert_eq!(doc.text_content(row).as_str(), "a");
+        assert_eq!(doc.children_count(row), 1);
+        assert_eq!(doc.text_content(copy).as_str(), "b");
+        assert_eq!(doc.children_count(copy), 2);
+        assert_eq!(doc.get(copy).parent, None);
+    }
+
+    #[test]
+    fn test_clone_node_ids_indexed_on_insert() {
+        let mut doc = Document::new();
+        let tpl = doc.create_element("div");
+        let inner = doc.create_element("span");
+        doc.append_child(doc.root, tpl);
+        doc.append_child(tpl, inner);
+        doc.set_attribute(inner, "id", "label");
+
+        let copy = doc.clone_node(tpl, true);
+        let copy_inner = doc.first_child(copy).unwrap();
+        // Detached clone does not steal the original's id
+        assert_eq!(doc.get_element_by_id("label"), Some(inner));
+
+        // Inserting the clone indexes its descendants too
+        doc.append_child(doc.root, copy);
+        assert_eq!(doc.get_element_by_id("label"), Some(copy_inner));
+    }
 }
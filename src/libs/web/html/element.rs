@@ -395,6 +395,40 @@ impl HtmlElement {
         self.attr("id", value)
     }
 
+    /// Appends a class to the `class` attribute when `cond` is true.
+    ///
+    /// If the element already has a `class` attribute the class is added to
+    /// it (space-separated); otherwise a new `class` attribute is created.
+    /// This is what the RSX `class:name={cond}` directive compiles to.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The class name to append.
+    /// * `cond`  - Whether to apply it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use volki::libs::web::html::element::*;
+    /// # use volki::core::volkiwithstds::collections::{String, Vec};
+    /// let is_active = true;
+    /// let tab = li().class("tab").class_if("active", is_active).text("Home").into_node();
+    /// // Produces: <li class="tab active">Home</li>
+    /// ```
+    pub fn class_if(mut self, value: &str, cond: bool) -> Self {
+        if !cond {
+            return self;
+        }
+        if let Some((_, existing)) = self.attrs.iter_mut().find(|(n, _)| n.as_str() == "class") {
+            if !existing.is_empty() {
+                existing.push(' ');
+            }
+            existing.push_str(value);
+            return self;
+        }
+        self.attr("class", value)
+    }
+
     /// Appends a single child node to this element.
     ///
     /// The child is added after any previously added children. Use
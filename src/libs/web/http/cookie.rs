@@ -0,0 +1,237 @@
+//! Cookies — `Cookie` request header parsing and `Set-Cookie` serialization.
+
+use crate::core::volkiwithstds::collections::String;
+use crate::core::volkiwithstds::time::Duration;
+use crate::vformat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// A cookie to send with `Response::set_cookie`.
+///
+/// The name and value are written verbatim, so values containing `;`, `,`,
+/// whitespace or quotes should be encoded by the caller first, e.g. with
+/// `http::encode_component` and read back with `Request::cookie_decoded`.
+#[derive(Debug, Clone)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    max_age: Option<Duration>,
+    http_only: bool,
+    secure: bool,
+    same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: String::from(name),
+            value: String::from(value),
+            path: None,
+            domain: None,
+            max_age: None,
+            http_only: false,
+            secure: false,
+            same_site: None,
+        }
+    }
+
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(String::from(path));
+        self
+    }
+
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(String::from(domain));
+        self
+    }
+
+    /// Sets `Max-Age` in whole seconds. A zero duration deletes the cookie.
+    pub fn max_age(mut self, age: Duration) -> Self {
+        self.max_age = Some(age);
+        self
+    }
+
+    pub fn http_only(mut self) -> Self {
+        self.http_only = true;
+        self
+    }
+
+    pub fn secure(mut self) -> Self {
+        self.secure = true;
+        self
+    }
+
+    /// Sets `SameSite`. Browsers reject `SameSite=None` without `Secure`,
+    /// so choosing `None` also marks the cookie secure.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        if same_site == SameSite::None {
+            self.secure = true;
+        }
+        self
+    }
+
+    /// Formats the cookie as a `Set-Cookie` header value.
+    pub fn to_header_value(&self) -> String {
+        let mut out = vformat!("{}={}", self.name, self.value);
+        if let Some(ref path) = self.path {
+            out.push_str(&vformat!("; Path={path}"));
+        }
+        if let Some(ref domain) = self.domain {
+            out.push_str(&vformat!("; Domain={domain}"));
+        }
+        if let Some(age) = self.max_age {
+            out.push_str(&vformat!("; Max-Age={}", age.as_secs()));
+        }
+        if self.http_only {
+            out.push_str("; HttpOnly");
+        }
+        if self.secure {
+            out.push_str("; Secure");
+        }
+        if let Some(same_site) = self.same_site {
+            out.push_str("; SameSite=");
+            out.push_str(same_site.as_str());
+        }
+        out
+    }
+}
+
+/// Finds a cookie by name in a `Cookie` request header value
+/// (`a=1; b=2`). Surrounding double quotes on the value are removed.
+pub fn find_cookie<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    for pair in header.split(';') {
+        let Some((k, v)) = pair.split_once('=') else {
+            continue;
+        };
+        if k.trim() == name {
+            let v = v.trim();
+            let v = v
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+                .unwrap_or(v);
+            return Some(v);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::Vec;
+    use crate::libs::web::http::headers::Headers;
+    use crate::libs::web::http::method::Method;
+    use crate::libs::web::http::request::Request;
+    use crate::libs::web::http::response::Response;
+
+    #[test]
+    fn test_find_cookie() {
+        let header = "theme=dark; sid=\"abc123\";flag; empty=";
+        assert_eq!(find_cookie(header, "theme"), Some("dark"));
+        assert_eq!(find_cookie(header, "sid"), Some("abc123"));
+        assert_eq!(find_cookie(header, "empty"), Some(""));
+        assert_eq!(find_cookie(header, "flag"), None);
+        assert_eq!(find_cookie(header, "missing"), None);
+    }
+
+    #[test]
+    fn test_cookie_attributes() {
+        let cookie = Cookie::new("sid", "abc123")
+            .path("/")
+            .domain("example.com")
+            .max_age(Duration::from_secs(3600))
+            .http_only()
+            .secure()
+            .same_site(SameSite::Lax);
+        assert_eq!(
+            cookie.to_header_value().as_str(),
+            "sid=abc123; Path=/; Domain=example.com; Max-Age=3600; HttpOnly; Secure; SameSite=Lax"
+        );
+        assert_eq!(Cookie::new("a", "b").to_header_value().as_str(), "a=b");
+    }
+
+    #[test]
+    fn test_same_site_none_implies_secure() {
+        let cookie = Cookie::new("a", "b").same_site(SameSite::None);
+        assert_eq!(
+            cookie.to_header_value().as_str(),
+            "a=b; Secure; SameSite=None"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let resp = Response::ok()
+            .set_cookie(Cookie::new("sid", "abc123").path("/").http_only())
+            .set_cookie(Cookie::new("theme", "dark"));
+        let set: Vec<&str> = resp
+            .headers
+            .iter()
+            .filter(|(k, _)| *k == "Set-Cookie")
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(
+            set.as_slice(),
+            &["sid=abc123; Path=/; HttpOnly", "theme=dark"]
+        );
+
+        // Echo the name=value parts back as a browser would
+        let mut header = String::new();
+        for v in set.iter() {
+            if !header.is_empty() {
+                header.push_str("; ");
+            }
+            header.push_str(v.split(';').next().unwrap());
+        }
+        let mut headers = Headers::new();
+        headers.set("Cookie", header.as_str());
+        let req = Request::new(Method::Get, String::from("/"), headers, Vec::new());
+        assert_eq!(req.cookie("sid"), Some("abc123"));
+        assert_eq!(req.cookie("theme"), Some("dark"));
+        assert_eq!(req.cookie("nope"), None);
+    }
+
+    #[test]
+    fn test_encoded_value_round_trip() {
+        let value = crate::libs::web::http::encode_component("a b;c=\u{e9}");
+        let set = Cookie::new("note", &value).to_header_value();
+        let mut headers = Headers::new();
+        headers.set("Cookie", set.as_str());
+        let req = Request::new(Method::Get, String::from("/"), headers, Vec::new());
+        assert_eq!(req.cookie_decoded("note").as_deref(), Some("a b;c=\u{e9}"));
+    }
+
+    #[test]
+    fn test_repeated_cookie_headers() {
+        let mut headers = Headers::new();
+        headers.append("Cookie", "sid=abc123");
+        headers.append("Cookie", "theme=dark; sid=later");
+        let req = Request::new(Method::Get, String::from("/"), headers, Vec::new());
+        assert_eq!(req.cookie("sid"), Some("abc123"));
+        assert_eq!(req.cookie("theme"), Some("dark"));
+    }
+
+    #[test]
+    fn test_no_cookie_header() {
+        let req = Request::new(Method::Get, String::from("/"), Headers::new(), Vec::new());
+        assert_eq!(req.cookie("sid"), None);
+    }
+}
@@ -13,20 +13,29 @@ impl Headers {
         }
     }
 
+    /// Returns the first value stored under `name`, compared ASCII
+    /// case-insensitively.
     pub fn get(&self, name: &str) -> Option<&str> {
-        let lower = ascii_lowercase(name);
-        for (k, v) in self.entries.iter() {
-            if ascii_lowercase(k.as_str()) == lower {
-                return Some(v.as_str());
-            }
-        }
-        None
+        self.entries
+            .iter()
+            .find(|(k, _)| k.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns every value of a repeated header (`Accept`, `Set-Cookie`),
+    /// in the order they were added.
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(k, _)| k.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .collect()
     }
 
+    /// Replaces the value of the first header named `name`, or adds it.
     pub fn set(&mut self, name: &str, value: &str) {
-        let lower = ascii_lowercase(name);
         for (k, v) in self.entries.iter_mut() {
-            if ascii_lowercase(k.as_str()) == lower {
+            if k.as_str().eq_ignore_ascii_case(name) {
                 *v = String::from(value);
                 return;
             }
@@ -34,6 +43,7 @@ impl Headers {
         self.entries.push((String::from(name), String::from(value)));
     }
 
+    /// Adds a header without touching existing values of the same name.
     pub fn append(&mut self, name: &str, value: &str) {
         self.entries.push((String::from(name), String::from(value)));
     }
@@ -48,10 +58,7 @@ impl Headers {
 
     pub fn connection_keep_alive(&self) -> bool {
         match self.get("connection") {
-            Some(v) => {
-                let lower = ascii_lowercase(v);
-                lower.as_str() != "close"
-            }
+            Some(v) => !v.eq_ignore_ascii_case("close"),
             None => true,
         }
     }
@@ -66,18 +73,6 @@ impl Headers {
     }
 }
 
-fn ascii_lowercase(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    for c in s.chars() {
-        if c.is_ascii_uppercase() {
-            result.push((c as u8 + 32) as char);
-        } else {
-            result.push(c);
-        }
-    }
-    result
-}
-
 fn parse_usize(s: &str) -> Option<usize> {
     let mut result: usize = 0;
     for b in s.as_bytes() {
@@ -99,6 +94,29 @@ mod tests {
         h.set("Content-Type", "text/html");
         assert_eq!(h.get("content-type"), Some("text/html"));
         assert_eq!(h.get("CONTENT-TYPE"), Some("text/html"));
+        assert_eq!(h.get("content-length"), None);
+    }
+
+    #[test]
+    fn test_get_all_repeated() {
+        let mut h = Headers::new();
+        h.append("Accept", "text/html");
+        h.append("Host", "example.com");
+        h.append("accept", "application/json");
+        assert_eq!(h.get("ACCEPT"), Some("text/html"));
+        assert_eq!(
+            h.get_all("Accept").as_slice(),
+            &["text/html", "application/json"]
+        );
+        assert!(h.get_all("Set-Cookie").is_empty());
+    }
+
+    #[test]
+    fn test_connection_close_any_case() {
+        let mut h = Headers::new();
+        assert!(h.connection_keep_alive());
+        h.set("Connection", "Close");
+        assert!(!h.connection_keep_alive());
     }
 
     #[test]
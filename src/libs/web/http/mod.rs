@@ -1,6 +1,11 @@
+pub mod cookie;
 pub mod headers;
 pub mod method;
+pub mod multipart;
 pub mod parser;
 pub mod request;
 pub mod response;
 pub mod status;
+pub mod url;
+
+pub use url::{decode_component, decode_query_component, encode_component, encode_query_component};
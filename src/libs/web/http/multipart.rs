@@ -0,0 +1,276 @@
+//! `multipart/form-data` request bodies (RFC 7578).
+//!
+//! The body is split on `--boundary` lines. Each part carries its own header
+//! block; `Content-Disposition` supplies the field name and, for uploads, the
+//! original filename. Lines may end in CRLF or a bare LF.
+
+use super::headers::Headers;
+use crate::core::volkiwithstds::collections::{String, Vec};
+
+/// One part of a multipart form.
+pub struct MultipartPart {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub headers: Headers,
+    pub data: Vec<u8>,
+}
+
+impl MultipartPart {
+    /// Whether this part is a file upload rather than a plain field.
+    pub fn is_file(&self) -> bool {
+        self.filename.is_some()
+    }
+
+    /// The payload as text, or `None` if it is not UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        core::str::from_utf8(&self.data).ok()
+    }
+}
+
+/// A parsed multipart form, parts kept in body order.
+pub struct MultipartForm {
+    parts: Vec<MultipartPart>,
+}
+
+impl MultipartForm {
+    pub fn parts(&self) -> &[MultipartPart] {
+        &self.parts
+    }
+
+    /// The first part with the given field name.
+    pub fn get(&self, name: &str) -> Option<&MultipartPart> {
+        self.parts.iter().find(|p| p.name.as_str() == name)
+    }
+
+    /// The text value of a non-file field.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.get(name)
+            .filter(|p| !p.is_file())
+            .and_then(|p| p.text())
+    }
+
+    /// The first file part with the given field name.
+    pub fn file(&self, name: &str) -> Option<&MultipartPart> {
+        self.get(name).filter(|p| p.is_file())
+    }
+}
+
+/// Extracts the `boundary` parameter of a `multipart/*` Content-Type.
+pub fn boundary(content_type: &str) -> Option<&str> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().to_ascii_lowercase().starts_with("multipart/") {
+        return None;
+    }
+    let value = params.split(';').find_map(|p| {
+        let (k, v) = p.split_once('=')?;
+        k.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then_some(v.trim())
+    })?;
+    let value = unquote(value);
+    if value.is_empty() { None } else { Some(value) }
+}
+
+/// Splits `body` into parts. Returns `None` when the opening delimiter is
+/// missing or a part is malformed; a missing closing `--boundary--` is
+/// tolerated so a truncated body still yields the complete parts.
+pub fn parse(body: &[u8], boundary: &str) -> Option<MultipartForm> {
+    let mut delim: Vec<u8> = Vec::with_capacity(boundary.len() + 2);
+    delim.extend_from_slice(b"--");
+    delim.extend_from_slice(boundary.as_bytes());
+
+    // Anything before the first delimiter is preamble and ignored
+    let mut pos = if body.starts_with(&delim) {
+        delim.len()
+    } else {
+        find(body, &line_delimiter(&delim, b"\n"), 0)? + delim.len() + 1
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        if body[pos..].starts_with(b"--") {
+            break;
+        }
+        // Transport padding may follow the delimiter before the line break
+        while pos < body.len() && (body[pos] == b' ' || body[pos] == b'\t') {
+            pos += 1;
+        }
+        pos += line_break_len(&body[pos..])?;
+
+        let (headers, header_len) = parse_headers(&body[pos..])?;
+        pos += header_len;
+
+        let (data_end, next) = match find(body, &line_delimiter(&delim, b"\n"), pos) {
+            Some(at) => {
+                let end = if at > pos && body[at - 1] == b'\r' {
+                    at - 1
+                } else {
+                    at
+                };
+                (end, at + 1 + delim.len())
+            }
+            // No closing delimiter: keep what we have and stop
+            None => (body.len(), body.len()),
+        };
+        parts.push(part(headers, &body[pos..data_end])?);
+        if next >= body.len() {
+            break;
+        }
+        pos = next;
+    }
+    Some(MultipartForm { parts })
+}
+
+fn part(headers: Headers, data: &[u8]) -> Option<MultipartPart> {
+    let disposition = headers.get("content-disposition")?;
+    let mut name = None;
+    let mut filename = None;
+    for (key, value) in disposition_params(disposition) {
+        if key.eq_ignore_ascii_case("name") {
+            name = Some(value);
+        } else if key.eq_ignore_ascii_case("filename") {
+            filename = Some(value);
+        }
+    }
+    let content_type = headers.get("content-type").map(String::from);
+    Some(MultipartPart {
+        name: name?,
+        filename,
+        content_type,
+        headers,
+        data: data.iter().copied().collect(),
+    })
+}
+
+/// Reads header lines up to the blank line. Returns the headers and the
+/// number of bytes consumed, including the blank line.
+fn parse_headers(bytes: &[u8]) -> Option<(Headers, usize)> {
+    let mut headers = Headers::new();
+    let mut pos = 0;
+    loop {
+        let end = pos + bytes[pos..].iter().position(|&b| b == b'\n')?;
+        let line = &bytes[pos..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        pos = end + 1;
+        if line.is_empty() {
+            return Some((headers, pos));
+        }
+        let line = core::str::from_utf8(line).ok()?;
+        let (k, v) = line.split_once(':')?;
+        headers.append(k.trim(), v.trim());
+    }
+}
+
+/// Splits `form-data; name="a"; filename="b;c.txt"` into `(key, value)`
+/// pairs, honouring quotes and backslash escapes in quoted values.
+fn disposition_params(value: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = match value.split_once(';') {
+        Some((_, rest)) => rest,
+        None => return params,
+    };
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ';']);
+        let Some(eq) = rest.find('=') else {
+            break;
+        };
+        let key = rest[..eq].trim();
+        rest = rest[eq + 1..].trim_start();
+        let mut val = String::new();
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut chars = quoted.char_indices();
+            let mut consumed = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            val.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        consumed = i + 1;
+                        break;
+                    }
+                    c => val.push(c),
+                }
+            }
+            rest = &quoted[consumed..];
+        } else {
+            let end = rest.find(';').unwrap_or(rest.len());
+            val.push_str(rest[..end].trim_end());
+            rest = &rest[end..];
+        }
+        params.push((String::from(key), val));
+    }
+    params
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(s)
+}
+
+fn line_delimiter(delim: &[u8], line_break: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(line_break.len() + delim.len());
+    out.extend_from_slice(line_break);
+    out.extend_from_slice(delim);
+    out
+}
+
+fn line_break_len(bytes: &[u8]) -> Option<usize> {
+    if bytes.starts_with(b"\r\n") {
+        Some(2)
+    } else if bytes.starts_with(b"\n") {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_from_content_type() {
+        assert_eq!(boundary("multipart/form-data; boundary=abc"), Some("abc"));
+        assert_eq!(
+            boundary("Multipart/Form-Data; charset=utf-8; Boundary=\"a b\""),
+            Some("a b")
+        );
+        assert_eq!(boundary("application/json; boundary=abc"), None);
+        assert_eq!(boundary("multipart/form-data"), None);
+    }
+
+    #[test]
+    fn test_lf_only_and_quoted_filename() {
+        let body = b"--x\nContent-Disposition: form-data; name=\"f\"; filename=\"a;\\\"b\\\".txt\"\n\nhi\n--x--\n";
+        let form = parse(body, "x").unwrap();
+        let f = form.file("f").unwrap();
+        assert_eq!(f.filename.as_deref(), Some("a;\"b\".txt"));
+        assert_eq!(f.data.as_slice(), b"hi");
+    }
+
+    #[test]
+    fn test_malformed_body() {
+        assert!(parse(b"no delimiter here", "x").is_none());
+        // Part without Content-Disposition
+        assert!(
+            parse(
+                b"--x\r\nContent-Type: text/plain\r\n\r\nv\r\n--x--\r\n",
+                "x"
+            )
+            .is_none()
+        );
+    }
+}
@@ -30,10 +30,8 @@ pub fn parse_request(buf: &[u8], limits: &SizeLimits) -> ParseResult {
     }
 
     // Parse request line
-    let first_line_end = match find_crlf(header_bytes) {
-        Some(pos) => pos,
-        None => return ParseResult::Error("malformed request line"),
-    };
+    // A request with no header fields is just the request line
+    let first_line_end = find_crlf(header_bytes).unwrap_or(header_bytes.len());
 
     let request_line = &header_bytes[..first_line_end];
 
@@ -64,7 +62,7 @@ pub fn parse_request(buf: &[u8], limits: &SizeLimits) -> ParseResult {
             let name = trim_bytes(&line[..colon]);
             let value = trim_bytes(&line[colon + 1..]);
             if let (Ok(n), Ok(v)) = (core::str::from_utf8(name), core::str::from_utf8(value)) {
-                headers.set(n, v);
+                headers.append(n, v);
             }
         }
         pos = line_end + 2; // skip \r\n
@@ -72,6 +70,35 @@ pub fn parse_request(buf: &[u8], limits: &SizeLimits) -> ParseResult {
 
     // Body handling
     let headers_total = header_end + 4; // include \r\n\r\n
+    // Repeated Transfer-Encoding headers form one list, as a proxy that
+    // folds them would see it; the body is chunked if that is the last coding
+    let is_chunked = headers
+        .get_all("transfer-encoding")
+        .iter()
+        .flat_map(|te| te.split(','))
+        .map(|coding| coding.trim())
+        .rfind(|coding| !coding.is_empty())
+        .is_some_and(|last| last.eq_ignore_ascii_case("chunked"));
+    if is_chunked {
+        return match decode_chunked(&buf[headers_total..], limits.max_body_size) {
+            Ok(Some((body, consumed))) => ParseResult::Complete(
+                Request::new(method, path, headers, body),
+                headers_total + consumed,
+            ),
+            Ok(None) => ParseResult::Incomplete,
+            Err(msg) => ParseResult::Error(msg),
+        };
+    }
+
+    // Repeated headers are kept, so a second Content-Length that disagrees
+    // with the first would make the body boundary ambiguous
+    let conflicting = {
+        let lengths = headers.get_all("content-length");
+        lengths.iter().any(|v| *v != lengths[0])
+    };
+    if conflicting {
+        return ParseResult::Error("conflicting content-length");
+    }
     let content_length = headers.content_length().unwrap_or(0);
 
     if content_length > limits.max_body_size {
@@ -95,6 +122,65 @@ pub fn parse_request(buf: &[u8], limits: &SizeLimits) -> ParseResult {
     ParseResult::Complete(request, total_needed)
 }
 
+/// Decodes a chunked body. `Ok(None)` means more data is needed. A chunk
+/// whose declared size would take the body past `max_body` is rejected as
+/// soon as its size line arrives, before any of its data is buffered.
+fn decode_chunked(buf: &[u8], max_body: usize) -> Result<Option<(Vec<u8>, usize)>, &'static str> {
+    let mut body = Vec::new();
+    let mut pos = 0;
+    loop {
+        let Some(line_len) = find_crlf(&buf[pos..]) else {
+            return Ok(None);
+        };
+        let line = &buf[pos..pos + line_len];
+        // Chunk extensions after `;` are ignored
+        let size_field = match memchr(b';', line) {
+            Some(semi) => &line[..semi],
+            None => line,
+        };
+        let size = parse_hex(trim_bytes(size_field)).ok_or("malformed chunk size")?;
+        pos += line_len + 2;
+
+        if size == 0 {
+            // Skip trailer fields up to the blank line that ends the body
+            loop {
+                let Some(len) = find_crlf(&buf[pos..]) else {
+                    return Ok(None);
+                };
+                pos += len + 2;
+                if len == 0 {
+                    return Ok(Some((body, pos)));
+                }
+            }
+        }
+
+        if size > max_body.saturating_sub(body.len()) {
+            return Err("body too large");
+        }
+        if buf.len() < pos + size + 2 {
+            return Ok(None);
+        }
+        body.extend_from_slice(&buf[pos..pos + size]);
+        pos += size;
+        if &buf[pos..pos + 2] != b"\r\n" {
+            return Err("malformed chunk");
+        }
+        pos += 2;
+    }
+}
+
+fn parse_hex(bytes: &[u8]) -> Option<usize> {
+    if bytes.is_empty() || bytes.len() > 2 * core::mem::size_of::<usize>() {
+        return None;
+    }
+    let mut n = 0usize;
+    for &b in bytes {
+        let digit = (b as char).to_digit(16)? as usize;
+        n = (n << 4) | digit;
+    }
+    Some(n)
+}
+
 fn parse_request_line(line: &[u8]) -> Option<(Method, String)> {
     let first_sp = memchr(b' ', line)?;
     let method = Method::from_bytes(&line[..first_sp])?;
@@ -178,6 +264,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_without_headers() {
+        let raw = b"GET /a HTTP/1.1\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        match parse_request(raw, &defaults()) {
+            ParseResult::Complete(req, consumed) => {
+                assert_eq!(req.route_path.as_str(), "/a");
+                assert_eq!(&raw[consumed..], b"GET /b HTTP/1.1\r\n\r\n");
+            }
+            _ => panic!("expected Complete"),
+        }
+    }
+
     #[test]
     fn test_parse_with_body() {
         let raw = b"POST /data HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
@@ -207,10 +305,9 @@ mod tests {
             ParseResult::Complete(req, _) => {
                 assert_eq!(req.route_path.as_str(), "/search");
                 assert_eq!(req.query_string.as_str(), "q=rust&page=1");
-                let params = req.query_params();
-                assert_eq!(params.len(), 2);
-                assert_eq!(params[0], ("q", "rust"));
-                assert_eq!(params[1], ("page", "1"));
+                assert_eq!(req.query_params().len(), 2);
+                assert_eq!(req.query("q"), Some("rust"));
+                assert_eq!(req.query("page"), Some("1"));
             }
             _ => panic!("expected Complete"),
         }
@@ -237,4 +334,101 @@ mod tests {
             _ => panic!("expected Error"),
         }
     }
+
+    fn small_body_limit() -> SizeLimits {
+        SizeLimits {
+            max_body_size: 16,
+            ..SizeLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_repeated_headers() {
+        let raw = b"GET / HTTP/1.1\r\nAccept: text/html\r\nHOST: a.com\r\naccept: */*\r\n\r\n";
+        match parse_request(raw, &defaults()) {
+            ParseResult::Complete(req, _) => {
+                assert_eq!(req.header("host"), Some("a.com"));
+                assert_eq!(req.header_all("Accept").as_slice(), &["text/html", "*/*"]);
+            }
+            _ => panic!("expected Complete"),
+        }
+    }
+
+    #[test]
+    fn test_conflicting_content_length_rejected() {
+        let raw = b"POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 2\r\n\r\nhello";
+        match parse_request(raw, &defaults()) {
+            ParseResult::Error(msg) => assert_eq!(msg, "conflicting content-length"),
+            _ => panic!("expected Error"),
+        }
+        let raw = b"POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello";
+        assert!(matches!(
+            parse_request(raw, &defaults()),
+            ParseResult::Complete(..)
+        ));
+    }
+
+    #[test]
+    fn test_content_length_over_limit_rejected_early() {
+        // Only the headers have arrived; the declared length alone is enough
+        let raw = b"POST /upload HTTP/1.1\r\nContent-Length: 17\r\n\r\n";
+        match parse_request(raw, &small_body_limit()) {
+            ParseResult::Error(msg) => assert_eq!(msg, "body too large"),
+            _ => panic!("expected Error"),
+        }
+        let raw = b"POST /upload HTTP/1.1\r\nContent-Length: 16\r\n\r\n";
+        assert!(matches!(
+            parse_request(raw, &small_body_limit()),
+            ParseResult::Incomplete
+        ));
+    }
+
+    #[test]
+    fn test_parse_chunked_body() {
+        let raw = b"POST /data HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+5;ext=1\r\nhello\r\n6\r\n world\r\n0\r\nX-Trailer: 1\r\n\r\nGET /next";
+        match parse_request(raw, &defaults()) {
+            ParseResult::Complete(req, consumed) => {
+                assert_eq!(req.body.as_slice(), b"hello world");
+                assert_eq!(&raw[consumed..], b"GET /next");
+            }
+            _ => panic!("expected Complete"),
+        }
+
+        let partial = b"POST /data HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhel";
+        assert!(matches!(
+            parse_request(partial, &defaults()),
+            ParseResult::Incomplete
+        ));
+    }
+
+    #[test]
+    fn test_repeated_transfer_encoding_uses_last_coding() {
+        let raw = b"POST /data HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\
+Transfer-Encoding: chunked\r\nContent-Length: 3\r\n\r\n2\r\nhi\r\n0\r\n\r\n";
+        match parse_request(raw, &defaults()) {
+            ParseResult::Complete(req, consumed) => {
+                assert_eq!(req.body.as_slice(), b"hi");
+                assert_eq!(consumed, raw.len());
+            }
+            _ => panic!("expected Complete"),
+        }
+    }
+
+    #[test]
+    fn test_chunked_over_limit_rejected_before_buffering() {
+        // Second chunk's size line pushes past the limit; its data never arrived
+        let raw = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+a\r\n0123456789\r\n7\r\n";
+        match parse_request(raw, &small_body_limit()) {
+            ParseResult::Error(msg) => assert_eq!(msg, "body too large"),
+            _ => panic!("expected Error"),
+        }
+
+        let raw = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n";
+        assert!(matches!(
+            parse_request(raw, &defaults()),
+            ParseResult::Error(_)
+        ));
+    }
 }
@@ -1,7 +1,11 @@
 //! HTTP request type.
 
+use super::cookie::find_cookie;
 use super::headers::Headers;
 use super::method::Method;
+use super::multipart::{self, MultipartForm};
+use super::url;
+use crate::core::volkiwithstds::collections::json::{self, JsonError, JsonValue};
 use crate::core::volkiwithstds::collections::{HashMap, String, Vec};
 
 pub struct Request {
@@ -12,11 +16,13 @@ pub struct Request {
     pub headers: Headers,
     pub body: Vec<u8>,
     pub params: HashMap<String, String>,
+    query: Vec<(String, String)>,
 }
 
 impl Request {
     pub fn new(method: Method, path: String, headers: Headers, body: Vec<u8>) -> Self {
         let (route_path, query_string) = split_path_query(&path);
+        let query = parse_query(query_string.as_str());
         Self {
             method,
             path,
@@ -25,6 +31,7 @@ impl Request {
             headers,
             body,
             params: HashMap::new(),
+            query,
         }
     }
 
@@ -32,26 +39,100 @@ impl Request {
         self.params.get(name).map(|s| s.as_str())
     }
 
-    pub fn query_params(&self) -> Vec<(&str, &str)> {
-        let mut result = Vec::new();
-        if self.query_string.is_empty() {
-            return result;
-        }
-        for pair in self.query_string.as_str().split('&') {
-            if let Some(eq_pos) = pair.find('=') {
-                let key = &pair[..eq_pos];
-                let val = &pair[eq_pos + 1..];
-                result.push((key, val));
-            } else if !pair.is_empty() {
-                result.push((pair, ""));
+    /// Returns the first value of a query-string parameter, percent-decoded.
+    /// A key without `=` (as in `?debug`) has the empty string as its value.
+    pub fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns every value of a repeated query-string parameter, in order.
+    pub fn query_all(&self, key: &str) -> Vec<&str> {
+        self.query
+            .iter()
+            .filter(|(k, _)| k.as_str() == key)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+
+    /// Returns the decoded query-string parameters. For repeated keys the
+    /// first value wins, matching `query`; use `query_all` for the rest.
+    pub fn query_params(&self) -> HashMap<String, String> {
+        let mut result = HashMap::new();
+        for (k, v) in self.query.iter() {
+            if !result.contains_key(k.as_str()) {
+                result.insert(k.clone(), v.clone());
             }
         }
         result
     }
 
+    /// Returns the value of a cookie sent in a `Cookie` header, searching
+    /// repeated headers in order.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get_all("cookie")
+            .into_iter()
+            .find_map(|header| find_cookie(header, name))
+    }
+
+    /// Like `cookie`, but percent-decoded, for values written with
+    /// `encode_component`.
+    pub fn cookie_decoded(&self, name: &str) -> Option<String> {
+        self.cookie(name).map(url::decode_component)
+    }
+
+    /// Parses an `application/x-www-form-urlencoded` body. For repeated
+    /// keys the first value wins, as in `query_params`. Returns `None` for
+    /// other content types or a non-UTF-8 body.
+    pub fn form_params(&self) -> Option<HashMap<String, String>> {
+        let mime = self.content_type()?.split(';').next()?.trim();
+        if !mime.eq_ignore_ascii_case("application/x-www-form-urlencoded") {
+            return None;
+        }
+        let text = core::str::from_utf8(&self.body).ok()?;
+        let mut result = HashMap::new();
+        for (k, v) in parse_query(text) {
+            if !result.contains_key(k.as_str()) {
+                result.insert(k, v);
+            }
+        }
+        Some(result)
+    }
+
+    /// Parses the body as JSON. An empty, non-UTF-8, truncated or otherwise
+    /// malformed body is an error, so a handler can answer it with a 400.
+    pub fn json(&self) -> Result<JsonValue, JsonError> {
+        let text = core::str::from_utf8(&self.body).map_err(|e| JsonError {
+            pos: e.valid_up_to(),
+            message: "body is not valid UTF-8",
+        })?;
+        json::parse(text)
+    }
+
+    /// Returns the first value of a request header. Names are matched
+    /// ASCII case-insensitively, so `content-type` finds `Content-Type`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)
+    }
+
+    /// Returns every value of a repeated request header, in order.
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.headers.get_all(name)
+    }
+
     pub fn content_type(&self) -> Option<&str> {
         self.headers.get("content-type")
     }
+
+    /// Parses a `multipart/form-data` body. Returns `None` when the
+    /// Content-Type has no boundary or the body is malformed.
+    pub fn multipart(&self) -> Option<MultipartForm> {
+        let boundary = multipart::boundary(self.content_type()?)?;
+        multipart::parse(&self.body, boundary)
+    }
 }
 
 fn split_path_query(path: &String) -> (String, String) {
@@ -63,3 +144,155 @@ fn split_path_query(path: &String) -> (String, String) {
         (path.clone(), String::new())
     }
 }
+
+/// Splits `a=1&b&c=` into decoded `(key, value)` pairs. Empty pairs are skipped.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for pair in query.split('&') {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, val) = match pair.find('=') {
+            Some(eq_pos) => (&pair[..eq_pos], &pair[eq_pos + 1..]),
+            None => (pair, ""),
+        };
+        pairs.push((percent_decode(key), percent_decode(val)));
+    }
+    pairs
+}
+
+/// Decodes `%XX` escapes and `+` as space. Malformed escapes are kept as-is.
+pub use url::decode_query_component as percent_decode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(path: &str) -> Request {
+        Request::new(Method::Get, String::from(path), Headers::new(), Vec::new())
+    }
+
+    #[test]
+    fn test_query_decoding() {
+        let req = get("/search?q=hello%20world&tag=a+b&sym=%26%3D%2b&utf=%C3%A9");
+        assert_eq!(req.query("q"), Some("hello world"));
+        assert_eq!(req.query("tag"), Some("a b"));
+        assert_eq!(req.query("sym"), Some("&=+"));
+        assert_eq!(req.query("utf"), Some("\u{e9}"));
+    }
+
+    #[test]
+    fn test_query_missing_and_empty() {
+        let req = get("/x?a=1&b&c=&&");
+        assert_eq!(req.query("a"), Some("1"));
+        assert_eq!(req.query("b"), Some(""));
+        assert_eq!(req.query("c"), Some(""));
+        assert_eq!(req.query("d"), None);
+        assert_eq!(req.query_params().len(), 3);
+
+        let req = get("/x");
+        assert_eq!(req.query("a"), None);
+        assert!(req.query_params().is_empty());
+    }
+
+    #[test]
+    fn test_query_repeated_keys() {
+        let req = get("/x?id=1&id=2&id=3");
+        assert_eq!(req.query("id"), Some("1"));
+        assert_eq!(req.query_all("id").as_slice(), &["1", "2", "3"]);
+        assert_eq!(req.query_params().get("id").map(|s| s.as_str()), Some("1"));
+    }
+
+    #[test]
+    fn test_json_body() {
+        let body = br#"{"name": "ada", "age": 36}"#;
+        let req = Request::new(
+            Method::Post,
+            String::from("/"),
+            Headers::new(),
+            body.iter().copied().collect(),
+        );
+        let v = req.json().unwrap();
+        let o = v.as_object().unwrap();
+        assert_eq!(o.get("name").and_then(|v| v.as_str()), Some("ada"));
+        assert_eq!(o.get("age").and_then(|v| v.as_i64()), Some(36));
+
+        assert!(get("/").json().is_err());
+    }
+
+    #[test]
+    fn test_json_body_rejects_malformed() {
+        let post = |body: &[u8]| {
+            Request::new(
+                Method::Post,
+                String::from("/"),
+                Headers::new(),
+                body.iter().copied().collect(),
+            )
+        };
+        assert!(post(br#"{"name": "ada""#).json().is_err());
+        assert!(post(br#"{"a": 1} trailing"#).json().is_err());
+        assert!(post(br#"[1, 2,]"#).json().is_err());
+        let err = post(b"[\"\xff\"]").json().unwrap_err();
+        assert_eq!(err.message, "body is not valid UTF-8");
+        assert_eq!(err.pos, 2);
+    }
+
+    #[test]
+    fn test_multipart_form() {
+        let body = b"--XyZ\r\n\
+Content-Disposition: form-data; name=\"title\"\r\n\
+\r\n\
+hello\r\n\
+--XyZ\r\n\
+Content-Disposition: form-data; name=\"upload\"; filename=\"notes.txt\"\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+line one\r\nline two\r\n\
+--XyZ--\r\n";
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "multipart/form-data; boundary=XyZ");
+        let req = Request::new(
+            Method::Post,
+            String::from("/upload"),
+            headers,
+            body.iter().copied().collect(),
+        );
+
+        let form = req.multipart().unwrap();
+        assert_eq!(form.parts().len(), 2);
+        assert_eq!(form.field("title"), Some("hello"));
+
+        let file = form.file("upload").unwrap();
+        assert_eq!(file.filename.as_deref(), Some("notes.txt"));
+        assert_eq!(file.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(file.data.as_slice(), b"line one\r\nline two");
+        assert!(form.field("upload").is_none());
+
+        assert!(get("/").multipart().is_none());
+    }
+
+    #[test]
+    fn test_form_params() {
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "application/x-www-form-urlencoded; charset=utf-8");
+        let req = Request::new(
+            Method::Post,
+            String::from("/login"),
+            headers,
+            b"user=ada+l&note=caf%C3%A9&user=x".iter().copied().collect(),
+        );
+        let form = req.form_params().unwrap();
+        assert_eq!(form.get("user").map(|s| s.as_str()), Some("ada l"));
+        assert_eq!(form.get("note").map(|s| s.as_str()), Some("caf\u{e9}"));
+
+        assert!(get("/").form_params().is_none());
+    }
+
+    #[test]
+    fn test_percent_decode_malformed() {
+        assert_eq!(percent_decode("100%").as_str(), "100%");
+        assert_eq!(percent_decode("%zz%4").as_str(), "%zz%4");
+        assert_eq!(percent_decode("a%2Fb").as_str(), "a/b");
+    }
+}
@@ -1,5 +1,8 @@
 //! HTTP response builder.
 
+use super::cookie::Cookie;
+use crate::core::compress;
+use crate::core::volkiwithstds::collections::json::ToJson;
 use super::headers::Headers;
 use super::status::StatusCode;
 use crate::core::volkiwithstds::collections::Vec;
@@ -37,6 +40,11 @@ impl Response {
         r
     }
 
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
     pub fn header(mut self, name: &str, value: &str) -> Self {
         self.headers.set(name, value);
         self
@@ -49,8 +57,14 @@ impl Response {
         self
     }
 
-    pub fn json(mut self, json: &str) -> Self {
-        self.headers.set("Content-Type", "application/json");
+    /// Serializes `value` as the body with `Content-Type: application/json`.
+    pub fn json<T: ToJson + ?Sized>(self, value: &T) -> Self {
+        self.json_raw(value.to_json().as_str())
+    }
+
+    /// Uses an already-serialized JSON string as the body.
+    pub fn json_raw(mut self, json: &str) -> Self {
+        self.headers.set("Content-Type", "application/json; charset=utf-8");
         self.body = Vec::new();
         self.body.extend_from_slice(json.as_bytes());
         self
@@ -68,18 +82,98 @@ impl Response {
         self.html(rendered.as_str())
     }
 
-    pub fn redirect(mut self, location: &str) -> Self {
-        self.status = StatusCode::FOUND;
+    /// Redirects with `302 Found`. `location` is sent as given, so encode
+    /// any dynamic parts of it with `http::encode_component`.
+    pub fn redirect(self, location: &str) -> Self {
+        self.redirect_with(StatusCode::FOUND, location)
+    }
+
+    /// Redirects with `301 Moved Permanently`; clients may cache it.
+    pub fn redirect_permanent(self, location: &str) -> Self {
+        self.redirect_with(StatusCode::MOVED_PERMANENTLY, location)
+    }
+
+    /// Redirects with `307 Temporary Redirect`, which makes the client
+    /// repeat the same method and body.
+    pub fn redirect_temporary(self, location: &str) -> Self {
+        self.redirect_with(StatusCode::TEMPORARY_REDIRECT, location)
+    }
+
+    fn redirect_with(mut self, status: StatusCode, location: &str) -> Self {
+        self.status = status;
         self.headers.set("Location", location);
         self
     }
 
+    /// Adds a `Set-Cookie` header. Each call adds a separate header.
+    pub fn set_cookie(mut self, cookie: Cookie) -> Self {
+        self.headers.append("Set-Cookie", cookie.to_header_value().as_str());
+        self
+    }
+
     pub fn body_bytes(mut self, bytes: &[u8]) -> Self {
         self.body = Vec::new();
         self.body.extend_from_slice(bytes);
         self
     }
 
+    /// Gzips the body if the client's `Accept-Encoding` allows it, the body
+    /// is at least `GZIP_MIN_SIZE` bytes, the content type is one that
+    /// compresses well, and it is not a byte-range response. Adds
+    /// `Vary: Accept-Encoding` to every response that qualifies, so caches
+    /// keep the two variants apart.
+    pub fn compress_for(&mut self, accept_encoding: Option<&str>) {
+        if self.body.len() < GZIP_MIN_SIZE
+            || self.headers.get("content-encoding").is_some()
+            || self.headers.get("content-range").is_some()
+        {
+            return;
+        }
+        if !self.headers.get("content-type").is_some_and(is_compressible) {
+            return;
+        }
+        self.vary_on_accept_encoding();
+        if !accept_encoding.is_some_and(accepts_gzip) {
+            return;
+        }
+        if let Ok(packed) = compress::gzip(&self.body)
+            && packed.len() < self.body.len()
+        {
+            self.body = packed;
+            self.headers.set("Content-Encoding", "gzip");
+        }
+    }
+
+    /// Adds `Accept-Encoding` to the `Vary` header, merging it into an
+    /// existing value rather than sending a second `Vary`.
+    fn vary_on_accept_encoding(&mut self) {
+        let merged = {
+            let existing = self.headers.get_all("vary");
+            let covered = existing.iter().flat_map(|v| v.split(',')).any(|field| {
+                let field = field.trim();
+                field == "*" || field.eq_ignore_ascii_case("accept-encoding")
+            });
+            if covered {
+                return;
+            }
+            existing.first().map(|first| crate::vformat!("{first}, Accept-Encoding"))
+        };
+        match merged {
+            Some(merged) => self.headers.set("Vary", merged.as_str()),
+            None => self.headers.append("Vary", "Accept-Encoding"),
+        }
+    }
+
+    /// Drops the body for a HEAD response. The `Content-Length` the body
+    /// would have had is kept, so call this after `compress_for`.
+    pub fn strip_body(&mut self) {
+        if self.headers.get("content-length").is_none() {
+            let len = crate::vformat!("{}", self.body.len());
+            self.headers.set("Content-Length", len.as_str());
+        }
+        self.body = Vec::new();
+    }
+
     pub fn serialize(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(256 + self.body.len());
 
@@ -120,6 +214,50 @@ impl Response {
     }
 }
 
+/// Responses smaller than this are sent uncompressed.
+pub const GZIP_MIN_SIZE: usize = 1024;
+
+/// Text-like types; images, fonts and archives are already compressed.
+fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json"
+                | "application/javascript"
+                | "application/xml"
+                | "application/wasm"
+                | "application/manifest+json"
+                | "image/svg+xml"
+        )
+}
+
+/// Whether an `Accept-Encoding` value allows gzip (explicitly or via `*`),
+/// honouring `q=0` as a refusal.
+/// An explicit `gzip` entry decides; otherwise a `*` entry does.
+fn accepts_gzip(accept_encoding: &str) -> bool {
+    let mut wildcard = None;
+    for item in accept_encoding.split(',') {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim();
+        let is_gzip = coding.eq_ignore_ascii_case("gzip");
+        if !is_gzip && coding != "*" {
+            continue;
+        }
+        let refused = parts.any(|p| {
+            p.trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        if is_gzip {
+            return !refused;
+        }
+        wildcard.get_or_insert(!refused);
+    }
+    wildcard.unwrap_or(false)
+}
+
 fn write_u16(val: u16, buf: &mut Vec<u8>) {
     let mut tmp = [0u8; 5];
     let mut pos = 5;
@@ -167,9 +305,106 @@ mod tests {
         assert!(s.ends_with("hello"));
     }
 
+    #[test]
+    fn test_redirects() {
+        let resp = Response::ok().redirect("/login");
+        assert_eq!(resp.status, StatusCode::FOUND);
+        assert_eq!(resp.headers.get("location"), Some("/login"));
+
+        let resp = Response::ok().redirect_permanent("/docs/");
+        assert_eq!(resp.status.code(), 301);
+        assert_eq!(resp.headers.get("location"), Some("/docs/"));
+
+        let resp = Response::ok().redirect_temporary("https://example.com/x");
+        assert_eq!(resp.status.code(), 307);
+        assert_eq!(resp.headers.get("location"), Some("https://example.com/x"));
+        let bytes = resp.serialize();
+        let s = core::str::from_utf8(&bytes).unwrap();
+        assert!(s.starts_with("HTTP/1.1 307 Temporary Redirect\r\n"));
+    }
+
     #[test]
     fn test_not_found() {
         let resp = Response::not_found();
         assert_eq!(resp.status, StatusCode::NOT_FOUND);
     }
+
+    #[test]
+    fn test_json() {
+        use crate::core::volkiwithstds::collections::{HashMap, String};
+
+        let mut user = HashMap::new();
+        user.insert(String::from("name"), String::from("ada"));
+        user.insert(String::from("role"), String::from("admin"));
+        let resp = Response::ok().json(&user);
+        assert_eq!(
+            resp.headers.get("content-type"),
+            Some("application/json; charset=utf-8")
+        );
+        assert_eq!(resp.body.as_slice(), br#"{"name":"ada","role":"admin"}"#);
+
+        let resp = Response::ok().json_raw("{\"ok\":true}");
+        assert_eq!(resp.body.as_slice(), b"{\"ok\":true}");
+    }
+
+    fn big_html() -> Response {
+        let mut html = crate::core::volkiwithstds::collections::String::new();
+        for _ in 0..200 {
+            html.push_str("<p class=\"row\">hello world</p>");
+        }
+        Response::ok().html(&html)
+    }
+
+    #[test]
+    fn test_compress_for_gzip() {
+        let mut resp = big_html();
+        let original = resp.body.clone();
+        resp.compress_for(Some("deflate, gzip;q=0.8, br"));
+        assert_eq!(resp.headers.get("content-encoding"), Some("gzip"));
+        assert_eq!(resp.headers.get("vary"), Some("Accept-Encoding"));
+        assert!(resp.body.len() < original.len());
+        assert_eq!(compress::gunzip(&resp.body).unwrap(), original);
+    }
+
+    #[test]
+    fn test_compress_for_skips() {
+        // Client doesn't accept gzip: still varies, not encoded
+        let mut resp = big_html();
+        resp.compress_for(Some("br, gzip;q=0"));
+        assert!(resp.headers.get("content-encoding").is_none());
+        assert_eq!(resp.headers.get("vary"), Some("Accept-Encoding"));
+
+        // Below the threshold
+        let mut resp = Response::ok().html("<p>tiny</p>");
+        resp.compress_for(Some("gzip"));
+        assert!(resp.headers.get("content-encoding").is_none());
+        assert!(resp.headers.get("vary").is_none());
+
+        // An existing Vary is extended, not repeated
+        let mut resp = big_html().header("Vary", "Origin");
+        resp.compress_for(Some("gzip"));
+        assert_eq!(resp.headers.get_all("vary").as_slice(), &["Origin, Accept-Encoding"]);
+        let mut resp = big_html().header("Vary", "accept-encoding");
+        resp.compress_for(Some("gzip"));
+        assert_eq!(resp.headers.get_all("vary").as_slice(), &["accept-encoding"]);
+
+        // Already-compressed content type
+        let mut resp = Response::ok()
+            .header("Content-Type", "image/png")
+            .body_bytes(&[0u8; 4096]);
+        resp.compress_for(Some("gzip"));
+        assert!(resp.headers.get("content-encoding").is_none());
+    }
+
+    #[test]
+    fn test_accepts_gzip() {
+        assert!(accepts_gzip("gzip"));
+        assert!(accepts_gzip("GZIP, deflate"));
+        assert!(accepts_gzip("*"));
+        assert!(!accepts_gzip("gzip;q=0"));
+        assert!(accepts_gzip("*;q=0, gzip"));
+        assert!(!accepts_gzip("*, gzip;q=0"));
+        assert!(!accepts_gzip("identity"));
+        assert!(!accepts_gzip(""));
+    }
 }
@@ -0,0 +1,133 @@
+//! Percent-encoding (RFC 3986) shared by query strings, form bodies,
+//! cookies and redirect targets.
+//!
+//! The `_component` functions treat `+` as an ordinary character. The
+//! `_query_component` variants follow `application/x-www-form-urlencoded`,
+//! where `+` stands for a space. Malformed escapes (`%`, `%4`, `%zz`) are
+//! kept as literal text when decoding, and bytes that do not form valid
+//! UTF-8 become U+FFFD.
+
+use crate::core::volkiwithstds::collections::{String, Vec};
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Percent-encodes everything except the RFC 3986 unreserved characters
+/// (`A-Z a-z 0-9 - . _ ~`), so the result is safe in any URL part.
+pub fn encode_component(s: &str) -> String {
+    encode(s, false)
+}
+
+/// Like [`encode_component`], but writes spaces as `+`.
+pub fn encode_query_component(s: &str) -> String {
+    encode(s, true)
+}
+
+/// Decodes `%XX` escapes. `+` is left as is.
+pub fn decode_component(s: &str) -> String {
+    decode(s, false)
+}
+
+/// Decodes `%XX` escapes and `+` as space.
+pub fn decode_query_component(s: &str) -> String {
+    decode(s, true)
+}
+
+fn encode(s: &str, space_as_plus: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for &b in s.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(b as char)
+            }
+            b' ' if space_as_plus => out.push('+'),
+            _ => {
+                out.push('%');
+                out.push(HEX[(b >> 4) as usize] as char);
+                out.push(HEX[(b & 0xf) as usize] as char);
+            }
+        }
+    }
+    out
+}
+
+fn decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_as_space => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => match (hex_val(bytes[i + 1]), hex_val(bytes[i + 2])) {
+                (Some(hi), Some(lo)) => {
+                    out.push((hi << 4) | lo);
+                    i += 2;
+                }
+                _ => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out)
+}
+
+fn hex_val(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_component() {
+        assert_eq!(encode_component("AZaz09-._~"), "AZaz09-._~");
+        assert_eq!(
+            encode_component("a b&c=d/e?f#g+h"),
+            "a%20b%26c%3Dd%2Fe%3Ff%23g%2Bh"
+        );
+        assert_eq!(encode_query_component("a b+c"), "a+b%2Bc");
+        assert_eq!(encode_component(""), "");
+    }
+
+    #[test]
+    fn test_round_trip_utf8() {
+        for s in [
+            "caf\u{e9}",
+            "\u{65e5}\u{672c}\u{8a9e}",
+            "emoji \u{1f980}!",
+            "100% + 1",
+        ] {
+            let encoded = encode_component(s);
+            assert!(
+                encoded
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"-._~%".contains(&b))
+            );
+            assert_eq!(decode_component(&encoded), s);
+            assert_eq!(decode_query_component(&encode_query_component(s)), s);
+        }
+        assert_eq!(encode_component("\u{e9}"), "%C3%A9");
+        assert_eq!(decode_component("%e2%82%ac"), "\u{20ac}");
+    }
+
+    #[test]
+    fn test_plus_only_in_query_context() {
+        assert_eq!(decode_component("a+b%20c"), "a+b c");
+        assert_eq!(decode_query_component("a+b%20c"), "a b c");
+    }
+
+    #[test]
+    fn test_decode_malformed_is_literal() {
+        assert_eq!(decode_component("100%"), "100%");
+        assert_eq!(decode_component("%zz%4"), "%zz%4");
+        assert_eq!(decode_component("%%41"), "%A");
+        // A lone continuation byte is not UTF-8
+        assert_eq!(decode_component("a%80b"), "a\u{fffd}b");
+    }
+}
@@ -34,6 +34,31 @@ pub struct ParsedMetadata {
     pub og_title: Option<String>,
     pub og_description: Option<String>,
     pub og_type: Option<String>,
+    pub og_url: Option<String>,
+    pub og_image: Option<String>,
+    pub twitter_card: Option<String>,
+    pub twitter_image: Option<String>,
+}
+
+impl ParsedMetadata {
+    /// The `<meta>` tags to emit, as `(attribute, key, content)`. Open Graph
+    /// tags use `property`; description and Twitter tags use `name`.
+    fn meta_tags(&self) -> Vec<(&'static str, &'static str, &str)> {
+        let fields = [
+            ("name", "description", &self.description),
+            ("property", "og:title", &self.og_title),
+            ("property", "og:description", &self.og_description),
+            ("property", "og:type", &self.og_type),
+            ("property", "og:url", &self.og_url),
+            ("property", "og:image", &self.og_image),
+            ("name", "twitter:card", &self.twitter_card),
+            ("name", "twitter:image", &self.twitter_image),
+        ];
+        fields
+            .into_iter()
+            .filter_map(|(attr, key, value)| value.as_ref().map(|v| (attr, key, v.as_str())))
+            .collect()
+    }
 }
 
 // DynamicPageData contains only Vec, String, HashMap — all Send+Sync in volkiwithstds.
@@ -49,35 +74,11 @@ pub fn interpret_page(data: &DynamicPageData, _req: &Request) -> HtmlDocument {
         if let Some(ref title) = meta.title {
             doc = doc.title(title.as_str());
         }
-        if let Some(ref desc) = meta.description {
+        for (attr, key, content) in meta.meta_tags() {
             doc = doc.head_node(
                 crate::libs::web::html::element::meta()
-                    .attr("name", "description")
-                    .attr("content", desc.as_str())
-                    .into_node(),
-            );
-        }
-        if let Some(ref og_title) = meta.og_title {
-            doc = doc.head_node(
-                crate::libs::web::html::element::meta()
-                    .attr("property", "og:title")
-                    .attr("content", og_title.as_str())
-                    .into_node(),
-            );
-        }
-        if let Some(ref og_desc) = meta.og_description {
-            doc = doc.head_node(
-                crate::libs::web::html::element::meta()
-                    .attr("property", "og:description")
-                    .attr("content", og_desc.as_str())
-                    .into_node(),
-            );
-        }
-        if let Some(ref og_type) = meta.og_type {
-            doc = doc.head_node(
-                crate::libs::web::html::element::meta()
-                    .attr("property", "og:type")
-                    .attr("content", og_type.as_str())
+                    .attr(attr, key)
+                    .attr("content", content)
                     .into_node(),
             );
         }
@@ -158,27 +159,219 @@ fn interpret_node(node: &RsxNode, fragments: &HashMap<String, Vec<RsxNode>>) ->
             v.push(HtmlNode::Raw(rendered));
             v
         }
-        RsxNode::CondAnd { body, .. } => {
-            // In dev-mode interpretation, always render the body
+        RsxNode::CondAnd { .. } | RsxNode::Ternary { .. } => {
             let mut v = Vec::new();
-            for node in body.iter() {
+            for node in conditional_branch(node).iter() {
                 let nodes = interpret_node(node, fragments);
                 for n in nodes { v.push(n); }
             }
             v
         }
-        RsxNode::Ternary { if_true, .. } => {
-            // In dev-mode interpretation, render the true branch
-            let mut v = Vec::new();
-            for node in if_true.iter() {
-                let nodes = interpret_node(node, fragments);
-                for n in nodes { v.push(n); }
+    }
+}
+
+/// The nodes a `CondAnd`/`Ternary` renders. Conditions built from literals
+/// are evaluated; anything dynamic renders the body / true branch, since
+/// dev mode has no values to evaluate it against.
+fn conditional_branch(node: &RsxNode) -> &[RsxNode] {
+    match node {
+        RsxNode::CondAnd { condition, body } => {
+            match eval_literal_condition(condition.as_str()) {
+                Some(false) => &[],
+                _ => body.as_slice(),
+            }
+        }
+        RsxNode::Ternary { condition, if_true, if_false } => {
+            match eval_literal_condition(condition.as_str()) {
+                Some(false) => if_false.as_slice(),
+                _ => if_true.as_slice(),
+            }
+        }
+        _ => &[],
+    }
+}
+
+/// Evaluate a condition made only of literals: `true`, `false`, `!cond`,
+/// parentheses, and `==`/`!=` between two literals. Returns None when the
+/// condition depends on anything else.
+fn eval_literal_condition(condition: &str) -> Option<bool> {
+    let cond = strip_outer_parens(condition.trim());
+    match cond {
+        "true" => return Some(true),
+        "false" => return Some(false),
+        _ => {}
+    }
+    if let Some((lhs, op, rhs)) = split_comparison(cond) {
+        let equal = literal_eq(lhs.trim(), rhs.trim())?;
+        return Some(if op == "==" { equal } else { !equal });
+    }
+    if let Some(rest) = cond.strip_prefix('!') {
+        return eval_literal_condition(rest).map(|b| !b);
+    }
+    None
+}
+
+/// Strip parentheses that wrap the whole expression, e.g. `((a))` → `a`.
+fn strip_outer_parens(mut s: &str) -> &str {
+    while s.starts_with('(') && s.ends_with(')') {
+        let mut depth = 0i32;
+        let mut wraps = true;
+        for (i, b) in s.bytes().enumerate() {
+            match b {
+                b'(' => depth += 1,
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 && i != s.len() - 1 {
+                        wraps = false;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !wraps {
+            break;
+        }
+        s = s[1..s.len() - 1].trim();
+    }
+    s
+}
+
+/// Split `a == b` / `a != b` at a top-level operator outside string literals.
+fn split_comparison(s: &str) -> Option<(&str, &str, &str)> {
+    let bytes = s.as_bytes();
+    let mut depth = 0i32;
+    let mut in_str = false;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        let b = bytes[i];
+        if in_str {
+            if b == b'\\' {
+                i += 1;
+            } else if b == b'"' {
+                in_str = false;
+            }
+        } else {
+            match b {
+                b'"' => in_str = true,
+                b'(' | b'[' => depth += 1,
+                b')' | b']' => depth -= 1,
+                b'=' | b'!' if depth == 0 && bytes[i + 1] == b'=' => {
+                    let op = if b == b'=' { "==" } else { "!=" };
+                    return Some((&s[..i], op, &s[i + 2..]));
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Compare two literals (bool, number, string or char). None if either side
+/// is not a literal or the kinds differ.
+fn literal_eq(lhs: &str, rhs: &str) -> Option<bool> {
+    let quoted = |s: &str, q: char| s.len() >= 2 && s.starts_with(q) && s.ends_with(q);
+    if (quoted(lhs, '"') && quoted(rhs, '"')) || (quoted(lhs, '\'') && quoted(rhs, '\'')) {
+        return Some(lhs == rhs);
+    }
+    if let (Ok(a), Ok(b)) = (lhs.parse::<bool>(), rhs.parse::<bool>()) {
+        return Some(a == b);
+    }
+    if let (Ok(a), Ok(b)) = (lhs.parse::<f64>(), rhs.parse::<f64>()) {
+        return Some(a == b);
+    }
+    None
+}
+
+/// Value of an attribute expression made only of literals: a string,
+/// number or bool, or `if cond { .. } else { .. }` over such values. The
+/// branch is picked like [`conditional_branch`] does: a literal condition
+/// decides, anything else previews the `if` branch. None when the value
+/// needs runtime data.
+fn eval_literal_attr(expr: &str) -> Option<String> {
+    let expr = strip_outer_parens(expr.trim());
+    if let Some(rest) = expr.strip_prefix("if ") {
+        let open = find_outside_strings(rest, b'{')?;
+        let close = open + block_end(&rest[open..])?;
+        let else_part = rest[close + 1..].trim_start().strip_prefix("else")?.trim_start();
+        let chosen = match eval_literal_condition(&rest[..open]) {
+            Some(false) => else_part,
+            _ => &rest[open..=close],
+        };
+        return eval_literal_attr(chosen);
+    }
+    if expr.starts_with('{') && block_end(expr) == Some(expr.len() - 1) {
+        return eval_literal_attr(&expr[1..expr.len() - 1]);
+    }
+    if let Some(inner) = expr.strip_prefix('"') {
+        return unescape_string_literal(inner);
+    }
+    if expr == "true" || expr == "false" || expr.parse::<f64>().is_ok() {
+        return Some(String::from(expr));
+    }
+    None
+}
+
+/// Index of the first `byte` outside string literals.
+fn find_outside_strings(s: &str, byte: u8) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut in_str = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_str => i += 1,
+            b'"' => in_str = !in_str,
+            b if b == byte && !in_str => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the `}` closing the block that `s` opens with.
+fn block_end(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut from = 0;
+    loop {
+        let open = find_outside_strings(&s[from..], b'{').map(|i| i + from);
+        let close = find_outside_strings(&s[from..], b'}').map(|i| i + from)?;
+        match open {
+            Some(o) if o < close => {
+                depth += 1;
+                from = o + 1;
+            }
+            _ => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(close);
+                }
+                from = close + 1;
             }
-            v
         }
     }
 }
 
+/// Decode the rest of a `"..."` literal (opening quote already stripped).
+/// None if anything follows the closing quote.
+fn unescape_string_literal(inner: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return chars.as_str().trim().is_empty().then_some(out),
+            '\\' => match chars.next()? {
+                'n' => out.push('\n'),
+                't' => out.push('\t'),
+                other => out.push(other),
+            },
+            c => out.push(c),
+        }
+    }
+    None
+}
+
 /// Interpret an expression node.
 ///
 /// - If it looks like a fragment call `name()`, look it up in fragments.
@@ -247,8 +440,13 @@ fn interpret_element(
             RsxAttrValue::Expr(v) => {
                 if attr.name.as_str().starts_with("on") {
                     runtime_attrs.push((crate::vformat!("data-volki-{}", attr.name), v.clone()));
+                } else if let Some(value) = eval_literal_attr(v.as_str()) {
+                    runtime_attrs.push((attr.name.clone(), value));
                 }
             }
+            // Spread values and class conditions are only known at runtime in
+            // compiled output
+            RsxAttrValue::Spread(_) | RsxAttrValue::ClassToggle(_) => {}
         }
     }
 
@@ -302,23 +500,8 @@ fn interpret_element(
                     );
                     runtime_children.push(RuntimeHtmlNode::Element(child_el));
                 }
-                RsxNode::CondAnd { body, .. } => {
-                    for node in body.iter() {
-                        let html_nodes = interpret_node(node, fragments);
-                        for html_node in html_nodes {
-                            match html_node {
-                                HtmlNode::Text(t) => runtime_children.push(RuntimeHtmlNode::Text(t)),
-                                HtmlNode::Raw(r) => runtime_children.push(RuntimeHtmlNode::Raw(r)),
-                                HtmlNode::Element(el) => {
-                                    let rendered = crate::libs::web::html::render::render_element(&el);
-                                    runtime_children.push(RuntimeHtmlNode::Raw(rendered));
-                                }
-                            }
-                        }
-                    }
-                }
-                RsxNode::Ternary { if_true, .. } => {
-                    for node in if_true.iter() {
+                RsxNode::CondAnd { .. } | RsxNode::Ternary { .. } => {
+                    for node in conditional_branch(child).iter() {
                         let html_nodes = interpret_node(node, fragments);
                         for html_node in html_nodes {
                             match html_node {
@@ -348,25 +531,22 @@ fn interpret_element(
 ///
 /// Looks for chained method calls like `.title("...")`, `.description("...")`, etc.
 pub fn extract_metadata(body: &str) -> Option<ParsedMetadata> {
-    let title = extract_string_arg(body, ".title(");
-    let description = extract_string_arg(body, ".description(");
-    let og_title = extract_string_arg(body, ".og_title(");
-    let og_description = extract_string_arg(body, ".og_description(");
-    let og_type = extract_string_arg(body, ".og_type(");
-
-    if title.is_none() && description.is_none() && og_title.is_none()
-        && og_description.is_none() && og_type.is_none()
-    {
+    let meta = ParsedMetadata {
+        title: extract_string_arg(body, ".title("),
+        description: extract_string_arg(body, ".description("),
+        og_title: extract_string_arg(body, ".og_title("),
+        og_description: extract_string_arg(body, ".og_description("),
+        og_type: extract_string_arg(body, ".og_type("),
+        og_url: extract_string_arg(body, ".og_url("),
+        og_image: extract_string_arg(body, ".og_image("),
+        twitter_card: extract_string_arg(body, ".twitter_card("),
+        twitter_image: extract_string_arg(body, ".twitter_image("),
+    };
+
+    if meta.title.is_none() && meta.meta_tags().is_empty() {
         return None;
     }
-
-    Some(ParsedMetadata {
-        title,
-        description,
-        og_title,
-        og_description,
-        og_type,
-    })
+    Some(meta)
 }
 
 /// Extract a string argument from a pattern like `.method("value")`.
@@ -604,6 +784,10 @@ mod tests {
                 og_title: None,
                 og_description: None,
                 og_type: None,
+                og_url: None,
+                og_image: None,
+                twitter_card: None,
+                twitter_image: None,
             }),
             client_glue_url: None,
         };
@@ -676,6 +860,44 @@ mod tests {
         assert!(meta.og_description.is_none());
     }
 
+    #[test]
+    fn test_extract_and_render_og_image() {
+        let body = r#"
+            Metadata::new()
+                .og_image("https://example.com/card.png")
+                .og_url("https://example.com/post")
+                .twitter_card("summary_large_image")
+                .twitter_image("https://example.com/tw.png")
+        "#;
+        let meta = extract_metadata(body).unwrap();
+        assert!(meta.title.is_none());
+        assert_eq!(meta.og_image.as_deref(), Some("https://example.com/card.png"));
+        assert_eq!(meta.og_url.as_deref(), Some("https://example.com/post"));
+        assert_eq!(meta.twitter_card.as_deref(), Some("summary_large_image"));
+        assert_eq!(meta.twitter_image.as_deref(), Some("https://example.com/tw.png"));
+
+        let data = DynamicPageData {
+            nodes: crate::vvec![RsxNode::Text(s("content"))],
+            css: String::new(),
+            fragments: HashMap::new(),
+            metadata: Some(meta),
+            client_glue_url: None,
+        };
+        let req = Request::new(
+            crate::libs::web::http::method::Method::Get,
+            String::from("/"),
+            crate::libs::web::http::headers::Headers::new(),
+            Vec::new(),
+        );
+        let html = interpret_page(&data, &req).render();
+        assert!(html.contains(
+            "<meta property=\"og:image\" content=\"https://example.com/card.png\">"
+        ));
+        assert!(html.contains("property=\"og:url\""));
+        assert!(html.contains("<meta name=\"twitter:card\" content=\"summary_large_image\">"));
+        assert!(html.contains("name=\"twitter:image\""));
+    }
+
     #[test]
     fn test_extract_metadata_none() {
         let body = "Response::ok()";
@@ -699,7 +921,9 @@ mod tests {
                     tag: s("input"),
                     attrs: crate::vvec![
                         RsxAttr { name: s("type"), value: RsxAttrValue::Literal(s("text")) },
-                        RsxAttr { name: s("placeholder"), value: RsxAttrValue::Literal(s("Search...")) }
+                        RsxAttr { name: s("placeholder"), value: RsxAttrValue::Literal(s("Search...")) },
+                        // Spreads over runtime values render nothing in the dev interpreter
+                        RsxAttr { name: s(""), value: RsxAttrValue::Spread(s("extra")) }
                     ],
                     children: Vec::new(),
                     self_closing: true,
@@ -778,4 +1002,117 @@ mod tests {
         let html = doc.render();
         assert!(html.contains("<script type=\"module\" src=\"/wasm/page_glue.js\"></script>"));
     }
+
+    fn render_nodes(nodes: Vec<RsxNode>) -> String {
+        let data = DynamicPageData {
+            nodes,
+            css: String::new(),
+            fragments: HashMap::new(),
+            metadata: None,
+            client_glue_url: None,
+        };
+        let req = Request::new(
+            crate::libs::web::http::method::Method::Get,
+            String::from("/"),
+            crate::libs::web::http::headers::Headers::new(),
+            Vec::new(),
+        );
+        interpret_page(&data, &req).render()
+    }
+
+    fn para(text: &str) -> RsxNode {
+        RsxNode::Element {
+            tag: s("p"),
+            attrs: Vec::new(),
+            children: crate::vvec![RsxNode::Text(s(text))],
+            self_closing: false,
+        }
+    }
+
+    #[test]
+    fn test_interpret_cond_and_literal_false_renders_nothing() {
+        let html = render_nodes(crate::vvec![RsxNode::Element {
+            tag: s("div"),
+            attrs: Vec::new(),
+            children: crate::vvec![RsxNode::CondAnd { condition: s("false"), body: crate::vvec![para("x")] }],
+            self_closing: false,
+        }]);
+        assert!(html.contains("<div></div>"));
+        assert!(!html.contains("<p>x</p>"));
+
+        let html = render_nodes(crate::vvec![RsxNode::CondAnd { condition: s("!true"), body: crate::vvec![para("x")] }]);
+        assert!(!html.contains("<p>x</p>"));
+    }
+
+    #[test]
+    fn test_interpret_ternary_literal_picks_branch() {
+        let ternary = |cond: &str| RsxNode::Ternary {
+            condition: s(cond),
+            if_true: crate::vvec![para("yes")],
+            if_false: crate::vvec![para("no")],
+        };
+
+        let html = render_nodes(crate::vvec![ternary("true")]);
+        assert!(html.contains("<p>yes</p>") && !html.contains("<p>no</p>"));
+        let html = render_nodes(crate::vvec![ternary("(false)")]);
+        assert!(html.contains("<p>no</p>") && !html.contains("<p>yes</p>"));
+        let html = render_nodes(crate::vvec![ternary("1 == 2")]);
+        assert!(html.contains("<p>no</p>"));
+        let html = render_nodes(crate::vvec![ternary("\"a\" != \"b\"")]);
+        assert!(html.contains("<p>yes</p>"));
+    }
+
+    #[test]
+    fn test_interpret_dynamic_condition_renders_true_branch() {
+        let html = render_nodes(crate::vvec![RsxNode::Ternary {
+            condition: s("user.is_admin"),
+            if_true: crate::vvec![para("yes")],
+            if_false: crate::vvec![para("no")],
+        }]);
+        assert!(html.contains("<p>yes</p>"));
+    }
+
+    #[test]
+    fn test_interpret_literal_expression_attrs() {
+        let el = |attrs: Vec<RsxAttr>| RsxNode::Element {
+            tag: s("div"),
+            attrs,
+            children: Vec::new(),
+            self_closing: false,
+        };
+        let attr = |name: &str, expr: &str| RsxAttr { name: s(name), value: RsxAttrValue::Expr(s(expr)) };
+
+        let html = render_nodes(crate::vvec![el(crate::vvec![
+            attr("class", "\"card wide\""),
+            attr("tabindex", "0"),
+            attr("style", "user_style()"),
+        ])]);
+        assert!(html.contains("<div class=\"card wide\" tabindex=\"0\"></div>"));
+
+        let html = render_nodes(crate::vvec![el(crate::vvec![attr("class", "if false { \"on\" } else { \"off\" }")])]);
+        assert!(html.contains("<div class=\"off\">"));
+    }
+
+    #[test]
+    fn test_eval_literal_attr() {
+        assert_eq!(eval_literal_attr("\"a \\\"b\\\"\"").as_deref(), Some("a \"b\""));
+        assert_eq!(eval_literal_attr("if active {\"on\"} else {\"off\"}").as_deref(), Some("on"));
+        assert_eq!(eval_literal_attr("if 1 == 2 { \"a\" } else if true { \"{b}\" } else { \"c\" }").as_deref(), Some("{b}"));
+        assert_eq!(eval_literal_attr("(42)").as_deref(), Some("42"));
+        assert_eq!(eval_literal_attr("if active { name } else { \"x\" }"), None);
+        assert_eq!(eval_literal_attr("\"a\" + b"), None);
+        assert_eq!(eval_literal_attr("format!(\"{}\", x)"), None);
+    }
+
+    #[test]
+    fn test_eval_literal_condition() {
+        assert_eq!(eval_literal_condition("true"), Some(true));
+        assert_eq!(eval_literal_condition("!(false)"), Some(true));
+        assert_eq!(eval_literal_condition("3 != 3"), Some(false));
+        assert_eq!(eval_literal_condition("'a' == 'a'"), Some(true));
+        assert_eq!(eval_literal_condition("(a) == (b)"), None);
+        assert_eq!(eval_literal_condition("!is_open"), None);
+        assert_eq!(eval_literal_condition("count == 0"), None);
+        assert_eq!(eval_literal_condition("\"==\" == \"==\""), Some(true));
+    }
 }
@@ -69,7 +69,7 @@ fn scan_dir(
         if let Some(data) = parse_volki_file(nf_volki.as_path(), root)? {
             routes.push(DynamicRoute {
                 kind: DynamicRouteKind::NotFound,
-                url_path: String::new(),
+                url_path: dir_to_url(dir, root),
                 data: Arc::new(data),
             });
         }
@@ -172,7 +172,7 @@ fn parse_volki_file(path: &Path, root: &Path) -> Result<Option<DynamicPageData>,
                     }
                 }
             }
-            RsxReturnType::Client | RsxReturnType::Component => {
+            RsxReturnType::Client | RsxReturnType::Component | RsxReturnType::Shared => {
                 // Client/Component functions can't be interpreted — skip
             }
         }
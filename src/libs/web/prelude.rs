@@ -3,11 +3,13 @@
 pub use super::server::Server;
 pub use super::http::method::Method;
 pub use super::http::status::StatusCode;
+pub use super::http::cookie::{Cookie, SameSite};
 pub use super::http::headers::Headers;
 pub use super::http::request::Request;
 pub use super::http::response::Response;
 pub use super::html::document::HtmlDocument;
 pub use super::router::file_route::FileRoute;
+pub use super::router::middleware::{Middleware, Next};
 pub use super::html::element::{
     div, span, p, h1, h2, h3, h4, h5, h6,
     a, nav, header, footer, main_el, section, article,
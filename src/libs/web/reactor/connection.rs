@@ -95,6 +95,12 @@ impl Connection {
         }
     }
 
+    /// Whether the client has sent more than `max_read_buf` bytes without
+    /// completing a request.
+    pub fn read_overflowed(&self) -> bool {
+        self.max_read_buf > 0 && self.read_buf.len() > self.max_read_buf
+    }
+
     /// Try to read data from the socket. Returns Ok(true) if data was read,
     /// Ok(false) if EAGAIN/WouldBlock, Err on real error.
     pub fn try_read(&mut self) -> Result<bool, IoError> {
@@ -117,8 +123,7 @@ impl Connection {
             if ret > 0 {
                 self.read_buf.extend_from_slice(&tmp[..ret as usize]);
                 self.last_activity = Instant::now();
-                if self.max_read_buf > 0 && self.read_buf.len() > self.max_read_buf {
-                    self.state = ConnState::Done;
+                if self.read_overflowed() {
                     return Err(IoError::new(IoErrorKind::Other, "read buffer exceeded"));
                 }
                 return Ok(true);
@@ -145,8 +150,7 @@ impl Connection {
             Ok(n) => {
                 self.read_buf.extend_from_slice(&tmp[..n]);
                 self.last_activity = Instant::now();
-                if self.max_read_buf > 0 && self.read_buf.len() > self.max_read_buf {
-                    self.state = ConnState::Done;
+                if self.read_overflowed() {
                     return Err(IoError::new(IoErrorKind::Other, "read buffer exceeded"));
                 }
                 Ok(true)
@@ -227,14 +231,27 @@ impl Connection {
         self.state = ConnState::WritingResponse;
     }
 
+    /// Drops the first `n` bytes of the read buffer — one parsed request —
+    /// keeping any pipelined request that arrived after it.
+    pub fn consume(&mut self, n: usize) {
+        self.read_buf.drain(..n.min(self.read_buf.len()));
+    }
+
+    /// Readies the connection for the next request. Unread bytes stay in
+    /// `read_buf`; see [`Connection::has_pending_request`].
     pub fn reset_for_keep_alive(&mut self) {
-        self.read_buf.clear();
         self.write_buf.clear();
         self.write_pos = 0;
         self.state = ConnState::ReadingRequest;
         self.last_activity = Instant::now();
     }
 
+    /// Whether bytes of a pipelined request are already buffered. The poller
+    /// won't report them again, so the caller must dispatch them itself.
+    pub fn has_pending_request(&self) -> bool {
+        self.state == ConnState::ReadingRequest && !self.read_buf.is_empty()
+    }
+
     /// Shut down TLS (if active) before closing the fd.
     pub fn shutdown_tls(&mut self) {
         if let IoMode::Tls { ssl } = &self.mode {
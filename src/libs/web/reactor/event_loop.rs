@@ -12,7 +12,7 @@ use crate::core::security::tls::stream::ssl_set_fd;
 use crate::libs::web::http::parser::{ParseResult, parse_request};
 use crate::libs::web::http::response::Response;
 use crate::libs::web::http::status::StatusCode;
-use crate::libs::web::router::Router;
+use crate::libs::web::router::{Router, RouterSlot};
 use crate::libs::web::security::{SecurityConfig, RateLimit};
 use crate::libs::web::static_files::server::try_serve_static;
 use crate::core::volkiwithstds::sys::syscalls;
@@ -23,6 +23,7 @@ pub struct EventLoop {
     pool: ThreadPool,
     connections: HashMap<i32, Connection>,
     router: Router,
+    router_slot: Option<RouterSlot>,
     public_dir: Option<crate::core::volkiwithstds::collections::String>,
     tls_ctx: Option<SslContext>,
     security: SecurityConfig,
@@ -54,6 +55,7 @@ impl EventLoop {
             pool,
             connections: HashMap::new(),
             router,
+            router_slot: None,
             public_dir,
             tls_ctx,
             security,
@@ -63,6 +65,11 @@ impl EventLoop {
         }
     }
 
+    /// Watch `slot` for a replacement router (dev hot reload).
+    pub fn set_router_slot(&mut self, slot: RouterSlot) {
+        self.router_slot = Some(slot);
+    }
+
     pub fn run(&mut self) -> ! {
         let mut events = [Event {
             fd: 0,
@@ -106,6 +113,13 @@ impl EventLoop {
             // Drain worker results
             self.drain_results();
 
+            // Swap in a router rebuilt by the dev watcher
+            if let Some(ref slot) = self.router_slot
+                && let Some(router) = slot.lock().take()
+            {
+                self.router = router;
+            }
+
             // Sweep timeouts every 500ms
             if self.last_sweep.elapsed() >= Duration::from_millis(500) {
                 self.sweep_timeouts();
@@ -202,6 +216,7 @@ impl EventLoop {
             return;
         }
 
+        let mut overflowed = false;
         let should_dispatch = if let Some(conn) = self.connections.get_mut(&fd) {
             if conn.state != ConnState::ReadingRequest {
                 false
@@ -215,6 +230,14 @@ impl EventLoop {
                             false
                         }
                     }
+                    Err(_) if conn.read_overflowed() => {
+                        // Streamed past the size limits: answer 413 instead of hanging up
+                        let status = StatusCode::PAYLOAD_TOO_LARGE;
+                        conn.set_response(Response::new(status).text(status.reason_phrase()).serialize());
+                        conn.keep_alive = false;
+                        overflowed = true;
+                        false
+                    }
                     Err(_) => {
                         conn.state = ConnState::Done;
                         true
@@ -225,6 +248,9 @@ impl EventLoop {
             false
         };
 
+        if overflowed {
+            let _ = self.poller.modify(fd, Interest::Write);
+        }
         if should_dispatch {
             self.try_dispatch(fd);
         }
@@ -276,7 +302,9 @@ impl EventLoop {
         };
 
         match parse_result {
-            ParseResult::Complete(mut request, _consumed) => {
+            ParseResult::Complete(mut request, consumed) => {
+                self.connections.get_mut(&fd).unwrap().consume(consumed);
+
                 // Check global rate limit — copy values to avoid borrow conflict
                 let global_rl = self.security.rate_limits.global.as_ref()
                     .map(|gl| (gl.requests, gl.window));
@@ -298,8 +326,13 @@ impl EventLoop {
 
                 // Check static files first
                 if let Some(ref dir) = self.public_dir {
-                    if request.method == crate::libs::web::http::method::Method::Get {
-                        if let Some(resp) = try_serve_static(dir.as_str(), request.route_path.as_str()) {
+                    let is_head = request.method == crate::libs::web::http::method::Method::Head;
+                    if request.method == crate::libs::web::http::method::Method::Get || is_head {
+                        if let Some(mut resp) = try_serve_static(dir.as_str(), request.route_path.as_str(), &request.headers) {
+                            resp.compress_for(request.headers.get("accept-encoding"));
+                            if is_head {
+                                resp.strip_body();
+                            }
                             let elapsed = start_time.elapsed();
                             log_request(
                                 request.method.as_str(),
@@ -347,6 +380,7 @@ impl EventLoop {
                     conn_fd: fd,
                     request,
                     handler: route_match.handler,
+                    middleware: route_match.middleware,
                     metadata_fn: route_match.metadata_fn,
                     start_time,
                     is_not_found: route_match.is_not_found,
@@ -399,10 +433,15 @@ impl EventLoop {
 
         if done {
             if keep_alive {
+                let mut pipelined = false;
                 if let Some(conn) = self.connections.get_mut(&fd) {
                     conn.reset_for_keep_alive();
+                    pipelined = conn.has_pending_request();
                     let _ = self.poller.modify(fd, Interest::Read);
                 }
+                if pipelined {
+                    self.try_dispatch(fd);
+                }
             } else {
                 self.close_connection(fd);
             }
@@ -533,3 +572,115 @@ impl EventLoop {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::String;
+    use crate::core::volkiwithstds::io::{Read, Write};
+    use crate::core::volkiwithstds::net::TcpStream;
+    use crate::core::volkiwithstds::thread;
+    use crate::libs::web::http::request::Request;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn alpha(_req: &Request) -> Response {
+        Response::ok().text("alpha")
+    }
+
+    fn beta(_req: &Request) -> Response {
+        Response::ok().text("beta")
+    }
+
+    /// Runs an event loop on an ephemeral port and returns the port.
+    fn start(keep_alive_timeout: Duration) -> u16 {
+        let port = crate::core::volkiwithstds::sync::Arc::new(AtomicUsize::new(0));
+        let shared = port.clone();
+        thread::spawn(move || {
+            let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+            listener.set_nonblocking(true).unwrap();
+            let addr = listener.local_addr().unwrap();
+            let p: usize = addr.rsplit(':').next().unwrap().parse().unwrap();
+            let mut router = Router::new();
+            router.api_route("/alpha", alpha);
+            router.api_route("/beta", beta);
+            let mut security = SecurityConfig::default();
+            security.timeouts.keep_alive_timeout = keep_alive_timeout;
+            let mut event_loop = EventLoop::new(listener, router, 1, None, None, security);
+            shared.store(p, Ordering::SeqCst);
+            event_loop.run()
+        });
+        while port.load(Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        port.load(Ordering::SeqCst) as u16
+    }
+
+    /// Reads until `n` complete responses (headers plus Content-Length
+    /// bytes) have arrived, and returns their bodies.
+    fn read_responses(stream: &mut TcpStream, n: usize) -> Vec<String> {
+        let mut buf: Vec<u8> = Vec::new();
+        loop {
+            let mut bodies = Vec::new();
+            let mut rest = buf.as_slice();
+            while let Some(end) = rest.windows(4).position(|w| w == b"\r\n\r\n") {
+                let head = core::str::from_utf8(&rest[..end]).unwrap();
+                let len: usize = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Content-Length: "))
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                let body_start = end + 4;
+                if rest.len() < body_start + len {
+                    break;
+                }
+                bodies.push(String::from_utf8_lossy(&rest[body_start..body_start + len]));
+                rest = &rest[body_start + len..];
+            }
+            if bodies.len() >= n {
+                return bodies;
+            }
+            let mut tmp = [0u8; 1024];
+            let read = stream.read(&mut tmp).unwrap();
+            assert!(read > 0, "server closed the connection early");
+            buf.extend_from_slice(&tmp[..read]);
+        }
+    }
+
+    #[test]
+    fn test_keep_alive_serves_sequential_requests() {
+        let port = start(Duration::from_secs(60));
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+        stream.write_all(b"GET /alpha HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(read_responses(&mut stream, 1).as_slice(), &["alpha"]);
+        stream.write_all(b"GET /beta HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(read_responses(&mut stream, 1).as_slice(), &["beta"]);
+    }
+
+    #[test]
+    fn test_pipelined_requests_answered_in_order() {
+        let port = start(Duration::from_secs(60));
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+        stream
+            .write_all(b"GET /beta HTTP/1.1\r\n\r\nGET /alpha HTTP/1.1\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        assert_eq!(read_responses(&mut stream, 2).as_slice(), &["beta", "alpha"]);
+        // `Connection: close` on the last request ends the connection
+        let mut tmp = [0u8; 16];
+        assert_eq!(stream.read(&mut tmp).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_idle_connection_closed_after_keep_alive_timeout() {
+        let port = start(Duration::from_millis(50));
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+
+        stream.write_all(b"GET /alpha HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(read_responses(&mut stream, 1).as_slice(), &["alpha"]);
+        // The sweep runs every 500ms; the idle connection is then closed
+        let mut tmp = [0u8; 16];
+        assert_eq!(stream.read(&mut tmp).unwrap(), 0);
+    }
+}
@@ -5,14 +5,17 @@ use crate::core::volkiwithstds::sync::{Arc, Mutex};
 use crate::core::volkiwithstds::thread;
 use crate::core::volkiwithstds::time::{Duration, Instant};
 use crate::libs::web::html::metadata::{MetadataFn, inject_metadata, is_html_content_type};
+use crate::libs::web::http::method::Method;
 use crate::libs::web::http::request::Request;
 use crate::libs::web::http::response::Response;
+use crate::libs::web::router::middleware::{Middleware, run_chain};
 use crate::libs::web::router::tree::MatchedHandler;
 
 pub struct Job {
     pub conn_fd: i32,
     pub request: Request,
     pub handler: MatchedHandler,
+    pub middleware: Vec<Middleware>,
     pub metadata_fn: Option<MetadataFn>,
     pub start_time: Instant,
     pub is_not_found: bool,
@@ -84,37 +87,9 @@ fn worker_loop(
             Some(j) => {
                 let method = j.request.method;
                 let path = j.request.route_path.clone();
-
-                let is_not_found = j.is_not_found;
-                let mut response = match j.handler {
-                    MatchedHandler::Handler(h) => h(&j.request),
-                    MatchedHandler::Page(h) => Response::ok().document(&h(&j.request)),
-                    MatchedHandler::DynamicPage(ref data) => {
-                        let doc = crate::libs::web::interpreter::interpret_page(data, &j.request);
-                        Response::ok().document(&doc)
-                    }
-                };
-                if is_not_found {
-                    response.status = crate::libs::web::http::status::StatusCode::NOT_FOUND;
-                }
+                let response = respond(&j);
                 let keep_alive = j.request.headers.connection_keep_alive();
 
-                // Auto-inject metadata if a metadata_fn is registered
-                if let Some(meta_fn) = j.metadata_fn {
-                    let meta = meta_fn(&j.request);
-                    // Validate — warnings are non-fatal, just discard for now
-                    let _warnings = meta.validate();
-                    // Only inject into HTML responses
-                    let is_html = response
-                        .headers
-                        .get("content-type")
-                        .map(|ct| is_html_content_type(ct))
-                        .unwrap_or(false);
-                    if is_html {
-                        inject_metadata(&mut response.body, &meta);
-                    }
-                }
-
                 let elapsed = j.start_time.elapsed();
                 log_request(method.as_str(), &path, response.status.code(), elapsed);
 
@@ -134,6 +109,42 @@ fn worker_loop(
     }
 }
 
+/// Runs a job's middleware and handler and finishes the response. A HEAD
+/// request gets the response its GET would, minus the body.
+fn respond(j: &Job) -> Response {
+    let is_not_found = j.is_not_found;
+    let handler = |req: &Request| {
+        let mut response = j.handler.call(req);
+        if is_not_found {
+            response.status = crate::libs::web::http::status::StatusCode::NOT_FOUND;
+        }
+        response
+    };
+    let mut response = run_chain(&j.middleware, &j.request, &handler);
+
+    // Auto-inject metadata if a metadata_fn is registered
+    if let Some(meta_fn) = j.metadata_fn {
+        let meta = meta_fn(&j.request);
+        // Validate — warnings are non-fatal, just discard for now
+        let _warnings = meta.validate();
+        // Only inject into HTML responses
+        let is_html = response
+            .headers
+            .get("content-type")
+            .map(|ct| is_html_content_type(ct))
+            .unwrap_or(false);
+        if is_html {
+            inject_metadata(&mut response.body, &meta);
+        }
+    }
+
+    response.compress_for(j.request.headers.get("accept-encoding"));
+    if j.request.method == Method::Head {
+        response.strip_body();
+    }
+    response
+}
+
 /// Log a request/response line to stderr.
 pub fn log_request(method: &str, path: &str, status: u16, elapsed: Duration) {
     use crate::core::cli::style;
@@ -156,3 +167,52 @@ pub fn log_request(method: &str, path: &str, status: u16, elapsed: Duration) {
 
     crate::veprintln!("  {method:<7} {path:<30} {colored_status} {dim_time}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::String;
+    use crate::libs::web::http::headers::Headers;
+    use crate::libs::web::router::Router;
+    use crate::libs::web::router::file_route::FileRoute;
+
+    fn hello(_req: &Request) -> Response {
+        Response::ok().text("hello")
+    }
+
+    fn job(router: &Router, method: Method, path: &str) -> Job {
+        let m = router.resolve(path, &method);
+        let mut request = Request::new(method, String::from(path), Headers::new(), Vec::new());
+        request.params = m.params;
+        Job {
+            conn_fd: -1,
+            request,
+            handler: m.handler,
+            middleware: m.middleware,
+            metadata_fn: m.metadata_fn,
+            start_time: Instant::now(),
+            is_not_found: m.is_not_found,
+        }
+    }
+
+    #[test]
+    fn test_head_strips_body_keeps_length() {
+        let mut router = Router::new();
+        router.api_route("/hello", hello);
+        router.file_route("/get-only", FileRoute::new().get(hello), true);
+
+        for path in ["/hello", "/get-only"] {
+            let resp = respond(&job(&router, Method::Head, path));
+            assert_eq!(resp.status.code(), 200);
+            assert!(resp.body.is_empty());
+            assert_eq!(resp.headers.get("content-type"), Some("text/plain; charset=utf-8"));
+            let bytes = resp.serialize();
+            let s = core::str::from_utf8(&bytes).unwrap();
+            assert!(s.contains("Content-Length: 5\r\n"));
+            assert!(s.ends_with("\r\n\r\n"));
+        }
+
+        let resp = respond(&job(&router, Method::Get, "/hello"));
+        assert_eq!(resp.body.as_slice(), b"hello");
+    }
+}
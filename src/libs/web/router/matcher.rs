@@ -5,10 +5,47 @@ use crate::core::volkiwithstds::collections::{String, Vec};
 #[derive(Debug, Clone)]
 pub enum RouteSegment {
     Static(String),
+    /// `[name]` or `:name` — matches exactly one path segment.
     Dynamic(String),
+    /// `[...name]` or `*name` — matches one or more trailing segments,
+    /// captured as a single `/`-joined param. Anything after it in the
+    /// pattern is ignored.
     CatchAll(String),
 }
 
+/// How the router treats a trailing slash on a request path. The root `/`
+/// is left alone under every policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    /// `/about/` redirects to `/about`.
+    #[default]
+    Strip,
+    /// `/about` redirects to `/about/`.
+    Add,
+    /// Both forms match the same route, without a redirect.
+    Off,
+}
+
+/// Returns the canonical form of `path` under `policy`, or `None` if `path`
+/// is already canonical. Repeated leading slashes collapse to one, since a
+/// `Location` of `//evil.com` is a protocol-relative URL to another host.
+pub fn canonical_path(path: &str, policy: TrailingSlash) -> Option<String> {
+    if path == "/" {
+        return None;
+    }
+    let path = match policy {
+        TrailingSlash::Strip if path.ends_with('/') => path.trim_end_matches('/'),
+        TrailingSlash::Add if !path.ends_with('/') => path,
+        _ => return None,
+    };
+    let mut out = String::from("/");
+    out.push_str(path.trim_start_matches('/'));
+    if policy == TrailingSlash::Add {
+        out.push('/');
+    }
+    Some(out)
+}
+
 pub fn parse_route_path(pattern: &str) -> Vec<RouteSegment> {
     let mut segments = Vec::new();
     let trimmed = pattern.trim_matches('/');
@@ -19,9 +56,13 @@ pub fn parse_route_path(pattern: &str) -> Vec<RouteSegment> {
         if part.starts_with("[...") && part.ends_with(']') {
             let name = &part[4..part.len() - 1];
             segments.push(RouteSegment::CatchAll(String::from(name)));
+        } else if let Some(name) = part.strip_prefix('*') {
+            segments.push(RouteSegment::CatchAll(String::from(name)));
         } else if part.starts_with('[') && part.ends_with(']') {
             let name = &part[1..part.len() - 1];
             segments.push(RouteSegment::Dynamic(String::from(name)));
+        } else if let Some(name) = part.strip_prefix(':') {
+            segments.push(RouteSegment::Dynamic(String::from(name)));
         } else {
             segments.push(RouteSegment::Static(String::from(part)));
         }
@@ -86,6 +127,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_colon_dynamic() {
+        let segs = parse_route_path("/posts/:id/edit");
+        assert_eq!(segs.len(), 3);
+        match &segs[1] {
+            RouteSegment::Dynamic(s) => assert_eq!(s.as_str(), "id"),
+            _ => panic!("expected dynamic"),
+        }
+    }
+
     #[test]
     fn test_parse_catch_all() {
         let segs = parse_route_path("/docs/[...slug]");
@@ -96,6 +147,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_star_catch_all() {
+        let segs = parse_route_path("/files/*rest");
+        assert_eq!(segs.len(), 2);
+        match &segs[1] {
+            RouteSegment::CatchAll(s) => assert_eq!(s.as_str(), "rest"),
+            _ => panic!("expected catch-all"),
+        }
+    }
+
     #[test]
     fn test_file_path_to_route_index() {
         assert_eq!(file_path_to_route("index.rs").as_str(), "/");
@@ -105,4 +166,26 @@ mod tests {
     fn test_file_path_to_route_nested() {
         assert_eq!(file_path_to_route("users/[id].rs").as_str(), "/users/[id]");
     }
+
+    #[test]
+    fn test_canonical_path() {
+        assert_eq!(canonical_path("/about/", TrailingSlash::Strip).as_deref(), Some("/about"));
+        assert_eq!(canonical_path("/about", TrailingSlash::Strip), None);
+        assert_eq!(canonical_path("/about", TrailingSlash::Add).as_deref(), Some("/about/"));
+        assert_eq!(canonical_path("/about/", TrailingSlash::Add), None);
+        assert_eq!(canonical_path("/about/", TrailingSlash::Off), None);
+        assert_eq!(canonical_path("//", TrailingSlash::Strip).as_deref(), Some("/"));
+        assert_eq!(
+            canonical_path("//evil.com/", TrailingSlash::Strip).as_deref(),
+            Some("/evil.com")
+        );
+        assert_eq!(
+            canonical_path("//evil.com", TrailingSlash::Add).as_deref(),
+            Some("/evil.com/")
+        );
+        // Root is canonical under every policy
+        for policy in [TrailingSlash::Strip, TrailingSlash::Add, TrailingSlash::Off] {
+            assert_eq!(canonical_path("/", policy), None);
+        }
+    }
 }
@@ -0,0 +1,157 @@
+//! Route middleware — functions that wrap the matched handler.
+//!
+//! A middleware receives the request and a `next` continuation. It can call
+//! `next(req)` to run the rest of the chain (and eventually the handler),
+//! inspect or modify the resulting response, or return its own response
+//! without calling `next` at all.
+
+use crate::core::volkiwithstds::collections::String;
+use crate::libs::web::http::request::Request;
+use crate::libs::web::http::response::Response;
+
+/// The rest of the chain, ending in the matched handler.
+pub type Next<'a> = &'a dyn Fn(&Request) -> Response;
+
+pub type Middleware = fn(&Request, Next<'_>) -> Response;
+
+/// A middleware registered for a path prefix.
+pub struct MiddlewareEntry {
+    prefix: String,
+    middleware: Middleware,
+}
+
+impl MiddlewareEntry {
+    pub fn new(prefix: &str, middleware: Middleware) -> Self {
+        let trimmed = prefix.trim_end_matches('/');
+        Self {
+            prefix: String::from(trimmed),
+            middleware,
+        }
+    }
+
+    pub fn middleware(&self) -> Middleware {
+        self.middleware
+    }
+
+    /// Prefixes match whole segments: `/api` applies to `/api` and
+    /// `/api/users`, but not `/apis`. The prefix `/` applies to every path.
+    pub fn applies_to(&self, path: &str) -> bool {
+        let prefix = self.prefix.as_str();
+        match path.strip_prefix(prefix) {
+            Some(rest) => prefix.is_empty() || rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
+/// Runs `chain` around `handler`. The first middleware is the outermost: it
+/// runs first and sees the final response last.
+pub fn run_chain(chain: &[Middleware], req: &Request, handler: Next<'_>) -> Response {
+    match chain.split_first() {
+        Some((mw, rest)) => mw(req, &|r: &Request| run_chain(rest, r, handler)),
+        None => handler(req),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::volkiwithstds::collections::Vec;
+    use crate::libs::web::http::headers::Headers;
+    use crate::libs::web::http::method::Method;
+    use crate::libs::web::http::status::StatusCode;
+    use crate::libs::web::router::Router;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn request(path: &str, headers: Headers) -> Request {
+        Request::new(Method::Get, String::from(path), headers, Vec::new())
+    }
+
+    fn ok_handler(_req: &Request) -> Response {
+        Response::ok().text("secret")
+    }
+
+    fn require_auth(req: &Request, next: Next<'_>) -> Response {
+        if req.headers.get("authorization").is_none() {
+            return Response::new(StatusCode::UNAUTHORIZED).text("Unauthorized");
+        }
+        next(req)
+    }
+
+    fn tag_outer(req: &Request, next: Next<'_>) -> Response {
+        let resp = next(req);
+        let body = crate::vformat!("outer({})", core::str::from_utf8(&resp.body).unwrap());
+        Response::ok().text(&body)
+    }
+
+    fn tag_inner(req: &Request, next: Next<'_>) -> Response {
+        let resp = next(req);
+        let body = crate::vformat!("inner({})", core::str::from_utf8(&resp.body).unwrap());
+        Response::ok().text(&body)
+    }
+
+    fn dispatch(router: &Router, req: &Request) -> Response {
+        let m = router.resolve(req.route_path.as_str(), &req.method);
+        run_chain(&m.middleware, req, &|r: &Request| m.handler.call(r))
+    }
+
+    #[test]
+    fn test_short_circuit_before_handler() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn counting_handler(_req: &Request) -> Response {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            Response::ok().text("secret")
+        }
+
+        let mut router = Router::new();
+        router.api_route("/api/secret", counting_handler);
+        router.use_middleware_at("/api", require_auth);
+
+        let resp = dispatch(&router, &request("/api/secret", Headers::new()));
+        assert_eq!(resp.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        let mut headers = Headers::new();
+        headers.set("Authorization", "Bearer t");
+        let resp = dispatch(&router, &request("/api/secret", headers));
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_first_registered_is_outermost() {
+        let mut router = Router::new();
+        router.api_route("/x", ok_handler);
+        router.use_middleware(tag_outer);
+        router.use_middleware(tag_inner);
+
+        let resp = dispatch(&router, &request("/x", Headers::new()));
+        assert_eq!(resp.body.as_slice(), b"outer(inner(secret))");
+    }
+
+    #[test]
+    fn test_prefix_matches_whole_segments() {
+        let mut router = Router::new();
+        router.api_route("/api", ok_handler);
+        router.api_route("/apis", ok_handler);
+        router.use_middleware_at("/api/", require_auth);
+
+        let resp = dispatch(&router, &request("/api", Headers::new()));
+        assert_eq!(resp.status, StatusCode::UNAUTHORIZED);
+        let resp = dispatch(&router, &request("/apis", Headers::new()));
+        assert_eq!(resp.status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let all = MiddlewareEntry::new("/", require_auth);
+        assert!(all.applies_to("/"));
+        assert!(all.applies_to("/anything/else"));
+
+        let admin = MiddlewareEntry::new("/admin", require_auth);
+        assert!(admin.applies_to("/admin"));
+        assert!(admin.applies_to("/admin/users"));
+        assert!(!admin.applies_to("/administrator"));
+        assert!(!admin.applies_to("/"));
+    }
+}
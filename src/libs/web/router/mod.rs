@@ -1,11 +1,15 @@
 pub mod file_route;
 pub mod loader;
 pub mod matcher;
+pub mod middleware;
 pub mod tree;
 
 use file_route::FileRoute;
-use tree::{RouteNode, RouteMatch, Handler, PageHandler, MatchedHandler};
-use crate::core::volkiwithstds::sync::Arc;
+use matcher::{TrailingSlash, canonical_path};
+use middleware::{Middleware, MiddlewareEntry};
+use tree::{RouteNode, RouteMatch, RouteHandler, Handler, PageHandler, MatchedHandler};
+use crate::core::volkiwithstds::collections::Vec;
+use crate::core::volkiwithstds::sync::{Arc, Mutex};
 use crate::core::volkiwithstds::time::Duration;
 use crate::libs::web::html::metadata::MetadataFn;
 use crate::libs::web::http::method::Method;
@@ -14,11 +18,17 @@ use crate::libs::web::http::response::Response;
 use crate::libs::web::http::status::StatusCode;
 use crate::libs::web::interpreter::DynamicPageData;
 
+/// A replacement router handed to a running server, which takes it out of
+/// the slot and swaps it in between event-loop polls.
+pub type RouterSlot = Arc<Mutex<Option<Router>>>;
+
 pub struct Router {
     root: RouteNode,
     not_found_handler: Option<Handler>,
     not_found_page: Option<PageHandler>,
     not_found_dynamic: Option<Arc<DynamicPageData>>,
+    middleware: Vec<MiddlewareEntry>,
+    trailing_slash: TrailingSlash,
 }
 
 impl Router {
@@ -28,9 +38,17 @@ impl Router {
             not_found_handler: None,
             not_found_page: None,
             not_found_dynamic: None,
+            middleware: Vec::new(),
+            trailing_slash: TrailingSlash::default(),
         }
     }
 
+    /// Sets how `/about` and `/about/` are reconciled. Defaults to
+    /// [`TrailingSlash::Strip`].
+    pub fn trailing_slash(&mut self, policy: TrailingSlash) {
+        self.trailing_slash = policy;
+    }
+
     pub fn page_route(&mut self, pattern: &str, handler: PageHandler) {
         self.root.insert_page(pattern, handler);
     }
@@ -70,6 +88,17 @@ impl Router {
         self.not_found_page = Some(handler);
     }
 
+    /// Registers a 404 handler for unmatched paths under `prefix`. The
+    /// nearest enclosing prefix wins; the global fallbacks apply only when
+    /// no prefix covers the path.
+    pub fn not_found_at(&mut self, prefix: &str, handler: Handler) {
+        self.root.insert_not_found(prefix, RouteHandler::Single(handler));
+    }
+
+    pub fn not_found_page_at(&mut self, prefix: &str, handler: PageHandler) {
+        self.root.insert_not_found(prefix, RouteHandler::Page(handler));
+    }
+
     pub fn dynamic_page_route(&mut self, pattern: &str, data: Arc<DynamicPageData>) {
         self.root.insert_dynamic_page(pattern, data);
     }
@@ -78,11 +107,66 @@ impl Router {
         self.not_found_dynamic = Some(data);
     }
 
+    pub fn not_found_dynamic_page_at(&mut self, prefix: &str, data: Arc<DynamicPageData>) {
+        self.root.insert_not_found(prefix, RouteHandler::DynamicPage(data));
+    }
+
+    /// Registers middleware for every route, including not-found fallbacks.
+    pub fn use_middleware(&mut self, middleware: Middleware) {
+        self.use_middleware_at("/", middleware);
+    }
+
+    /// Registers middleware for routes under `prefix`. Middleware runs in
+    /// registration order, the first registered being the outermost.
+    pub fn use_middleware_at(&mut self, prefix: &str, middleware: Middleware) {
+        self.middleware.push(MiddlewareEntry::new(prefix, middleware));
+    }
+
+    fn middleware_for(&self, path: &str) -> Vec<Middleware> {
+        self.middleware
+            .iter()
+            .filter(|e| e.applies_to(path))
+            .map(|e| e.middleware())
+            .collect()
+    }
+
     pub fn resolve(&self, path: &str, method: &Method) -> RouteMatch {
+        let mut m = self
+            .redirect_to_canonical(path, method)
+            .unwrap_or_else(|| self.resolve_handler(path, method));
+        m.middleware = self.middleware_for(path);
+        m
+    }
+
+    /// A 301 to the canonical form of a routed path that was requested with
+    /// the other trailing-slash form. Only GET and HEAD are redirected, as a
+    /// 301 may turn a POST into a GET; other methods match either form.
+    fn redirect_to_canonical(&self, path: &str, method: &Method) -> Option<RouteMatch> {
+        if !matches!(method, Method::Get | Method::Head) {
+            return None;
+        }
+        let canonical = canonical_path(path, self.trailing_slash)?;
+        self.root.match_path(path, method)?;
+        Some(RouteMatch {
+            handler: MatchedHandler::Redirect(canonical),
+            params: crate::core::volkiwithstds::collections::HashMap::new(),
+            is_api: false,
+            metadata_fn: None,
+            is_not_found: false,
+            rate_limit: None,
+            middleware: Vec::new(),
+        })
+    }
+
+    fn resolve_handler(&self, path: &str, method: &Method) -> RouteMatch {
         if let Some(m) = self.root.match_path(path, method) {
             return m;
         }
 
+        if let Some(m) = self.root.match_not_found(path, method) {
+            return m;
+        }
+
         // Not found fallbacks — dynamic pages, then static pages, then handlers
         if let Some(ref data) = self.not_found_dynamic {
             return RouteMatch {
@@ -92,6 +176,7 @@ impl Router {
                 metadata_fn: None,
                 is_not_found: true,
                 rate_limit: None,
+                middleware: Vec::new(),
             };
         }
 
@@ -103,6 +188,7 @@ impl Router {
                 metadata_fn: None,
                 is_not_found: true,
                 rate_limit: None,
+                middleware: Vec::new(),
             };
         }
 
@@ -114,6 +200,7 @@ impl Router {
                 metadata_fn: None,
                 is_not_found: true,
                 rate_limit: None,
+                middleware: Vec::new(),
             };
         }
 
@@ -124,6 +211,7 @@ impl Router {
             metadata_fn: None,
             is_not_found: true,
             rate_limit: None,
+            middleware: Vec::new(),
         }
     }
 }
@@ -131,3 +219,123 @@ impl Router {
 fn default_not_found(_req: &Request) -> Response {
     Response::new(StatusCode::NOT_FOUND).text("404 Not Found")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs_not_found(_req: &Request) -> Response {
+        Response::new(StatusCode::NOT_FOUND).text("docs 404")
+    }
+
+    fn global_not_found(_req: &Request) -> Response {
+        Response::new(StatusCode::NOT_FOUND).text("global 404")
+    }
+
+    fn ok_handler(_req: &Request) -> Response {
+        Response::ok().text("ok")
+    }
+
+    fn body_of(router: &Router, path: &str) -> Vec<u8> {
+        let m = router.resolve(path, &Method::Get);
+        assert!(m.is_not_found);
+        let req = Request::new(
+            Method::Get,
+            crate::core::volkiwithstds::collections::String::from(path),
+            crate::libs::web::http::headers::Headers::new(),
+            Vec::new(),
+        );
+        m.handler.call(&req).body
+    }
+
+    #[test]
+    fn test_subtree_not_found_before_global() {
+        let mut router = Router::new();
+        router.api_route("/docs/intro", ok_handler);
+        router.not_found(global_not_found);
+        router.not_found_at("/docs", docs_not_found);
+
+        assert_eq!(body_of(&router, "/docs/missing").as_slice(), b"docs 404");
+        assert_eq!(body_of(&router, "/docs/a/b").as_slice(), b"docs 404");
+        assert_eq!(body_of(&router, "/missing").as_slice(), b"global 404");
+        assert_eq!(body_of(&router, "/documents").as_slice(), b"global 404");
+        assert!(!router.resolve("/docs/intro", &Method::Get).is_not_found);
+    }
+
+    fn get(router: &Router, path: &str) -> Response {
+        let (route, query) = path.split_once('?').unwrap_or((path, ""));
+        let m = router.resolve(route, &Method::Get);
+        let mut target = crate::core::volkiwithstds::collections::String::from(route);
+        if !query.is_empty() {
+            target.push('?');
+            target.push_str(query);
+        }
+        let req = Request::new(
+            Method::Get,
+            target,
+            crate::libs::web::http::headers::Headers::new(),
+            Vec::new(),
+        );
+        m.handler.call(&req)
+    }
+
+    fn about_router(policy: TrailingSlash) -> Router {
+        let mut router = Router::new();
+        router.api_route("/about", ok_handler);
+        router.trailing_slash(policy);
+        router
+    }
+
+    #[test]
+    fn test_trailing_slash_strip_is_default() {
+        let router = about_router(TrailingSlash::default());
+        let resp = get(&router, "/about/?tab=team");
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(resp.headers.get("location"), Some("/about?tab=team"));
+        assert_eq!(get(&router, "/about").body.as_slice(), b"ok");
+        // Unrouted paths 404 rather than redirect
+        assert!(router.resolve("/missing/", &Method::Get).is_not_found);
+    }
+
+    #[test]
+    fn test_trailing_slash_add() {
+        let router = about_router(TrailingSlash::Add);
+        let resp = get(&router, "/about");
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(resp.headers.get("location"), Some("/about/"));
+        assert_eq!(get(&router, "/about/").body.as_slice(), b"ok");
+    }
+
+    #[test]
+    fn test_trailing_slash_off_matches_both() {
+        let router = about_router(TrailingSlash::Off);
+        assert_eq!(get(&router, "/about/").body.as_slice(), b"ok");
+        assert_eq!(get(&router, "/about").body.as_slice(), b"ok");
+    }
+
+    #[test]
+    fn test_trailing_slash_leaves_root_and_posts_alone() {
+        let mut router = Router::new();
+        router.api_route("/", ok_handler);
+        router.api_route("/about", ok_handler);
+        router.trailing_slash(TrailingSlash::Add);
+        assert_eq!(get(&router, "/").body.as_slice(), b"ok");
+
+        router.trailing_slash(TrailingSlash::Strip);
+        let m = router.resolve("/about/", &Method::Post);
+        assert!(!matches!(m.handler, MatchedHandler::Redirect(_)));
+    }
+
+    #[test]
+    fn test_trailing_slash_redirect_stays_on_site() {
+        let mut router = Router::new();
+        router.api_route("/[slug]", ok_handler);
+        let resp = get(&router, "//evil.com/");
+        assert_eq!(resp.status, StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(resp.headers.get("location"), Some("/evil.com"));
+
+        router.trailing_slash(TrailingSlash::Add);
+        let resp = get(&router, "//evil.com");
+        assert_eq!(resp.headers.get("location"), Some("/evil.com/"));
+    }
+}
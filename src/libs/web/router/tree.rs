@@ -2,6 +2,7 @@
 
 use super::file_route::FileRoute;
 use super::matcher::{RouteSegment, parse_route_path};
+use super::middleware::Middleware;
 use crate::core::volkiwithstds::collections::{Box, HashMap, String, Vec};
 use crate::core::volkiwithstds::sync::Arc;
 use crate::core::volkiwithstds::time::Duration;
@@ -20,6 +21,8 @@ pub enum MatchedHandler {
     Handler(Handler),
     Page(PageHandler),
     DynamicPage(Arc<DynamicPageData>),
+    /// 301 to this path, keeping the request's query string.
+    Redirect(String),
 }
 
 /// A route endpoint can be a single handler, a page, or a per-method file route.
@@ -41,6 +44,28 @@ impl RouteHandler {
     }
 }
 
+impl MatchedHandler {
+    /// Runs the handler and builds its response.
+    pub fn call(&self, req: &Request) -> Response {
+        match self {
+            MatchedHandler::Handler(h) => h(req),
+            MatchedHandler::Page(h) => Response::ok().document(&h(req)),
+            MatchedHandler::DynamicPage(data) => {
+                let doc = crate::libs::web::interpreter::interpret_page(data, req);
+                Response::ok().document(&doc)
+            }
+            MatchedHandler::Redirect(path) => {
+                let location = if req.query_string.is_empty() {
+                    path.clone()
+                } else {
+                    crate::vformat!("{}?{}", path, req.query_string)
+                };
+                Response::ok().redirect_permanent(location.as_str())
+            }
+        }
+    }
+}
+
 pub struct RouteMatch {
     pub handler: MatchedHandler,
     pub params: HashMap<String, String>,
@@ -48,6 +73,8 @@ pub struct RouteMatch {
     pub metadata_fn: Option<MetadataFn>,
     pub is_not_found: bool,
     pub rate_limit: Option<(u32, Duration)>,
+    /// Middleware to run around the handler, outermost first.
+    pub middleware: Vec<Middleware>,
 }
 
 pub struct RouteNode {
@@ -57,7 +84,9 @@ pub struct RouteNode {
     rate_limit: Option<(u32, Duration)>,
     static_children: HashMap<String, RouteNode>,
     dynamic_child: Option<(String, Box<RouteNode>)>,
-    catch_all: Option<(String, RouteHandler, bool, Option<MetadataFn>)>,
+    catch_all: Option<(String, Box<RouteNode>)>,
+    /// 404 handler for paths under this node that match no route.
+    not_found: Option<RouteHandler>,
 }
 
 impl RouteNode {
@@ -70,6 +99,7 @@ impl RouteNode {
             static_children: HashMap::new(),
             dynamic_child: None,
             catch_all: None,
+            not_found: None,
         }
     }
 
@@ -148,6 +178,31 @@ impl RouteNode {
         self.insert_segments(&segments, 0, RouteHandler::FileRoute(file_route), is_api, meta_fn, None);
     }
 
+    /// Registers a 404 handler for unmatched paths under `prefix`. A
+    /// catch-all segment ends the prefix, since it has no subtree of its own.
+    pub fn insert_not_found(&mut self, prefix: &str, handler: RouteHandler) {
+        let segments = parse_route_path(prefix);
+        let mut node = self;
+        for segment in segments.iter() {
+            node = match segment {
+                RouteSegment::Static(name) => {
+                    if !node.static_children.contains_key(name.as_str()) {
+                        node.static_children.insert(name.clone(), RouteNode::new());
+                    }
+                    node.static_children.get_mut(name.as_str()).unwrap()
+                }
+                RouteSegment::Dynamic(param_name) => {
+                    if node.dynamic_child.is_none() {
+                        node.dynamic_child = Some((param_name.clone(), Box::new(RouteNode::new())));
+                    }
+                    &mut node.dynamic_child.as_mut().unwrap().1
+                }
+                RouteSegment::CatchAll(_) => break,
+            };
+        }
+        node.not_found = Some(handler);
+    }
+
     fn insert_segments(
         &mut self,
         segments: &[RouteSegment],
@@ -183,7 +238,11 @@ impl RouteNode {
                 child.insert_segments(segments, idx + 1, route_handler, is_api, meta_fn, rl);
             }
             RouteSegment::CatchAll(param_name) => {
-                self.catch_all = Some((param_name.clone(), route_handler, is_api, meta_fn));
+                // The catch-all consumes the rest of the path, so its node is
+                // always an endpoint.
+                let mut child = RouteNode::new();
+                child.insert_segments(&[], 0, route_handler, is_api, meta_fn, rl);
+                self.catch_all = Some((param_name.clone(), Box::new(child)));
             }
         }
     }
@@ -207,17 +266,7 @@ impl RouteNode {
         method: &Method,
     ) -> Option<RouteMatch> {
         if idx >= segments.len() {
-            if let Some(ref rh) = self.handler {
-                return Some(RouteMatch {
-                    handler: rh.resolve(method),
-                    params: params.clone(),
-                    is_api: self.is_api,
-                    metadata_fn: self.metadata_fn,
-                    is_not_found: false,
-                    rate_limit: self.rate_limit,
-                });
-            }
-            return None;
+            return self.endpoint(params, method);
         }
 
         let segment = segments[idx];
@@ -238,23 +287,60 @@ impl RouteNode {
             params.remove(param_name.as_str());
         }
 
-        // Try catch-all
-        if let Some((ref param_name, ref rh, ref is_api, ref meta_fn)) = self.catch_all {
+        // Try catch-all — lowest priority, captures the remaining segments
+        if let Some((ref param_name, ref child)) = self.catch_all {
             let remaining: Vec<&str> = segments[idx..].iter().copied().collect();
-            let joined = remaining.join("/");
-            params.insert(param_name.clone(), joined);
-            return Some(RouteMatch {
-                handler: rh.resolve(method),
-                params: params.clone(),
-                is_api: *is_api,
-                metadata_fn: *meta_fn,
-                is_not_found: false,
-                rate_limit: None,
-            });
+            params.insert(param_name.clone(), remaining.join("/"));
+            if let Some(m) = child.endpoint(params, method) {
+                return Some(m);
+            }
+            params.remove(param_name.as_str());
         }
 
         None
     }
+
+    /// Finds the 404 handler registered nearest to `path`, walking down the
+    /// tree as far as the path's segments go.
+    pub fn match_not_found(&self, path: &str, method: &Method) -> Option<RouteMatch> {
+        let mut node = self;
+        let mut nearest = node.not_found.as_ref();
+        for segment in path.trim_matches('/').split('/').filter(|s| !s.is_empty()) {
+            node = match node.static_children.get(segment) {
+                Some(child) => child,
+                None => match node.dynamic_child {
+                    Some((_, ref child)) => child,
+                    None => break,
+                },
+            };
+            if node.not_found.is_some() {
+                nearest = node.not_found.as_ref();
+            }
+        }
+        let rh = nearest?;
+        Some(RouteMatch {
+            handler: rh.resolve(method),
+            params: HashMap::new(),
+            is_api: false,
+            metadata_fn: None,
+            is_not_found: true,
+            rate_limit: None,
+            middleware: Vec::new(),
+        })
+    }
+
+    fn endpoint(&self, params: &HashMap<String, String>, method: &Method) -> Option<RouteMatch> {
+        let rh = self.handler.as_ref()?;
+        Some(RouteMatch {
+            handler: rh.resolve(method),
+            params: params.clone(),
+            is_api: self.is_api,
+            metadata_fn: self.metadata_fn,
+            is_not_found: false,
+            rate_limit: self.rate_limit,
+            middleware: Vec::new(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -293,6 +379,53 @@ mod tests {
         assert_eq!(m.params.get("slug").unwrap().as_str(), "a/b/c");
     }
 
+    #[test]
+    fn test_star_catch_all() {
+        let mut root = RouteNode::new();
+        root.insert("/files/*rest", dummy_handler, false);
+        let m = root.match_path("/files/a/b/c", &Method::Get).unwrap();
+        assert_eq!(m.params.get("rest").unwrap().as_str(), "a/b/c");
+        // A catch-all needs at least one segment
+        assert!(root.match_path("/files", &Method::Get).is_none());
+    }
+
+    #[test]
+    fn test_catch_all_lowest_priority() {
+        let mut root = RouteNode::new();
+        root.insert("/docs/*path", dummy_handler, false);
+        root.insert("/docs/[page]", post_handler, false);
+        root.insert("/docs/index", post_handler, true);
+
+        let m = root.match_path("/docs/index", &Method::Get).unwrap();
+        assert!(m.is_api);
+        assert!(m.params.get("path").is_none());
+
+        let m = root.match_path("/docs/intro", &Method::Get).unwrap();
+        assert_eq!(m.params.get("page").unwrap().as_str(), "intro");
+        assert!(m.params.get("path").is_none());
+
+        let m = root.match_path("/docs/guide/intro", &Method::Get).unwrap();
+        assert_eq!(as_handler(&m.handler) as usize, dummy_handler as Handler as usize);
+        assert_eq!(m.params.get("path").unwrap().as_str(), "guide/intro");
+        assert!(m.params.get("page").is_none());
+    }
+
+    #[test]
+    fn test_catch_all_keeps_parent_params_and_rate_limit() {
+        let mut root = RouteNode::new();
+        root.insert_with_rate_limit(
+            "/repo/[owner]/*tree",
+            dummy_handler,
+            true,
+            5,
+            Duration::from_secs(60),
+        );
+        let m = root.match_path("/repo/acme/src/lib.rs", &Method::Get).unwrap();
+        assert_eq!(m.params.get("owner").unwrap().as_str(), "acme");
+        assert_eq!(m.params.get("tree").unwrap().as_str(), "src/lib.rs");
+        assert_eq!(m.rate_limit.map(|(n, _)| n), Some(5));
+    }
+
     #[test]
     fn test_no_match() {
         let root = RouteNode::new();
@@ -319,6 +452,7 @@ mod tests {
             MatchedHandler::Handler(h) => *h,
             MatchedHandler::Page(_) => panic!("expected Handler, got Page"),
             MatchedHandler::DynamicPage(_) => panic!("expected Handler, got DynamicPage"),
+            MatchedHandler::Redirect(_) => panic!("expected Handler, got Redirect"),
         }
     }
 
@@ -358,4 +492,26 @@ mod tests {
         assert!(!m.is_api);
         assert!(matches!(m.handler, MatchedHandler::Page(_)));
     }
+
+    #[test]
+    fn test_match_not_found_nearest_subtree() {
+        let mut root = RouteNode::new();
+        root.insert("/docs/intro", dummy_handler, false);
+        root.insert_not_found("/", RouteHandler::Single(dummy_handler));
+        root.insert_not_found("/docs", RouteHandler::Single(post_handler));
+
+        let m = root.match_not_found("/docs/a/b", &Method::Get).unwrap();
+        assert!(m.is_not_found);
+        assert_eq!(as_handler(&m.handler) as usize, post_handler as Handler as usize);
+
+        let m = root.match_not_found("/blog/a", &Method::Get).unwrap();
+        assert_eq!(as_handler(&m.handler) as usize, dummy_handler as Handler as usize);
+    }
+
+    #[test]
+    fn test_match_not_found_none_registered() {
+        let mut root = RouteNode::new();
+        root.insert("/docs/intro", dummy_handler, false);
+        assert!(root.match_not_found("/docs/missing", &Method::Get).is_none());
+    }
 }
@@ -2,6 +2,10 @@
 
 use crate::core::volkiwithstds::time::Duration;
 
+/// Request body limit used unless `[web].max_body_size` or
+/// `--max-body-size` sets another.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
 /// Maximum allowed sizes for request components.
 pub struct SizeLimits {
     pub max_header_size: usize,
@@ -13,7 +17,7 @@ impl Default for SizeLimits {
     fn default() -> Self {
         Self {
             max_header_size: 8 * 1024,         // 8KB
-            max_body_size: 10 * 1024 * 1024,   // 10MB
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
             max_uri_length: 8192,
         }
     }
@@ -12,14 +12,16 @@ use crate::libs::web::http::request::Request;
 use crate::libs::web::http::response::Response;
 use crate::libs::web::interpreter::DynamicPageData;
 use crate::libs::web::reactor::event_loop::EventLoop;
-use crate::libs::web::router::Router;
+use crate::libs::web::router::{Router, RouterSlot};
 use crate::libs::web::router::file_route::FileRoute;
+use crate::libs::web::router::middleware::Middleware;
 use crate::libs::web::security::{SecurityConfig, RateLimit};
 
 pub struct Server {
     host: String,
     port: u16,
     router: Router,
+    router_slot: Option<RouterSlot>,
     public_dir: Option<String>,
     num_workers: usize,
     tls_config: Option<TlsConfig>,
@@ -32,6 +34,7 @@ impl Server {
             host: String::from("127.0.0.1"),
             port: 3000,
             router: Router::new(),
+            router_slot: None,
             public_dir: None,
             num_workers: 4,
             tls_config: None,
@@ -117,6 +120,18 @@ impl Server {
 
     // ── Route builders ──────────────────────────────────────────────────
 
+    /// Wraps every route in `middleware`. The first registered runs outermost.
+    pub fn middleware(mut self, middleware: Middleware) -> Self {
+        self.router.use_middleware(middleware);
+        self
+    }
+
+    /// Wraps routes under `prefix` (whole segments) in `middleware`.
+    pub fn middleware_at(mut self, prefix: &str, middleware: Middleware) -> Self {
+        self.router.use_middleware_at(prefix, middleware);
+        self
+    }
+
     pub fn page(mut self, pattern: &str, handler: fn(&Request) -> HtmlDocument) -> Self {
         self.router.page_route(pattern, handler);
         self
@@ -170,6 +185,17 @@ impl Server {
         self
     }
 
+    /// Serves `handler` for unmatched paths under `prefix` (whole segments).
+    pub fn not_found_at(mut self, prefix: &str, handler: fn(&Request) -> Response) -> Self {
+        self.router.not_found_at(prefix, handler);
+        self
+    }
+
+    pub fn not_found_page_at(mut self, prefix: &str, handler: fn(&Request) -> HtmlDocument) -> Self {
+        self.router.not_found_page_at(prefix, handler);
+        self
+    }
+
     pub fn dynamic_page(mut self, pattern: &str, data: Arc<DynamicPageData>) -> Self {
         self.router.dynamic_page_route(pattern, data);
         self
@@ -180,6 +206,23 @@ impl Server {
         self
     }
 
+    pub fn not_found_dynamic_page_at(mut self, prefix: &str, data: Arc<DynamicPageData>) -> Self {
+        self.router.not_found_dynamic_page_at(prefix, data);
+        self
+    }
+
+    /// Replace the router built up so far.
+    pub fn router(mut self, router: Router) -> Self {
+        self.router = router;
+        self
+    }
+
+    /// Swap in any router placed in `slot` while serving (dev hot reload).
+    pub fn reload_from(mut self, slot: RouterSlot) -> Self {
+        self.router_slot = Some(slot);
+        self
+    }
+
     pub fn listen(self) -> ! {
         let listener =
             TcpListener::bind((self.host.as_str(), self.port)).expect("failed to bind");
@@ -204,6 +247,9 @@ impl Server {
             self.security,
         );
 
+        if let Some(slot) = self.router_slot {
+            event_loop.set_router_slot(slot);
+        }
         event_loop.run()
     }
 }
@@ -1,11 +1,26 @@
 //! Extension to MIME type mapping.
 
+/// Maps a file extension (without the dot, any case) to a `Content-Type`.
+/// Unknown extensions are served as `application/octet-stream`.
 pub fn mime_from_extension(ext: &str) -> &'static str {
+    // Lowercase into a stack buffer; no known extension is longer than this.
+    let mut buf = [0u8; 16];
+    if ext.len() > buf.len() {
+        return "application/octet-stream";
+    }
+    for (dst, src) in buf.iter_mut().zip(ext.bytes()) {
+        *dst = src.to_ascii_lowercase();
+    }
+    let ext = core::str::from_utf8(&buf[..ext.len()]).unwrap_or("");
+
     match ext {
         "html" | "htm" => "text/html; charset=utf-8",
         "css" => "text/css; charset=utf-8",
         "js" | "mjs" => "application/javascript; charset=utf-8",
         "json" => "application/json",
+        // Source maps are JSON
+        "map" => "application/json",
+        "webmanifest" => "application/manifest+json",
         "xml" => "application/xml",
         "txt" => "text/plain; charset=utf-8",
         "csv" => "text/csv",
@@ -43,6 +58,50 @@ mod tests {
         assert_eq!(mime_from_extension("json"), "application/json");
     }
 
+    #[test]
+    fn test_every_known_extension() {
+        let table = [
+            ("html", "text/html; charset=utf-8"),
+            ("htm", "text/html; charset=utf-8"),
+            ("css", "text/css; charset=utf-8"),
+            ("js", "application/javascript; charset=utf-8"),
+            ("mjs", "application/javascript; charset=utf-8"),
+            ("json", "application/json"),
+            ("map", "application/json"),
+            ("webmanifest", "application/manifest+json"),
+            ("xml", "application/xml"),
+            ("txt", "text/plain; charset=utf-8"),
+            ("csv", "text/csv"),
+            ("png", "image/png"),
+            ("jpg", "image/jpeg"),
+            ("jpeg", "image/jpeg"),
+            ("gif", "image/gif"),
+            ("svg", "image/svg+xml"),
+            ("ico", "image/x-icon"),
+            ("webp", "image/webp"),
+            ("avif", "image/avif"),
+            ("woff", "font/woff"),
+            ("woff2", "font/woff2"),
+            ("ttf", "font/ttf"),
+            ("otf", "font/otf"),
+            ("pdf", "application/pdf"),
+            ("zip", "application/zip"),
+            ("wasm", "application/wasm"),
+            ("mp4", "video/mp4"),
+            ("webm", "video/webm"),
+            ("mp3", "audio/mpeg"),
+        ];
+        for (ext, mime) in table {
+            assert_eq!(mime_from_extension(ext), mime, "extension {ext}");
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(mime_from_extension("SVG"), "image/svg+xml");
+        assert_eq!(mime_from_extension("Wasm"), "application/wasm");
+    }
+
     #[test]
     fn test_unknown() {
         assert_eq!(mime_from_extension("xyz"), "application/octet-stream");
@@ -4,10 +4,16 @@ use super::mime::mime_from_extension;
 use crate::core::volkiwithstds::collections::String;
 use crate::core::volkiwithstds::fs;
 use crate::core::volkiwithstds::path::PathBuf;
+use crate::libs::web::http::headers::Headers;
 use crate::libs::web::http::response::Response;
 use crate::libs::web::http::status::StatusCode;
+use crate::vformat;
 
-pub fn try_serve_static(public_dir: &str, url_path: &str) -> Option<Response> {
+/// Serves a file from `public_dir`. A single `Range: bytes=...` request
+/// header yields `206 Partial Content` (or `416` if it starts past the end);
+/// anything else gets the whole file with `200`. Every response carries a
+/// weak `ETag`, and a matching `If-None-Match` short-circuits to `304`.
+pub fn try_serve_static(public_dir: &str, url_path: &str, headers: &Headers) -> Option<Response> {
     // Sanitize path — reject traversal and hidden files
     let clean = sanitize_path(url_path)?;
 
@@ -19,10 +25,20 @@ pub fn try_serve_static(public_dir: &str, url_path: &str) -> Option<Response> {
         file_path.push("index.html");
     }
 
-    if !fs::is_file(file_path.as_path()) {
+    let meta = fs::metadata(file_path.as_path()).ok()?;
+    if !meta.is_file() {
         return None;
     }
 
+    let etag = weak_etag(&meta);
+    if headers.get("if-none-match").is_some_and(|inm| etag_matches(inm, &etag)) {
+        return Some(
+            Response::new(StatusCode::NOT_MODIFIED)
+                .header("ETag", &etag)
+                .header("Cache-Control", "public, max-age=3600"),
+        );
+    }
+
     let data = match fs::read(file_path.as_path()) {
         Ok(d) => d,
         Err(_) => return None,
@@ -34,11 +50,97 @@ pub fn try_serve_static(public_dir: &str, url_path: &str) -> Option<Response> {
     let resp = Response::new(StatusCode::OK)
         .header("Content-Type", mime)
         .header("Cache-Control", "public, max-age=3600")
-        .body_bytes(data.as_slice());
+        .header("Accept-Ranges", "bytes")
+        .header("ETag", &etag);
+
+    let total = data.len();
+    let resp = match headers.get("range").map(|r| parse_range(r, total)) {
+        Some(RangeRequest::Satisfiable(start, end)) => resp
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header("Content-Range", &vformat!("bytes {start}-{end}/{total}"))
+            .body_bytes(&data[start..=end]),
+        Some(RangeRequest::Unsatisfiable) => resp
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header("Content-Range", &vformat!("bytes */{total}")),
+        Some(RangeRequest::Ignored) | None => resp.body_bytes(data.as_slice()),
+    };
 
     Some(resp)
 }
 
+/// `W/"<size>-<mtime>"` in hex — cheap to compute without reading the file.
+fn weak_etag(meta: &fs::Metadata) -> String {
+    let mtime = meta.modified();
+    vformat!(
+        "W/\"{:x}-{:x}.{:x}\"",
+        meta.len(),
+        mtime.as_secs(),
+        mtime.subsec_nanos()
+    )
+}
+
+/// Weak comparison against an `If-None-Match` list (or `*`).
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let bare = |t: &str| -> String {
+        let t = t.trim();
+        String::from(t.strip_prefix("W/").unwrap_or(t))
+    };
+    let etag = bare(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || bare(candidate) == etag)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    /// Inclusive byte offsets, clamped to the file.
+    Satisfiable(usize, usize),
+    Unsatisfiable,
+    /// Malformed, multi-range or non-byte ranges: serve the whole file.
+    Ignored,
+}
+
+/// Parses `bytes=start-end`, `bytes=start-` and `bytes=-suffix` against a
+/// file of `len` bytes.
+fn parse_range(header: &str, len: usize) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Ignored;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Ignored;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Ignored;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        // Suffix range: the last `n` bytes
+        return match end.parse::<usize>() {
+            Ok(0) => RangeRequest::Unsatisfiable,
+            Ok(_) if len == 0 => RangeRequest::Unsatisfiable,
+            Ok(n) => RangeRequest::Satisfiable(len.saturating_sub(n), len - 1),
+            Err(_) => RangeRequest::Ignored,
+        };
+    }
+
+    let Ok(start) = start.parse::<usize>() else {
+        return RangeRequest::Ignored;
+    };
+    let end = if end.is_empty() {
+        usize::MAX
+    } else {
+        match end.parse::<usize>() {
+            Ok(e) if e >= start => e,
+            _ => return RangeRequest::Ignored,
+        }
+    };
+    if start >= len {
+        return RangeRequest::Unsatisfiable;
+    }
+    RangeRequest::Satisfiable(start, end.min(len - 1))
+}
+
 fn sanitize_path(url_path: &str) -> Option<String> {
     let trimmed = url_path.trim_start_matches('/');
 
@@ -55,9 +157,11 @@ fn sanitize_path(url_path: &str) -> Option<String> {
     Some(String::from(trimmed))
 }
 
+/// Extension of the last path segment, so dots in directory names are ignored.
 fn extract_extension(path: &str) -> &str {
-    if let Some(dot_pos) = path.rfind('.') {
-        &path[dot_pos + 1..]
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if let Some(dot_pos) = name.rfind('.') {
+        &name[dot_pos + 1..]
     } else {
         ""
     }
@@ -90,5 +194,107 @@ mod tests {
         assert_eq!(extract_extension("style.css"), "css");
         assert_eq!(extract_extension("file.tar.gz"), "gz");
         assert_eq!(extract_extension("noext"), "");
+        assert_eq!(extract_extension("public/v1.2/LICENSE"), "");
+        assert_eq!(extract_extension("public/pkg/app_bg.wasm"), "wasm");
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), RangeRequest::Satisfiable(0, 99));
+        assert_eq!(parse_range("bytes=500-", 1000), RangeRequest::Satisfiable(500, 999));
+        assert_eq!(parse_range("bytes=-100", 1000), RangeRequest::Satisfiable(900, 999));
+        assert_eq!(parse_range("bytes=-5000", 1000), RangeRequest::Satisfiable(0, 999));
+        assert_eq!(parse_range("bytes=900-5000", 1000), RangeRequest::Satisfiable(900, 999));
+        assert_eq!(parse_range("bytes=1000-", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), RangeRequest::Ignored);
+        assert_eq!(parse_range("bytes=9-3", 1000), RangeRequest::Ignored);
+        assert_eq!(parse_range("items=0-1", 1000), RangeRequest::Ignored);
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        let dir = crate::core::volkiwithstds::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(dir.as_path());
+        fs::create_dir_all(dir.join("pkg").as_path()).unwrap();
+        fs::write(dir.join("pkg/app.wasm").as_path(), b"\0asm").unwrap();
+        fs::write(dir.join("logo.svg").as_path(), b"<svg/>").unwrap();
+        fs::write(dir.join("clip.mp4").as_path(), b"0123456789").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_serve_sets_content_type() {
+        let dir = fixture("volki_static_mime_test");
+        let none = Headers::new();
+        let resp = try_serve_static(dir.as_str(), "/pkg/app.wasm", &none).unwrap();
+        assert_eq!(resp.headers.get("content-type"), Some("application/wasm"));
+        let resp = try_serve_static(dir.as_str(), "/logo.svg", &none).unwrap();
+        assert_eq!(resp.headers.get("content-type"), Some("image/svg+xml"));
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        assert!(etag_matches("W/\"a-b\"", "W/\"a-b\""));
+        assert!(etag_matches("\"x\", W/\"a-b\"", "W/\"a-b\""));
+        assert!(etag_matches("\"a-b\"", "W/\"a-b\""));
+        assert!(etag_matches("*", "W/\"a-b\""));
+        assert!(!etag_matches("W/\"a-c\"", "W/\"a-b\""));
+    }
+
+    #[test]
+    fn test_serve_conditional_get() {
+        let dir = fixture("volki_static_etag_test");
+        let resp = try_serve_static(dir.as_str(), "/logo.svg", &Headers::new()).unwrap();
+        assert_eq!(resp.status, StatusCode::OK);
+        let etag = String::from(resp.headers.get("etag").unwrap());
+        assert!(etag.starts_with("W/\""));
+
+        let mut h = Headers::new();
+        h.set("If-None-Match", etag.as_str());
+        let resp = try_serve_static(dir.as_str(), "/logo.svg", &h).unwrap();
+        assert_eq!(resp.status, StatusCode::NOT_MODIFIED);
+        assert!(resp.body.is_empty());
+        assert_eq!(resp.headers.get("etag"), Some(etag.as_str()));
+
+        // Changing the file changes the tag
+        fs::write(dir.join("logo.svg").as_path(), b"<svg></svg>").unwrap();
+        let resp = try_serve_static(dir.as_str(), "/logo.svg", &h).unwrap();
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.body.as_slice(), b"<svg></svg>");
+
+        let _ = fs::remove_dir_all(dir.as_path());
+    }
+
+    #[test]
+    fn test_serve_ranges() {
+        let dir = fixture("volki_static_range_test");
+        let with_range = |r: &str| {
+            let mut h = Headers::new();
+            h.set("Range", r);
+            try_serve_static(dir.as_str(), "/clip.mp4", &h).unwrap()
+        };
+
+        let resp = try_serve_static(dir.as_str(), "/clip.mp4", &Headers::new()).unwrap();
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.headers.get("accept-ranges"), Some("bytes"));
+        assert_eq!(resp.body.as_slice(), b"0123456789");
+
+        let resp = with_range("bytes=2-4");
+        assert_eq!(resp.status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(resp.headers.get("content-range"), Some("bytes 2-4/10"));
+        assert_eq!(resp.body.as_slice(), b"234");
+
+        let resp = with_range("bytes=7-");
+        assert_eq!(resp.headers.get("content-range"), Some("bytes 7-9/10"));
+        assert_eq!(resp.body.as_slice(), b"789");
+
+        let resp = with_range("bytes=10-");
+        assert_eq!(resp.status, StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(resp.headers.get("content-range"), Some("bytes */10"));
+        assert!(resp.body.is_empty());
+
+        let _ = fs::remove_dir_all(dir.as_path());
     }
 }
@@ -5,6 +5,7 @@ use crate::libs::web::compiler::parser::{RsxAttrValue, RsxNode};
 
 /// Walk all nodes and collect individual class names from `class` attributes.
 /// Class values are split on whitespace so `"flex p-4"` yields `["flex", "p-4"]`.
+/// Conditionally applied `class:name={cond}` directives contribute `name`.
 pub fn collect_classes(nodes: &[RsxNode]) -> Vec<String> {
     let mut classes = Vec::new();
     for node in nodes {
@@ -17,6 +18,9 @@ fn collect_from_node(node: &RsxNode, out: &mut Vec<String>) {
     match node {
         RsxNode::Element { attrs, children, .. } => {
             for attr in attrs.iter() {
+                if let Some(class) = attr.toggled_class() {
+                    out.push(String::from(class));
+                }
                 if attr.name.as_str() == "class" {
                     if let RsxAttrValue::Literal(v) = &attr.value {
                         for part in v.as_str().split_whitespace() {
@@ -197,4 +201,21 @@ mod tests {
         assert_eq!(classes[2].as_str(), "bg-light");
         assert_eq!(classes[3].as_str(), "text-black");
     }
+
+    #[test]
+    fn test_collect_class_directive() {
+        let nodes = vvec![RsxNode::Element {
+            tag: s("li"),
+            attrs: vvec![
+                RsxAttr { name: s("class"), value: RsxAttrValue::Literal(s("p-4")) },
+                RsxAttr { name: s("class:hidden"), value: RsxAttrValue::ClassToggle(s("collapsed")) },
+            ],
+            children: empty_nodes(),
+            self_closing: false,
+        }];
+        let classes = collect_classes(&nodes);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].as_str(), "p-4");
+        assert_eq!(classes[1].as_str(), "hidden");
+    }
 }
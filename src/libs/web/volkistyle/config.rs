@@ -38,6 +38,9 @@ pub struct VolkiStyleConfig {
     pub blocklist: Vec<String>,
     pub theme: ThemeConfig,
     pub variants: VariantConfig,
+    /// Emit the preflight reset ahead of the utilities. Turn off when the
+    /// CSS is embedded in a page that brings its own base styles.
+    pub preflight: bool,
 }
 
 impl Default for VolkiStyleConfig {
@@ -64,6 +67,7 @@ impl Default for VolkiStyleConfig {
                 enable_supports: true,
                 enable_group_peer_named: true,
             },
+            preflight: true,
         }
     }
 }
@@ -121,6 +125,10 @@ fn apply_table(cfg: &mut VolkiStyleConfig, table: &Table) {
         };
     }
 
+    if let Some(v) = table.get("web.volkistyle", "preflight").and_then(|v| v.as_bool()) {
+        cfg.preflight = v;
+    }
+
     if let Some(v) = table.get("web.volkistyle", "safelist").and_then(|v| v.as_str_array()) {
         let mut list = Vec::new();
         for item in v {
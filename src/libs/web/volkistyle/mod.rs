@@ -7,6 +7,7 @@ pub mod escape;
 pub mod palette;
 pub mod preflight;
 pub mod resolver;
+pub mod scope;
 pub mod variants;
 
 use crate::core::volkiwithstds::collections::{String, Vec};
@@ -80,6 +81,9 @@ pub fn generate_css_with_config(classes: &[String], config: &VolkiStyleConfig) -
                 for sfx in parsed.selector_suffixes.iter() {
                     selector.push_str(sfx.as_str());
                 }
+                if let Some(pe) = &parsed.pseudo_element {
+                    selector.push_str(pe.as_str());
+                }
                 for pref in parsed.selector_prefixes.iter().rev() {
                     let mut wrapped = pref.clone();
                     wrapped.push_str(selector.as_str());
@@ -111,6 +115,9 @@ pub fn generate_css_with_config(classes: &[String], config: &VolkiStyleConfig) -
                 for sfx in parsed.selector_suffixes.iter() {
                     selector.push_str(sfx.as_str());
                 }
+                if let Some(pe) = &parsed.pseudo_element {
+                    selector.push_str(pe.as_str());
+                }
                 for pref in parsed.selector_prefixes.iter().rev() {
                     let mut wrapped = pref.clone();
                     wrapped.push_str(selector.as_str());
@@ -135,10 +142,16 @@ pub fn generate_css_with_config(classes: &[String], config: &VolkiStyleConfig) -
     }
 
     rules.sort();
+    let mut rules = merge_rules(rules);
+    for rule in rules.iter_mut() {
+        rule.declarations = sort_declarations(rule.declarations.as_str());
+    }
 
     let mut out = String::new();
     if !rules.is_empty() {
-        out.push_str(preflight::preflight_css());
+        if config.preflight {
+            out.push_str(preflight::preflight_css());
+        }
 
         let mut media_groups = Vec::<(String, Vec<usize>)>::new();
         for (i, rule) in rules.iter().enumerate() {
@@ -229,12 +242,16 @@ fn has_unknown_diag(diags: &[StyleDiagnostic], class_name: &str) -> bool {
     false
 }
 
+/// Join media conditions with `and`. A media type such as `print` must lead
+/// the query, so it is moved ahead of the `(feature)` conditions.
 fn combine_media_queries(list: &[String]) -> Option<String> {
     if list.is_empty() {
         return None;
     }
     let mut out = String::new();
-    for (i, mq) in list.iter().enumerate() {
+    let types = list.iter().filter(|mq| !mq.as_str().starts_with('('));
+    let features = list.iter().filter(|mq| mq.as_str().starts_with('('));
+    for (i, mq) in types.chain(features).enumerate() {
         if i > 0 {
             out.push_str(" and ");
         }
@@ -245,16 +262,166 @@ fn combine_media_queries(list: &[String]) -> Option<String> {
 
 fn make_important(decls: &str) -> String {
     let mut out = String::new();
-    for part in decls.split(';') {
-        if part.is_empty() {
+    for part in split_declarations(decls) {
+        out.push_str(part);
+        if !is_important(part) {
+            out.push_str(" !important");
+        }
+        out.push(';');
+    }
+    out
+}
+
+fn is_important(decl: &str) -> bool {
+    decl.trim_end().ends_with("!important")
+}
+
+/// Collapse rules that share a selector and media query into one, last
+/// declaration winning per property. Sorting keeps same-selector rules
+/// together and in source order; different selectors are never merged,
+/// since an element may carry only one of them.
+fn merge_rules(rules: Vec<CssRule>) -> Vec<CssRule> {
+    let mut merged = Vec::<CssRule>::with_capacity(rules.len());
+    for rule in rules {
+        let same = merged
+            .iter_mut()
+            .rev()
+            .take_while(|m| m.layer == rule.layer && m.selector.as_str() == rule.selector.as_str())
+            .find(|m| m.media == rule.media);
+        if let Some(target) = same {
+            let mut decls = target.declarations.clone();
+            decls.push(';');
+            decls.push_str(rule.declarations.as_str());
+            target.declarations = merge_declarations(decls.as_str());
             continue;
         }
-        out.push_str(part);
-        out.push_str(" !important;");
+        merged.push(rule);
+    }
+    merged
+}
+
+/// Keep one declaration per property: the last one, unless an earlier one
+/// is `!important` and the later one is not, as the cascade would decide.
+/// A replaced property moves to the end so shorthand/longhand order holds.
+fn merge_declarations(decls: &str) -> String {
+    let mut kept = Vec::<&str>::new();
+    for decl in split_declarations(decls) {
+        let prop = decl_property(decl);
+        if let Some(i) = kept.iter().position(|d| decl_property(d) == prop) {
+            if is_important(kept[i]) && !is_important(decl) {
+                continue;
+            }
+            kept.remove(i);
+        }
+        kept.push(decl);
+    }
+    let mut out = String::with_capacity(decls.len());
+    for decl in kept {
+        out.push_str(decl);
+        out.push(';');
     }
     out
 }
 
+/// Order declarations by property name so the output does not depend on
+/// resolution order. Two declarations that can affect the same longhand
+/// (`margin` and `margin-top`, `inset` and `left`) keep their relative
+/// order, since swapping them would change which one wins.
+fn sort_declarations(decls: &str) -> String {
+    let mut list = split_declarations(decls);
+    for i in 1..list.len() {
+        let mut j = i;
+        while j > 0 {
+            let (prev, cur) = (decl_property(list[j - 1]), decl_property(list[j]));
+            if prev <= cur || properties_overlap(prev, cur) {
+                break;
+            }
+            list.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    let mut out = String::with_capacity(decls.len());
+    for decl in list {
+        out.push_str(decl);
+        out.push(';');
+    }
+    out
+}
+
+/// Split a declaration block on the `;` that end declarations, skipping
+/// those inside quotes or parentheses (`content:';'`,
+/// `url(data:image/png;base64,...)`). Empty declarations are dropped.
+fn split_declarations(decls: &str) -> Vec<&str> {
+    let mut list = Vec::new();
+    let mut push = |from: usize, to: usize| {
+        let decl = decls[from..to].trim();
+        if !decl.is_empty() {
+            list.push(decl);
+        }
+    };
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, b) in decls.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (b, quote) {
+            (b'\\', _) => escaped = true,
+            (b'\'' | b'"', None) => quote = Some(b),
+            (_, Some(q)) if b == q => quote = None,
+            (_, Some(_)) => {}
+            (b'(', None) => depth += 1,
+            (b')', None) => depth = depth.saturating_sub(1),
+            (b';', None) if depth == 0 => {
+                push(start, i);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    push(start, decls.len());
+    list
+}
+
+/// Shorthands whose longhands do not share their leading name segment.
+const CROSS_FAMILY_SHORTHANDS: &[(&str, &[&str])] = &[
+    ("inset", &["top", "right", "bottom", "left"]),
+    ("gap", &["row", "column"]),
+    ("place", &["align", "justify"]),
+    ("font", &["line"]),
+    ("grid", &["row", "column"]),
+];
+
+/// Whether two properties may set the same longhand. Properties are grouped
+/// by their first name segment (`border-top-color` -> `border`), ignoring
+/// vendor prefixes; custom properties only overlap themselves.
+fn properties_overlap(a: &str, b: &str) -> bool {
+    if a.starts_with("--") || b.starts_with("--") {
+        return a == b;
+    }
+    let (fa, fb) = (property_family(a), property_family(b));
+    fa == fb
+        || CROSS_FAMILY_SHORTHANDS.iter().any(|(short, longs)| {
+            (fa == *short && longs.contains(&fb)) || (fb == *short && longs.contains(&fa))
+        })
+}
+
+fn property_family(prop: &str) -> &str {
+    let prop = if let Some(rest) = prop.strip_prefix('-') {
+        rest.split_once('-').map_or(rest, |(_, p)| p)
+    } else {
+        prop
+    };
+    prop.split('-').next().unwrap_or(prop)
+}
+
+fn decl_property(decl: &str) -> &str {
+    decl.split(':').next().unwrap_or(decl).trim()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,6 +445,55 @@ mod tests {
         assert_eq!(css.as_str().matches(".flex{").count(), 1);
     }
 
+    #[test]
+    fn test_preflight_disabled() {
+        let classes = crate::vvec![s("flex")];
+        let with = generate_css(&classes);
+        assert!(with.as_str().starts_with(preflight::preflight_css()));
+
+        let config = VolkiStyleConfig {
+            preflight: false,
+            ..VolkiStyleConfig::default()
+        };
+        let report = generate_css_with_config(&classes, &config);
+        assert!(report.css.as_str().starts_with(".flex{display:flex;}"));
+    }
+
+    #[test]
+    fn test_output_is_order_independent() {
+        let a = crate::vvec![s("sr-only"), s("md:flex"), s("hover:bg-[#123456]"), s("p-4"), s("mt-2")];
+        let b = crate::vvec![s("mt-2"), s("p-4"), s("hover:bg-[#123456]"), s("md:flex"), s("sr-only")];
+        let css = generate_css(&a);
+        assert_eq!(css, generate_css(&b));
+        assert!(css.as_str().contains(
+            ".sr-only{border-width:0;clip:rect(0,0,0,0);height:1px;margin:-1px;overflow:hidden;padding:0;position:absolute;white-space:nowrap;width:1px;}"
+        ));
+    }
+
+    #[test]
+    fn test_sort_declarations_keeps_overlapping_order() {
+        assert_eq!(sort_declarations("width:1px;color:red;"), "color:red;width:1px;");
+        assert_eq!(sort_declarations("margin-top:0;margin:1px;"), "margin-top:0;margin:1px;");
+        assert_eq!(sort_declarations("left:0;inset:auto;color:red;"), "color:red;left:0;inset:auto;");
+        assert_eq!(
+            sort_declarations("display:-webkit-box;-webkit-line-clamp:2;"),
+            "-webkit-line-clamp:2;display:-webkit-box;"
+        );
+    }
+
+    #[test]
+    fn test_sort_declarations_keeps_quoted_semicolons() {
+        assert_eq!(sort_declarations("content:';';color:red;"), "color:red;content:';';");
+        assert_eq!(
+            sort_declarations("content:\"a;b\";background:url(data:image/png;base64,AA==);"),
+            "background:url(data:image/png;base64,AA==);content:\"a;b\";"
+        );
+        assert_eq!(split_declarations("content:'\\';x';;a:b;").as_slice(), &["content:'\\';x'", "a:b"]);
+
+        let css = generate_css(&crate::vvec![s("before:content-['a;b']")]);
+        assert!(css.as_str().contains("::before{content:'a;b';}"));
+    }
+
     #[test]
     fn test_unresolved_diagnostic() {
         let classes = crate::vvec![s("definitely-not-real")];
@@ -313,4 +529,91 @@ mod tests {
         assert_eq!(report.resolved_count, 1);
         assert!(report.css.as_str().contains("background-color:#30363d;"));
     }
+
+    #[test]
+    fn test_print_variant() {
+        let css = generate_css(&crate::vvec![s("print:hidden")]);
+        assert!(css.as_str().contains("@media print{.print\\:hidden{display:none;}}"));
+    }
+
+    #[test]
+    fn test_motion_variants() {
+        let css = generate_css(&crate::vvec![s("motion-reduce:animate-none")]);
+        assert!(css.as_str().contains("@media (prefers-reduced-motion:reduce){.motion-reduce\\:animate-none{"));
+
+        let css = generate_css(&crate::vvec![s("motion-safe:transition"), s("motion-safe:duration-300")]);
+        assert_eq!(css.as_str().matches("@media (prefers-reduced-motion:no-preference){").count(), 1);
+        assert!(css.as_str().contains("{.motion-safe\\:duration-300{transition-duration:300ms;}.motion-safe\\:transition{transition-property:"));
+    }
+
+    #[test]
+    fn test_media_type_leads_combined_query() {
+        let css = generate_css(&crate::vvec![s("md:print:block"), s("print:md:flex")]);
+        assert!(css.as_str().contains("@media print and (min-width:768px){"));
+        assert!(!css.as_str().contains("(min-width:768px) and print"));
+    }
+
+    #[test]
+    fn test_direction_variant_prefix() {
+        let css = generate_css(&crate::vvec![s("rtl:hover:pl-4"), s("ltr:ml-2")]);
+        assert!(css.as_str().contains("[dir=\"rtl\"] .rtl\\:hover\\:pl-4:hover{padding-left:1rem;}"));
+        assert!(css.as_str().contains("[dir=\"ltr\"] .ltr\\:ml-2{margin-left:0.5rem;}"));
+
+        let css = generate_css(&crate::vvec![s("md:rtl:text-right")]);
+        assert!(css.as_str().contains("@media (min-width:768px){[dir=\"rtl\"] .md\\:rtl\\:text-right{text-align:right;}}"));
+    }
+
+    #[test]
+    fn test_duplicate_selectors_collapse_last_wins() {
+        let rule = |decls: &str, media: Option<&str>| CssRule {
+            selector: s(".card"),
+            declarations: s(decls),
+            media: media.map(s),
+            layer: if media.is_some() { 1 } else { 0 },
+        };
+        let merged = merge_rules(crate::vvec![
+            rule("padding:1rem;color:red;", None),
+            rule("padding:0.5rem;content:'a;b'", None),
+            rule("color:blue;", None),
+            rule("padding:2rem;", Some("print")),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].declarations.as_str(), "padding:0.5rem;content:'a;b';color:blue;");
+        assert_eq!(merged[1].declarations.as_str(), "padding:2rem;");
+
+        // An important declaration is not overridden by a later normal one
+        let merged = merge_rules(crate::vvec![
+            rule("margin:0 !important;", None),
+            rule("margin:1rem;", None)
+        ]);
+        assert_eq!(merged[0].declarations.as_str(), "margin:0 !important;");
+    }
+
+    #[test]
+    fn test_conflicting_utilities_keep_own_selectors() {
+        let css = generate_css(&crate::vvec![s("p-4"), s("p-2"), s("!p-2")]);
+        assert!(css.as_str().contains(".p-2{padding:0.5rem;}.p-4{padding:1rem;}"));
+        assert!(css.as_str().contains("{padding:0.5rem !important;}"));
+        assert_eq!(make_important("a:b !important;c:d"), "a:b !important;c:d !important;");
+        assert_eq!(make_important("content:'a;b'"), "content:'a;b' !important;");
+    }
+
+    #[test]
+    fn test_form_and_hint_utilities_resolve() {
+        let report = generate_css_with_config(
+            &crate::vvec![s("accent-blue-500/50"), s("caret-red-500"), s("will-change-transform")],
+            &VolkiStyleConfig::default(),
+        );
+        assert!(report.diagnostics.is_empty());
+        assert!(report.css.as_str().contains("{accent-color:rgb(59 130 246 / 0.5);}"));
+        assert!(report.css.as_str().contains(".caret-red-500{caret-color:#ef4444;}"));
+        assert!(report.css.as_str().contains(".will-change-transform{will-change:transform;}"));
+    }
+
+    #[test]
+    fn test_pseudo_element_variant_ends_selector() {
+        let css = generate_css(&crate::vvec![s("before:content-['']"), s("after:hover:content-['→']")]);
+        assert!(css.as_str().contains(".before\\:content-\\[\\'\\'\\]::before{content:'';}"));
+        assert!(css.as_str().contains(":hover::after{content:'→';}"));
+    }
 }
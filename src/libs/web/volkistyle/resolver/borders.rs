@@ -62,67 +62,7 @@ pub fn resolve(class: &str) -> Option<ResolvedUtility> {
         "ring-8" => "box-shadow:0 0 0 8px rgba(59,130,246,0.5);",
         "ring-inset" => "--tw-ring-inset:inset;",
 
-        // Divide (child combinator)
-        "divide-x" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-left-width:1px;"),
-            });
-        }
-        "divide-y" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-top-width:1px;"),
-            });
-        }
-        "divide-x-0" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-left-width:0px;"),
-            });
-        }
-        "divide-y-0" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-top-width:0px;"),
-            });
-        }
-        "divide-x-2" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-left-width:2px;"),
-            });
-        }
-        "divide-y-2" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-top-width:2px;"),
-            });
-        }
-        "divide-x-4" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-left-width:4px;"),
-            });
-        }
-        "divide-y-4" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-top-width:4px;"),
-            });
-        }
-        "divide-x-8" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-left-width:8px;"),
-            });
-        }
-        "divide-y-8" => {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: String::from("border-top-width:8px;"),
-            });
-        }
+        // Divide (child combinator); widths are resolved in resolve_prefix
         "divide-solid" => {
             return Some(ResolvedUtility::Custom {
                 selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
@@ -301,20 +241,61 @@ fn resolve_prefix(class: &str) -> Option<ResolvedUtility> {
         return None;
     }
 
-    // Divide color
+    // Divide reverse — flips the divide width onto the trailing side
+    if class == "divide-x-reverse" {
+        return Some(ResolvedUtility::Custom {
+            selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
+            declarations: String::from("--tw-divide-x-reverse:1;"),
+        });
+    }
+    if class == "divide-y-reverse" {
+        return Some(ResolvedUtility::Custom {
+            selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
+            declarations: String::from("--tw-divide-y-reverse:1;"),
+        });
+    }
+
+    // Divide width
+    if let Some(r) = divide_width(class) {
+        return Some(r);
+    }
+
+    // Divide color (with optional /opacity)
     if let Some(rest) = class.strip_prefix("divide-") {
-        if let Some(hex) = palette::color_hex(rest) {
-            return Some(ResolvedUtility::Custom {
-                selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-                declarations: crate::vformat!("border-color:{};", hex),
-            });
-        }
-        return None;
+        let declarations = resolve_color_with_opacity(rest, "border-color")?;
+        return Some(ResolvedUtility::Custom {
+            selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
+            declarations,
+        });
     }
 
     None
 }
 
+/// `divide-x`, `divide-x-N`, `divide-y`, `divide-y-N` — a border between
+/// siblings, on the leading side unless `divide-*-reverse` sets the variable.
+fn divide_width(class: &str) -> Option<ResolvedUtility> {
+    let (rest, lead, trail, var) = if let Some(r) = class.strip_prefix("divide-x") {
+        (r, "left", "right", "--tw-divide-x-reverse")
+    } else if let Some(r) = class.strip_prefix("divide-y") {
+        (r, "top", "bottom", "--tw-divide-y-reverse")
+    } else {
+        return None;
+    };
+    let width = if rest.is_empty() {
+        1
+    } else {
+        parse_u32(rest.strip_prefix('-')?)?
+    };
+    Some(ResolvedUtility::Custom {
+        selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
+        declarations: crate::vformat!(
+            "border-{}-width:calc({}px * var({},0));border-{}-width:calc({}px * calc(1 - var({},0)));",
+            trail, width, var, lead, width, var
+        ),
+    })
+}
+
 fn radius_value(size: &str) -> Option<&'static str> {
     match size {
         "none" => Some("0px"),
@@ -380,7 +361,38 @@ mod tests {
     fn test_divide() {
         let r = resolve("divide-x").unwrap();
         assert!(r.as_str().contains(">:not([hidden])~:not([hidden])"));
-        assert!(r.as_str().contains("border-left-width:1px;"));
+        assert!(r.as_str().contains("border-left-width:calc(1px * calc(1 - var(--tw-divide-x-reverse,0)));"));
+    }
+
+    #[test]
+    fn test_divide_width_targets_following_siblings() {
+        assert_eq!(
+            resolve("divide-x-2").unwrap().as_str(),
+            ".divide-x-2>:not([hidden])~:not([hidden]){border-right-width:calc(2px * var(--tw-divide-x-reverse,0));border-left-width:calc(2px * calc(1 - var(--tw-divide-x-reverse,0)));}"
+        );
+        let r = resolve("divide-y-4").unwrap();
+        assert!(r.as_str().starts_with(".divide-y-4>:not([hidden])~:not([hidden]){"));
+        assert!(r.as_str().contains("border-bottom-width:calc(4px * var(--tw-divide-y-reverse,0));"));
+        assert!(resolve("divide-x-abc").is_none());
+    }
+
+    #[test]
+    fn test_divide_reverse() {
+        assert_eq!(
+            resolve("divide-y-reverse").unwrap().as_str(),
+            ".divide-y-reverse>:not([hidden])~:not([hidden]){--tw-divide-y-reverse:1;}"
+        );
+        assert!(resolve("divide-x-reverse").unwrap().as_str().contains("--tw-divide-x-reverse:1;"));
+    }
+
+    #[test]
+    fn test_divide_color() {
+        assert_eq!(
+            resolve("divide-gray-300").unwrap().as_str(),
+            ".divide-gray-300>:not([hidden])~:not([hidden]){border-color:#d1d5db;}"
+        );
+        assert!(resolve("divide-gray-300/50").unwrap().as_str().contains("border-color:rgb(209 213 219 / 0.5);"));
+        assert!(resolve("divide-nope").is_none());
     }
 
     #[test]
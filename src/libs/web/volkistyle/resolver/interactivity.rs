@@ -63,11 +63,12 @@ pub fn resolve(class: &str) -> Option<ResolvedUtility> {
         "scroll-auto" => "scroll-behavior:auto;",
         "scroll-smooth" => "scroll-behavior:smooth;",
 
-        // Scroll snap type
+        // Scroll snap type — strictness defaults to proximity when no
+        // snap-mandatory/snap-proximity class sets the variable
         "snap-none" => "scroll-snap-type:none;",
-        "snap-x" => "scroll-snap-type:x var(--tw-scroll-snap-strictness);",
-        "snap-y" => "scroll-snap-type:y var(--tw-scroll-snap-strictness);",
-        "snap-both" => "scroll-snap-type:both var(--tw-scroll-snap-strictness);",
+        "snap-x" => "scroll-snap-type:x var(--tw-scroll-snap-strictness,proximity);",
+        "snap-y" => "scroll-snap-type:y var(--tw-scroll-snap-strictness,proximity);",
+        "snap-both" => "scroll-snap-type:both var(--tw-scroll-snap-strictness,proximity);",
 
         // Scroll snap strictness
         "snap-mandatory" => "--tw-scroll-snap-strictness:mandatory;",
@@ -198,6 +199,7 @@ fn resolve_prefix(class: &str) -> Option<ResolvedUtility> {
 #[cfg(test)]
 mod tests {
     use super::super::resolve;
+    use crate::core::volkiwithstds::collections::String;
 
     #[test]
     fn test_cursor() {
@@ -227,6 +229,28 @@ mod tests {
     fn test_scroll_snap() {
         assert!(resolve("snap-x").unwrap().as_str().contains("scroll-snap-type:x"));
         assert_eq!(resolve("snap-start").unwrap().as_str(), ".snap-start{scroll-snap-align:start;}");
+        assert_eq!(resolve("snap-center").unwrap().as_str(), ".snap-center{scroll-snap-align:center;}");
+    }
+
+    #[test]
+    fn test_scroll_snap_type_with_strictness() {
+        // snap-x on its own is a valid declaration (proximity)...
+        assert_eq!(
+            resolve("snap-x").unwrap().as_str(),
+            ".snap-x{scroll-snap-type:x var(--tw-scroll-snap-strictness,proximity);}"
+        );
+        // ...and snap-mandatory on the same element makes it `x mandatory`
+        let classes = crate::vvec![String::from("snap-x"), String::from("snap-mandatory")];
+        let css = crate::libs::web::volkistyle::generate_css(&classes);
+        assert!(css.as_str().contains(".snap-mandatory{--tw-scroll-snap-strictness:mandatory;}"));
+        assert!(css.as_str().contains(".snap-x{scroll-snap-type:x var(--tw-scroll-snap-strictness,proximity);}"));
+    }
+
+    #[test]
+    fn test_scroll_margin_padding() {
+        assert_eq!(resolve("scroll-m-4").unwrap().as_str(), ".scroll-m-4{scroll-margin:1rem;}");
+        assert_eq!(resolve("scroll-px-2").unwrap().as_str(), ".scroll-px-2{scroll-padding-left:0.5rem;scroll-padding-right:0.5rem;}");
+        assert!(resolve("scroll-p-nope").is_none());
     }
 
     #[test]
@@ -238,17 +262,22 @@ mod tests {
     #[test]
     fn test_will_change() {
         assert_eq!(resolve("will-change-transform").unwrap().as_str(), ".will-change-transform{will-change:transform;}");
+        assert_eq!(resolve("will-change-scroll").unwrap().as_str(), ".will-change-scroll{will-change:scroll-position;}");
     }
 
     #[test]
     fn test_accent_color() {
         assert_eq!(resolve("accent-red-500").unwrap().as_str(), ".accent-red-500{accent-color:#ef4444;}");
         assert_eq!(resolve("accent-auto").unwrap().as_str(), ".accent-auto{accent-color:auto;}");
+        assert_eq!(resolve("accent-blue-500/50").unwrap().as_str(), ".accent-blue-500\\/50{accent-color:rgb(59 130 246 / 0.5);}");
+        assert_eq!(resolve("accent-blue-500/5").unwrap().as_str(), ".accent-blue-500\\/5{accent-color:rgb(59 130 246 / 0.05);}");
+        assert!(resolve("accent-blue-500/150").is_none());
     }
 
     #[test]
     fn test_caret_color() {
         assert_eq!(resolve("caret-blue-500").unwrap().as_str(), ".caret-blue-500{caret-color:#3b82f6;}");
+        assert_eq!(resolve("caret-red-500").unwrap().as_str(), ".caret-red-500{caret-color:#ef4444;}");
     }
 
     #[test]
@@ -204,6 +204,8 @@ pub fn resolve_color_with_opacity(color_part: &str, property: &str) -> Option<St
             String::from("0")
         } else if opacity_val % 10 == 0 {
             crate::vformat!("0.{}", opacity_val / 10)
+        } else if opacity_val < 10 {
+            crate::vformat!("0.0{}", opacity_val)
         } else {
             crate::vformat!("0.{}", opacity_val)
         };
@@ -17,18 +17,25 @@ pub fn resolve(class: &str) -> Option<ResolvedUtility> {
             declarations: String::from("--tw-space-y-reverse:1;"),
         });
     }
+    // The reverse variable moves the margin to the trailing side
     if let Some(rest) = class.strip_prefix("space-x-") {
         let val = parse_spacing_value(rest)?;
         return Some(ResolvedUtility::Custom {
             selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-            declarations: crate::vformat!("margin-left:{};", val),
+            declarations: crate::vformat!(
+                "margin-right:calc({} * var(--tw-space-x-reverse,0));margin-left:calc({} * calc(1 - var(--tw-space-x-reverse,0)));",
+                val, val
+            ),
         });
     }
     if let Some(rest) = class.strip_prefix("space-y-") {
         let val = parse_spacing_value(rest)?;
         return Some(ResolvedUtility::Custom {
             selector_suffix: String::from(">:not([hidden])~:not([hidden])"),
-            declarations: crate::vformat!("margin-top:{};", val),
+            declarations: crate::vformat!(
+                "margin-bottom:calc({} * var(--tw-space-y-reverse,0));margin-top:calc({} * calc(1 - var(--tw-space-y-reverse,0)));",
+                val, val
+            ),
         });
     }
 
@@ -242,10 +249,22 @@ mod tests {
     #[test]
     fn test_space_between() {
         let r = resolve("space-x-4").unwrap();
-        assert!(r.as_str().contains("margin-left:1rem;"));
+        assert!(r.as_str().contains("margin-left:calc(1rem * calc(1 - var(--tw-space-x-reverse,0)));"));
         assert!(r.as_str().contains(">:not([hidden])~:not([hidden])"));
     }
 
+    #[test]
+    fn test_space_reverse() {
+        assert_eq!(
+            resolve("space-y-2").unwrap().as_str(),
+            ".space-y-2>:not([hidden])~:not([hidden]){margin-bottom:calc(0.5rem * var(--tw-space-y-reverse,0));margin-top:calc(0.5rem * calc(1 - var(--tw-space-y-reverse,0)));}"
+        );
+        assert_eq!(
+            resolve("space-x-reverse").unwrap().as_str(),
+            ".space-x-reverse>:not([hidden])~:not([hidden]){--tw-space-x-reverse:1;}"
+        );
+    }
+
     #[test]
     fn test_arbitrary_padding() {
         assert_eq!(resolve("p-[20px]").unwrap().as_str(), ".p-\\[20px\\]{padding:20px;}");
@@ -220,6 +220,12 @@ fn resolve_prefix(class: &str) -> Option<ResolvedUtility> {
         return Some(ResolvedUtility::Standard(crate::vformat!("text-underline-offset:{}px;", n)));
     }
 
+    // Arbitrary content: content-[''], content-['→'], content-['Read_more']
+    if let Some(rest) = class.strip_prefix("content-") {
+        let val = super::parse_arbitrary(rest)?;
+        return Some(ResolvedUtility::Standard(crate::vformat!("content:{};", String::from(val).replace("_", " "))));
+    }
+
     // Text indent
     if let Some(rest) = class.strip_prefix("indent-") {
         if let Some(val) = super::parse_spacing_value(rest) {
@@ -344,4 +350,12 @@ mod tests {
             ".text-\\[\\#e6edf3\\]{color:#e6edf3;}"
         );
     }
+
+    #[test]
+    fn test_content() {
+        assert_eq!(resolve("content-none").unwrap().as_str(), ".content-none{content:none;}");
+        assert_eq!(resolve("content-['']").unwrap().as_str(), ".content-\\[\\'\\'\\]{content:'';}");
+        assert!(resolve("content-['Read_more']").unwrap().as_str().ends_with("{content:'Read more';}"));
+        assert!(resolve("content-['→']").unwrap().as_str().ends_with("{content:'→';}"));
+    }
 }
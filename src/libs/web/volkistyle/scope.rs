@@ -0,0 +1,326 @@
+//! Scoped styles — `<Style scoped>{CSS}</Style>`.
+//!
+//! Every element a file renders is stamped with a `data-v-*` attribute
+//! derived from the file path, and the scoped CSS has that attribute added
+//! to each selector, so its rules only reach the file's own markup.
+//! Selectors are rewritten at render time because `{CSS}` is a Rust
+//! expression, not a literal the compiler can see.
+
+use crate::core::volkiwithstds::collections::{String, Vec};
+use crate::libs::web::compiler::parser::{RsxAttr, RsxAttrValue, RsxNode};
+
+/// At-rules whose block holds ordinary rules that should be scoped.
+/// Anything else (`@keyframes`, `@font-face`, ...) is copied as-is.
+const NESTING_AT_RULES: &[&str] = &["media", "supports", "container", "layer", "document"];
+
+/// The `data-v-*` attribute name for a file. Stable for a given `key`.
+pub fn scope_attr(key: &str) -> String {
+    let mut h: u32 = 0x811c9dc5;
+    for &b in key.as_bytes() {
+        h ^= b as u32;
+        h = h.wrapping_mul(0x01000193);
+    }
+    crate::vformat!("data-v-{:08x}", h)
+}
+
+/// Whether any `<Style>` in `nodes` carries the `scoped` attribute.
+pub fn has_scoped_style(nodes: &[RsxNode]) -> bool {
+    nodes.iter().any(|node| match node {
+        RsxNode::Element {
+            tag,
+            attrs,
+            children,
+            ..
+        } => {
+            (tag.as_str() == "Style" && attrs.iter().any(|a| a.name.as_str() == "scoped"))
+                || has_scoped_style(children)
+        }
+        RsxNode::CondAnd { body, .. } => has_scoped_style(body),
+        RsxNode::Ternary {
+            if_true, if_false, ..
+        } => has_scoped_style(if_true) || has_scoped_style(if_false),
+        RsxNode::Text(_) | RsxNode::Expr(_) => false,
+    })
+}
+
+/// Adds `attr=""` to every HTML element in `nodes`, and records `attr` as
+/// the value of each `<Style scoped>` so codegen knows which attribute to
+/// scope its CSS to. Component tags (uppercase) are left alone; their
+/// children are still stamped.
+pub fn stamp_scope(nodes: &[RsxNode], attr: &str) -> Vec<RsxNode> {
+    nodes.iter().map(|node| stamp_node(node, attr)).collect()
+}
+
+fn stamp_node(node: &RsxNode, attr: &str) -> RsxNode {
+    match node {
+        RsxNode::Element {
+            tag,
+            attrs,
+            children,
+            self_closing,
+        } => {
+            let mut attrs = attrs.clone();
+            if tag.as_str() == "Style" {
+                for a in attrs.iter_mut() {
+                    if a.name.as_str() == "scoped" {
+                        a.value = RsxAttrValue::Literal(String::from(attr));
+                    }
+                }
+            } else if !tag.as_str().starts_with(|c: char| c.is_ascii_uppercase()) {
+                attrs.push(RsxAttr {
+                    name: String::from(attr),
+                    value: RsxAttrValue::Literal(String::new()),
+                });
+            }
+            RsxNode::Element {
+                tag: tag.clone(),
+                attrs,
+                children: stamp_scope(children, attr),
+                self_closing: *self_closing,
+            }
+        }
+        RsxNode::CondAnd { condition, body } => RsxNode::CondAnd {
+            condition: condition.clone(),
+            body: stamp_scope(body, attr),
+        },
+        RsxNode::Ternary {
+            condition,
+            if_true,
+            if_false,
+        } => RsxNode::Ternary {
+            condition: condition.clone(),
+            if_true: stamp_scope(if_true, attr),
+            if_false: stamp_scope(if_false, attr),
+        },
+        RsxNode::Text(_) | RsxNode::Expr(_) => node.clone(),
+    }
+}
+
+/// Rewrites every selector in `css` to also require `[attr]`. The attribute
+/// goes on the last compound selector, before any pseudo-element, so
+/// `.list li::before` becomes `.list li[attr]::before`.
+pub fn scope_css(css: &str, attr: &str) -> String {
+    let mut out = String::with_capacity(css.len() + 64);
+    scope_rules(css, attr, &mut out);
+    out
+}
+
+fn scope_rules(css: &str, attr: &str, out: &mut String) {
+    let mut rest = css;
+    loop {
+        let trimmed = rest.trim_start();
+        out.push_str(&rest[..rest.len() - trimmed.len()]);
+        rest = trimmed;
+        if rest.is_empty() {
+            return;
+        }
+        if let Some(after) = rest.strip_prefix("/*") {
+            let end = after.find("*/").map_or(rest.len(), |i| i + 4);
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let Some(open) = top_level_find(rest, |c| c == '{' || c == ';') else {
+            out.push_str(rest);
+            return;
+        };
+        if rest.as_bytes()[open] == b';' {
+            // `@import ...;` and friends
+            out.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        }
+        let prelude = &rest[..open];
+        let close = matching_brace(rest, open);
+        let block = &rest[open + 1..close.min(rest.len())];
+
+        if let Some(at) = prelude.strip_prefix('@') {
+            let name = at
+                .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+                .next()
+                .unwrap_or("");
+            out.push_str(prelude);
+            out.push('{');
+            if NESTING_AT_RULES.contains(&name) {
+                scope_rules(block, attr, out);
+            } else {
+                out.push_str(block);
+            }
+        } else {
+            scope_selector_list(prelude, attr, out);
+            out.push('{');
+            out.push_str(block);
+        }
+        if close < rest.len() {
+            out.push('}');
+            rest = &rest[close + 1..];
+        } else {
+            rest = "";
+        }
+    }
+}
+
+fn scope_selector_list(list: &str, attr: &str, out: &mut String) {
+    let trailing = &list[list.trim_end().len()..];
+    let mut first = true;
+    for selector in split_top_level(list.trim_end(), ',') {
+        if !first {
+            out.push(',');
+        }
+        first = false;
+        let selector = selector.trim();
+        let at = pseudo_element_start(selector).unwrap_or(selector.len());
+        out.push_str(&selector[..at]);
+        out.push('[');
+        out.push_str(attr);
+        out.push(']');
+        out.push_str(&selector[at..]);
+    }
+    out.push_str(trailing);
+}
+
+/// Pseudo-elements CSS 2 wrote with a single colon, still accepted that way.
+const LEGACY_PSEUDO_ELEMENTS: &[&str] = &["before", "after", "first-line", "first-letter"];
+
+/// Byte offset of a pseudo-element (`::name`, or one of the legacy
+/// single-colon forms) in the selector's last compound.
+fn pseudo_element_start(selector: &str) -> Option<usize> {
+    let bytes = selector.as_bytes();
+    let mut depth = 0i32;
+    let mut found = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth -= 1,
+            b' ' | b'>' | b'+' | b'~' if depth == 0 => found = None,
+            b':' if depth == 0
+                && found.is_none()
+                && (bytes.get(i + 1) == Some(&b':')
+                    || is_legacy_pseudo_element(&selector[i + 1..])) =>
+            {
+                found = Some(i)
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Whether `rest` (the text after a single `:`) names a legacy pseudo-element.
+fn is_legacy_pseudo_element(rest: &str) -> bool {
+    let end = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(rest.len());
+    let name = &rest[..end];
+    LEGACY_PSEUDO_ELEMENTS
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(name))
+}
+
+/// First byte matching `pred` outside parentheses, brackets and strings.
+fn top_level_find(s: &str, pred: impl Fn(char) -> bool) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, c) if depth == 0 && pred(c) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = s;
+    while let Some(i) = top_level_find(rest, |c| c == sep) {
+        parts.push(&rest[..i]);
+        rest = &rest[i + 1..];
+    }
+    parts.push(rest);
+    parts
+}
+
+/// Index of the `}` closing the block opened at `open`, or `s.len()`.
+fn matching_brace(s: &str, open: usize) -> usize {
+    let mut depth = 0i32;
+    for (i, b) in s.bytes().enumerate().skip(open) {
+        match b {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_css_rules() {
+        let attr = "data-v-1";
+        assert_eq!(
+            scope_css(".card{color:red}", attr),
+            ".card[data-v-1]{color:red}"
+        );
+        assert_eq!(
+            scope_css(".a > .b, p::first-line { margin: 0 }", attr),
+            ".a > .b[data-v-1],p[data-v-1]::first-line { margin: 0 }"
+        );
+        assert_eq!(
+            scope_css("a:not([href]):hover{x:y}", attr),
+            "a:not([href]):hover[data-v-1]{x:y}"
+        );
+    }
+
+    #[test]
+    fn test_scope_css_legacy_pseudo_elements() {
+        let attr = "data-v-1";
+        assert_eq!(
+            scope_css("li:hover:before, p:first-letter, a:AFTER{x:y}", attr),
+            "li:hover[data-v-1]:before,p[data-v-1]:first-letter,a[data-v-1]:AFTER{x:y}"
+        );
+        assert_eq!(
+            scope_css("p:first-child, a:before-x{x:y}", attr),
+            "p:first-child[data-v-1],a:before-x[data-v-1]{x:y}"
+        );
+    }
+
+    #[test]
+    fn test_scope_css_at_rules() {
+        let attr = "data-v-1";
+        assert_eq!(
+            scope_css("@media (min-width: 600px){.a{x:y}}", attr),
+            "@media (min-width: 600px){.a[data-v-1]{x:y}}"
+        );
+        let keyframes = "@keyframes spin{from{a:b}to{c:d}}";
+        assert_eq!(scope_css(keyframes, attr), keyframes);
+        assert_eq!(
+            scope_css("@import url(\"x.css\");/* c */.a{}", attr),
+            "@import url(\"x.css\");/* c */.a[data-v-1]{}"
+        );
+    }
+
+    #[test]
+    fn test_scope_attr_is_stable() {
+        assert_eq!(scope_attr("app/page.volki"), scope_attr("app/page.volki"));
+        assert_ne!(
+            scope_attr("app/page.volki"),
+            scope_attr("app/about/page.volki")
+        );
+        assert!(scope_attr("x").starts_with("data-v-"));
+    }
+}
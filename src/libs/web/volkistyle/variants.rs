@@ -8,8 +8,10 @@ use super::config::{DarkModeStrategy, VolkiStyleConfig};
 pub struct ParsedClass {
     /// The bare utility name (without variant prefixes).
     pub utility: String,
-    /// Pseudo-class/element selectors to append (e.g. ":hover", "::before").
+    /// Pseudo-class selectors to append (e.g. ":hover", "[data-open]").
     pub pseudo_classes: Vec<String>,
+    /// Pseudo-element (e.g. "::before"), always last in the selector.
+    pub pseudo_element: Option<String>,
     /// Ancestor prefixes that wrap the selector (e.g. ".dark ", ".group:hover ").
     pub selector_prefixes: Vec<String>,
     /// Selector suffixes to append after pseudo classes.
@@ -83,6 +85,7 @@ pub fn parse_variants_with_config(class: &str, config: &VolkiStyleConfig) -> Par
         return ParsedClass {
             utility: String::from(rest),
             pseudo_classes: Vec::new(),
+            pseudo_element: None,
             selector_prefixes: Vec::new(),
             selector_suffixes: Vec::new(),
             media_queries: Vec::new(),
@@ -93,6 +96,7 @@ pub fn parse_variants_with_config(class: &str, config: &VolkiStyleConfig) -> Par
     }
 
     let mut pseudo_classes = Vec::new();
+    let mut pseudo_element_sel = None;
     let mut selector_prefixes = Vec::new();
     let selector_suffixes = Vec::new();
     let mut media_queries = Vec::new();
@@ -122,13 +126,19 @@ pub fn parse_variants_with_config(class: &str, config: &VolkiStyleConfig) -> Par
             continue;
         }
 
+        // Text direction — scoped under an ancestor `dir` attribute
+        if *prefix == "rtl" || *prefix == "ltr" {
+            selector_prefixes.push(crate::vformat!("[dir=\"{}\"] ", prefix));
+            continue;
+        }
+
         if let Some(pc) = pseudo_class(prefix) {
             pseudo_classes.push(String::from(pc));
             continue;
         }
 
         if let Some(pe) = pseudo_element(prefix) {
-            pseudo_classes.push(String::from(pe));
+            pseudo_element_sel = Some(String::from(pe));
             continue;
         }
 
@@ -217,6 +227,7 @@ pub fn parse_variants_with_config(class: &str, config: &VolkiStyleConfig) -> Par
     ParsedClass {
         utility: String::from(parts[parts.len() - 1]),
         pseudo_classes,
+        pseudo_element: pseudo_element_sel,
         selector_prefixes,
         selector_suffixes,
         media_queries,
@@ -358,4 +369,35 @@ mod tests {
         let p = parse_variants("hover:bg-red-500");
         assert!(!p.is_custom);
     }
+
+    #[test]
+    fn test_media_variants() {
+        let p = parse_variants("print:hidden");
+        assert_eq!(p.utility.as_str(), "hidden");
+        assert_eq!(p.media_queries[0].as_str(), "print");
+        let p = parse_variants("motion-reduce:transition-none");
+        assert_eq!(p.media_queries[0].as_str(), "(prefers-reduced-motion:reduce)");
+        let p = parse_variants("motion-safe:hover:animate-spin");
+        assert_eq!(p.media_queries[0].as_str(), "(prefers-reduced-motion:no-preference)");
+        assert_eq!(p.pseudo_classes[0].as_str(), ":hover");
+    }
+
+    #[test]
+    fn test_direction_variants() {
+        let p = parse_variants("rtl:hover:pl-4");
+        assert_eq!(p.utility.as_str(), "pl-4");
+        assert_eq!(p.selector_prefixes[0].as_str(), "[dir=\"rtl\"] ");
+        assert_eq!(p.pseudo_classes[0].as_str(), ":hover");
+        let p = parse_variants("ltr:mr-2");
+        assert_eq!(p.selector_prefixes[0].as_str(), "[dir=\"ltr\"] ");
+    }
+
+    #[test]
+    fn test_pseudo_element_kept_apart_from_pseudo_classes() {
+        let p = parse_variants("before:hover:content-['']");
+        assert_eq!(p.utility.as_str(), "content-['']");
+        assert_eq!(p.pseudo_element.as_deref(), Some("::before"));
+        assert_eq!(p.pseudo_classes.len(), 1);
+        assert_eq!(p.pseudo_classes[0].as_str(), ":hover");
+    }
 }
@@ -10,6 +10,7 @@
 //!     el.set_attr("disabled", "true");
 //!     let val = dom::query("#input").get_value();
 //!     dom::log("debug message");
+//!     storage::set("last-clicked", target);
 //! }
 //! ```
 //!
@@ -36,6 +37,8 @@
 /// - `handle.toggle_class(class)` — Toggle a CSS class on the element.
 /// - `handle.get_attr(name)` — Get an attribute value from the element.
 /// - `handle.remove_attr(name)` — Remove an attribute from the element.
+/// - `handle.append_html(html)` — Parse `html` and append it after the element's children.
+/// - `handle.remove()` — Remove the element from the DOM (same as `dom::remove(handle)`).
 ///
 /// # Element creation and manipulation
 ///
@@ -64,6 +67,39 @@ pub mod dom {
     // in the user's source code during development.
 }
 
+/// `window.localStorage` namespace, for state that should survive a reload.
+///
+/// - `storage::get(key)` — Read a value. A missing key reads back as `""`.
+/// - `storage::set(key, value)` — Store a string value.
+/// - `storage::remove(key)` — Delete a key.
+pub mod storage {
+    // Marker namespace, like `dom` — calls are rewritten by the compiler.
+}
+
+/// HTTP namespace.
+///
+/// - `net::fetch(url, "callback")` — Start a `GET` request and return at once.
+///
+/// WASM has no way to block on a browser promise, so the response comes back
+/// through another Client function, named by the second argument, with the
+/// signature `fn callback(status: i32, body: &str) -> Client`. The glue calls
+/// it like any other handler once the body has been read. A network error is
+/// delivered as status `0` with an empty body.
+pub mod net {
+    // Marker namespace, like `dom` — calls are rewritten by the compiler.
+}
+
+/// Event control, for the DOM event that triggered the running handler.
+///
+/// - `event::prevent_default()` — Cancel the browser's default action.
+/// - `event::stop_propagation()` — Stop the event from reaching ancestors.
+///
+/// Only valid in `-> Client` functions bound with `on*={handler}`; outside
+/// of an event both calls do nothing.
+pub mod event {
+    // Marker namespace, like `dom` — calls are rewritten by the compiler.
+}
+
 /// The set of DOM API patterns the compiler recognizes and their extern mappings.
 ///
 /// Each entry: `(source_pattern, extern_fn_name, param_style)`
@@ -84,6 +120,8 @@ pub const DOM_API_MAP: &[(&str, &str, DomCallStyle)] = &[
     (".toggle_class(",       "__volki_dom_toggle_class",     DomCallStyle::HandleString),
     (".get_attr(",           "__volki_dom_get_attr",         DomCallStyle::HandleStringToString),
     (".remove_attr(",        "__volki_dom_remove_attr",      DomCallStyle::HandleString),
+    (".append_html(",        "__volki_dom_append_html",      DomCallStyle::HandleString),
+    (".remove()",            "__volki_dom_remove",           DomCallStyle::HandleVoid),
     ("dom::query_all_count(","__volki_dom_query_all_count",  DomCallStyle::StringToI32),
     ("dom::query_all_get(",  "__volki_dom_query_all_get",    DomCallStyle::StringI32ToHandle),
     // Full DOM API extensions
@@ -96,6 +134,15 @@ pub const DOM_API_MAP: &[(&str, &str, DomCallStyle)] = &[
     ("dom::add_event(",      "__volki_dom_add_event",        DomCallStyle::HandleStringI32),
     ("dom::remove_event(",   "__volki_dom_remove_event",     DomCallStyle::HandleStringI32),
     ("dom::dispatch(",       "__volki_dom_dispatch",         DomCallStyle::HandleString),
+    // localStorage
+    ("storage::get(",        "__volki_storage_get",          DomCallStyle::StringToString),
+    ("storage::set(",        "__volki_storage_set",          DomCallStyle::StringString),
+    ("storage::remove(",     "__volki_storage_remove",       DomCallStyle::StringVoid),
+    // HTTP
+    ("net::fetch(",          "__volki_fetch",                DomCallStyle::StringString),
+    // Event control
+    ("event::prevent_default(",  "__volki_event_prevent_default",  DomCallStyle::NoArgs),
+    ("event::stop_propagation(", "__volki_event_stop_propagation", DomCallStyle::NoArgs),
 ];
 
 /// Describes how a DOM API call's parameters map to WASM ABI.
@@ -115,7 +162,7 @@ pub enum DomCallStyle {
     HandleStringString,
     /// `dom::append(parent, child)` — two handles in, void out.
     HandleHandle,
-    /// `dom::remove(handle)` — handle in, void out.
+    /// `dom::remove(handle)`, `handle.remove()` — handle in, void out.
     HandleVoid,
     /// `.get_attr(name)` — handle + string in, string out.
     HandleStringToString,
@@ -131,6 +178,12 @@ pub enum DomCallStyle {
     HandleStringToI32,
     /// `dom::add_event(handle, type, cb_id)` — handle + string + i32 in, void out.
     HandleStringI32,
+    /// `storage::get(key) -> &str` — string in, string out.
+    StringToString,
+    /// `storage::set(key, value)`, `net::fetch(url, callback)` — two strings in, void out.
+    StringString,
+    /// `event::prevent_default()` — no arguments, void out.
+    NoArgs,
 }
 
 #[cfg(test)]
@@ -139,7 +192,16 @@ mod tests {
 
     #[test]
     fn test_dom_api_map_has_all_entries() {
-        assert_eq!(DOM_API_MAP.len(), 26);
+        assert_eq!(DOM_API_MAP.len(), 34);
+    }
+
+    #[test]
+    fn test_dom_api_map_storage() {
+        let find = |p: &str| DOM_API_MAP.iter().find(|(pat, _, _)| *pat == p).unwrap();
+        assert_eq!(find("storage::get(").1, "__volki_storage_get");
+        assert_eq!(find("storage::get(").2, DomCallStyle::StringToString);
+        assert_eq!(find("storage::set(").2, DomCallStyle::StringString);
+        assert_eq!(find("storage::remove(").2, DomCallStyle::StringVoid);
     }
 
     #[test]
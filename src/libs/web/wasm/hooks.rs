@@ -52,6 +52,17 @@ pub fn use_state<T>(_initial: T) -> T { _initial }
 ///     dom::log("count changed!");
 /// }
 /// ```
+///
+/// The body can also be written inline as a closure, which the compiler
+/// hoists into `__effect_{component}_{slot}(deps...)`. A closure returned as
+/// the tail expression becomes the cleanup. Empty deps run once after mount.
+///
+/// ```rust,ignore
+/// use_effect(&[count], move || {
+///     dom::log(count);
+///     move || { dom::log("before next run"); }
+/// });
+/// ```
 pub fn use_effect(_deps: &[i32]) {}
 
 /// Phase 1: Initialize a mutable ref slot. Does NOT trigger rerender on set.
@@ -15,7 +15,8 @@ pub enum WasmType {
 /// How a Rust type maps to the WASM boundary.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WasmAbi {
-    /// Direct pass-through (i32, u32, bool, f32, f64, i64, u64).
+    /// Scalar passed as one WASM value. Integers narrower than 32 bits, `u32`
+    /// and `bool` widen to i32; `i64`/`u64` use i64.
     Direct(WasmType),
     /// String types (&str, String) flatten to (ptr: i32, len: i32).
     StringPair,
@@ -42,7 +43,9 @@ pub struct WasmSignature {
 /// Map a Rust type string to its WASM ABI representation.
 pub fn rust_type_to_wasm(ty: &str) -> WasmAbi {
     match ty {
-        "i32" | "u32" | "bool" => WasmAbi::Direct(WasmType::I32),
+        "i32" | "u32" | "i16" | "u16" | "i8" | "u8" | "isize" | "usize" | "bool" => {
+            WasmAbi::Direct(WasmType::I32)
+        }
         "i64" | "u64" => WasmAbi::Direct(WasmType::I64),
         "f32" => WasmAbi::Direct(WasmType::F32),
         "f64" => WasmAbi::Direct(WasmType::F64),
@@ -103,6 +106,8 @@ mod tests {
         assert_eq!(rust_type_to_wasm("i32"), WasmAbi::Direct(WasmType::I32));
         assert_eq!(rust_type_to_wasm("u32"), WasmAbi::Direct(WasmType::I32));
         assert_eq!(rust_type_to_wasm("bool"), WasmAbi::Direct(WasmType::I32));
+        assert_eq!(rust_type_to_wasm("i8"), WasmAbi::Direct(WasmType::I32));
+        assert_eq!(rust_type_to_wasm("u16"), WasmAbi::Direct(WasmType::I32));
     }
 
     #[test]
@@ -30,7 +30,7 @@ pub extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
     let cli = volki::core::cli::build_cli();
     if let Err(e) = cli.run() {
         volki::core::cli::print_cli_error(&e);
-        volki::core::volkiwithstds::process::exit(1);
+        volki::core::volkiwithstds::process::exit(e.exit_code());
     }
     0
 }